@@ -26,21 +26,78 @@ pub enum SolSocialError {
     #[msg("Insufficient key balance for chat access")]
     InsufficientKeyBalance,
     
+    #[msg("Invalid amount specified")]
+    InvalidAmount,
+    
+    #[msg("Exceeds maximum purchase limit")]
+    ExceedsMaxPurchase,
+    
+    #[msg("Insufficient keys to sell")]
+    InsufficientKeys,
+    
+    #[msg("Profile still has outstanding keys")]
+    KeysOutstanding,
+    
+    #[msg("Cannot transfer keys to yourself")]
+    SelfTransfer,
+    
+    #[msg("Not following this user")]
+    NotFollowing,
+    
+    #[msg("User is already blocked")]
+    UserAlreadyBlocked,
+    
+    #[msg("User is not blocked")]
+    UserNotBlocked,
+    
+    #[msg("Key market description exceeds maximum length")]
+    DescriptionTooLong,
+    
+    #[msg("Too many key market links")]
+    TooManyLinks,
+    
+    #[msg("Key market link exceeds maximum length")]
+    LinkTooLong,
+    
     #[msg("Invalid chat room configuration")]
     InvalidChatRoom,
     
     #[msg("Message content exceeds maximum length")]
     MessageTooLong,
     
+    #[msg("Edit reason exceeds maximum length")]
+    EditReasonTooLong,
+    
+    #[msg("A ban needs a reason no longer than the maximum length")]
+    InvalidBanReason,
+    
+    #[msg("Reaction exceeds maximum length")]
+    ReactionTooLong,
+    
+    #[msg("Too many tags")]
+    TooManyTags,
+    
+    #[msg("Tag exceeds maximum length")]
+    TagTooLong,
+    
+    #[msg("Message has reached its reaction limit")]
+    TooManyReactions,
+    
     #[msg("Invalid user reputation score")]
     InvalidReputationScore,
     
+    #[msg("Reputation too low")]
+    ReputationTooLow,
+    
     #[msg("Content moderation violation")]
     ContentViolation,
     
     #[msg("Trading is currently paused")]
     TradingPaused,
     
+    #[msg("Posting is currently paused")]
+    PostingPaused,
+    
     #[msg("Invalid slippage tolerance")]
     InvalidSlippageTolerance,
     
@@ -50,6 +107,15 @@ pub enum SolSocialError {
     #[msg("Invalid creator fee percentage")]
     InvalidCreatorFee,
     
+    #[msg("Fee percentage exceeds the maximum")]
+    InvalidFeePercentage,
+    
+    #[msg("Referral bonus exceeds the creator share")]
+    InvalidReferralBonus,
+    
+    #[msg("Too many pinned posts")]
+    TooManyPinnedPosts,
+    
     #[msg("Protocol fee calculation error")]
     ProtocolFeeError,
     
@@ -71,6 +137,9 @@ pub enum SolSocialError {
     #[msg("Badge already claimed")]
     BadgeAlreadyClaimed,
     
+    #[msg("Badge already earned")]
+    BadgeAlreadyEarned,
+    
     #[msg("Insufficient influence score")]
     InsufficientInfluence,
     
@@ -98,6 +167,18 @@ pub enum SolSocialError {
     #[msg("Account not initialized")]
     AccountNotInitialized,
     
+    #[msg("Account already initialized")]
+    AlreadyInitialized,
+    
+    #[msg("Account not initialized")]
+    NotInitialized,
+    
+    #[msg("Unauthorized access")]
+    Unauthorized,
+    
+    #[msg("Invalid account provided")]
+    InvalidAccount,
+    
     #[msg("Invalid account owner")]
     InvalidAccountOwner,
     
@@ -110,6 +191,12 @@ pub enum SolSocialError {
     #[msg("Arithmetic underflow")]
     ArithmeticUnderflow,
     
+    #[msg("Math overflow error")]
+    MathOverflow,
+    
+    #[msg("Math underflow error")]
+    MathUnderflow,
+    
     #[msg("Division by zero")]
     DivisionByZero,
     
@@ -512,6 +599,9 @@ pub enum SolSocialError {
     #[msg("Already voted")]
     AlreadyVoted,
     
+    #[msg("Already replied to this post")]
+    AlreadyCommented,
+    
     #[msg("Invalid vote")]
     InvalidVote,
     
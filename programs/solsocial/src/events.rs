@@ -0,0 +1,61 @@
+use anchor_lang::prelude::*;
+
+#[event]
+pub struct MarketSellsPaused {
+    pub subject: Pubkey,
+    pub escrow_balance: u64,
+    pub redemption_value: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct MarketSellsResumed {
+    pub subject: Pubkey,
+    pub escrow_balance: u64,
+    pub redemption_value: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ReputationBootstrapped {
+    pub buyer: Pubkey,
+    pub subject: Pubkey,
+    pub creator_reputation: u64,
+    pub amount: u64,
+    pub total_bootstrapped: u64,
+}
+
+#[event]
+pub struct PriceQuote {
+    pub subject: Pubkey,
+    pub is_buy: bool,
+    pub supply: u64,
+    pub amount: u64,
+    pub base_price: u64,
+    pub protocol_fee: u64,
+    pub subject_fee: u64,
+    pub total: u64,
+    /// How far the trade would move the spot price, so clients can warn before large trades
+    pub impact_bps: u64,
+}
+
+#[event]
+pub struct MessageReactionChanged {
+    pub message_id: u64,
+    pub chat_room: Pubkey,
+    pub user: Pubkey,
+    pub emoji: String,
+    pub added: bool,
+    pub count: u32,
+}
+
+/// Only the settings that were changed are `Some`
+#[event]
+pub struct PlatformSettingsUpdated {
+    pub authority: Pubkey,
+    pub fee_rate: Option<u64>,
+    pub creator_fee_rate: Option<u64>,
+    pub max_keys_per_trade: Option<u64>,
+    pub min_solvency_ratio_bps: Option<u16>,
+    pub timestamp: i64,
+}
@@ -0,0 +1,39 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+pub struct AcceptAuthority<'info> {
+    #[account(
+        mut,
+        seeds = [b"platform_config"],
+        bump = platform_config.bump,
+        constraint = platform_config.pending_authority == Some(new_authority.key()) @ SolSocialError::UnauthorizedUser
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+
+    pub new_authority: Signer<'info>,
+}
+
+pub fn accept_authority(ctx: Context<AcceptAuthority>) -> Result<()> {
+    let platform_config = &mut ctx.accounts.platform_config;
+    let previous_authority = platform_config.authority;
+
+    platform_config.authority = ctx.accounts.new_authority.key();
+    platform_config.pending_authority = None;
+
+    emit!(AuthorityTransferred {
+        previous_authority,
+        new_authority: platform_config.authority,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct AuthorityTransferred {
+    pub previous_authority: Pubkey,
+    pub new_authority: Pubkey,
+    pub timestamp: i64,
+}
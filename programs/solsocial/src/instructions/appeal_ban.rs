@@ -0,0 +1,74 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+pub struct AppealBan<'info> {
+    #[account(
+        seeds = [b"user", authority.key().as_ref()],
+        bump = user.bump,
+        constraint = user.authority == authority.key() @ SolSocialError::UnauthorizedUser
+    )]
+    pub user: Account<'info, User>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = BanAppeal::LEN,
+        seeds = [b"ban_appeal", authority.key().as_ref(), &user.banned_at.to_le_bytes()],
+        bump
+    )]
+    pub ban_appeal: Account<'info, BanAppeal>,
+
+    #[account(
+        seeds = [b"platform_config"],
+        bump = platform_config.bump,
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// A banned user gets one appeal per ban, filed within the platform's appeal window
+pub fn appeal_ban(ctx: Context<AppealBan>, message: String) -> Result<()> {
+    let user = &ctx.accounts.user;
+    let current_time = Clock::get()?.unix_timestamp;
+
+    require!(user.banned, SolSocialError::InvalidResourceState);
+    require!(
+        current_time <= user.banned_at.saturating_add(ctx.accounts.platform_config.ban_appeal_window_seconds),
+        SolSocialError::ResourceExpired
+    );
+    require!(message.len() <= BanAppeal::MAX_MESSAGE_LENGTH, SolSocialError::MessageTooLong);
+
+    ctx.accounts.ban_appeal.set_inner(BanAppeal {
+        user: user.authority,
+        banned_at: user.banned_at,
+        message,
+        submitted_at: current_time,
+        is_resolved: false,
+        is_approved: false,
+        resolved_by: Pubkey::default(),
+        bump: ctx.bumps.ban_appeal,
+    });
+
+    emit!(BanAppealed {
+        user: user.authority,
+        appeal: ctx.accounts.ban_appeal.key(),
+        banned_at: user.banned_at,
+        timestamp: current_time,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct BanAppealed {
+    pub user: Pubkey,
+    pub appeal: Pubkey,
+    pub banned_at: i64,
+    pub timestamp: i64,
+}
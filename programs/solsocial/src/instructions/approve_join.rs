@@ -0,0 +1,51 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+#[instruction(room_id: String, applicant: Pubkey)]
+pub struct ApproveJoin<'info> {
+    #[account(
+        mut,
+        seeds = [b"chat_room", room_id.as_bytes()],
+        bump = chat_room.bump,
+        constraint = chat_room.creator == creator.key() @ SolSocialError::UnauthorizedUser
+    )]
+    pub chat_room: Account<'info, ChatRoom>,
+
+    #[account(
+        mut,
+        seeds = [b"join_request", chat_room.key().as_ref(), applicant.as_ref()],
+        bump = join_request.bump,
+    )]
+    pub join_request: Account<'info, JoinRequest>,
+
+    pub creator: Signer<'info>,
+}
+
+pub fn approve_join(ctx: Context<ApproveJoin>, _room_id: String, applicant: Pubkey) -> Result<()> {
+    let chat_room = &mut ctx.accounts.chat_room;
+    let join_request = &mut ctx.accounts.join_request;
+    let current_time = Clock::get()?.unix_timestamp;
+
+    require!(!join_request.is_approved, SolSocialError::InvalidResourceState);
+
+    join_request.is_approved = true;
+    join_request.approved_at = Some(current_time);
+    chat_room.add_participant(applicant)?;
+
+    emit!(JoinApproved {
+        chat_room: chat_room.key(),
+        applicant,
+        timestamp: current_time,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct JoinApproved {
+    pub chat_room: Pubkey,
+    pub applicant: Pubkey,
+    pub timestamp: i64,
+}
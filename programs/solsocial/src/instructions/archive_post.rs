@@ -0,0 +1,41 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+pub struct ArchivePost<'info> {
+    #[account(
+        mut,
+        seeds = [b"post", post.author.as_ref(), &post.id.to_le_bytes()],
+        bump = post.bump,
+        constraint = post.author == author.key() @ SolSocialError::UnauthorizedUser
+    )]
+    pub post: Account<'info, Post>,
+
+    pub author: Signer<'info>,
+}
+
+/// Takes the post out of trending and virality ranking and hides it from everyone but the author
+pub fn archive_post(ctx: Context<ArchivePost>) -> Result<()> {
+    let post = &mut ctx.accounts.post;
+    post.archive()?;
+
+    emit!(PostArchived {
+        post: post.key(),
+        author: post.author,
+        is_trending: post.is_trending()?,
+        virality_score: post.calculate_virality_score()?,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct PostArchived {
+    pub post: Pubkey,
+    pub author: Pubkey,
+    pub is_trending: bool,
+    pub virality_score: u64,
+    pub timestamp: i64,
+}
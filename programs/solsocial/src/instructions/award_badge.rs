@@ -0,0 +1,90 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+#[instruction(badge_type: BadgeType)]
+pub struct AwardBadge<'info> {
+    #[account(
+        seeds = [b"user_profile", user.key().as_ref()],
+        bump = user_profile.bump,
+    )]
+    pub user_profile: Account<'info, UserProfile>,
+
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = UserBadge::LEN,
+        seeds = [b"user_badge", user.key().as_ref(), &[badge_type.clone() as u8]],
+        bump
+    )]
+    pub badge: Account<'info, UserBadge>,
+
+    #[account(
+        seeds = [b"platform_config"],
+        bump = platform_config.bump,
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Self-service claim for badges whose eligibility can be checked on-chain. Verified is
+/// issued by the authority through `verify_user`, and a revoked badge comes back through
+/// `restore_badge`, not a fresh claim.
+pub fn award_badge(ctx: Context<AwardBadge>, badge_type: BadgeType) -> Result<()> {
+    let user_profile = &ctx.accounts.user_profile;
+    let platform_config = &ctx.accounts.platform_config;
+    let badge = &mut ctx.accounts.badge;
+
+    require!(badge.user == Pubkey::default(), SolSocialError::BadgeAlreadyEarned);
+
+    match badge_type {
+        BadgeType::EarlyAdopter => {
+            require!(user_profile.is_early_adopter, SolSocialError::ReputationTooLow);
+        }
+        BadgeType::TopTrader => {
+            require!(
+                user_profile.total_volume > platform_config.top_trader_volume_threshold,
+                SolSocialError::ReputationTooLow
+            );
+        }
+        BadgeType::Influencer => {
+            require!(
+                user_profile.follower_count > platform_config.influencer_follower_threshold,
+                SolSocialError::InsufficientInfluence
+            );
+        }
+        _ => return err!(SolSocialError::PermissionDenied),
+    }
+
+    let current_time = Clock::get()?.unix_timestamp;
+    badge.set_inner(UserBadge {
+        user: ctx.accounts.user.key(),
+        badge_type: badge_type.clone(),
+        metadata_uri: String::new(),
+        earned_at: current_time,
+        is_active: true,
+        bump: ctx.bumps.badge,
+    });
+
+    emit!(BadgeAwarded {
+        user: badge.user,
+        badge: badge.key(),
+        badge_type,
+        timestamp: current_time,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct BadgeAwarded {
+    pub user: Pubkey,
+    pub badge: Pubkey,
+    pub badge_type: BadgeType,
+    pub timestamp: i64,
+}
@@ -0,0 +1,72 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+pub struct BanUser<'info> {
+    #[account(
+        mut,
+        seeds = [b"user", banned_user.authority.as_ref()],
+        bump = banned_user.bump,
+    )]
+    pub banned_user: Account<'info, User>,
+
+    #[account(
+        seeds = [b"platform_config"],
+        bump = platform_config.bump,
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+
+    /// Only needed when the signer is a moderator rather than the platform authority
+    #[account(
+        seeds = [b"user_profile", moderator.key().as_ref()],
+        bump = moderator_profile.bump,
+    )]
+    pub moderator_profile: Option<Account<'info, UserProfile>>,
+
+    pub moderator: Signer<'info>,
+}
+
+/// The platform authority, or any profile flagged as a moderator
+pub(crate) fn can_moderate_users(
+    moderator: &Pubkey,
+    platform_config: &PlatformConfig,
+    moderator_profile: Option<&Account<UserProfile>>,
+) -> bool {
+    *moderator == platform_config.authority
+        || moderator_profile.map_or(false, |profile| profile.is_moderator)
+}
+
+/// Every ban carries a reason so it can be audited and, within the appeal window, appealed
+pub fn ban_user(ctx: Context<BanUser>, ban_reason: String) -> Result<()> {
+    let moderator = ctx.accounts.moderator.key();
+    require!(
+        can_moderate_users(&moderator, &ctx.accounts.platform_config, ctx.accounts.moderator_profile.as_ref()),
+        SolSocialError::ModeratorPrivilegesRequired
+    );
+
+    let banned_user = &mut ctx.accounts.banned_user;
+    require!(banned_user.authority != moderator, SolSocialError::SelfInteractionNotAllowed);
+
+    let current_time = Clock::get()?.unix_timestamp;
+    banned_user.ban(ban_reason.clone(), moderator, current_time)?;
+
+    emit!(UserBanned {
+        user: banned_user.authority,
+        banned_by: moderator,
+        ban_reason,
+        appeal_deadline: current_time.saturating_add(ctx.accounts.platform_config.ban_appeal_window_seconds),
+        timestamp: current_time,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct UserBanned {
+    pub user: Pubkey,
+    pub banned_by: Pubkey,
+    pub ban_reason: String,
+    pub appeal_deadline: i64,
+    pub timestamp: i64,
+}
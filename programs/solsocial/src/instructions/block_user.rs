@@ -0,0 +1,56 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+pub struct BlockUser<'info> {
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = BlockList::LEN,
+        seeds = [b"block_list", authority.key().as_ref()],
+        bump
+    )]
+    pub block_list: Account<'info, BlockList>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn block_user(ctx: Context<BlockUser>, target: Pubkey) -> Result<()> {
+    let block_list = &mut ctx.accounts.block_list;
+    let authority = ctx.accounts.authority.key();
+
+    require!(target != authority, SolSocialError::InvalidSocialConnection);
+
+    if block_list.owner == Pubkey::default() {
+        block_list.owner = authority;
+        block_list.blocked = Vec::new();
+        block_list.bump = ctx.bumps.block_list;
+    }
+
+    require!(!block_list.is_blocked(&target), SolSocialError::UserAlreadyBlocked);
+    require!(
+        block_list.blocked.len() < BlockList::MAX_BLOCKED,
+        SolSocialError::ConnectionLimitExceeded
+    );
+
+    block_list.blocked.push(target);
+
+    emit!(UserBlocked {
+        blocker: authority,
+        blocked: target,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct UserBlocked {
+    pub blocker: Pubkey,
+    pub blocked: Pubkey,
+    pub timestamp: i64,
+}
@@ -3,6 +3,10 @@ use anchor_spl::token::{self, Token, TokenAccount, Transfer};
 use crate::state::*;
 use crate::errors::*;
 use crate::events::*;
+use crate::utils::reputation::*;
+use crate::utils::revenue_share::{apply_fee_discount, distribute_buy_revenue};
+use crate::utils::bonding_curve::{BondingCurve, TradeQuote};
+use crate::constants::CREATOR_INITIAL_KEYS;
 
 #[derive(Accounts)]
 #[instruction(amount: u64)]
@@ -11,23 +15,78 @@ pub struct BuyKeys<'info> {
     pub buyer: Signer<'info>,
     
     #[account(
-        mut,
-        seeds = [b"user", subject.key().as_ref()],
-        bump = user_account.bump,
+        seeds = [b"user_profile", subject.key().as_ref()],
+        bump = subject_profile.bump,
     )]
-    pub user_account: Account<'info, UserAccount>,
+    pub subject_profile: Account<'info, UserProfile>,
     
     /// CHECK: This is the subject whose keys are being bought
     pub subject: AccountInfo<'info>,
     
+    // The market itself: supply, volume and trading rules
+    #[account(
+        mut,
+        seeds = [b"user_keys", subject.key().as_ref()],
+        bump = user_keys.bump,
+    )]
+    pub user_keys: Account<'info, UserKeys>,
+    
+    /// CHECK: The market creator's `UserProfile`, tracking their issuance across all of their
+    /// markets. Loaded in the handler since it's the buyer's own profile on a self-buy.
+    #[account(
+        mut,
+        seeds = [b"user_profile", user_keys.creator.as_ref()],
+        bump,
+    )]
+    pub creator_profile: UncheckedAccount<'info>,
+    
+    // Carries the subject's own creator fee
+    #[account(
+        mut,
+        seeds = [b"user_key", subject.key().as_ref()],
+        bump = user_key.bump,
+    )]
+    pub user_key: Account<'info, UserKey>,
+    
+    #[account(
+        mut,
+        seeds = [b"user_profile", buyer.key().as_ref()],
+        bump = buyer_profile.bump,
+    )]
+    pub buyer_profile: Account<'info, UserProfile>,
+    
+    #[account(
+        seeds = [b"platform_config"],
+        bump = platform_config.bump,
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+    
+    // The same holding transfer_keys moves between wallets
     #[account(
         init_if_needed,
         payer = buyer,
-        space = 8 + KeysBalance::INIT_SPACE,
-        seeds = [b"keys_balance", buyer.key().as_ref(), subject.key().as_ref()],
+        space = KeyHolder::LEN,
+        seeds = [b"key_holder", user_key.key().as_ref(), buyer.key().as_ref()],
+        bump
+    )]
+    pub key_holder: Account<'info, KeyHolder>,
+    
+    /// Only needed by traders who opted in to receipts for this market
+    #[account(
+        mut,
+        seeds = [b"trade_ledger", buyer.key().as_ref(), subject.key().as_ref()],
+        bump = trade_ledger.bump,
+    )]
+    pub trade_ledger: Option<Account<'info, TradeLedger>>,
+    
+    #[account(
+        init,
+        payer = buyer,
+        space = TradeReceipt::LEN,
+        seeds = [b"trade_receipt", trade_ledger.key().as_ref(), &trade_ledger.next_sequence.to_le_bytes()],
         bump
     )]
-    pub keys_balance: Account<'info, KeysBalance>,
+    pub trade_receipt: Option<Account<'info, TradeReceipt>>,
     
     #[account(
         mut,
@@ -46,7 +105,7 @@ pub struct BuyKeys<'info> {
     #[account(
         mut,
         associated_token::mint = payment_mint,
-        associated_token::authority = user_account,
+        associated_token::authority = subject,
     )]
     pub subject_token_account: Account<'info, TokenAccount>,
     
@@ -57,6 +116,16 @@ pub struct BuyKeys<'info> {
     )]
     pub protocol_token_account: Account<'info, TokenAccount>,
     
+    /// The buyer's referrer, if any, paid a share of the trade's fees
+    #[account(mut)]
+    pub referrer_profile: Option<Account<'info, UserProfile>>,
+    
+    #[account(
+        mut,
+        token::mint = payment_mint,
+    )]
+    pub referrer_token_account: Option<Account<'info, TokenAccount>>,
+    
     /// CHECK: Payment mint for the transaction
     pub payment_mint: AccountInfo<'info>,
     
@@ -65,41 +134,77 @@ pub struct BuyKeys<'info> {
     pub rent: Sysvar<'info, Rent>,
 }
 
-pub fn buy_keys(ctx: Context<BuyKeys>, amount: u64) -> Result<()> {
-    require!(amount > 0, SolSocialError::InvalidAmount);
-    require!(amount <= MAX_KEYS_PER_TRANSACTION, SolSocialError::ExceedsMaxAmount);
+pub fn buy_keys(
+    ctx: Context<BuyKeys>,
+    amount: u64,
+    max_cost: u64,
+    deadline: Option<i64>,
+) -> Result<()> {
+    check_trade_deadline(deadline)?;
+    // A trade needs some bound: its own max_cost or the platform default
+    require!(
+        max_cost > 0 || ctx.accounts.platform_config.default_max_slippage_bps > 0,
+        SolSocialError::InvalidSlippageTolerance
+    );
     
-    let user_account = &mut ctx.accounts.user_account;
-    let keys_balance = &mut ctx.accounts.keys_balance;
+    let subject_profile = &ctx.accounts.subject_profile;
+    let user_keys = &mut ctx.accounts.user_keys;
+    let key_holder = &mut ctx.accounts.key_holder;
     let protocol_fees = &mut ctx.accounts.protocol_fees;
+    let buyer_profile = &mut ctx.accounts.buyer_profile;
+    let platform_config = &ctx.accounts.platform_config;
+    let now = Clock::get()?.unix_timestamp;
+    
+    // Validate the subject's profile is active
+    require!(subject_profile.is_active, SolSocialError::UserAccountInactive);
+    
+    let is_own_market = ctx.accounts.buyer.key() == ctx.accounts.subject.key();
+    validate_buy(platform_config, user_keys, is_own_market, key_holder.amount, amount, now)?;
+    
+    // Calculate price and fees using bonding curve
+    let quote = quote_buy_for(
+        platform_config,
+        buyer_profile,
+        user_keys,
+        &ctx.accounts.user_key,
+        is_own_market,
+        amount,
+    )?;
+    let price = quote.base_price;
+    let protocol_fee = quote.protocol_fee;
+    let subject_fee = quote.subject_fee;
+    let total_cost = quote.total;
+    
+    // Protect the buyer if supply moved between quote and execution. Without a bound of
+    // their own, the reference is the price this slot opened at, before any earlier trades
+    if max_cost > 0 {
+        require!(total_cost <= max_cost, SolSocialError::SlippageExceeded);
+    } else {
+        require!(
+            BondingCurve::standard().is_within_slippage(
+                user_keys.supply_at_slot_start(Clock::get()?.slot),
+                amount,
+                price,
+                platform_config.default_max_slippage_bps,
+                true,
+            )?,
+            SolSocialError::SlippageExceeded
+        );
+    }
     
-    // Validate user account is active
-    require!(user_account.is_active, SolSocialError::UserAccountInactive);
-    
-    // Calculate current supply before purchase
-    let current_supply = user_account.keys_supply;
-    
-    // Calculate price using bonding curve
-    let price = calculate_buy_price(current_supply, amount)?;
-    
-    // Calculate fees
-    let protocol_fee = price
-        .checked_mul(protocol_fees.protocol_fee_percent as u64)
-        .ok_or(SolSocialError::MathOverflow)?
-        .checked_div(10000)
-        .ok_or(SolSocialError::MathOverflow)?;
-    
-    let subject_fee = price
-        .checked_mul(protocol_fees.subject_fee_percent as u64)
-        .ok_or(SolSocialError::MathOverflow)?
-        .checked_div(10000)
-        .ok_or(SolSocialError::MathOverflow)?;
-    
-    let total_cost = price
-        .checked_add(protocol_fee)
-        .ok_or(SolSocialError::MathOverflow)?
-        .checked_add(subject_fee)
-        .ok_or(SolSocialError::MathOverflow)?;
+    // The referrer's cut comes out of the fees above, so the buyer pays the same either way
+    check_referrer_payee(
+        ctx.accounts.referrer_profile.as_deref(),
+        ctx.accounts.referrer_token_account.as_ref().map(|account| account.owner),
+    )?;
+    let distribution = distribute_buy_revenue(
+        user_keys,
+        &ctx.accounts.buyer.key(),
+        ctx.accounts.referrer_profile.as_deref_mut(),
+        price,
+        protocol_fee,
+        subject_fee,
+    )?;
     
     // Validate buyer has sufficient balance
     require!(
@@ -107,12 +212,14 @@ pub fn buy_keys(ctx: Context<BuyKeys>, amount: u64) -> Result<()> {
         SolSocialError::InsufficientFunds
     );
     
-    // Check for supply overflow
-    let new_supply = current_supply
-        .checked_add(amount)
-        .ok_or(SolSocialError::MathOverflow)?;
-    
-    require!(new_supply <= MAX_KEYS_SUPPLY, SolSocialError::ExceedsMaxSupply);
+    with_creator_profile(
+        &ctx.accounts.buyer.key(),
+        buyer_profile,
+        &user_keys.creator,
+        &ctx.accounts.creator_profile.to_account_info(),
+        ctx.program_id,
+        |creator_profile| reserve_creator_supply(platform_config, creator_profile, amount),
+    )?;
     
     // Transfer payment from buyer to subject
     let transfer_to_subject_ctx = CpiContext::new(
@@ -126,7 +233,7 @@ pub fn buy_keys(ctx: Context<BuyKeys>, amount: u64) -> Result<()> {
     token::transfer(transfer_to_subject_ctx, price)?;
     
     // Transfer protocol fee
-    if protocol_fee > 0 {
+    if distribution.protocol_amount > 0 {
         let transfer_protocol_fee_ctx = CpiContext::new(
             ctx.accounts.token_program.to_account_info(),
             Transfer {
@@ -135,11 +242,11 @@ pub fn buy_keys(ctx: Context<BuyKeys>, amount: u64) -> Result<()> {
                 authority: ctx.accounts.buyer.to_account_info(),
             },
         );
-        token::transfer(transfer_protocol_fee_ctx, protocol_fee)?;
+        token::transfer(transfer_protocol_fee_ctx, distribution.protocol_amount)?;
     }
     
     // Transfer subject fee
-    if subject_fee > 0 {
+    if distribution.creator_amount > 0 {
         let transfer_subject_fee_ctx = CpiContext::new(
             ctx.accounts.token_program.to_account_info(),
             Transfer {
@@ -148,38 +255,87 @@ pub fn buy_keys(ctx: Context<BuyKeys>, amount: u64) -> Result<()> {
                 authority: ctx.accounts.buyer.to_account_info(),
             },
         );
-        token::transfer(transfer_subject_fee_ctx, subject_fee)?;
+        token::transfer(transfer_subject_fee_ctx, distribution.creator_amount)?;
     }
     
-    // Initialize keys balance if needed
-    if keys_balance.owner == Pubkey::default() {
-        keys_balance.owner = ctx.accounts.buyer.key();
-        keys_balance.subject = ctx.accounts.subject.key();
-        keys_balance.balance = 0;
-        keys_balance.bump = ctx.bumps.keys_balance;
+    // Transfer referrer fee
+    if let Some(referrer_token_account) = &ctx.accounts.referrer_token_account {
+        if distribution.referrer_amount > 0 {
+            let transfer_referrer_fee_ctx = CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.buyer_token_account.to_account_info(),
+                    to: referrer_token_account.to_account_info(),
+                    authority: ctx.accounts.buyer.to_account_info(),
+                },
+            );
+            token::transfer(transfer_referrer_fee_ctx, distribution.referrer_amount)?;
+        }
     }
     
-    // Update balances
-    keys_balance.balance = keys_balance.balance
-        .checked_add(amount)
-        .ok_or(SolSocialError::MathOverflow)?;
+    // Initialize the holding if needed
+    if key_holder.holder == Pubkey::default() {
+        key_holder.holder = ctx.accounts.buyer.key();
+        key_holder.subject = ctx.accounts.subject.key();
+        key_holder.amount = 0;
+        key_holder.average_price = 0;
+        key_holder.total_invested = 0;
+        key_holder.first_purchase_at = now;
+        key_holder.bump = ctx.bumps.key_holder;
+    }
     
-    user_account.keys_supply = new_supply;
-    user_account.total_volume = user_account.total_volume
-        .checked_add(total_cost)
-        .ok_or(SolSocialError::MathOverflow)?;
+    record_buy(
+        platform_config,
+        buyer_profile,
+        user_keys,
+        &mut ctx.accounts.user_key,
+        key_holder,
+        &quote,
+        amount,
+        now,
+    )?;
+    
+    issue_trade_receipt(
+        ctx.accounts.trade_ledger.as_mut(),
+        ctx.accounts.trade_receipt.as_mut(),
+        ctx.bumps.trade_receipt,
+        TradeType::Buy,
+        amount,
+        price,
+        protocol_fee.checked_add(subject_fee).ok_or(SolSocialError::MathOverflow)?,
+        total_cost,
+        now,
+    )?;
     
     // Update protocol fees collected
     protocol_fees.total_fees_collected = protocol_fees.total_fees_collected
-        .checked_add(protocol_fee)
+        .checked_add(distribution.protocol_amount)
         .ok_or(SolSocialError::MathOverflow)?;
     
-    // Update user stats
-    user_account.holders_count = user_account.holders_count
-        .checked_add(if keys_balance.balance == amount { 1 } else { 0 })
-        .ok_or(SolSocialError::MathOverflow)?;
-    
-    user_account.last_activity_timestamp = Clock::get()?.unix_timestamp;
+    // Backing an established creator lends the buyer a little standing of their own
+    let bootstrap = calculate_reputation_bootstrap(
+        subject_profile.reputation_score,
+        platform_config.bootstrap_reputation_rate,
+        buyer_profile.bootstrap_reputation_earned,
+        platform_config.max_bootstrap_reputation,
+    )?;
+    
+    if bootstrap > 0 {
+        buyer_profile.reputation_score = buyer_profile.reputation_score
+            .checked_add(bootstrap)
+            .ok_or(SolSocialError::MathOverflow)?;
+        buyer_profile.bootstrap_reputation_earned = buyer_profile.bootstrap_reputation_earned
+            .checked_add(bootstrap)
+            .ok_or(SolSocialError::MathOverflow)?;
+        
+        emit!(ReputationBootstrapped {
+            buyer: ctx.accounts.buyer.key(),
+            subject: ctx.accounts.subject.key(),
+            creator_reputation: subject_profile.reputation_score,
+            amount: bootstrap,
+            total_bootstrapped: buyer_profile.bootstrap_reputation_earned,
+        });
+    }
     
     // Emit event
     emit!(KeysPurchased {
@@ -189,54 +345,256 @@ pub fn buy_keys(ctx: Context<BuyKeys>, amount: u64) -> Result<()> {
         price,
         protocol_fee,
         subject_fee,
-        new_supply,
-        timestamp: Clock::get()?.unix_timestamp,
+        new_supply: user_keys.total_supply,
+        timestamp: now,
     });
     
     Ok(())
 }
 
-fn calculate_buy_price(supply: u64, amount: u64) -> Result<u64> {
-    // Bonding curve: price = (supply^2 + supply * amount + amount^2) / 3 * BASE_PRICE
-    const BASE_PRICE: u64 = 1_000_000; // 0.001 SOL in lamports
-    
-    let supply_squared = supply
-        .checked_mul(supply)
+/// A trade naming a referrer must pay them: `payee` is the wallet receiving their share, or the
+/// owner of the token account receiving it
+pub(crate) fn check_referrer_payee(
+    referrer_profile: Option<&UserProfile>,
+    payee: Option<Pubkey>,
+) -> Result<()> {
+    if let Some(referrer) = referrer_profile {
+        require!(payee == Some(referrer.authority), SolSocialError::InvalidAccount);
+    }
+    Ok(())
+}
+
+/// Protocol fee rate for the trader's next trade. Doesn't use up a free trade; `record_buy`
+/// and `record_sell` do that once the trade actually settles.
+pub(crate) fn protocol_fee_bps_for(
+    trader_profile: &UserProfile,
+    platform_config: &PlatformConfig,
+) -> Result<u64> {
+    // Newcomers' first trades skip the protocol fee; the subject fee is always paid
+    if trader_profile.free_trades_remaining > 0 {
+        return Ok(0);
+    }
+
+    let protocol_fee_percent = platform_config.protocol_fee_percent as u64;
+
+    // Supporters spread across many markets get part of the protocol fee back
+    if platform_config.multi_holder_threshold > 0
+        && trader_profile.active_positions >= platform_config.multi_holder_threshold
+    {
+        return apply_fee_discount(protocol_fee_percent, platform_config.multi_holder_discount_bps);
+    }
+
+    Ok(protocol_fee_percent)
+}
+
+/// Checks every path that mints keys runs before pricing: `buy_keys`, `buy_keys_multi`
+/// and a filled buy order
+pub(crate) fn validate_buy(
+    platform_config: &PlatformConfig,
+    user_keys: &UserKeys,
+    is_own_market: bool,
+    current_balance: u64,
+    amount: u64,
+    now: i64,
+) -> Result<()> {
+    require!(platform_config.is_trading_enabled, SolSocialError::TradingPaused);
+    require!(amount > 0, SolSocialError::InvalidAmount);
+    require!(amount >= platform_config.min_buy_amount, SolSocialError::MinimumKeyAmountNotMet);
+    require!(amount <= platform_config.max_keys_per_trade, SolSocialError::ExceedsMaxAmount);
+    require!(user_keys.is_within_trading_window(now), SolSocialError::MarketClosed);
+    check_self_holding_cap(platform_config, is_own_market, current_balance, amount)?;
+
+    let new_supply = user_keys.total_supply
+        .checked_add(amount)
         .ok_or(SolSocialError::MathOverflow)?;
-    
-    let supply_times_amount = supply
-        .checked_mul(amount)
+    require!(new_supply <= MAX_KEYS_SUPPLY, SolSocialError::ExceedsMaxSupply);
+
+    Ok(())
+}
+
+/// What `amount` keys cost this buyer right now. A subject buying their own keys would only
+/// pay the subject fee to themselves, so it's waived on their own market.
+pub(crate) fn quote_buy_for(
+    platform_config: &PlatformConfig,
+    buyer_profile: &UserProfile,
+    user_keys: &UserKeys,
+    user_key: &UserKey,
+    is_own_market: bool,
+    amount: u64,
+) -> Result<TradeQuote> {
+    let subject_fee_bps = if is_own_market {
+        0
+    } else {
+        user_key.subject_fee_percent as u64
+    };
+
+    BondingCurve::standard().quote_buy(
+        user_keys.total_supply,
+        amount,
+        protocol_fee_bps_for(buyer_profile, platform_config)?,
+        subject_fee_bps,
+    )
+}
+
+/// Settles a priced buy: uses up a free trade, counts the cost against the buyer's spend cap,
+/// opens the position if it's new, and moves the holding and the market. Payment is the
+/// caller's, since each path pays differently.
+pub(crate) fn record_buy(
+    platform_config: &PlatformConfig,
+    buyer_profile: &mut UserProfile,
+    user_keys: &mut UserKeys,
+    user_key: &mut UserKey,
+    key_holder: &mut KeyHolder,
+    quote: &TradeQuote,
+    amount: u64,
+    now: i64,
+) -> Result<()> {
+    if buyer_profile.free_trades_remaining > 0 {
+        buyer_profile.free_trades_remaining -= 1;
+    }
+    buyer_profile.record_spend(quote.total, now)?;
+    buyer_profile.total_volume = buyer_profile.total_volume
+        .checked_add(quote.base_price)
         .ok_or(SolSocialError::MathOverflow)?;
-    
-    let amount_squared = amount
-        .checked_mul(amount)
+
+    if key_holder.amount == 0 {
+        open_position(platform_config, Some(buyer_profile), user_key)?;
+    }
+
+    user_keys.mark_slot_start(Clock::get()?.slot);
+
+    key_holder.amount = key_holder.amount
+        .checked_add(amount)
         .ok_or(SolSocialError::MathOverflow)?;
-    
-    let numerator = supply_squared
-        .checked_add(supply_times_amount)
-        .ok_or(SolSocialError::MathOverflow)?
-        .checked_add(amount_squared)
+    key_holder.total_invested = key_holder.total_invested
+        .checked_add(quote.base_price)
         .ok_or(SolSocialError::MathOverflow)?;
-    
-    let price_factor = numerator
-        .checked_div(3)
+    key_holder.average_price = key_holder.total_invested / key_holder.amount;
+    key_holder.last_trade_at = now;
+
+    user_keys.total_supply = user_keys.total_supply
+        .checked_add(amount)
         .ok_or(SolSocialError::MathOverflow)?;
-    
-    let final_price = price_factor
-        .checked_mul(BASE_PRICE)
-        .ok_or(SolSocialError::MathOverflow)?
-        .checked_div(1000)
+    user_keys.price_per_key = BondingCurve::standard().get_price(user_keys.total_supply)?;
+    user_keys.total_volume = user_keys.total_volume
+        .checked_add(quote.total)
         .ok_or(SolSocialError::MathOverflow)?;
-    
-    // Minimum price protection
-    let min_price = amount
-        .checked_mul(BASE_PRICE)
-        .ok_or(SolSocialError::MathOverflow)?
-        .checked_div(1000)
+    user_keys.last_trade_at = now;
+
+    Ok(())
+}
+
+/// A wallet's first keys in a market open a position: it counts against the wallet's
+/// `max_positions` and adds a holder to the market. Every path that takes a holding up from
+/// zero goes through here, and every one that empties it through `close_position`, so the
+/// two counts can't drift apart. Wallets without a profile (gift recipients) have no
+/// position count to keep.
+pub(crate) fn open_position(
+    platform_config: &PlatformConfig,
+    profile: Option<&mut UserProfile>,
+    user_key: &mut UserKey,
+) -> Result<()> {
+    if let Some(profile) = profile {
+        require!(
+            platform_config.max_positions == 0
+                || profile.active_positions < platform_config.max_positions,
+            SolSocialError::ConnectionLimitExceeded
+        );
+        profile.active_positions = profile.active_positions
+            .checked_add(1)
+            .ok_or(SolSocialError::MathOverflow)?;
+    }
+    user_key.holder_count = user_key.holder_count
+        .checked_add(1)
         .ok_or(SolSocialError::MathOverflow)?;
-    
-    Ok(std::cmp::max(final_price, min_price))
+
+    Ok(())
+}
+
+/// Frees the slot an emptied holding took under `max_positions` and drops it from the
+/// market's holders
+pub(crate) fn close_position(profile: Option<&mut UserProfile>, user_key: &mut UserKey) {
+    if let Some(profile) = profile {
+        profile.active_positions = profile.active_positions.saturating_sub(1);
+    }
+    user_key.holder_count = user_key.holder_count.saturating_sub(1);
+}
+
+/// Runs `f` against the market creator's profile. Creators usually trade their own market,
+/// and then the trader's profile is already held mutably by the instruction: a second copy
+/// would overwrite it on exit, so the trader's is used directly.
+pub(crate) fn with_creator_profile<'info>(
+    trader: &Pubkey,
+    trader_profile: &mut UserProfile,
+    creator: &Pubkey,
+    creator_profile_info: &AccountInfo<'info>,
+    program_id: &Pubkey,
+    f: impl FnOnce(&mut UserProfile) -> Result<()>,
+) -> Result<()> {
+    if creator == trader {
+        return f(trader_profile);
+    }
+
+    let mut creator_profile = Account::<UserProfile>::try_from(creator_profile_info)?;
+    f(&mut creator_profile)?;
+    creator_profile.exit(program_id)
+}
+
+/// Rejects a trade submitted after its `deadline`; a missing or zero deadline never expires
+pub(crate) fn check_trade_deadline(deadline: Option<i64>) -> Result<()> {
+    if let Some(deadline) = deadline.filter(|d| *d != 0) {
+        require!(
+            Clock::get()?.unix_timestamp <= deadline,
+            SolSocialError::TradeDeadlineExceeded
+        );
+    }
+
+    Ok(())
+}
+
+/// With `cap_self_holdings` on, subjects may buy back up to their initial allocation
+/// of their own keys but never past it
+pub(crate) fn check_self_holding_cap(
+    platform_config: &PlatformConfig,
+    is_own_market: bool,
+    current_balance: u64,
+    amount: u64,
+) -> Result<()> {
+    if platform_config.cap_self_holdings && is_own_market {
+        let balance_after = current_balance
+            .checked_add(amount)
+            .ok_or(SolSocialError::MathOverflow)?;
+        require!(
+            balance_after <= CREATOR_INITIAL_KEYS,
+            SolSocialError::SelfInteractionNotAllowed
+        );
+    }
+
+    Ok(())
+}
+
+/// Counts new keys against the market creator's `max_creator_supply`. Issuance is tracked
+/// even while no cap is set, so a cap introduced later sees the creator's real footprint.
+pub(crate) fn reserve_creator_supply(
+    platform_config: &PlatformConfig,
+    creator_profile: &mut UserProfile,
+    amount: u64,
+) -> Result<()> {
+    let issued_after = creator_profile.issued_key_supply
+        .checked_add(amount)
+        .ok_or(SolSocialError::MathOverflow)?;
+    require!(
+        platform_config.max_creator_supply == 0 || issued_after <= platform_config.max_creator_supply,
+        SolSocialError::ExceedsMaxSupply
+    );
+    creator_profile.issued_key_supply = issued_after;
+
+    Ok(())
+}
+
+/// Burned supply frees the creator's capacity under `max_creator_supply`
+pub(crate) fn release_creator_supply(creator_profile: &mut UserProfile, amount: u64) {
+    creator_profile.issued_key_supply = creator_profile.issued_key_supply.saturating_sub(amount);
 }
 
-const MAX_KEYS_PER_TRANSACTION: u64 = 1000;
-const MAX_KEYS_SUPPLY: u64 = 1_000_000;
\ No newline at end of file
+pub(crate) const MAX_KEYS_SUPPLY: u64 = 1_000_000;
\ No newline at end of file
@@ -0,0 +1,285 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{self, CreateAccount};
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use crate::state::*;
+use crate::errors::*;
+use super::buy_keys::{quote_buy_for, record_buy, reserve_creator_supply, validate_buy, with_creator_profile};
+
+/// Keeps a whole batch inside the compute budget
+pub const MAX_BATCH_LEGS: usize = 8;
+
+/// Accounts each leg passes in `remaining_accounts`, in order: the subject's `UserProfile`,
+/// their `UserKeys`, their payment token account, the buyer's `KeyHolder` for them, the
+/// subject's `UserKey` carrying their creator fee, and the market creator's `UserProfile`
+/// tracking their issuance
+const ACCOUNTS_PER_LEG: usize = 6;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct BatchBuyLeg {
+    pub subject: Pubkey,
+    pub amount: u64,
+}
+
+#[derive(Accounts)]
+pub struct BuyKeysMulti<'info> {
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"user_profile", buyer.key().as_ref()],
+        bump = buyer_profile.bump,
+    )]
+    pub buyer_profile: Account<'info, UserProfile>,
+
+    #[account(
+        seeds = [b"platform_config"],
+        bump = platform_config.bump,
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"protocol_fees"],
+        bump
+    )]
+    pub protocol_fees: Account<'info, ProtocolFees>,
+
+    #[account(
+        mut,
+        associated_token::mint = payment_mint,
+        associated_token::authority = buyer,
+    )]
+    pub buyer_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::mint = payment_mint,
+        associated_token::authority = protocol_fees,
+    )]
+    pub protocol_token_account: Account<'info, TokenAccount>,
+
+    /// CHECK: Payment mint for the transaction
+    pub payment_mint: AccountInfo<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Buys from several subjects' curves in one transaction. Any failing leg, or a combined cost
+/// above `max_total_cost`, reverts the whole batch.
+pub fn buy_keys_multi<'info>(
+    ctx: Context<'_, '_, '_, 'info, BuyKeysMulti<'info>>,
+    legs: Vec<BatchBuyLeg>,
+    max_total_cost: u64,
+) -> Result<()> {
+    require!(
+        !legs.is_empty() && legs.len() <= MAX_BATCH_LEGS,
+        SolSocialError::InvalidAmount
+    );
+    require!(
+        ctx.remaining_accounts.len() == legs.len() * ACCOUNTS_PER_LEG,
+        SolSocialError::InvalidInstructionData
+    );
+
+    // Reject bad legs before any funds move
+    for (index, leg) in legs.iter().enumerate() {
+        require!(
+            !legs[..index].iter().any(|earlier| earlier.subject == leg.subject),
+            SolSocialError::InvalidInstructionData
+        );
+    }
+
+    let buyer_key = ctx.accounts.buyer.key();
+    let now = Clock::get()?.unix_timestamp;
+    let mut total_cost: u64 = 0;
+    let mut total_keys: u64 = 0;
+
+    for (leg, accounts) in legs.iter().zip(ctx.remaining_accounts.chunks(ACCOUNTS_PER_LEG)) {
+        let (subject_profile_info, user_keys_info, subject_token_info, key_holder_info, user_key_info) =
+            (&accounts[0], &accounts[1], &accounts[2], &accounts[3], &accounts[4]);
+        let creator_profile_info = &accounts[5];
+
+        let (expected_subject_profile, _) =
+            Pubkey::find_program_address(&[b"user_profile", leg.subject.as_ref()], ctx.program_id);
+        let (expected_user_keys, _) =
+            Pubkey::find_program_address(&[b"user_keys", leg.subject.as_ref()], ctx.program_id);
+        let (expected_user_key, _) =
+            Pubkey::find_program_address(&[b"user_key", leg.subject.as_ref()], ctx.program_id);
+        require_keys_eq!(subject_profile_info.key(), expected_subject_profile, SolSocialError::InvalidSeed);
+        require_keys_eq!(user_keys_info.key(), expected_user_keys, SolSocialError::InvalidSeed);
+        require_keys_eq!(user_key_info.key(), expected_user_key, SolSocialError::InvalidSeed);
+
+        let subject_profile = Account::<UserProfile>::try_from(subject_profile_info)?;
+        let mut user_keys = Account::<UserKeys>::try_from(user_keys_info)?;
+        let subject_token_account = Account::<TokenAccount>::try_from(subject_token_info)?;
+        let mut user_key = Account::<UserKey>::try_from(user_key_info)?;
+
+        require!(subject_profile.is_active, SolSocialError::UserAccountInactive);
+        require_keys_eq!(subject_token_account.owner, leg.subject, SolSocialError::InvalidTokenAccount);
+        require_keys_eq!(
+            subject_token_account.mint,
+            ctx.accounts.payment_mint.key(),
+            SolSocialError::TokenMintMismatch
+        );
+
+        // Load the holding up front: the per-trade checks need its balance
+        let (expected_key_holder, key_holder_bump) = Pubkey::find_program_address(
+            &[b"key_holder", expected_user_key.as_ref(), buyer_key.as_ref()],
+            ctx.program_id,
+        );
+        require_keys_eq!(key_holder_info.key(), expected_key_holder, SolSocialError::InvalidSeed);
+        let held = if key_holder_info.data_is_empty() {
+            0
+        } else {
+            Account::<KeyHolder>::try_from(key_holder_info)?.amount
+        };
+
+        let is_own_market = leg.subject == buyer_key;
+        validate_buy(&ctx.accounts.platform_config, &user_keys, is_own_market, held, leg.amount, now)?;
+        let quote = quote_buy_for(
+            &ctx.accounts.platform_config,
+            &ctx.accounts.buyer_profile,
+            &user_keys,
+            &user_key,
+            is_own_market,
+            leg.amount,
+        )?;
+
+        let (expected_creator_profile, _) =
+            Pubkey::find_program_address(&[b"user_profile", user_keys.creator.as_ref()], ctx.program_id);
+        require_keys_eq!(creator_profile_info.key(), expected_creator_profile, SolSocialError::InvalidSeed);
+        let platform_config = &ctx.accounts.platform_config;
+        with_creator_profile(
+            &buyer_key,
+            &mut ctx.accounts.buyer_profile,
+            &user_keys.creator,
+            creator_profile_info,
+            ctx.program_id,
+            |creator_profile| reserve_creator_supply(platform_config, creator_profile, leg.amount),
+        )?;
+
+        total_cost = total_cost
+            .checked_add(quote.total)
+            .ok_or(SolSocialError::MathOverflow)?;
+        require!(total_cost <= max_total_cost, SolSocialError::SlippageExceeded);
+        require!(
+            ctx.accounts.buyer_token_account.amount >= total_cost,
+            SolSocialError::InsufficientFunds
+        );
+
+        // Price and subject fee go to the subject, the protocol fee to the protocol
+        token::transfer(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.buyer_token_account.to_account_info(),
+                    to: subject_token_info.clone(),
+                    authority: ctx.accounts.buyer.to_account_info(),
+                },
+            ),
+            quote.base_price
+                .checked_add(quote.subject_fee)
+                .ok_or(SolSocialError::MathOverflow)?,
+        )?;
+
+        if quote.protocol_fee > 0 {
+            token::transfer(
+                CpiContext::new(
+                    ctx.accounts.token_program.to_account_info(),
+                    Transfer {
+                        from: ctx.accounts.buyer_token_account.to_account_info(),
+                        to: ctx.accounts.protocol_token_account.to_account_info(),
+                        authority: ctx.accounts.buyer.to_account_info(),
+                    },
+                ),
+                quote.protocol_fee,
+            )?;
+        }
+
+        // Legs may open brand new positions, so create the holder PDA when it's missing
+        if key_holder_info.data_is_empty() {
+            let space = KeyHolder::LEN;
+            system_program::create_account(
+                CpiContext::new_with_signer(
+                    ctx.accounts.system_program.to_account_info(),
+                    CreateAccount {
+                        from: ctx.accounts.buyer.to_account_info(),
+                        to: key_holder_info.clone(),
+                    },
+                    &[&[
+                        b"key_holder",
+                        expected_user_key.as_ref(),
+                        buyer_key.as_ref(),
+                        &[key_holder_bump],
+                    ]],
+                ),
+                Rent::get()?.minimum_balance(space),
+                space as u64,
+                ctx.program_id,
+            )?;
+        }
+
+        let is_new_holder = key_holder_info.try_borrow_data()?[..8] == [0u8; 8];
+        let mut key_holder = if is_new_holder {
+            Account::<KeyHolder>::try_from_unchecked(key_holder_info)?
+        } else {
+            Account::<KeyHolder>::try_from(key_holder_info)?
+        };
+        if is_new_holder {
+            key_holder.holder = buyer_key;
+            key_holder.subject = leg.subject;
+            key_holder.amount = 0;
+            key_holder.average_price = 0;
+            key_holder.total_invested = 0;
+            key_holder.first_purchase_at = now;
+            key_holder.bump = key_holder_bump;
+        }
+
+        record_buy(
+            &ctx.accounts.platform_config,
+            &mut ctx.accounts.buyer_profile,
+            &mut user_keys,
+            &mut user_key,
+            &mut key_holder,
+            &quote,
+            leg.amount,
+            now,
+        )?;
+
+        let protocol_fees = &mut ctx.accounts.protocol_fees;
+        protocol_fees.total_fees_collected = protocol_fees.total_fees_collected
+            .checked_add(quote.protocol_fee)
+            .ok_or(SolSocialError::MathOverflow)?;
+
+        total_keys = total_keys
+            .checked_add(leg.amount)
+            .ok_or(SolSocialError::MathOverflow)?;
+
+        // Remaining accounts aren't persisted automatically
+        user_keys.exit(ctx.program_id)?;
+        user_key.exit(ctx.program_id)?;
+        key_holder.exit(ctx.program_id)?;
+    }
+
+    emit!(BatchKeysPurchased {
+        buyer: buyer_key,
+        subjects: legs.iter().map(|leg| leg.subject).collect(),
+        amounts: legs.iter().map(|leg| leg.amount).collect(),
+        total_keys,
+        total_cost,
+        timestamp: now,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct BatchKeysPurchased {
+    pub buyer: Pubkey,
+    pub subjects: Vec<Pubkey>,
+    pub amounts: Vec<u64>,
+    pub total_keys: u64,
+    pub total_cost: u64,
+    pub timestamp: i64,
+}
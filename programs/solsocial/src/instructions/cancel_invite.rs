@@ -0,0 +1,54 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+#[instruction(room_id: String, invite_id: u64)]
+pub struct CancelInvite<'info> {
+    #[account(mut)]
+    pub inviter: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"chat_room", room_id.as_bytes()],
+        bump = chat_room.bump,
+    )]
+    pub chat_room: Account<'info, ChatRoom>,
+
+    #[account(
+        mut,
+        seeds = [b"chat_invite", chat_room.key().as_ref(), &invite_id.to_le_bytes()],
+        bump = invite.bump,
+        constraint = invite.inviter == inviter.key() @ SolSocialError::UnauthorizedUser,
+        close = inviter
+    )]
+    pub invite: Account<'info, ChatInvite>,
+}
+
+/// Withdraws an unredeemed invite, freeing its slot and refunding its rent
+pub fn cancel_invite(ctx: Context<CancelInvite>, _room_id: String, invite_id: u64) -> Result<()> {
+    let chat_room = &mut ctx.accounts.chat_room;
+
+    require!(!ctx.accounts.invite.is_used, SolSocialError::InvalidResourceState);
+
+    chat_room.close_invite();
+
+    emit!(InviteCancelled {
+        chat_room: chat_room.key(),
+        invite_id,
+        inviter: ctx.accounts.inviter.key(),
+        open_invites: chat_room.open_invites,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct InviteCancelled {
+    pub chat_room: Pubkey,
+    pub invite_id: u64,
+    pub inviter: Pubkey,
+    pub open_invites: u32,
+    pub timestamp: i64,
+}
@@ -0,0 +1,65 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+pub struct CancelOrder<'info> {
+    #[account(
+        mut,
+        seeds = [b"order", order.owner.as_ref(), order.subject.as_ref(), &order.order_id.to_le_bytes()],
+        bump = order.bump,
+        constraint = order.owner == owner.key() @ SolSocialError::CannotCancelOrder,
+        close = owner
+    )]
+    pub order: Account<'info, Order>,
+
+    #[account(
+        seeds = [b"user_key", order.subject.as_ref()],
+        bump = user_key.bump,
+    )]
+    pub user_key: Account<'info, UserKey>,
+
+    #[account(
+        mut,
+        seeds = [b"key_holder", user_key.key().as_ref(), owner.key().as_ref()],
+        bump = key_holder.bump,
+    )]
+    pub key_holder: Account<'info, KeyHolder>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+}
+
+/// Closing the order refunds the escrowed lamports and the unpaid keeper bounty to the owner.
+/// Expired orders are cancelled the same way.
+pub fn cancel_order(ctx: Context<CancelOrder>) -> Result<()> {
+    let order = &ctx.accounts.order;
+    let key_holder = &mut ctx.accounts.key_holder;
+
+    if order.side == TradeType::Sell {
+        key_holder.amount = key_holder.amount
+            .checked_add(order.amount)
+            .ok_or(SolSocialError::MathOverflow)?;
+    }
+
+    emit!(OrderCancelled {
+        order: order.key(),
+        owner: order.owner,
+        subject: order.subject,
+        side: order.side.clone(),
+        amount: order.amount,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct OrderCancelled {
+    pub order: Pubkey,
+    pub owner: Pubkey,
+    pub subject: Pubkey,
+    pub side: TradeType,
+    pub amount: u64,
+    pub timestamp: i64,
+}
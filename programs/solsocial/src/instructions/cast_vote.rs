@@ -0,0 +1,77 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+pub struct CastVote<'info> {
+    #[account(
+        mut,
+        seeds = [b"proposal", proposal.subject.as_ref(), &proposal.proposal_id.to_le_bytes()],
+        bump = proposal.bump,
+    )]
+    pub proposal: Account<'info, Proposal>,
+
+    #[account(
+        seeds = [b"holder_snapshot", proposal.key().as_ref()],
+        bump = holder_snapshot.bump,
+        constraint = holder_snapshot.key() == proposal.snapshot @ SolSocialError::InvalidGovernanceProposal
+    )]
+    pub holder_snapshot: Account<'info, HolderSnapshot>,
+
+    // init fails if the voter already has a record for this proposal
+    #[account(
+        init,
+        payer = voter,
+        space = VoteRecord::LEN,
+        seeds = [b"vote", proposal.key().as_ref(), voter.key().as_ref()],
+        bump
+    )]
+    pub vote_record: Account<'info, VoteRecord>,
+
+    #[account(mut)]
+    pub voter: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn cast_vote(ctx: Context<CastVote>, in_favor: bool) -> Result<()> {
+    let proposal = &mut ctx.accounts.proposal;
+    let holder_snapshot = &ctx.accounts.holder_snapshot;
+    let vote_record = &mut ctx.accounts.vote_record;
+    let voter = ctx.accounts.voter.key();
+    let current_time = Clock::get()?.unix_timestamp;
+
+    require!(proposal.is_voting_open(current_time), SolSocialError::VotingPeriodEnded);
+
+    // Holdings acquired after the snapshot don't count
+    let weight = holder_snapshot.voting_power(&voter);
+    require!(weight > 0, SolSocialError::InvalidVotingPower);
+
+    proposal.record_vote(weight, in_favor)?;
+
+    vote_record.proposal = proposal.key();
+    vote_record.voter = voter;
+    vote_record.weight = weight;
+    vote_record.in_favor = in_favor;
+    vote_record.voted_at = current_time;
+    vote_record.bump = ctx.bumps.vote_record;
+
+    emit!(VoteCast {
+        proposal: proposal.key(),
+        voter,
+        weight,
+        in_favor,
+        timestamp: current_time,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct VoteCast {
+    pub proposal: Pubkey,
+    pub voter: Pubkey,
+    pub weight: u64,
+    pub in_favor: bool,
+    pub timestamp: i64,
+}
@@ -0,0 +1,77 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+
+#[derive(Accounts)]
+pub struct CheckPostAccess<'info> {
+    #[account(
+        seeds = [b"post", post.author.as_ref(), &post.id.to_le_bytes()],
+        bump = post.bump,
+    )]
+    pub post: Account<'info, Post>,
+
+    #[account(
+        seeds = [b"user_keys", post.author.as_ref()],
+        bump = author_keys.bump,
+    )]
+    pub author_keys: Account<'info, UserKeys>,
+
+    /// Only needed to view follower-only posts
+    #[account(
+        seeds = [b"follow", viewer.key().as_ref(), post.author.as_ref()],
+        bump = follow_relation.bump,
+    )]
+    pub follow_relation: Option<Account<'info, FollowRelation>>,
+
+    /// Only needed to view premium posts through a subscription
+    #[account(
+        seeds = [b"subscription", post.author.as_ref(), viewer.key().as_ref()],
+        bump = subscription.bump,
+    )]
+    pub subscription: Option<Account<'info, Subscription>>,
+
+    pub viewer: Signer<'info>,
+}
+
+/// Read-only: simulate this and read the `PostAccessChecked` log to see whether the
+/// viewer's keys cover the post at the author's current price
+pub fn check_post_access(ctx: Context<CheckPostAccess>) -> Result<()> {
+    let post = &ctx.accounts.post;
+    let author_keys = &ctx.accounts.author_keys;
+    let viewer = ctx.accounts.viewer.key();
+
+    let required_keys = post.effective_required_keys(author_keys.total_supply)?;
+    let viewer_keys = author_keys.get_holder_balance(&viewer);
+    let now = Clock::get()?.unix_timestamp;
+    let is_subscribed = ctx.accounts.subscription
+        .as_ref()
+        .map_or(false, |subscription| subscription.is_active(now));
+
+    emit!(PostAccessChecked {
+        post: post.key(),
+        viewer,
+        author_key_supply: author_keys.total_supply,
+        required_keys,
+        viewer_keys,
+        is_subscribed,
+        can_view: post.can_view(
+            &viewer,
+            viewer_keys,
+            required_keys,
+            ctx.accounts.follow_relation.as_deref(),
+            is_subscribed,
+        ),
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct PostAccessChecked {
+    pub post: Pubkey,
+    pub viewer: Pubkey,
+    pub author_key_supply: u64,
+    pub required_keys: u64,
+    pub viewer_keys: u64,
+    pub is_subscribed: bool,
+    pub can_view: bool,
+}
@@ -0,0 +1,39 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+pub struct CloseUserProfile<'info> {
+    #[account(
+        mut,
+        seeds = [b"user", user.authority.as_ref()],
+        bump = user.bump,
+        constraint = user.authority == authority.key() @ SolSocialError::AccessDenied,
+        constraint = user.key_supply == 0 @ SolSocialError::KeysOutstanding,
+        close = authority
+    )]
+    pub user: Account<'info, User>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+/// Deletes a profile with no live keys and refunds its rent to the authority
+pub fn close_user_profile(ctx: Context<CloseUserProfile>) -> Result<()> {
+    let user = &ctx.accounts.user;
+
+    emit!(UserProfileClosed {
+        user: user.authority,
+        username: user.username.clone(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct UserProfileClosed {
+    pub user: Pubkey,
+    pub username: String,
+    pub timestamp: i64,
+}
@@ -16,7 +16,7 @@ pub struct CreateChat<'info> {
 
     #[account(
         mut,
-        seeds = [b"user", creator.key().as_ref()],
+        seeds = [b"user_profile", creator.key().as_ref()],
         bump = user_profile.bump,
         constraint = user_profile.owner == creator.key() @ SolSocialError::UnauthorizedUser
     )]
@@ -43,11 +43,13 @@ pub fn create_chat(
     is_private: bool,
     required_keys: u64,
     max_participants: u32,
+    tags: Vec<String>,
 ) -> Result<()> {
     require!(chat_id.len() <= 32, SolSocialError::ChatIdTooLong);
     require!(name.len() <= 64, SolSocialError::ChatNameTooLong);
     require!(description.len() <= 256, SolSocialError::ChatDescriptionTooLong);
     require!(max_participants > 0 && max_participants <= 1000, SolSocialError::InvalidMaxParticipants);
+    ChatRoomMetadata::validate_tags(&tags)?;
     
     if is_private {
         require!(required_keys > 0, SolSocialError::PrivateChatRequiresKeys);
@@ -95,6 +97,7 @@ pub fn create_chat(
         is_private,
         required_keys,
         max_participants,
+        tags,
         timestamp: Clock::get()?.unix_timestamp,
     });
 
@@ -112,5 +115,6 @@ pub struct ChatCreatedEvent {
     pub is_private: bool,
     pub required_keys: u64,
     pub max_participants: u32,
+    pub tags: Vec<String>,
     pub timestamp: i64,
 }
\ No newline at end of file
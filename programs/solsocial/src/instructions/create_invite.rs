@@ -0,0 +1,96 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+#[instruction(room_id: String)]
+pub struct CreateInvite<'info> {
+    #[account(mut)]
+    pub inviter: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"chat_room", room_id.as_bytes()],
+        bump = chat_room.bump,
+        constraint = chat_room.is_active @ SolSocialError::ChatRoomInactive
+    )]
+    pub chat_room: Account<'info, ChatRoom>,
+
+    /// The inviter's own record, needed unless they're the room's creator
+    #[account(
+        seeds = [b"participant", chat_room.key().as_ref(), inviter.key().as_ref()],
+        bump = inviter_participant.bump,
+    )]
+    pub inviter_participant: Option<Account<'info, ChatParticipant>>,
+
+    #[account(
+        init,
+        payer = inviter,
+        space = ChatInvite::LEN,
+        seeds = [b"chat_invite", chat_room.key().as_ref(), &chat_room.invite_count.to_le_bytes()],
+        bump
+    )]
+    pub invite: Account<'info, ChatInvite>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn create_invite(
+    ctx: Context<CreateInvite>,
+    _room_id: String,
+    invitee: Pubkey,
+    expires_at: Option<i64>,
+    invite_type: InviteType,
+) -> Result<()> {
+    let inviter = ctx.accounts.inviter.key();
+    let chat_room = &mut ctx.accounts.chat_room;
+    let now = Clock::get()?.unix_timestamp;
+
+    require!(chat_room.is_participant(&inviter), SolSocialError::UnauthorizedUser);
+    let can_invite_users = inviter == chat_room.creator
+        || ctx.accounts.inviter_participant
+            .as_ref()
+            .map_or(false, |participant| participant.permissions.can_invite_users);
+    require!(can_invite_users, SolSocialError::PermissionDenied);
+    // Only direct invites name their invitee; anyone holding a link invite may redeem it
+    if invite_type == InviteType::Direct {
+        require!(!chat_room.is_participant(&invitee), SolSocialError::InvalidResourceState);
+    }
+    if let Some(expires_at) = expires_at {
+        require!(expires_at > now, SolSocialError::InvalidTimestamp);
+    }
+
+    let invite_id = chat_room.open_invite()?;
+
+    let invite = &mut ctx.accounts.invite;
+    invite.invite_id = invite_id;
+    invite.room_id = chat_room.room_id;
+    invite.inviter = inviter;
+    invite.invitee = invitee;
+    invite.created_at = now;
+    invite.expires_at = expires_at;
+    invite.is_used = false;
+    invite.invite_type = invite_type;
+    invite.bump = ctx.bumps.invite;
+
+    emit!(InviteCreated {
+        chat_room: chat_room.key(),
+        invite_id,
+        inviter,
+        invitee,
+        open_invites: chat_room.open_invites,
+        timestamp: now,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct InviteCreated {
+    pub chat_room: Pubkey,
+    pub invite_id: u64,
+    pub inviter: Pubkey,
+    pub invitee: Pubkey,
+    pub open_invites: u32,
+    pub timestamp: i64,
+}
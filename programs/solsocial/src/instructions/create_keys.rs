@@ -3,6 +3,8 @@ use anchor_spl::token::{self, Token, TokenAccount, Mint};
 use crate::state::*;
 use crate::errors::*;
 use crate::constants::*;
+use crate::utils::bonding_curve::BondingCurve;
+use super::buy_keys::reserve_creator_supply;
 
 #[derive(Accounts)]
 #[instruction(user_pubkey: Pubkey)]
@@ -16,6 +18,16 @@ pub struct CreateKeys<'info> {
     )]
     pub user_keys: Account<'info, UserKeys>,
     
+    // Per-subject fee settings and holder bookkeeping every trade path reads
+    #[account(
+        init,
+        payer = payer,
+        space = UserKey::LEN,
+        seeds = [b"user_key", user_pubkey.as_ref()],
+        bump
+    )]
+    pub user_key: Account<'info, UserKey>,
+    
     #[account(
         init,
         payer = payer,
@@ -59,6 +71,20 @@ pub struct CreateKeys<'info> {
     )]
     pub protocol_treasury: SystemAccount<'info>,
     
+    #[account(
+        seeds = [b"platform_config"],
+        bump = platform_config.bump
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+    
+    // Tracks the creator's issuance across all of their markets
+    #[account(
+        mut,
+        seeds = [b"user_profile", payer.key().as_ref()],
+        bump = creator_profile.bump,
+    )]
+    pub creator_profile: Account<'info, UserProfile>,
+    
     pub system_program: Program<'info, System>,
     pub token_program: Program<'info, Token>,
     pub associated_token_program: Program<'info, anchor_spl::associated_token::AssociatedToken>,
@@ -71,6 +97,8 @@ pub fn create_keys(
     name: String,
     symbol: String,
     uri: String,
+    description: String,
+    links: Vec<String>,
 ) -> Result<()> {
     require!(name.len() <= MAX_NAME_LENGTH, SolSocialError::NameTooLong);
     require!(symbol.len() <= MAX_SYMBOL_LENGTH, SolSocialError::SymbolTooLong);
@@ -89,6 +117,7 @@ pub fn create_keys(
     user_keys.name = name.clone();
     user_keys.symbol = symbol.clone();
     user_keys.uri = uri.clone();
+    user_keys.set_metadata(description, links)?;
     user_keys.total_supply = 0;
     user_keys.created_at = clock.unix_timestamp;
     user_keys.last_trade_at = clock.unix_timestamp;
@@ -97,7 +126,12 @@ pub fn create_keys(
     
     // Calculate initial key price (first key is free for creator)
     let initial_supply = CREATOR_INITIAL_KEYS;
-    let price = calculate_bonding_curve_price(0, initial_supply)?;
+    reserve_creator_supply(
+        &ctx.accounts.platform_config,
+        &mut ctx.accounts.creator_profile,
+        initial_supply,
+    )?;
+    let price = BondingCurve::standard().get_buy_price(0, initial_supply)?;
     
     // Calculate protocol fee
     let protocol_fee = price
@@ -114,6 +148,32 @@ pub fn create_keys(
     
     let total_cost = price.checked_add(protocol_fee).ok_or(SolSocialError::MathOverflow)?;
     
+    // Flat anti-spam cost for opening a market, on top of the protocol fee
+    let launch_fee = ctx.accounts.platform_config.launch_fee;
+    let treasury_due = protocol_fee
+        .checked_add(launch_fee)
+        .ok_or(SolSocialError::MathOverflow)?;
+    require!(
+        ctx.accounts.payer.lamports() >= treasury_due,
+        SolSocialError::InsufficientFunds
+    );
+    
+    if launch_fee > 0 {
+        let transfer_instruction = anchor_lang::solana_program::system_instruction::transfer(
+            &ctx.accounts.payer.key(),
+            &ctx.accounts.protocol_treasury.key(),
+            launch_fee,
+        );
+        
+        anchor_lang::solana_program::program::invoke(
+            &transfer_instruction,
+            &[
+                ctx.accounts.payer.to_account_info(),
+                ctx.accounts.protocol_treasury.to_account_info(),
+            ],
+        )?;
+    }
+    
     // Transfer protocol fee to treasury
     if protocol_fee > 0 {
         let transfer_instruction = anchor_lang::solana_program::system_instruction::transfer(
@@ -152,6 +212,24 @@ pub fn create_keys(
     // Update supply
     user_keys.total_supply = initial_supply;
     
+    // Subjects start on the platform's default creator fee and can adjust it with set_key_fee
+    let platform_config = &ctx.accounts.platform_config;
+    let user_key = &mut ctx.accounts.user_key;
+    user_key.subject = user_pubkey;
+    user_key.supply = initial_supply;
+    user_key.price = BondingCurve::standard().get_price(initial_supply)?;
+    user_key.protocol_fee_percent = platform_config.protocol_fee_percent;
+    user_key.subject_fee_percent = platform_config.subject_fee_percent
+        .min(platform_config.max_subject_fee_percent);
+    user_key.total_volume = 0;
+    user_key.holder_count = 0;
+    user_key.created_at = clock.unix_timestamp;
+    user_key.last_trade_at = clock.unix_timestamp;
+    user_key.is_tradeable = true;
+    user_key.snapshot_count = 0;
+    user_key.last_snapshot_at = 0;
+    user_key.bump = ctx.bumps.user_key;
+    
     // Update protocol stats
     let protocol_config = &mut ctx.accounts.protocol_config;
     protocol_config.total_keys_created = protocol_config
@@ -176,46 +254,13 @@ pub fn create_keys(
         price: price,
         protocol_fee: protocol_fee,
         creator_fee: creator_fee,
+        launch_fee: launch_fee,
         timestamp: clock.unix_timestamp,
     });
     
     Ok(())
 }
 
-fn calculate_bonding_curve_price(current_supply: u64, amount: u64) -> Result<u64> {
-    // Quadratic bonding curve: price = base_price + (supply^2 * curve_factor)
-    let base_price = BASE_KEY_PRICE;
-    let curve_factor = BONDING_CURVE_FACTOR;
-    
-    let mut total_cost = 0u64;
-    
-    for i in 0..amount {
-        let supply_at_step = current_supply
-            .checked_add(i)
-            .ok_or(SolSocialError::MathOverflow)?;
-        
-        let supply_squared = supply_at_step
-            .checked_mul(supply_at_step)
-            .ok_or(SolSocialError::MathOverflow)?;
-        
-        let curve_component = supply_squared
-            .checked_mul(curve_factor)
-            .ok_or(SolSocialError::MathOverflow)?
-            .checked_div(1_000_000)
-            .ok_or(SolSocialError::MathOverflow)?;
-        
-        let step_price = base_price
-            .checked_add(curve_component)
-            .ok_or(SolSocialError::MathOverflow)?;
-        
-        total_cost = total_cost
-            .checked_add(step_price)
-            .ok_or(SolSocialError::MathOverflow)?;
-    }
-    
-    Ok(total_cost)
-}
-
 #[event]
 pub struct KeysCreatedEvent {
     pub user: Pubkey,
@@ -228,5 +273,6 @@ pub struct KeysCreatedEvent {
     pub price: u64,
     pub protocol_fee: u64,
     pub creator_fee: u64,
+    pub launch_fee: u64,
     pub timestamp: i64,
 }
\ No newline at end of file
@@ -1,9 +1,11 @@
 use anchor_lang::prelude::*;
 use crate::state::*;
 use crate::errors::*;
+use crate::utils::reputation::{calculate_content_length_limit, daily_post_bonus};
+use crate::utils::media::is_media_host_allowed;
 
 #[derive(Accounts)]
-#[instruction(content: String, media_urls: Vec<String>)]
+#[instruction(content: String, media_urls: Vec<String>, post_type: PostType, reply_to: Option<Pubkey>)]
 pub struct CreatePost<'info> {
     #[account(
         init,
@@ -29,6 +31,26 @@ pub struct CreatePost<'info> {
     )]
     pub platform_state: Account<'info, PlatformState>,
 
+    #[account(
+        seeds = [b"platform_config"],
+        bump = platform_config.bump
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+
+    /// The post being replied to; required for replies
+    #[account(mut)]
+    pub parent_post: Option<Account<'info, Post>>,
+
+    /// Required only when replying to a post that allows one reply per user
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = ReplyReceipt::LEN,
+        seeds = [b"reply", reply_to.unwrap_or_default().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub reply_receipt: Option<Account<'info, ReplyReceipt>>,
+
     #[account(mut)]
     pub user: Signer<'info>,
 
@@ -43,42 +65,24 @@ pub fn create_post(
     post_type: PostType,
     reply_to: Option<Pubkey>,
     tags: Vec<String>,
+    single_reply_per_user: bool,
+    is_premium: bool,
 ) -> Result<()> {
     let clock = &ctx.accounts.clock;
     let current_timestamp = clock.unix_timestamp;
 
-    // Validate content length
-    require!(
-        content.len() >= 1 && content.len() <= 2000,
-        SolSocialError::InvalidContentLength
-    );
-
-    // Validate media URLs count
-    require!(
-        media_urls.len() <= 10,
-        SolSocialError::TooManyMediaUrls
-    );
-
-    // Validate each media URL length
-    for url in &media_urls {
-        require!(
-            url.len() <= 200,
-            SolSocialError::MediaUrlTooLong
-        );
-    }
+    let platform_config = &ctx.accounts.platform_config;
+    require!(platform_config.is_posting_enabled, SolSocialError::PostingPaused);
 
-    // Validate tags
-    require!(
-        tags.len() <= 20,
-        SolSocialError::TooManyTags
-    );
-
-    for tag in &tags {
-        require!(
-            tag.len() >= 1 && tag.len() <= 50,
-            SolSocialError::InvalidTagLength
-        );
-    }
+    validate_new_post(
+        platform_config,
+        &ctx.accounts.user_profile,
+        &content,
+        &media_urls,
+        &tags,
+        is_premium,
+        current_timestamp,
+    )?;
 
     // Validate reply_to if it's a reply
     if post_type == PostType::Reply {
@@ -86,28 +90,32 @@ pub fn create_post(
             reply_to.is_some(),
             SolSocialError::ReplyMissingParent
         );
-    }
 
-    // Check user reputation for posting limits
-    let user_profile = &mut ctx.accounts.user_profile;
-    require!(
-        user_profile.reputation >= 0,
-        SolSocialError::InsufficientReputation
-    );
+        let parent_post = ctx.accounts.parent_post.as_mut().ok_or(SolSocialError::ReplyMissingParent)?;
+        require!(
+            reply_to == Some(parent_post.key()),
+            SolSocialError::ReplyMissingParent
+        );
+        parent_post.add_reply(ctx.accounts.platform_config.max_engagement_score)?;
 
-    // Rate limiting based on reputation
-    let time_since_last_post = current_timestamp - user_profile.last_post_timestamp;
-    let min_interval = match user_profile.reputation {
-        0..=100 => 300,      // 5 minutes for new users
-        101..=500 => 60,     // 1 minute for established users
-        501..=1000 => 30,    // 30 seconds for reputable users
-        _ => 10,             // 10 seconds for highly reputable users
-    };
+        if parent_post.single_reply_per_user {
+            let reply_receipt = ctx.accounts.reply_receipt.as_mut().ok_or(SolSocialError::AlreadyCommented)?;
+            require!(
+                reply_receipt.user == Pubkey::default(),
+                SolSocialError::AlreadyCommented
+            );
 
-    require!(
-        time_since_last_post >= min_interval,
-        SolSocialError::PostingTooFrequently
-    );
+            reply_receipt.set_inner(ReplyReceipt {
+                parent: parent_post.key(),
+                user: ctx.accounts.user.key(),
+                reply: ctx.accounts.post.key(),
+                timestamp: current_timestamp,
+                bump: *ctx.bumps.get("reply_receipt").unwrap(),
+            });
+        }
+    }
+
+    let user_profile = &mut ctx.accounts.user_profile;
 
     // Initialize post
     let post = &mut ctx.accounts.post;
@@ -123,10 +131,21 @@ pub fn create_post(
     post.replies = 0;
     post.tips_received = 0;
     post.engagement_score = 0;
+    post.downgrade_after_seconds = 0;
+    post.downgrade_engagement_threshold = 0;
     post.is_pinned = false;
     post.is_deleted = false;
+    post.single_reply_per_user = single_reply_per_user;
+    post.is_premium = is_premium;
     post.bump = *ctx.bumps.get("post").unwrap();
 
+    // First post of the UTC day earns the daily bonus; must be checked before the timestamp moves
+    let daily_bonus = daily_post_bonus(
+        user_profile.last_post_timestamp,
+        current_timestamp,
+        ctx.accounts.platform_config.daily_post_reputation_bonus,
+    );
+
     // Update user profile
     user_profile.post_count += 1;
     user_profile.last_post_timestamp = current_timestamp;
@@ -134,6 +153,17 @@ pub fn create_post(
     // Increase reputation for posting (small amount)
     user_profile.reputation += 1;
 
+    if daily_bonus > 0 {
+        user_profile.reputation += daily_bonus as i64;
+
+        emit!(DailyPostBonusAwarded {
+            user: ctx.accounts.user.key(),
+            bonus: daily_bonus,
+            reputation: user_profile.reputation,
+            timestamp: current_timestamp,
+        });
+    }
+
     // Update platform statistics
     let platform_state = &mut ctx.accounts.platform_state;
     platform_state.total_posts += 1;
@@ -162,6 +192,90 @@ pub fn create_post(
     Ok(())
 }
 
+/// Checks every path that creates a post has to pass: content length for the author's
+/// reputation tier, media, tags, premium eligibility and the reputation-based posting interval
+pub(crate) fn validate_new_post(
+    platform_config: &PlatformConfig,
+    user_profile: &UserProfile,
+    content: &str,
+    media_urls: &[String],
+    tags: &[String],
+    is_premium: bool,
+    now: i64,
+) -> Result<()> {
+    // Validate content length against the author's reputation tier
+    let max_content_length = calculate_content_length_limit(
+        user_profile.reputation.max(0) as u64,
+        platform_config.base_content_length,
+        platform_config.content_length_per_tier,
+        platform_config.reputation_per_content_tier,
+        Post::MAX_TIERED_CONTENT_LENGTH,
+    );
+    require!(
+        content.len() >= 1 && content.len() <= max_content_length,
+        SolSocialError::InvalidContentLength
+    );
+
+    // Validate media URLs count
+    require!(
+        media_urls.len() <= 10,
+        SolSocialError::TooManyMediaUrls
+    );
+
+    // Validate each media URL length and host
+    for url in media_urls {
+        require!(
+            url.len() <= 200,
+            SolSocialError::MediaUrlTooLong
+        );
+        require!(
+            is_media_host_allowed(url, &platform_config.media_host_allowlist),
+            SolSocialError::InvalidDataFormat
+        );
+    }
+
+    // Validate tags
+    require!(
+        tags.len() <= 20,
+        SolSocialError::TooManyTags
+    );
+
+    for tag in tags {
+        require!(
+            tag.len() >= 1 && tag.len() <= 50,
+            SolSocialError::InvalidTagLength
+        );
+    }
+
+    // Check user reputation for posting limits
+    require!(
+        user_profile.reputation >= 0,
+        SolSocialError::InsufficientReputation
+    );
+
+    // Only established creators may paywall content
+    require!(
+        !is_premium || user_profile.reputation as u64 >= platform_config.min_reputation_for_premium,
+        SolSocialError::ReputationTooLow
+    );
+
+    // Rate limiting based on reputation
+    let time_since_last_post = now - user_profile.last_post_timestamp;
+    let min_interval = match user_profile.reputation {
+        0..=100 => 300,      // 5 minutes for new users
+        101..=500 => 60,     // 1 minute for established users
+        501..=1000 => 30,    // 30 seconds for reputable users
+        _ => 10,             // 10 seconds for highly reputable users
+    };
+
+    require!(
+        time_since_last_post >= min_interval,
+        SolSocialError::PostingTooFrequently
+    );
+
+    Ok(())
+}
+
 #[event]
 pub struct PostCreated {
     pub post_id: Pubkey,
@@ -171,4 +285,12 @@ pub struct PostCreated {
     pub timestamp: i64,
     pub reply_to: Option<Pubkey>,
     pub tags: Vec<String>,
+}
+
+#[event]
+pub struct DailyPostBonusAwarded {
+    pub user: Pubkey,
+    pub bonus: u64,
+    pub reputation: i64,
+    pub timestamp: i64,
 }
\ No newline at end of file
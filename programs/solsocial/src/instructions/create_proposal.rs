@@ -0,0 +1,145 @@
+use anchor_lang::prelude::*;
+use std::collections::BTreeMap;
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+#[instruction(proposal_id: u64)]
+pub struct CreateProposal<'info> {
+    #[account(
+        init,
+        payer = proposer,
+        space = Proposal::LEN,
+        seeds = [b"proposal", user_keys.owner.as_ref(), &proposal_id.to_le_bytes()],
+        bump
+    )]
+    pub proposal: Account<'info, Proposal>,
+
+    #[account(
+        init,
+        payer = proposer,
+        space = HolderSnapshot::LEN,
+        seeds = [b"holder_snapshot", proposal.key().as_ref()],
+        bump
+    )]
+    pub holder_snapshot: Account<'info, HolderSnapshot>,
+
+    #[account(
+        seeds = [b"user_keys", user_keys.owner.as_ref()],
+        bump = user_keys.bump,
+    )]
+    pub user_keys: Account<'info, UserKeys>,
+
+    #[account(
+        seeds = [b"user_key", user_keys.owner.as_ref()],
+        bump = user_key.bump,
+    )]
+    pub user_key: Account<'info, UserKey>,
+
+    #[account(mut)]
+    pub proposer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Every `KeyHolder` of the market is passed in `remaining_accounts`. The trade paths keep
+/// those balances, so they are what gets frozen; the count is checked against the market's
+/// `holder_count` so a proposer can't leave out the holders likely to vote against them.
+pub fn create_proposal<'info>(
+    ctx: Context<'_, '_, '_, 'info, CreateProposal<'info>>,
+    proposal_id: u64,
+    title: String,
+    voting_period: i64,
+) -> Result<()> {
+    require!(
+        !title.is_empty() && title.len() <= MAX_PROPOSAL_TITLE_LENGTH,
+        SolSocialError::InvalidGovernanceProposal
+    );
+    require!(voting_period > 0, SolSocialError::InvalidGovernanceProposal);
+
+    let user_keys = &ctx.accounts.user_keys;
+    let balances = collect_holder_balances(
+        &ctx.accounts.user_key,
+        ctx.remaining_accounts,
+        ctx.program_id,
+    )?;
+    require!(
+        balances.contains_key(&ctx.accounts.proposer.key()),
+        SolSocialError::InsufficientVotingPower
+    );
+
+    let proposal = &mut ctx.accounts.proposal;
+    let holder_snapshot = &mut ctx.accounts.holder_snapshot;
+    let current_time = Clock::get()?.unix_timestamp;
+
+    // Freeze balances now; votes are weighted against this snapshot only
+    holder_snapshot.proposal = proposal.key();
+    holder_snapshot.subject = user_keys.owner;
+    holder_snapshot.capture(&balances, current_time)?;
+    holder_snapshot.bump = ctx.bumps.holder_snapshot;
+
+    proposal.proposal_id = proposal_id;
+    proposal.subject = user_keys.owner;
+    proposal.proposer = ctx.accounts.proposer.key();
+    proposal.title = title.clone();
+    proposal.snapshot = holder_snapshot.key();
+    proposal.created_at = current_time;
+    proposal.voting_ends_at = current_time
+        .checked_add(voting_period)
+        .ok_or(SolSocialError::ArithmeticOverflow)?;
+    proposal.yes_weight = 0;
+    proposal.no_weight = 0;
+    proposal.bump = ctx.bumps.proposal;
+
+    emit!(ProposalCreated {
+        proposal: proposal.key(),
+        subject: proposal.subject,
+        proposer: proposal.proposer,
+        title,
+        snapshot_total_weight: holder_snapshot.total_weight,
+        voting_ends_at: proposal.voting_ends_at,
+    });
+
+    Ok(())
+}
+
+/// Reads each market `KeyHolder` into a holder -> balance map. Holdings emptied by open sell
+/// orders still count towards `holder_count`, so they must be passed too but carry no weight.
+fn collect_holder_balances<'info>(
+    user_key: &Account<'info, UserKey>,
+    key_holders: &[AccountInfo<'info>],
+    program_id: &Pubkey,
+) -> Result<BTreeMap<Pubkey, u64>> {
+    require!(
+        key_holders.len() as u64 == user_key.holder_count,
+        SolSocialError::InvalidVotingPower
+    );
+
+    let mut balances = BTreeMap::new();
+    for info in key_holders {
+        let key_holder = Account::<KeyHolder>::try_from(info)?;
+        let (expected, _) = Pubkey::find_program_address(
+            &[b"key_holder", user_key.key().as_ref(), key_holder.holder.as_ref()],
+            program_id,
+        );
+        require_keys_eq!(info.key(), expected, SolSocialError::InvalidVotingPower);
+
+        require!(
+            balances.insert(key_holder.holder, key_holder.amount).is_none(),
+            SolSocialError::InvalidVotingPower
+        );
+    }
+    balances.retain(|_, amount| *amount > 0);
+
+    Ok(balances)
+}
+
+#[event]
+pub struct ProposalCreated {
+    pub proposal: Pubkey,
+    pub subject: Pubkey,
+    pub proposer: Pubkey,
+    pub title: String,
+    pub snapshot_total_weight: u64,
+    pub voting_ends_at: i64,
+}
@@ -0,0 +1,66 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::utils::decay::*;
+
+#[derive(Accounts)]
+pub struct DecayInfluence<'info> {
+    #[account(
+        mut,
+        seeds = [b"user", user.authority.as_ref()],
+        bump = user.bump,
+    )]
+    pub user: Account<'info, User>,
+
+    #[account(
+        seeds = [b"platform_config"],
+        bump = platform_config.bump,
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+}
+
+/// Permissionless: indexers call this to keep influence rankings current for dormant users
+pub fn decay_influence(ctx: Context<DecayInfluence>) -> Result<()> {
+    let user = &mut ctx.accounts.user;
+    let platform_config = &ctx.accounts.platform_config;
+    let current_time = Clock::get()?.unix_timestamp;
+
+    // Only decay the stretch that hasn't already been decayed
+    let last_active_at = user.last_active_at();
+    let days_inactive = elapsed_days(
+        std::cmp::max(last_active_at, user.influence_decayed_at),
+        current_time,
+    );
+    let (new_score, decayed_through) = match decay_for_inactivity(
+        user.influence_score,
+        platform_config.influence_decay_rate,
+        last_active_at,
+        user.influence_decayed_at,
+        current_time,
+    )? {
+        Some(decayed) => decayed,
+        None => return Ok(()),
+    };
+
+    let previous_score = user.influence_score;
+    user.influence_score = new_score;
+    user.influence_decayed_at = decayed_through;
+
+    emit!(InfluenceDecayed {
+        user: user.authority,
+        previous_score,
+        new_score: user.influence_score,
+        days_inactive,
+        timestamp: current_time,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct InfluenceDecayed {
+    pub user: Pubkey,
+    pub previous_score: u64,
+    pub new_score: u64,
+    pub days_inactive: u64,
+    pub timestamp: i64,
+}
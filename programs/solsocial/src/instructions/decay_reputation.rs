@@ -0,0 +1,62 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::utils::decay::*;
+
+#[derive(Accounts)]
+pub struct DecayReputation<'info> {
+    #[account(
+        mut,
+        seeds = [b"user_profile", user_profile.authority.as_ref()],
+        bump = user_profile.bump,
+    )]
+    pub user_profile: Account<'info, UserProfile>,
+
+    #[account(
+        seeds = [b"platform_config"],
+        bump = platform_config.bump,
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+}
+
+/// Permissionless: applies at most one day's `reputation_decay_rate` per call, and only
+/// once 24 hours have passed since the last decay, so calling it again the same day is a no-op
+pub fn decay_reputation(ctx: Context<DecayReputation>) -> Result<()> {
+    let user_profile = &mut ctx.accounts.user_profile;
+    let platform_config = &ctx.accounts.platform_config;
+    let current_time = Clock::get()?.unix_timestamp;
+
+    // Decay applies to everything earned so far, settled or not
+    user_profile.settle_reputation();
+
+    let previous_score = user_profile.reputation_score;
+    let new_score = match daily_decay(
+        previous_score,
+        platform_config.reputation_decay_rate,
+        user_profile.last_reputation_update,
+        current_time,
+    )? {
+        Some(new_score) => new_score,
+        None => return Ok(()),
+    };
+
+    // Decay is a fraction of the current score, so it bottoms out at zero
+    user_profile.reputation_score = new_score;
+    user_profile.last_reputation_update = current_time;
+
+    emit!(ReputationDecayed {
+        user: user_profile.authority,
+        previous_score,
+        new_score,
+        timestamp: current_time,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct ReputationDecayed {
+    pub user: Pubkey,
+    pub previous_score: u64,
+    pub new_score: u64,
+    pub timestamp: i64,
+}
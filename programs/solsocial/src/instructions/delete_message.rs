@@ -0,0 +1,71 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+#[instruction(room_id: String, message_id: u64)]
+pub struct DeleteMessage<'info> {
+    pub caller: Signer<'info>,
+
+    #[account(
+        seeds = [b"chat_room", room_id.as_bytes()],
+        bump = chat_room.bump,
+    )]
+    pub chat_room: Account<'info, ChatRoom>,
+
+    #[account(
+        seeds = [b"participant", chat_room.key().as_ref(), caller.key().as_ref()],
+        bump = participant.bump,
+        constraint = participant.user == caller.key() @ SolSocialError::UnauthorizedUser
+    )]
+    pub participant: Account<'info, ChatParticipant>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"message",
+            chat_room.key().as_ref(),
+            &message_id.to_le_bytes()
+        ],
+        bump = message.bump,
+    )]
+    pub message: Account<'info, Message>,
+}
+
+/// Senders can delete their own messages; anyone else needs `can_delete_messages`
+pub fn delete_message(
+    ctx: Context<DeleteMessage>,
+    _room_id: String,
+    message_id: u64,
+) -> Result<()> {
+    let caller = ctx.accounts.caller.key();
+    let participant = &ctx.accounts.participant;
+    let message = &mut ctx.accounts.message;
+
+    require!(
+        caller == message.sender || participant.permissions.can_delete_messages,
+        SolSocialError::PermissionDenied
+    );
+    require!(!message.is_deleted, SolSocialError::InvalidResourceState);
+
+    message.delete();
+
+    emit!(MessageDeleted {
+        message_id,
+        chat_room: ctx.accounts.chat_room.key(),
+        sender: message.sender,
+        deleted_by: caller,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct MessageDeleted {
+    pub message_id: u64,
+    pub chat_room: Pubkey,
+    pub sender: Pubkey,
+    pub deleted_by: Pubkey,
+    pub timestamp: i64,
+}
@@ -0,0 +1,78 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+pub struct DeletePost<'info> {
+    #[account(
+        mut,
+        seeds = [b"post", post.author.as_ref(), &post.id.to_le_bytes()],
+        bump = post.bump,
+        constraint = post.author == author.key() @ SolSocialError::UnauthorizedUser
+    )]
+    pub post: Account<'info, Post>,
+
+    #[account(
+        mut,
+        seeds = [b"user_profile", author.key().as_ref()],
+        bump = author_profile.bump,
+    )]
+    pub author_profile: Account<'info, UserProfile>,
+
+    #[account(
+        mut,
+        seeds = [b"platform_state"],
+        bump = platform_state.bump
+    )]
+    pub platform_state: Account<'info, PlatformState>,
+
+    #[account(mut)]
+    pub author: Signer<'info>,
+}
+
+/// Takes the post down and adjusts the author's and platform's counts. With `close_account`
+/// the post account is closed and its rent refunded, which is only allowed while nothing
+/// replies to it.
+pub fn delete_post(ctx: Context<DeletePost>, close_account: bool) -> Result<()> {
+    let post = &mut ctx.accounts.post;
+    let author_profile = &mut ctx.accounts.author_profile;
+    let platform_state = &mut ctx.accounts.platform_state;
+
+    require!(!post.is_deleted, SolSocialError::PostNotFound);
+    // Replies keep pointing at their parent, so it has to stay around as a tombstone
+    require!(!close_account || post.replies == 0, SolSocialError::InvalidResourceState);
+
+    post.is_deleted = true;
+    post.status = PostStatus::Removed;
+
+    // post_count also seeds new post addresses, so deletions are tracked separately
+    author_profile.deleted_post_count = author_profile.deleted_post_count.saturating_add(1);
+    if post.is_pinned {
+        post.is_pinned = false;
+        author_profile.pinned_posts_count = author_profile.pinned_posts_count.saturating_sub(1);
+    }
+    platform_state.total_posts = platform_state.total_posts.saturating_sub(1);
+
+    emit!(PostDeleted {
+        post: post.key(),
+        author: post.author,
+        closed: close_account,
+        live_post_count: author_profile.live_post_count(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    if close_account {
+        post.close(ctx.accounts.author.to_account_info())?;
+    }
+
+    Ok(())
+}
+
+#[event]
+pub struct PostDeleted {
+    pub post: Pubkey,
+    pub author: Pubkey,
+    pub closed: bool,
+    pub live_post_count: u64,
+    pub timestamp: i64,
+}
@@ -0,0 +1,64 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+pub struct DepositRevenue<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: The creator the revenue is owed to
+    pub subject: AccountInfo<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = RevenueShare::LEN,
+        seeds = [b"revenue_share", subject.key().as_ref()],
+        bump
+    )]
+    pub revenue_share: Account<'info, RevenueShare>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Escrows `amount` lamports in the subject's revenue share until they withdraw it
+pub fn deposit_revenue(ctx: Context<DepositRevenue>, amount: u64) -> Result<()> {
+    require!(amount > 0, SolSocialError::InvalidAmount);
+
+    let transfer_instruction = anchor_lang::system_program::Transfer {
+        from: ctx.accounts.payer.to_account_info(),
+        to: ctx.accounts.revenue_share.to_account_info(),
+    };
+    anchor_lang::system_program::transfer(
+        CpiContext::new(ctx.accounts.system_program.to_account_info(), transfer_instruction),
+        amount,
+    )?;
+
+    let now = Clock::get()?.unix_timestamp;
+    let revenue_share = &mut ctx.accounts.revenue_share;
+    if revenue_share.subject == Pubkey::default() {
+        revenue_share.subject = ctx.accounts.subject.key();
+        revenue_share.bump = ctx.bumps.revenue_share;
+    }
+    revenue_share.credit(amount, now)?;
+
+    emit!(RevenueDeposited {
+        subject: revenue_share.subject,
+        payer: ctx.accounts.payer.key(),
+        amount,
+        pending_withdrawal: revenue_share.pending_withdrawal,
+        timestamp: now,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct RevenueDeposited {
+    pub subject: Pubkey,
+    pub payer: Pubkey,
+    pub amount: u64,
+    pub pending_withdrawal: u64,
+    pub timestamp: i64,
+}
@@ -0,0 +1,75 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+#[instruction(room_id: String, message_id: u64)]
+pub struct EditMessage<'info> {
+    pub sender: Signer<'info>,
+
+    #[account(
+        seeds = [b"chat_room", room_id.as_bytes()],
+        bump = chat_room.bump,
+    )]
+    pub chat_room: Account<'info, ChatRoom>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"message",
+            chat_room.key().as_ref(),
+            &message_id.to_le_bytes()
+        ],
+        bump = message.bump,
+        constraint = message.sender == sender.key() @ SolSocialError::UnauthorizedUser
+    )]
+    pub message: Account<'info, Message>,
+}
+
+pub fn edit_message(
+    ctx: Context<EditMessage>,
+    _room_id: String,
+    message_id: u64,
+    new_content: String,
+    edit_reason: Option<String>,
+) -> Result<()> {
+    let message = &mut ctx.accounts.message;
+
+    // Both the new content and the displaced content end up stored, so hold them to the LEN budget
+    require!(
+        !new_content.is_empty() && new_content.len() <= Message::MAX_CONTENT_LENGTH,
+        SolSocialError::MessageTooLong
+    );
+    require!(
+        message.content.len() <= Message::MAX_CONTENT_LENGTH,
+        SolSocialError::MessageTooLong
+    );
+
+    if let Some(reason) = &edit_reason {
+        require!(
+            reason.len() <= Message::MAX_EDIT_REASON_LENGTH,
+            SolSocialError::EditReasonTooLong
+        );
+    }
+
+    message.edit_content(new_content, edit_reason)?;
+
+    emit!(MessageEdited {
+        message_id,
+        chat_room: ctx.accounts.chat_room.key(),
+        sender: ctx.accounts.sender.key(),
+        edit_count: message.edit_history.len() as u32,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct MessageEdited {
+    pub message_id: u64,
+    pub chat_room: Pubkey,
+    pub sender: Pubkey,
+    pub edit_count: u32,
+    pub timestamp: i64,
+}
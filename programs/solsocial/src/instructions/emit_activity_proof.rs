@@ -0,0 +1,47 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+pub struct EmitActivityProof<'info> {
+    #[account(
+        seeds = [b"user", authority.key().as_ref()],
+        bump = user.bump,
+        constraint = user.authority == authority.key() @ SolSocialError::UnauthorizedUser
+    )]
+    pub user: Account<'info, User>,
+
+    pub authority: Signer<'info>,
+}
+
+/// Publishes a portable reputation attestation; the authority's signature on the
+/// transaction and the slot anchor the summary to a specific account state
+pub fn emit_activity_proof(ctx: Context<EmitActivityProof>) -> Result<()> {
+    let user = &ctx.accounts.user;
+    let clock = Clock::get()?;
+
+    emit!(ActivityProof {
+        user: user.authority,
+        posts_count: user.post_count,
+        total_engagement: user.total_engagement_received(),
+        trade_volume: user.total_volume,
+        reputation_score: user.reputation_score,
+        key_stats_hash: user.key_stats_hash(),
+        slot: clock.slot,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct ActivityProof {
+    pub user: Pubkey,
+    pub posts_count: u64,
+    pub total_engagement: u64,
+    pub trade_volume: u64,
+    pub reputation_score: u64,
+    pub key_stats_hash: [u8; 32],
+    pub slot: u64,
+    pub timestamp: i64,
+}
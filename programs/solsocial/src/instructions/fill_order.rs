@@ -0,0 +1,331 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+use crate::utils::bonding_curve::BondingCurve;
+use super::buy_keys::{check_referrer_payee, quote_buy_for, record_buy, release_creator_supply, reserve_creator_supply, validate_buy, with_creator_profile};
+use super::sell_keys::{quote_sell_for, record_sell, validate_sell};
+use crate::utils::reputation::REPUTATION_PER_HELD_KEY;
+use crate::utils::revenue_share::{check_sell_solvency, distribute_buy_revenue, distribute_sell_revenue};
+
+#[derive(Accounts)]
+pub struct FillOrder<'info> {
+    #[account(
+        mut,
+        seeds = [b"order", order.owner.as_ref(), order.subject.as_ref(), &order.order_id.to_le_bytes()],
+        bump = order.bump,
+        close = owner
+    )]
+    pub order: Account<'info, Order>,
+
+    /// CHECK: Order owner, receives the trade and the order's remaining lamports
+    #[account(mut, address = order.owner)]
+    pub owner: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"user_profile", order.owner.as_ref()],
+        bump = owner_profile.bump,
+    )]
+    pub owner_profile: Account<'info, UserProfile>,
+
+    #[account(
+        seeds = [b"user_profile", order.subject.as_ref()],
+        bump = user_profile.bump,
+    )]
+    pub user_profile: Account<'info, UserProfile>,
+
+    #[account(
+        mut,
+        seeds = [b"user_keys", order.subject.as_ref()],
+        bump = user_keys.bump,
+    )]
+    pub user_keys: Account<'info, UserKeys>,
+
+    /// CHECK: The market creator's `UserProfile`, tracking their issuance across all of their
+    /// markets. Loaded in the handler in case it's the owner's own profile.
+    #[account(
+        mut,
+        seeds = [b"user_profile", user_keys.creator.as_ref()],
+        bump,
+    )]
+    pub creator_profile: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"user_key", order.subject.as_ref()],
+        bump = user_key.bump,
+    )]
+    pub user_key: Account<'info, UserKey>,
+
+    #[account(
+        mut,
+        seeds = [b"key_holder", user_key.key().as_ref(), order.owner.as_ref()],
+        bump = key_holder.bump,
+    )]
+    pub key_holder: Account<'info, KeyHolder>,
+
+    /// Only needed when the owner opted in to receipts for this market
+    #[account(
+        mut,
+        seeds = [b"trade_ledger", order.owner.as_ref(), order.subject.as_ref()],
+        bump = trade_ledger.bump,
+    )]
+    pub trade_ledger: Option<Account<'info, TradeLedger>>,
+
+    #[account(
+        init,
+        payer = keeper,
+        space = TradeReceipt::LEN,
+        seeds = [b"trade_receipt", trade_ledger.key().as_ref(), &trade_ledger.next_sequence.to_le_bytes()],
+        bump
+    )]
+    pub trade_receipt: Option<Account<'info, TradeReceipt>>,
+
+    /// CHECK: The subject's wallet, paid the subject fee as buy_keys and sell_keys pay it
+    #[account(mut, address = order.subject)]
+    pub subject: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"protocol_treasury"],
+        bump
+    )]
+    pub protocol_treasury: SystemAccount<'info>,
+
+    /// The referrer recorded on the order, paid a share of the fees
+    #[account(mut)]
+    pub referrer_profile: Option<Account<'info, UserProfile>>,
+
+    /// CHECK: The referrer's wallet, checked against `referrer_profile` in the handler
+    #[account(mut)]
+    pub referrer: Option<AccountInfo<'info>>,
+
+    /// CHECK: Escrow backing the subject's curve
+    #[account(
+        mut,
+        seeds = [b"escrow", order.subject.as_ref()],
+        bump
+    )]
+    pub escrow_account: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"platform_config"],
+        bump = platform.bump,
+    )]
+    pub platform: Account<'info, PlatformConfig>,
+
+    #[account(mut)]
+    pub keeper: Signer<'info>,
+}
+
+/// Permissionless: anyone may execute an order once the curve's spot price crosses its target,
+/// collecting the order's keeper bounty for doing so.
+pub fn fill_order(ctx: Context<FillOrder>) -> Result<()> {
+    let order = &ctx.accounts.order;
+    let user_profile = &ctx.accounts.user_profile;
+    let user_keys = &mut ctx.accounts.user_keys;
+    let user_key = &mut ctx.accounts.user_key;
+    let owner_profile = &mut ctx.accounts.owner_profile;
+    let key_holder = &mut ctx.accounts.key_holder;
+    let platform = &mut ctx.accounts.platform;
+    let creator_profile_info = ctx.accounts.creator_profile.to_account_info();
+    let now = Clock::get()?.unix_timestamp;
+
+    require!(!order.is_expired(now), SolSocialError::OrderExpired);
+    require!(
+        ctx.accounts.referrer_profile.as_ref().map(|referrer| referrer.authority) == order.referrer,
+        SolSocialError::InvalidAccount
+    );
+    check_referrer_payee(
+        ctx.accounts.referrer_profile.as_deref(),
+        ctx.accounts.referrer.as_ref().map(|referrer| referrer.key()),
+    )?;
+    let referrer_info = ctx.accounts.referrer.as_ref().map(|referrer| referrer.to_account_info());
+
+    let curve = BondingCurve::standard();
+    let spot_price = curve.get_price(user_keys.total_supply)?;
+    require!(order.is_triggered(spot_price), SolSocialError::PriceOutOfRange);
+
+    // A fill is the owner's trade executed late, so it runs the same checks and fees as
+    // buy_keys/sell_keys would have at this moment
+    let amount = order.amount;
+    let is_own_market = order.owner == order.subject;
+    let (price, settled, fees) = match order.side {
+        TradeType::Buy => {
+            validate_buy(platform, user_keys, is_own_market, key_holder.amount, amount, now)?;
+            let quote = quote_buy_for(platform, owner_profile, user_keys, user_key, is_own_market, amount)?;
+            require!(quote.total <= order.max_cost, SolSocialError::SlippageExceeded);
+
+            let platform_config: &PlatformConfig = platform;
+            with_creator_profile(
+                &order.owner,
+                owner_profile,
+                &user_keys.creator,
+                &creator_profile_info,
+                ctx.program_id,
+                |creator_profile| reserve_creator_supply(platform_config, creator_profile, amount),
+            )?;
+
+            let distribution = distribute_buy_revenue(
+                user_keys,
+                &order.owner,
+                ctx.accounts.referrer_profile.as_deref_mut(),
+                quote.base_price,
+                quote.protocol_fee,
+                quote.subject_fee,
+            )?;
+
+            // Only the price backs the curve: the subject fee goes to the subject as in buy_keys,
+            // and the protocol fee to the protocol treasury
+            let order_info = order.to_account_info();
+            move_lamports(&order_info, &ctx.accounts.escrow_account, quote.base_price)?;
+            move_lamports(&order_info, &ctx.accounts.subject, distribution.creator_amount)?;
+            move_lamports(&order_info, &ctx.accounts.protocol_treasury.to_account_info(), distribution.protocol_amount)?;
+            if let Some(referrer) = &referrer_info {
+                move_lamports(&order_info, referrer, distribution.referrer_amount)?;
+            }
+
+            record_buy(platform, owner_profile, user_keys, user_key, key_holder, &quote, amount, now)?;
+
+            let reputation_gain = amount
+                .checked_mul(REPUTATION_PER_HELD_KEY)
+                .ok_or(SolSocialError::MathOverflow)?;
+            owner_profile.reputation_score = owner_profile.reputation_score
+                .checked_add(reputation_gain)
+                .ok_or(SolSocialError::MathOverflow)?;
+
+            let fees = quote.protocol_fee
+                .checked_add(quote.subject_fee)
+                .ok_or(SolSocialError::MathOverflow)?;
+            (quote.base_price, quote.total, fees)
+        }
+        TradeType::Sell => {
+            check_sell_solvency(
+                user_profile.sells_paused,
+                ctx.accounts.escrow_account.lamports(),
+                user_keys.total_supply,
+                platform.min_solvency_ratio_bps,
+            )?;
+
+            // The order locked these keys out of the holding when it was placed; hand them
+            // back so the sale settles exactly like a direct one
+            key_holder.amount = key_holder.amount
+                .checked_add(amount)
+                .ok_or(SolSocialError::MathOverflow)?;
+
+            validate_sell(
+                platform,
+                user_keys,
+                is_own_market,
+                key_holder.amount,
+                key_holder.last_trade_at,
+                amount,
+                now,
+            )?;
+            let quote = quote_sell_for(platform, owner_profile, user_keys, user_key, amount)?;
+            let distribution = distribute_sell_revenue(
+                user_keys,
+                &order.owner,
+                ctx.accounts.referrer_profile.as_deref_mut(),
+                quote.base_price,
+                quote.protocol_fee,
+                quote.subject_fee,
+            )?;
+
+            // The escrow pays out the whole sale price: proceeds to the owner, the subject fee to
+            // the subject as in sell_keys, and the protocol fee to the protocol treasury
+            let escrow = &ctx.accounts.escrow_account;
+            move_lamports(escrow, &ctx.accounts.owner, quote.total)?;
+            move_lamports(escrow, &ctx.accounts.subject, distribution.creator_amount)?;
+            move_lamports(escrow, &ctx.accounts.protocol_treasury.to_account_info(), distribution.protocol_amount)?;
+            if let Some(referrer) = &referrer_info {
+                move_lamports(escrow, referrer, distribution.referrer_amount)?;
+            }
+
+            record_sell(owner_profile, user_keys, user_key, key_holder, &quote, amount, now)?;
+            with_creator_profile(
+                &order.owner,
+                owner_profile,
+                &user_keys.creator,
+                &creator_profile_info,
+                ctx.program_id,
+                |creator_profile| {
+                    release_creator_supply(creator_profile, amount);
+                    Ok(())
+                },
+            )?;
+
+            let reputation_loss = amount
+                .checked_mul(REPUTATION_PER_HELD_KEY)
+                .ok_or(SolSocialError::MathOverflow)?;
+            owner_profile.reputation_score = owner_profile.reputation_score.saturating_sub(reputation_loss);
+
+            let fees = quote.protocol_fee
+                .checked_add(quote.subject_fee)
+                .ok_or(SolSocialError::MathOverflow)?;
+            (quote.base_price, quote.total, fees)
+        }
+    };
+
+    platform.total_volume = platform.total_volume
+        .checked_add(price)
+        .ok_or(SolSocialError::MathOverflow)?;
+
+    issue_trade_receipt(
+        ctx.accounts.trade_ledger.as_mut(),
+        ctx.accounts.trade_receipt.as_mut(),
+        ctx.bumps.trade_receipt,
+        order.side.clone(),
+        amount,
+        price,
+        fees,
+        settled,
+        now,
+    )?;
+
+    // Whatever is left on the order after the bounty goes back to the owner when it closes
+    move_lamports(&order.to_account_info(), &ctx.accounts.keeper.to_account_info(), order.keeper_bounty)?;
+
+    emit!(OrderFilled {
+        order: order.key(),
+        owner: order.owner,
+        subject: order.subject,
+        keeper: ctx.accounts.keeper.key(),
+        side: order.side.clone(),
+        amount,
+        price,
+        settled,
+        keeper_bounty: order.keeper_bounty,
+        supply_after: user_keys.total_supply,
+        timestamp: now,
+    });
+
+    Ok(())
+}
+
+fn move_lamports(from: &AccountInfo, to: &AccountInfo, amount: u64) -> Result<()> {
+    let remaining = from.lamports()
+        .checked_sub(amount)
+        .ok_or(SolSocialError::InsufficientLiquidity)?;
+    **from.try_borrow_mut_lamports()? = remaining;
+    **to.try_borrow_mut_lamports()? = to.lamports()
+        .checked_add(amount)
+        .ok_or(SolSocialError::MathOverflow)?;
+    Ok(())
+}
+
+#[event]
+pub struct OrderFilled {
+    pub order: Pubkey,
+    pub owner: Pubkey,
+    pub subject: Pubkey,
+    pub keeper: Pubkey,
+    pub side: TradeType,
+    pub amount: u64,
+    pub price: u64,
+    pub settled: u64, // cost paid for a buy, proceeds received for a sell
+    pub keeper_bounty: u64,
+    pub supply_after: u64,
+    pub timestamp: i64,
+}
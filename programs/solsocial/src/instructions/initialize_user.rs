@@ -9,7 +9,7 @@ pub struct InitializeUser<'info> {
         init,
         payer = authority,
         space = UserProfile::SPACE,
-        seeds = [b"user", authority.key().as_ref()],
+        seeds = [b"user_profile", authority.key().as_ref()],
         bump
     )]
     pub user_profile: Account<'info, UserProfile>,
@@ -32,6 +32,12 @@ pub struct InitializeUser<'info> {
     )]
     pub user_stats: Account<'info, UserStats>,
     
+    #[account(
+        seeds = [b"platform_config"],
+        bump = platform_config.bump,
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+    
     #[account(mut)]
     pub authority: Signer<'info>,
     
@@ -73,13 +79,21 @@ pub fn handler(
     user_profile.created_at = clock.unix_timestamp;
     user_profile.updated_at = clock.unix_timestamp;
     user_profile.is_verified = false;
+    user_profile.is_moderator = false;
+    user_profile.is_early_adopter = ctx.accounts.platform_config.total_users < PlatformConfig::EARLY_ADOPTER_USER_LIMIT;
     user_profile.is_active = true;
     user_profile.reputation_score = 100; // Starting reputation
+    user_profile.pending_reputation = 0;
+    user_profile.last_reputation_update = clock.unix_timestamp;
+    user_profile.recent_unfollows = 0;
+    user_profile.unfollow_window_start = 0;
     user_profile.influence_score = 0;
     user_profile.total_earnings = 0;
     user_profile.followers_count = 0;
     user_profile.following_count = 0;
     user_profile.posts_count = 0;
+    user_profile.free_trades_remaining = ctx.accounts.platform_config.free_trades_per_user;
+    user_profile.analytics_enabled = true;
     user_profile.bump = ctx.bumps.user_profile;
     
     // Initialize user keys
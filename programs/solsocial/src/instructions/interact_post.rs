@@ -1,6 +1,7 @@
 use anchor_lang::prelude::*;
 use crate::state::*;
 use crate::errors::*;
+use crate::utils::reputation::*;
 
 #[derive(Accounts)]
 #[instruction(interaction_type: u8)]
@@ -38,6 +39,12 @@ pub struct InteractPost<'info> {
     )]
     pub post_author: Account<'info, User>,
 
+    #[account(
+        seeds = [b"user_profile", post.author.as_ref()],
+        bump = author_profile.bump,
+    )]
+    pub author_profile: Account<'info, UserProfile>,
+
     #[account(
         seeds = [b"user_keys", user.authority.as_ref()],
         bump = user_keys.bump,
@@ -45,6 +52,12 @@ pub struct InteractPost<'info> {
     )]
     pub user_keys: Account<'info, UserKeys>,
 
+    #[account(
+        seeds = [b"platform_config"],
+        bump = platform_config.bump,
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+
     #[account(mut)]
     pub authority: Signer<'info>,
 
@@ -62,7 +75,12 @@ pub fn interact_post(
     let interaction = &mut ctx.accounts.interaction;
     let post_author = &mut ctx.accounts.post_author;
     let user_keys = &ctx.accounts.user_keys;
+    let platform_config = &ctx.accounts.platform_config;
     let clock = &ctx.accounts.clock;
+    // Authors who opted out of analytics still get likes and counters, just no SocialStats breakdown
+    let track_stats = ctx.accounts.author_profile.analytics_enabled;
+
+    require!(platform_config.is_posting_enabled, SolSocialError::PostingPaused);
 
     // Validate interaction type
     require!(
@@ -78,6 +96,14 @@ pub fn interact_post(
         );
     }
 
+    // Rate limit interactions so likes can't be spammed to farm reputation
+    require!(
+        clock.unix_timestamp.saturating_sub(user.last_interaction_at)
+            >= platform_config.interaction_cooldown_seconds,
+        SolSocialError::InteractionCooldown
+    );
+    user.last_interaction_at = clock.unix_timestamp;
+
     // Check if user can interact (must own keys or be the author)
     let can_interact = user.authority == post.author || user_keys.supply > 0;
     require!(can_interact, SolSocialError::InsufficientKeysForInteraction);
@@ -89,6 +115,10 @@ pub fn interact_post(
         std::cmp::min(user_keys.supply / 1_000_000, 100) // Max weight of 100
     };
 
+    // Likes from holders other than the author count towards curation
+    let boost_weight = if user.authority == post.author { 0 } else { interaction_weight };
+    let mut curated = false;
+
     // Initialize interaction if needed
     if interaction.user == Pubkey::default() {
         interaction.user = user.key();
@@ -105,7 +135,7 @@ pub fn interact_post(
                 // Unlike
                 interaction.liked = false;
                 post.likes = post.likes.saturating_sub(1);
-                post.engagement_score = post.engagement_score.saturating_sub(interaction_weight);
+                post.remove_boost(boost_weight);
                 
                 // Update user stats
                 user.total_likes_given = user.total_likes_given.saturating_sub(1);
@@ -114,11 +144,15 @@ pub fn interact_post(
                 // Like
                 interaction.liked = true;
                 post.likes = post.likes.saturating_add(1);
-                post.engagement_score = post.engagement_score.saturating_add(interaction_weight);
+                curated = post.add_boost(boost_weight, platform_config.curation_boost_threshold);
                 
                 // Update user stats
                 user.total_likes_given = user.total_likes_given.saturating_add(1);
                 post_author.total_likes_received = post_author.total_likes_received.saturating_add(1);
+                if track_stats {
+                    user.update_social_stats(SocialStatType::Like, 1, true)?;
+                    post_author.update_social_stats(SocialStatType::Like, 1, false)?;
+                }
             }
         },
         1 => {
@@ -127,20 +161,23 @@ pub fn interact_post(
             
             interaction.commented = true;
             interaction.comment_content = content.unwrap();
-            post.comments = post.comments.saturating_add(1);
-            post.engagement_score = post.engagement_score.saturating_add(interaction_weight * 2); // Comments worth more
+            post.replies = post.replies.saturating_add(1);
             
             // Update user stats
             user.total_comments_made = user.total_comments_made.saturating_add(1);
             post_author.total_comments_received = post_author.total_comments_received.saturating_add(1);
+            if track_stats {
+                user.update_social_stats(SocialStatType::Comment, 1, true)?;
+                post_author.update_social_stats(SocialStatType::Comment, 1, false)?;
+            }
         },
         2 => {
             // Share/Unshare
             if interaction.shared {
                 // Unshare
                 interaction.shared = false;
-                post.shares = post.shares.saturating_sub(1);
-                post.engagement_score = post.engagement_score.saturating_sub(interaction_weight * 3);
+                post.reposts = post.reposts.saturating_sub(1);
+                post.counted_reposts = post.counted_reposts.min(post.reposts);
                 
                 // Update user stats
                 user.total_shares_made = user.total_shares_made.saturating_sub(1);
@@ -148,17 +185,53 @@ pub fn interact_post(
             } else {
                 // Share
                 interaction.shared = true;
-                post.shares = post.shares.saturating_add(1);
-                post.engagement_score = post.engagement_score.saturating_add(interaction_weight * 3); // Shares worth most
+                post.reposts = post.reposts.saturating_add(1);
+                if platform_config.max_counted_reposts == 0
+                    || post.counted_reposts < platform_config.max_counted_reposts
+                {
+                    post.counted_reposts = post.counted_reposts.saturating_add(1);
+                }
                 
                 // Update user stats
                 user.total_shares_made = user.total_shares_made.saturating_add(1);
                 post_author.total_shares_received = post_author.total_shares_received.saturating_add(1);
+                if track_stats {
+                    user.update_social_stats(SocialStatType::Share, 1, true)?;
+                    post_author.update_social_stats(SocialStatType::Share, 1, false)?;
+                }
             }
         },
         _ => return Err(SolSocialError::InvalidInteractionType.into()),
     }
 
+    // Recompute from the counters rather than nudging the score, so every path agrees with repost/reply
+    post.update_engagement_score(platform_config.max_engagement_score)?;
+
+    // Reward the author only when engagement reaches a new high, so unlike/relike can't farm reputation
+    if user.authority != post.author && post.engagement_score > post.peak_engagement_score {
+        let engagement_gain = post.engagement_score - post.peak_engagement_score;
+        post.peak_engagement_score = post.engagement_score;
+
+        let remaining_reward = platform_config.max_author_reputation_per_post
+            .saturating_sub(post.author_reputation_awarded);
+        let author_reward = std::cmp::min(
+            engagement_gain.saturating_mul(platform_config.author_reputation_per_engagement as u64),
+            remaining_reward,
+        );
+
+        if author_reward > 0 {
+            post_author.reputation_score = post_author.reputation_score.saturating_add(author_reward);
+            post.author_reputation_awarded = post.author_reputation_awarded.saturating_add(author_reward);
+        }
+    }
+
+    // Indexers rank feeds by this blend rather than raw engagement
+    post.feed_score = calculate_feed_score(
+        post.engagement_score,
+        post_author.reputation_score,
+        platform_config.feed_reputation_weight,
+    )?;
+
     // Update interaction timestamp
     interaction.updated_at = clock.unix_timestamp;
 
@@ -166,7 +239,8 @@ pub fn interact_post(
     post.last_activity = clock.unix_timestamp;
 
     // Calculate and update user influence scores
-    update_influence_scores(user, post_author)?;
+    user.calculate_influence_score()?;
+    post_author.calculate_influence_score()?;
 
     // Emit interaction event
     emit!(PostInteractionEvent {
@@ -175,26 +249,24 @@ pub fn interact_post(
         interaction_type,
         timestamp: clock.unix_timestamp,
         engagement_score: post.engagement_score,
+        feed_score: post.feed_score,
         interaction_weight,
     });
 
-    Ok(())
-}
+    if curated {
+        emit!(PostCurated {
+            post: post.key(),
+            author: post.author,
+            boost_weight: post.boost_weight,
+            curation_threshold: platform_config.curation_boost_threshold,
+            timestamp: clock.unix_timestamp,
+        });
+    }
 
-fn update_influence_scores(user: &mut User, post_author: &mut User) -> Result<()> {
-    // Update user influence based on activity
-    let user_activity_score = user.total_likes_given
-        .saturating_add(user.total_comments_made * 2)
-        .saturating_add(user.total_shares_made * 3);
-    
-    user.influence_score = std::cmp::min(user_activity_score / 10, 10000);
-
-    // Update post author influence based on engagement received
-    let author_engagement_score = post_author.total_likes_received
-        .saturating_add(post_author.total_comments_received * 2)
-        .saturating_add(post_author.total_shares_received * 3);
-    
-    post_author.influence_score = std::cmp::min(author_engagement_score / 5, 10000);
+    // Nothing left to track once everything is reversed: close the PDA and refund its rent
+    if !interaction.liked && !interaction.commented && !interaction.shared {
+        interaction.close(ctx.accounts.authority.to_account_info())?;
+    }
 
     Ok(())
 }
@@ -206,5 +278,15 @@ pub struct PostInteractionEvent {
     pub interaction_type: u8,
     pub timestamp: i64,
     pub engagement_score: u64,
+    pub feed_score: u64,
     pub interaction_weight: u64,
-}
\ No newline at end of file
+}
+
+#[event]
+pub struct PostCurated {
+    pub post: Pubkey,
+    pub author: Pubkey,
+    pub boost_weight: u64,
+    pub curation_threshold: u64,
+    pub timestamp: i64,
+}
@@ -0,0 +1,244 @@
+use anchor_lang::prelude::*;
+use anchor_spl::metadata::MetadataAccount;
+use anchor_spl::token::TokenAccount;
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+#[instruction(room_id: String)]
+pub struct JoinChatRoom<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"chat_room", room_id.as_bytes()],
+        bump = chat_room.bump,
+        constraint = chat_room.is_active @ SolSocialError::ChatRoomInactive
+    )]
+    pub chat_room: Account<'info, ChatRoom>,
+
+    #[account(
+        seeds = [b"user_key", chat_room.creator.as_ref()],
+        bump = creator_key.bump,
+    )]
+    pub creator_key: Account<'info, UserKey>,
+
+    #[account(
+        seeds = [
+            b"key_holder",
+            creator_key.key().as_ref(),
+            user.key().as_ref()
+        ],
+        bump = key_holder.bump,
+    )]
+    pub key_holder: Account<'info, KeyHolder>,
+
+    #[account(
+        seeds = [b"user_profile", user.key().as_ref()],
+        bump = user_profile.bump,
+    )]
+    pub user_profile: Account<'info, UserProfile>,
+
+    /// CHECK: may not exist yet; `BlockList::account_blocks` treats an empty account as blocking nobody
+    #[account(
+        seeds = [b"block_list", chat_room.creator.as_ref()],
+        bump,
+    )]
+    pub creator_block_list: UncheckedAccount<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = JoinRequest::LEN,
+        seeds = [b"join_request", chat_room.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub join_request: Account<'info, JoinRequest>,
+
+    /// Required only when the room has a welcome message and the join takes effect immediately
+    #[account(
+        init,
+        payer = user,
+        space = Message::LEN,
+        seeds = [
+            b"message",
+            chat_room.key().as_ref(),
+            &chat_room.message_count.to_le_bytes()
+        ],
+        bump
+    )]
+    pub welcome_message: Option<Account<'info, Message>>,
+
+    /// Required only when the join takes effect immediately; approved joins get theirs later
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = ChatParticipant::LEN,
+        seeds = [b"participant", chat_room.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub participant: Option<Account<'info, ChatParticipant>>,
+
+    /// Required only when the room is gated on an NFT collection: the caller's token
+    /// account holding the NFT and that NFT's Metaplex metadata
+    pub nft_token_account: Option<Account<'info, TokenAccount>>,
+    pub nft_metadata: Option<Account<'info, MetadataAccount>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn join_chat_room(ctx: Context<JoinChatRoom>, _room_id: String) -> Result<()> {
+    let user = &ctx.accounts.user;
+    let chat_room = &mut ctx.accounts.chat_room;
+    let key_holder = &ctx.accounts.key_holder;
+    let join_request = &mut ctx.accounts.join_request;
+    let current_time = Clock::get()?.unix_timestamp;
+
+    // Key balance gates every join; creator approval is an extra layer on top
+    require!(
+        key_holder.amount >= chat_room.required_key_amount,
+        SolSocialError::InsufficientKeyBalance
+    );
+    // Users the creator has blocked can't get into their rooms
+    require!(
+        !BlockList::account_blocks(&ctx.accounts.creator_block_list, &user.key())?,
+        SolSocialError::UserBanned
+    );
+    require!(
+        !chat_room.access_control.require_verification || ctx.accounts.user_profile.is_verified,
+        SolSocialError::UserVerificationRequired
+    );
+    if let Some(collection) = chat_room.access_control.required_nft_collection {
+        require!(
+            holds_collection_nft(
+                &user.key(),
+                &collection,
+                ctx.accounts.nft_token_account.as_ref(),
+                ctx.accounts.nft_metadata.as_ref(),
+            ),
+            SolSocialError::NFTNotOwned
+        );
+    }
+    require!(
+        !chat_room.is_participant(&user.key()),
+        SolSocialError::InvalidResourceState
+    );
+    require!(
+        ctx.accounts.participant.is_some() != chat_room.require_creator_approval,
+        SolSocialError::InvalidResourceState
+    );
+    // Don't leave an empty message account behind when no welcome will be posted
+    require!(
+        ctx.accounts.welcome_message.is_none()
+            || (!chat_room.require_creator_approval && !chat_room.welcome_message.is_empty()),
+        SolSocialError::InvalidResourceState
+    );
+
+    join_request.room = chat_room.key();
+    join_request.applicant = user.key();
+    join_request.requested_at = current_time;
+    join_request.bump = ctx.bumps.join_request;
+
+    if chat_room.require_creator_approval {
+        join_request.is_approved = false;
+        join_request.approved_at = None;
+
+        emit!(JoinRequested {
+            chat_room: chat_room.key(),
+            applicant: user.key(),
+            timestamp: current_time,
+        });
+    } else {
+        join_request.is_approved = true;
+        join_request.approved_at = Some(current_time);
+        chat_room.add_participant(user.key())?;
+
+        let participant = ctx.accounts.participant
+            .as_mut()
+            .ok_or(SolSocialError::InvalidResourceState)?;
+        participant.set_inner(ChatParticipant::new(
+            user.key(),
+            chat_room.room_id,
+            ParticipantRole::Member,
+            ctx.bumps.participant.unwrap(),
+        ));
+
+        if !chat_room.welcome_message.is_empty() {
+            let welcome_message = ctx.accounts.welcome_message
+                .as_mut()
+                .ok_or(SolSocialError::InvalidResourceState)?;
+
+            // Sent by the room itself rather than any member
+            welcome_message.set_inner(Message::new(
+                chat_room.message_count,
+                chat_room.room_id,
+                chat_room.key(),
+                chat_room.welcome_message.clone(),
+                MessageType::System,
+                None,
+                Vec::new(),
+                ctx.bumps.welcome_message.unwrap(),
+            ));
+            chat_room.increment_message_count();
+
+            emit!(WelcomeMessageSent {
+                chat_room: chat_room.key(),
+                message: welcome_message.key(),
+                new_member: user.key(),
+                timestamp: current_time,
+            });
+        }
+
+        emit!(ChatRoomJoined {
+            chat_room: chat_room.key(),
+            user: user.key(),
+            timestamp: current_time,
+        });
+    }
+
+    Ok(())
+}
+
+/// True when the token account holds an NFT for `user` whose metadata carries a verified
+/// membership in `collection`
+fn holds_collection_nft(
+    user: &Pubkey,
+    collection: &Pubkey,
+    token_account: Option<&Account<TokenAccount>>,
+    metadata: Option<&Account<MetadataAccount>>,
+) -> bool {
+    match (token_account, metadata) {
+        (Some(token_account), Some(metadata)) => {
+            token_account.owner == *user
+                && token_account.amount >= 1
+                && token_account.mint == metadata.mint
+                && metadata.collection
+                    .as_ref()
+                    .map_or(false, |nft_collection| nft_collection.verified && nft_collection.key == *collection)
+        }
+        _ => false,
+    }
+}
+
+#[event]
+pub struct JoinRequested {
+    pub chat_room: Pubkey,
+    pub applicant: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct WelcomeMessageSent {
+    pub chat_room: Pubkey,
+    pub message: Pubkey,
+    pub new_member: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ChatRoomJoined {
+    pub chat_room: Pubkey,
+    pub user: Pubkey,
+    pub timestamp: i64,
+}
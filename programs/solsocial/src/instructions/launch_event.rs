@@ -0,0 +1,104 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{self, Transfer};
+use crate::state::*;
+use crate::errors::*;
+
+/// Keeps a launch cohort inside the compute budget
+pub const MAX_LAUNCH_MARKETS: usize = 10;
+
+#[derive(Accounts)]
+pub struct LaunchEvent<'info> {
+    #[account(
+        seeds = [b"platform_config"],
+        bump = platform_config.bump,
+        constraint = platform_config.authority == authority.key() @ SolSocialError::UnauthorizedUser
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"protocol_treasury"],
+        bump
+    )]
+    pub protocol_treasury: SystemAccount<'info>,
+
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Seeds each subject's empty market escrow with `seed_amount` from the protocol treasury so
+/// a cohort of creators launches with baseline liquidity. Escrows are passed in
+/// `remaining_accounts` in the same order as `subjects`; the treasury must cover the whole
+/// cohort or nothing is seeded.
+pub fn launch_event<'info>(
+    ctx: Context<'_, '_, '_, 'info, LaunchEvent<'info>>,
+    subjects: Vec<Pubkey>,
+    seed_amount: u64,
+) -> Result<()> {
+    require!(
+        !subjects.is_empty() && subjects.len() <= MAX_LAUNCH_MARKETS,
+        SolSocialError::InvalidAmount
+    );
+    require!(
+        ctx.remaining_accounts.len() == subjects.len(),
+        SolSocialError::InvalidInstructionData
+    );
+    // An escrow is a plain system account, so it has to end up rent exempt
+    require!(
+        seed_amount >= Rent::get()?.minimum_balance(0),
+        SolSocialError::InvalidAmount
+    );
+
+    let total = seed_amount
+        .checked_mul(subjects.len() as u64)
+        .ok_or(SolSocialError::MathOverflow)?;
+    require!(
+        ctx.accounts.protocol_treasury.lamports() >= total,
+        SolSocialError::InsufficientFunds
+    );
+
+    let treasury_bump = ctx.bumps.protocol_treasury;
+    let treasury_seeds: &[&[u8]] = &[b"protocol_treasury", &[treasury_bump]];
+
+    for (index, (subject, escrow)) in subjects.iter().zip(ctx.remaining_accounts.iter()).enumerate() {
+        require!(
+            !subjects[..index].contains(subject),
+            SolSocialError::InvalidInstructionData
+        );
+
+        let (expected_escrow, _) = Pubkey::find_program_address(&[b"escrow", subject.as_ref()], ctx.program_id);
+        require_keys_eq!(escrow.key(), expected_escrow, SolSocialError::InvalidSeed);
+        // Only new markets are bootstrapped; funded ones already have their own liquidity
+        require!(escrow.lamports() == 0, SolSocialError::InvalidResourceState);
+
+        system_program::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.system_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.protocol_treasury.to_account_info(),
+                    to: escrow.clone(),
+                },
+                &[treasury_seeds],
+            ),
+            seed_amount,
+        )?;
+    }
+
+    emit!(LaunchEventSeeded {
+        subjects,
+        seed_amount,
+        total,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct LaunchEventSeeded {
+    pub subjects: Vec<Pubkey>,
+    pub seed_amount: u64,
+    pub total: u64,
+    pub timestamp: i64,
+}
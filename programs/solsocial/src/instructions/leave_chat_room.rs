@@ -0,0 +1,57 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+#[instruction(room_id: String)]
+pub struct LeaveChatRoom<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"chat_room", room_id.as_bytes()],
+        bump = chat_room.bump,
+    )]
+    pub chat_room: Account<'info, ChatRoom>,
+
+    /// Members who joined before participant records existed have none to close
+    #[account(
+        mut,
+        close = user,
+        seeds = [b"participant", chat_room.key().as_ref(), user.key().as_ref()],
+        bump = participant.bump,
+    )]
+    pub participant: Option<Account<'info, ChatParticipant>>,
+}
+
+pub fn leave_chat_room(ctx: Context<LeaveChatRoom>, _room_id: String) -> Result<()> {
+    let user = &ctx.accounts.user;
+    let chat_room = &mut ctx.accounts.chat_room;
+
+    // The room would be left without anyone to run it
+    let is_owner = user.key() == chat_room.creator
+        || ctx.accounts.participant
+            .as_ref()
+            .map_or(false, |participant| participant.role == ParticipantRole::Owner);
+    require!(!is_owner, SolSocialError::PermissionDenied);
+
+    chat_room.remove_participant(user.key())?;
+
+    emit!(ChatRoomLeft {
+        chat_room: chat_room.key(),
+        user: user.key(),
+        remaining_participants: chat_room.participants.len() as u32,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct ChatRoomLeft {
+    pub chat_room: Pubkey,
+    pub user: Pubkey,
+    pub remaining_participants: u32,
+    pub timestamp: i64,
+}
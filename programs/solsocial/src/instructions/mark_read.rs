@@ -0,0 +1,52 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+#[instruction(room_id: String)]
+pub struct MarkRead<'info> {
+    #[account(
+        seeds = [b"chat_room", room_id.as_bytes()],
+        bump = chat_room.bump,
+    )]
+    pub chat_room: Account<'info, ChatRoom>,
+
+    #[account(
+        mut,
+        seeds = [b"participant", chat_room.key().as_ref(), user.key().as_ref()],
+        bump = participant.bump,
+        constraint = participant.user == user.key() @ SolSocialError::UnauthorizedUser
+    )]
+    pub participant: Account<'info, ChatParticipant>,
+
+    pub user: Signer<'info>,
+}
+
+/// Records that the caller has seen `message_id`; an older id leaves the pointer where it is
+pub fn mark_read(ctx: Context<MarkRead>, _room_id: String, message_id: u64) -> Result<()> {
+    let chat_room = &ctx.accounts.chat_room;
+    let participant = &mut ctx.accounts.participant;
+
+    require!(message_id < chat_room.message_count, SolSocialError::InvalidInstructionData);
+
+    participant.update_last_read(message_id);
+
+    emit!(ReadReceipt {
+        chat_room: chat_room.key(),
+        user: participant.user,
+        message_id,
+        last_read_message: participant.last_read_message,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct ReadReceipt {
+    pub chat_room: Pubkey,
+    pub user: Pubkey,
+    pub message_id: u64,
+    pub last_read_message: u64,
+    pub timestamp: i64,
+}
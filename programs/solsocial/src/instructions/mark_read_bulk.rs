@@ -0,0 +1,56 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+#[instruction(room_id: String)]
+pub struct MarkReadBulk<'info> {
+    #[account(
+        seeds = [b"chat_room", room_id.as_bytes()],
+        bump = chat_room.bump,
+    )]
+    pub chat_room: Account<'info, ChatRoom>,
+
+    #[account(
+        mut,
+        seeds = [b"participant", chat_room.key().as_ref(), user.key().as_ref()],
+        bump = participant.bump,
+        constraint = participant.user == user.key() @ SolSocialError::UnauthorizedUser
+    )]
+    pub participant: Account<'info, ChatParticipant>,
+
+    pub user: Signer<'info>,
+}
+
+/// Catches a participant up in one call; ids at or below the current pointer are ignored
+pub fn mark_read_bulk(
+    ctx: Context<MarkReadBulk>,
+    _room_id: String,
+    up_to_message_id: u64,
+) -> Result<()> {
+    let chat_room = &ctx.accounts.chat_room;
+    let participant = &mut ctx.accounts.participant;
+
+    // Never point past the newest message in the room
+    let latest_message_id = chat_room.message_count.saturating_sub(1);
+    let previous_last_read = participant.last_read_message;
+
+    participant.update_last_read(up_to_message_id.min(latest_message_id));
+
+    if participant.last_read_message != previous_last_read {
+        emit!(MessagesMarkedRead {
+            chat_room: chat_room.key(),
+            user: participant.user,
+            last_read_message: participant.last_read_message,
+        });
+    }
+
+    Ok(())
+}
+
+#[event]
+pub struct MessagesMarkedRead {
+    pub chat_room: Pubkey,
+    pub user: Pubkey,
+    pub last_read_message: u64,
+}
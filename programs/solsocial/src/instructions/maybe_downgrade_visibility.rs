@@ -0,0 +1,46 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+pub struct MaybeDowngradeVisibility<'info> {
+    #[account(
+        mut,
+        seeds = [b"post", post.author.as_ref(), &post.id.to_le_bytes()],
+        bump = post.bump,
+    )]
+    pub post: Account<'info, Post>,
+}
+
+/// Permissionless: makes the post public if its author's downgrade conditions are met,
+/// and leaves it untouched otherwise
+pub fn maybe_downgrade_visibility(ctx: Context<MaybeDowngradeVisibility>) -> Result<()> {
+    let post = &mut ctx.accounts.post;
+    let now = Clock::get()?.unix_timestamp;
+
+    require!(post.status == PostStatus::Active, SolSocialError::PostNotActive);
+
+    if !post.should_downgrade(now) {
+        return Ok(());
+    }
+
+    post.visibility = PostVisibility::Public;
+    post.is_premium = false;
+
+    emit!(PostVisibilityDowngraded {
+        post: post.key(),
+        author: post.author,
+        engagement_score: post.engagement_score,
+        timestamp: now,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct PostVisibilityDowngraded {
+    pub post: Pubkey,
+    pub author: Pubkey,
+    pub engagement_score: u64,
+    pub timestamp: i64,
+}
@@ -9,8 +9,58 @@ pub mod like_post;
 pub mod comment_post;
 pub mod create_chat_room;
 pub mod send_message;
+pub mod interact_post;
+pub mod initialize_user;
+pub mod create_keys;
+pub mod create_chat;
 pub mod join_chat_room;
 pub mod leave_chat_room;
+pub mod schedule_post;
+pub mod publish_scheduled_post;
+pub mod set_max_scheduled_posts;
+pub mod transfer_room_ownership;
+pub mod set_min_reputation_for_premium;
+pub mod set_max_creator_supply;
+pub mod snapshot_market_stats;
+pub mod set_snapshot_interval;
+pub mod set_required_nft_collection;
+pub mod report_post;
+pub mod moderate_post;
+pub mod set_moderator;
+pub mod set_report_hide_threshold;
+pub mod ban_user;
+pub mod unban_user;
+pub mod appeal_ban;
+pub mod resolve_ban_appeal;
+pub mod set_ban_appeal_window;
+pub mod verify_user;
+pub mod revoke_verification;
+pub mod set_curation_threshold;
+pub mod award_badge;
+pub mod set_badge_thresholds;
+pub mod archive_post;
+pub mod unarchive_post;
+pub mod set_spend_cap;
+pub mod mark_read;
+pub mod delete_post;
+pub mod revalidate_participant;
+pub mod recompute_influence;
+pub mod launch_event;
+pub mod set_max_replies;
+pub mod subscribe;
+pub mod set_subscription_price;
+pub mod set_min_participants;
+pub mod set_influence_decay_rate;
+pub mod set_max_tip;
+pub mod set_author_reputation;
+pub mod set_max_positions;
+pub mod set_reputation_bootstrap;
+pub mod set_badge_revocation;
+pub mod set_free_trades_per_user;
+pub mod set_feed_reputation_weight;
+pub mod set_max_counted_reposts;
+pub mod set_default_max_slippage;
+pub mod set_reputation_decay_rate;
 pub mod create_social_token;
 pub mod stake_social_token;
 pub mod unstake_social_token;
@@ -20,8 +70,81 @@ pub mod mint_social_badge;
 pub mod set_profile_nft;
 pub mod follow_user;
 pub mod unfollow_user;
+pub mod set_max_engagement_score;
+pub mod block_user;
+pub mod unblock_user;
+pub mod set_welcome_message;
+pub mod decay_reputation;
+pub mod set_max_reactions_per_user;
+pub mod set_unfollow_penalty;
+pub mod set_payout_expiry;
+pub mod sweep_expired_payout;
+pub mod set_interaction_cooldown;
+pub mod update_keys_metadata;
+pub mod buy_keys_multi;
+pub mod set_min_buy_amount;
+pub mod place_order;
+pub mod cancel_order;
+pub mod fill_order;
+pub mod settle_reputation;
+pub mod set_reputation_settlement_threshold;
+pub mod set_self_repost_cooldown;
+pub mod set_platform_status;
+pub mod set_media_host_allowlist;
+pub mod set_reactions_require_keys;
+pub mod set_key_fee;
+pub mod set_max_subject_fee;
+pub mod set_visibility_downgrade;
+pub mod maybe_downgrade_visibility;
+pub mod set_cap_self_holdings;
+pub mod set_trade_receipts;
 pub mod tip_creator;
 pub mod withdraw_earnings;
+pub mod set_referral_bonus;
+pub mod decay_influence;
+pub mod create_proposal;
+pub mod cast_vote;
+pub mod tip_post;
+pub mod set_tip_goal;
+pub mod pin_post;
+pub mod unpin_post;
+pub mod emit_activity_proof;
+pub mod edit_message;
+pub mod revoke_badge;
+pub mod restore_badge;
+pub mod set_sell_interval;
+pub mod set_attachment_limit;
+pub mod propose_authority_transfer;
+pub mod accept_authority;
+pub mod set_trading_window;
+pub mod mark_read_bulk;
+pub mod approve_join;
+pub mod set_join_approval;
+pub mod quote_buy;
+pub mod quote_sell;
+pub mod repost_post;
+pub mod set_max_keys_per_trade;
+pub mod close_user_profile;
+pub mod set_launch_fee;
+pub mod set_premium_access_value;
+pub mod check_post_access;
+pub mod set_participant_role;
+pub mod delete_message;
+pub mod set_content_length_tiers;
+pub mod react_to_message;
+pub mod unreact_to_message;
+pub mod set_daily_post_bonus;
+pub mod mute_participant;
+pub mod transfer_keys;
+pub mod set_max_open_invites;
+pub mod create_invite;
+pub mod redeem_invite;
+pub mod cancel_invite;
+pub mod set_analytics_enabled;
+pub mod deposit_revenue;
+pub mod withdraw_revenue;
+pub mod set_multi_holder_discount;
+pub mod set_post_visibility;
 
 pub use initialize_platform::*;
 pub use create_user_profile::*;
@@ -34,6 +157,10 @@ pub use like_post::*;
 pub use comment_post::*;
 pub use create_chat_room::*;
 pub use send_message::*;
+pub use interact_post::*;
+pub use initialize_user::*;
+pub use create_keys::*;
+pub use create_chat::*;
 pub use join_chat_room::*;
 pub use leave_chat_room::*;
 pub use create_social_token::*;
@@ -46,4 +173,123 @@ pub use set_profile_nft::*;
 pub use follow_user::*;
 pub use unfollow_user::*;
 pub use tip_creator::*;
-pub use withdraw_earnings::*;
\ No newline at end of file
+pub use withdraw_earnings::*;
+pub use set_referral_bonus::*;
+pub use decay_influence::*;
+pub use create_proposal::*;
+pub use cast_vote::*;
+pub use tip_post::*;
+pub use set_tip_goal::*;
+pub use pin_post::*;
+pub use unpin_post::*;
+pub use emit_activity_proof::*;
+pub use edit_message::*;
+pub use revoke_badge::*;
+pub use restore_badge::*;
+pub use set_sell_interval::*;
+pub use set_attachment_limit::*;
+pub use propose_authority_transfer::*;
+pub use accept_authority::*;
+pub use set_trading_window::*;
+pub use mark_read_bulk::*;
+pub use approve_join::*;
+pub use set_join_approval::*;
+pub use quote_buy::*;
+pub use quote_sell::*;
+pub use repost_post::*;
+pub use set_max_keys_per_trade::*;
+pub use close_user_profile::*;
+pub use set_launch_fee::*;
+pub use set_premium_access_value::*;
+pub use check_post_access::*;
+pub use set_participant_role::*;
+pub use delete_message::*;
+pub use set_content_length_tiers::*;
+pub use react_to_message::*;
+pub use unreact_to_message::*;
+pub use set_daily_post_bonus::*;
+pub use mute_participant::*;
+pub use transfer_keys::*;
+pub use set_max_open_invites::*;
+pub use create_invite::*;
+pub use redeem_invite::*;
+pub use cancel_invite::*;
+pub use set_analytics_enabled::*;
+pub use deposit_revenue::*;
+pub use withdraw_revenue::*;
+pub use set_multi_holder_discount::*;
+pub use set_post_visibility::*;
+pub use set_max_engagement_score::*;
+pub use block_user::*;
+pub use unblock_user::*;
+pub use set_welcome_message::*;
+pub use decay_reputation::*;
+pub use set_max_reactions_per_user::*;
+pub use set_unfollow_penalty::*;
+pub use set_payout_expiry::*;
+pub use sweep_expired_payout::*;
+pub use set_interaction_cooldown::*;
+pub use update_keys_metadata::*;
+pub use buy_keys_multi::*;
+pub use set_min_buy_amount::*;
+pub use place_order::*;
+pub use cancel_order::*;
+pub use fill_order::*;
+pub use settle_reputation::*;
+pub use set_reputation_settlement_threshold::*;
+pub use set_self_repost_cooldown::*;
+pub use set_platform_status::*;
+pub use set_media_host_allowlist::*;
+pub use set_reactions_require_keys::*;
+pub use set_key_fee::*;
+pub use set_max_subject_fee::*;
+pub use set_visibility_downgrade::*;
+pub use maybe_downgrade_visibility::*;
+pub use set_cap_self_holdings::*;
+pub use set_trade_receipts::*;
+pub use schedule_post::*;
+pub use publish_scheduled_post::*;
+pub use set_max_scheduled_posts::*;
+pub use transfer_room_ownership::*;
+pub use set_min_reputation_for_premium::*;
+pub use set_max_creator_supply::*;
+pub use snapshot_market_stats::*;
+pub use set_snapshot_interval::*;
+pub use set_required_nft_collection::*;
+pub use report_post::*;
+pub use moderate_post::*;
+pub use set_moderator::*;
+pub use set_report_hide_threshold::*;
+pub use ban_user::*;
+pub use unban_user::*;
+pub use appeal_ban::*;
+pub use resolve_ban_appeal::*;
+pub use set_ban_appeal_window::*;
+pub use verify_user::*;
+pub use revoke_verification::*;
+pub use set_curation_threshold::*;
+pub use award_badge::*;
+pub use set_badge_thresholds::*;
+pub use archive_post::*;
+pub use unarchive_post::*;
+pub use set_spend_cap::*;
+pub use mark_read::*;
+pub use delete_post::*;
+pub use revalidate_participant::*;
+pub use recompute_influence::*;
+pub use launch_event::*;
+pub use set_max_replies::*;
+pub use subscribe::*;
+pub use set_subscription_price::*;
+pub use set_min_participants::*;
+pub use set_influence_decay_rate::*;
+pub use set_max_tip::*;
+pub use set_author_reputation::*;
+pub use set_max_positions::*;
+pub use set_reputation_bootstrap::*;
+pub use set_badge_revocation::*;
+pub use set_free_trades_per_user::*;
+pub use set_feed_reputation_weight::*;
+pub use set_max_counted_reposts::*;
+pub use set_default_max_slippage::*;
+pub use set_reputation_decay_rate::*;
\ No newline at end of file
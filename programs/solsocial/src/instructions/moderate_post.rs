@@ -0,0 +1,50 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+pub struct ModeratePost<'info> {
+    #[account(
+        mut,
+        seeds = [b"post", post.author.as_ref(), &post.id.to_le_bytes()],
+        bump = post.bump,
+    )]
+    pub post: Account<'info, Post>,
+
+    #[account(
+        seeds = [b"user_profile", moderator.key().as_ref()],
+        bump = moderator_profile.bump,
+        constraint = moderator_profile.is_moderator @ SolSocialError::ModeratorPrivilegesRequired
+    )]
+    pub moderator_profile: Account<'info, UserProfile>,
+
+    pub moderator: Signer<'info>,
+}
+
+/// `Hidden` or `Removed` takes the post down; `Active` restores it and clears its reports
+pub fn moderate_post(ctx: Context<ModeratePost>, status: PostStatus) -> Result<()> {
+    let post = &mut ctx.accounts.post;
+
+    if status == PostStatus::Active {
+        post.restore()?;
+    } else {
+        post.moderate(status.clone())?;
+    }
+
+    emit!(PostModerated {
+        post: post.key(),
+        moderator: ctx.accounts.moderator.key(),
+        status,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct PostModerated {
+    pub post: Pubkey,
+    pub moderator: Pubkey,
+    pub status: PostStatus,
+    pub timestamp: i64,
+}
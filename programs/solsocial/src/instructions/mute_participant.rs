@@ -0,0 +1,67 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+#[instruction(room_id: String, member: Pubkey)]
+pub struct MuteParticipant<'info> {
+    pub moderator: Signer<'info>,
+
+    #[account(
+        seeds = [b"chat_room", room_id.as_bytes()],
+        bump = chat_room.bump,
+    )]
+    pub chat_room: Account<'info, ChatRoom>,
+
+    #[account(
+        seeds = [b"participant", chat_room.key().as_ref(), moderator.key().as_ref()],
+        bump = moderator_participant.bump,
+        constraint = moderator_participant.user == moderator.key() @ SolSocialError::UnauthorizedUser
+    )]
+    pub moderator_participant: Account<'info, ChatParticipant>,
+
+    #[account(
+        mut,
+        seeds = [b"participant", chat_room.key().as_ref(), member.as_ref()],
+        bump = participant.bump,
+    )]
+    pub participant: Account<'info, ChatParticipant>,
+}
+
+/// Mutes `member` for `duration_seconds`, or until unmuted when no duration is given.
+/// Re-muting replaces any earlier mute.
+pub fn mute_participant(
+    ctx: Context<MuteParticipant>,
+    _room_id: String,
+    member: Pubkey,
+    duration_seconds: Option<i64>,
+) -> Result<()> {
+    require!(
+        ctx.accounts.moderator_participant.permissions.can_mute_users,
+        SolSocialError::PermissionDenied
+    );
+    require!(member != ctx.accounts.moderator.key(), SolSocialError::InvalidResourceState);
+    if let Some(duration) = duration_seconds {
+        require!(duration > 0, SolSocialError::InvalidAmount);
+    }
+
+    let participant = &mut ctx.accounts.participant;
+    participant.mute(duration_seconds);
+
+    emit!(ParticipantMuted {
+        chat_room: ctx.accounts.chat_room.key(),
+        member,
+        muted_by: ctx.accounts.moderator.key(),
+        muted_until: participant.muted_until,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct ParticipantMuted {
+    pub chat_room: Pubkey,
+    pub member: Pubkey,
+    pub muted_by: Pubkey,
+    pub muted_until: Option<i64>,
+}
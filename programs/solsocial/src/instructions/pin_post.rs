@@ -0,0 +1,62 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+pub struct PinPost<'info> {
+    #[account(
+        mut,
+        seeds = [b"post", author.key().as_ref(), &post.id.to_le_bytes()],
+        bump = post.bump,
+        constraint = post.author == author.key() @ SolSocialError::UnauthorizedUser
+    )]
+    pub post: Account<'info, Post>,
+
+    #[account(
+        mut,
+        seeds = [b"user_profile", author.key().as_ref()],
+        bump = user_profile.bump,
+    )]
+    pub user_profile: Account<'info, UserProfile>,
+
+    #[account(
+        seeds = [b"platform_config"],
+        bump = platform_config.bump,
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+
+    pub author: Signer<'info>,
+}
+
+pub fn pin_post(ctx: Context<PinPost>) -> Result<()> {
+    let post = &mut ctx.accounts.post;
+    let user_profile = &mut ctx.accounts.user_profile;
+    let platform_config = &ctx.accounts.platform_config;
+
+    require!(post.status == PostStatus::Active, SolSocialError::PostNotActive);
+    require!(!post.is_pinned, SolSocialError::InvalidResourceState);
+    require!(
+        user_profile.pinned_posts_count < platform_config.max_pinned_posts,
+        SolSocialError::TooManyPinnedPosts
+    );
+
+    post.is_pinned = true;
+    user_profile.pinned_posts_count = user_profile.pinned_posts_count
+        .checked_add(1)
+        .ok_or(SolSocialError::ArithmeticOverflow)?;
+
+    emit!(PostPinned {
+        post: post.key(),
+        author: post.author,
+        pinned_posts_count: user_profile.pinned_posts_count,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct PostPinned {
+    pub post: Pubkey,
+    pub author: Pubkey,
+    pub pinned_posts_count: u64,
+}
@@ -0,0 +1,166 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+#[instruction(order_id: u64)]
+pub struct PlaceOrder<'info> {
+    #[account(
+        init,
+        payer = owner,
+        space = Order::LEN,
+        seeds = [b"order", owner.key().as_ref(), subject.key().as_ref(), &order_id.to_le_bytes()],
+        bump
+    )]
+    pub order: Account<'info, Order>,
+
+    #[account(
+        seeds = [b"user_profile", subject.key().as_ref()],
+        bump = user_profile.bump,
+    )]
+    pub user_profile: Account<'info, UserProfile>,
+
+    /// CHECK: Subject whose keys the order trades
+    pub subject: AccountInfo<'info>,
+
+    #[account(
+        seeds = [b"user_key", subject.key().as_ref()],
+        bump = user_key.bump,
+    )]
+    pub user_key: Account<'info, UserKey>,
+
+    // Buy orders credit this holding when filled; sell orders lock keys out of it now
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = KeyHolder::LEN,
+        seeds = [b"key_holder", user_key.key().as_ref(), owner.key().as_ref()],
+        bump
+    )]
+    pub key_holder: Account<'info, KeyHolder>,
+
+    #[account(
+        seeds = [b"platform_config"],
+        bump = platform_config.bump,
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+
+    /// The owner's referrer, if any, paid a share of the fees when the order fills
+    pub referrer_profile: Option<Account<'info, UserProfile>>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn place_order(
+    ctx: Context<PlaceOrder>,
+    order_id: u64,
+    side: TradeType,
+    target_price: u64,
+    amount: u64,
+    max_cost: u64,
+    expires_at: i64,
+) -> Result<()> {
+    let owner = &ctx.accounts.owner;
+    let subject = &ctx.accounts.subject;
+    let platform_config = &ctx.accounts.platform_config;
+    let key_holder = &mut ctx.accounts.key_holder;
+    let now = Clock::get()?.unix_timestamp;
+
+    // The fill updates the owner's and the subject's profiles separately, so they can't alias
+    require!(owner.key() != subject.key(), SolSocialError::InvalidOrder);
+    require!(amount > 0, SolSocialError::InvalidOrderSize);
+    require!(amount <= platform_config.max_keys_per_trade, SolSocialError::OrderSizeTooLarge);
+    require!(target_price > 0, SolSocialError::InvalidOrderPrice);
+    require!(expires_at > now, SolSocialError::InvalidOrder);
+
+    let referrer = ctx.accounts.referrer_profile.as_ref().map(|referrer| referrer.authority);
+    require!(
+        referrer != Some(owner.key()) && referrer != Some(subject.key()),
+        SolSocialError::SelfInteractionNotAllowed
+    );
+
+    if key_holder.holder == Pubkey::default() {
+        key_holder.holder = owner.key();
+        key_holder.subject = subject.key();
+        key_holder.amount = 0;
+        key_holder.average_price = 0;
+        key_holder.total_invested = 0;
+        key_holder.first_purchase_at = now;
+        key_holder.last_trade_at = now;
+        key_holder.bump = ctx.bumps.key_holder;
+    }
+
+    let escrowed = match side {
+        TradeType::Buy => {
+            require!(amount >= platform_config.min_buy_amount, SolSocialError::OrderSizeTooSmall);
+            require!(max_cost > 0, SolSocialError::InvalidAmount);
+            max_cost
+        }
+        TradeType::Sell => {
+            // Lock the keys so they can't be sold twice while the order is open
+            require!(key_holder.amount >= amount, SolSocialError::InsufficientKeys);
+            key_holder.amount = key_holder.amount
+                .checked_sub(amount)
+                .ok_or(SolSocialError::MathOverflow)?;
+            0
+        }
+    };
+
+    let deposit = escrowed
+        .checked_add(Order::KEEPER_BOUNTY)
+        .ok_or(SolSocialError::MathOverflow)?;
+    anchor_lang::system_program::transfer(
+        CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            anchor_lang::system_program::Transfer {
+                from: owner.to_account_info(),
+                to: ctx.accounts.order.to_account_info(),
+            },
+        ),
+        deposit,
+    )?;
+
+    let order = &mut ctx.accounts.order;
+    order.owner = owner.key();
+    order.subject = subject.key();
+    order.order_id = order_id;
+    order.side = side.clone();
+    order.target_price = target_price;
+    order.amount = amount;
+    order.max_cost = escrowed;
+    order.keeper_bounty = Order::KEEPER_BOUNTY;
+    order.referrer = referrer;
+    order.expires_at = expires_at;
+    order.created_at = now;
+    order.bump = ctx.bumps.order;
+
+    emit!(OrderPlaced {
+        order: order.key(),
+        owner: order.owner,
+        subject: order.subject,
+        side,
+        target_price,
+        amount,
+        max_cost: escrowed,
+        expires_at,
+        timestamp: now,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct OrderPlaced {
+    pub order: Pubkey,
+    pub owner: Pubkey,
+    pub subject: Pubkey,
+    pub side: TradeType,
+    pub target_price: u64,
+    pub amount: u64,
+    pub max_cost: u64,
+    pub expires_at: i64,
+    pub timestamp: i64,
+}
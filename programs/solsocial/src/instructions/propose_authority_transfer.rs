@@ -0,0 +1,47 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+pub struct ProposeAuthorityTransfer<'info> {
+    #[account(
+        mut,
+        seeds = [b"platform_config"],
+        bump = platform_config.bump,
+        constraint = platform_config.authority == authority.key() @ SolSocialError::UnauthorizedUser
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+
+    pub authority: Signer<'info>,
+}
+
+/// First half of the handoff: nothing changes until `new_authority` accepts, and a
+/// mistaken proposal can simply be overwritten by proposing again
+pub fn propose_authority_transfer(
+    ctx: Context<ProposeAuthorityTransfer>,
+    new_authority: Pubkey,
+) -> Result<()> {
+    let platform_config = &mut ctx.accounts.platform_config;
+
+    require!(
+        new_authority != Pubkey::default() && new_authority != platform_config.authority,
+        SolSocialError::InvalidPublicKey
+    );
+
+    platform_config.pending_authority = Some(new_authority);
+
+    emit!(AuthorityTransferProposed {
+        current_authority: platform_config.authority,
+        pending_authority: new_authority,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct AuthorityTransferProposed {
+    pub current_authority: Pubkey,
+    pub pending_authority: Pubkey,
+    pub timestamp: i64,
+}
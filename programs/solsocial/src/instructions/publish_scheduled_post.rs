@@ -0,0 +1,48 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+pub struct PublishScheduledPost<'info> {
+    #[account(
+        mut,
+        seeds = [b"post", post.author.as_ref(), &post.id.to_le_bytes()],
+        bump = post.bump,
+    )]
+    pub post: Account<'info, Post>,
+
+    #[account(
+        mut,
+        seeds = [b"user_profile", post.author.as_ref()],
+        bump = user_profile.bump,
+    )]
+    pub user_profile: Account<'info, UserProfile>,
+}
+
+/// Permissionless: anyone may publish a scheduled post once its time has come
+pub fn publish_scheduled_post(ctx: Context<PublishScheduledPost>) -> Result<()> {
+    let post = &mut ctx.accounts.post;
+    let user_profile = &mut ctx.accounts.user_profile;
+    let current_time = Clock::get()?.unix_timestamp;
+
+    require!(post.status == PostStatus::Scheduled, SolSocialError::InvalidResourceState);
+    require!(current_time >= post.timestamp, SolSocialError::InvalidTimestamp);
+
+    post.status = PostStatus::Active;
+    user_profile.scheduled_posts_count = user_profile.scheduled_posts_count.saturating_sub(1);
+
+    emit!(ScheduledPostPublished {
+        post: post.key(),
+        author: post.author,
+        timestamp: current_time,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct ScheduledPostPublished {
+    pub post: Pubkey,
+    pub author: Pubkey,
+    pub timestamp: i64,
+}
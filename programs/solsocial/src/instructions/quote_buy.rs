@@ -0,0 +1,69 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+use crate::events::*;
+use crate::utils::bonding_curve::BondingCurve;
+use super::buy_keys::quote_buy_for;
+
+#[derive(Accounts)]
+pub struct QuoteBuy<'info> {
+    #[account(
+        seeds = [b"user_keys", subject.key().as_ref()],
+        bump = user_keys.bump,
+    )]
+    pub user_keys: Account<'info, UserKeys>,
+
+    /// CHECK: The subject whose keys are being quoted
+    pub subject: AccountInfo<'info>,
+
+    #[account(
+        seeds = [b"user_key", subject.key().as_ref()],
+        bump = user_key.bump,
+    )]
+    pub user_key: Account<'info, UserKey>,
+
+    /// CHECK: The wallet the quote is for; free trades and the own-market waiver depend on it
+    pub trader: AccountInfo<'info>,
+
+    #[account(
+        seeds = [b"user_profile", trader.key().as_ref()],
+        bump = trader_profile.bump,
+    )]
+    pub trader_profile: Account<'info, UserProfile>,
+
+    #[account(
+        seeds = [b"platform_config"],
+        bump = platform_config.bump,
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+}
+
+/// Read-only: simulate this and read the `PriceQuote` log to preview a `buy_keys`
+pub fn quote_buy(ctx: Context<QuoteBuy>, amount: u64) -> Result<()> {
+    require!(amount > 0, SolSocialError::InvalidAmount);
+
+    let supply = ctx.accounts.user_keys.total_supply;
+    let quote = quote_buy_for(
+        &ctx.accounts.platform_config,
+        &ctx.accounts.trader_profile,
+        &ctx.accounts.user_keys,
+        &ctx.accounts.user_key,
+        ctx.accounts.trader.key() == ctx.accounts.subject.key(),
+        amount,
+    )?;
+    let impact_bps = BondingCurve::standard().price_impact_bps(supply, amount, true)?;
+
+    emit!(PriceQuote {
+        subject: ctx.accounts.subject.key(),
+        is_buy: true,
+        supply,
+        amount,
+        base_price: quote.base_price,
+        protocol_fee: quote.protocol_fee,
+        subject_fee: quote.subject_fee,
+        total: quote.total,
+        impact_bps,
+    });
+
+    Ok(())
+}
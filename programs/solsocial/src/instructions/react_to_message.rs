@@ -0,0 +1,107 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+use crate::events::*;
+
+#[derive(Accounts)]
+#[instruction(room_id: String, message_id: u64)]
+pub struct ReactToMessage<'info> {
+    pub user: Signer<'info>,
+
+    #[account(
+        seeds = [b"chat_room", room_id.as_bytes()],
+        bump = chat_room.bump,
+    )]
+    pub chat_room: Account<'info, ChatRoom>,
+
+    #[account(
+        seeds = [b"participant", chat_room.key().as_ref(), user.key().as_ref()],
+        bump = participant.bump,
+        constraint = participant.user == user.key() @ SolSocialError::UnauthorizedUser
+    )]
+    pub participant: Account<'info, ChatParticipant>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"message",
+            chat_room.key().as_ref(),
+            &message_id.to_le_bytes()
+        ],
+        bump = message.bump,
+    )]
+    pub message: Account<'info, Message>,
+
+    #[account(
+        seeds = [b"user_key", chat_room.creator.as_ref()],
+        bump = creator_key.bump,
+    )]
+    pub creator_key: Account<'info, UserKey>,
+
+    // Only needed in rooms that reserve reactions for key holders
+    #[account(
+        seeds = [b"key_holder", creator_key.key().as_ref(), user.key().as_ref()],
+        bump = key_holder.bump,
+    )]
+    pub key_holder: Option<Account<'info, KeyHolder>>,
+}
+
+pub fn react_to_message(
+    ctx: Context<ReactToMessage>,
+    _room_id: String,
+    message_id: u64,
+    emoji: String,
+) -> Result<()> {
+    let user = ctx.accounts.user.key();
+    let chat_room = &ctx.accounts.chat_room;
+    let message = &mut ctx.accounts.message;
+
+    require!(
+        ctx.accounts.participant.permissions.can_send_messages,
+        SolSocialError::PermissionDenied
+    );
+    require!(
+        !emoji.is_empty() && emoji.len() <= Message::MAX_REACTION_LENGTH,
+        SolSocialError::ReactionTooLong
+    );
+    require!(!message.is_deleted, SolSocialError::InvalidResourceState);
+
+    if chat_room.reactions_require_keys {
+        let holds_keys = ctx.accounts.key_holder.as_ref().map_or(false, |holder| holder.amount > 0);
+        require!(holds_keys, SolSocialError::InsufficientKeyBalance);
+    }
+
+    // Both caps keep the map inside the space reserved by Message::LEN
+    match message.reactions.get(&emoji) {
+        Some(users) => require!(
+            users.contains(&user) || users.len() < Message::MAX_REACTORS_PER_TYPE,
+            SolSocialError::TooManyReactions
+        ),
+        None => require!(
+            message.reactions.len() < Message::MAX_REACTION_TYPES,
+            SolSocialError::TooManyReactions
+        ),
+    }
+
+    // Re-adding an existing reaction is left to add_reaction to reject
+    let already_reacted = message.reactions.get(&emoji).map_or(false, |users| users.contains(&user));
+    require!(
+        already_reacted
+            || chat_room.max_reactions_per_user == 0
+            || message.reactions_by(&user) < chat_room.max_reactions_per_user as usize,
+        SolSocialError::ResourceNotAvailable
+    );
+
+    message.add_reaction(emoji.clone(), user)?;
+
+    emit!(MessageReactionChanged {
+        message_id,
+        chat_room: chat_room.key(),
+        user,
+        emoji: emoji.clone(),
+        added: true,
+        count: message.reactions.get(&emoji).map_or(0, |users| users.len() as u32),
+    });
+
+    Ok(())
+}
@@ -0,0 +1,59 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::utils::decay::*;
+
+#[derive(Accounts)]
+pub struct RecomputeInfluence<'info> {
+    #[account(
+        mut,
+        seeds = [b"user", user.authority.as_ref()],
+        bump = user.bump,
+    )]
+    pub user: Account<'info, User>,
+
+    #[account(
+        seeds = [b"platform_config"],
+        bump = platform_config.bump,
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+}
+
+/// Permissionless: brings accounts scored by older formulas in line with
+/// `User::calculate_influence_score`, re-applying the inactivity decay the raw formula doesn't know about
+pub fn recompute_influence(ctx: Context<RecomputeInfluence>) -> Result<()> {
+    let user = &mut ctx.accounts.user;
+    let platform_config = &ctx.accounts.platform_config;
+    let current_time = Clock::get()?.unix_timestamp;
+
+    let previous_score = user.influence_score;
+    user.calculate_influence_score()?;
+
+    // The fresh score is undecayed, so decay it over the whole stretch since the last activity
+    if let Some((new_score, decayed_through)) = decay_for_inactivity(
+        user.influence_score,
+        platform_config.influence_decay_rate,
+        user.last_active_at(),
+        0,
+        current_time,
+    )? {
+        user.influence_score = new_score;
+        user.influence_decayed_at = decayed_through;
+    }
+
+    emit!(InfluenceRecomputed {
+        user: user.authority,
+        previous_score,
+        new_score: user.influence_score,
+        timestamp: current_time,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct InfluenceRecomputed {
+    pub user: Pubkey,
+    pub previous_score: u64,
+    pub new_score: u64,
+    pub timestamp: i64,
+}
@@ -0,0 +1,84 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+#[instruction(room_id: String, invite_id: u64)]
+pub struct RedeemInvite<'info> {
+    #[account(mut)]
+    pub redeemer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"chat_room", room_id.as_bytes()],
+        bump = chat_room.bump,
+        constraint = chat_room.is_active @ SolSocialError::ChatRoomInactive
+    )]
+    pub chat_room: Account<'info, ChatRoom>,
+
+    #[account(
+        mut,
+        seeds = [b"chat_invite", chat_room.key().as_ref(), &invite_id.to_le_bytes()],
+        bump = invite.bump,
+    )]
+    pub invite: Account<'info, ChatInvite>,
+
+    /// Optional so unfunded invitees can still redeem; a role can be assigned later instead
+    #[account(
+        init_if_needed,
+        payer = redeemer,
+        space = ChatParticipant::LEN,
+        seeds = [b"participant", chat_room.key().as_ref(), redeemer.key().as_ref()],
+        bump
+    )]
+    pub participant: Option<Account<'info, ChatParticipant>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn redeem_invite(ctx: Context<RedeemInvite>, _room_id: String, invite_id: u64) -> Result<()> {
+    let redeemer = ctx.accounts.redeemer.key();
+    let chat_room = &mut ctx.accounts.chat_room;
+    let invite = &mut ctx.accounts.invite;
+    let now = Clock::get()?.unix_timestamp;
+
+    require!(!invite.is_used, SolSocialError::InvalidResourceState);
+    require!(!invite.is_expired(now), SolSocialError::ResourceExpired);
+    if invite.invite_type == InviteType::Direct {
+        require_keys_eq!(invite.invitee, redeemer, SolSocialError::UnauthorizedUser);
+    }
+
+    // Link invites record whoever ended up using them
+    invite.invitee = redeemer;
+    invite.is_used = true;
+    chat_room.close_invite();
+    chat_room.add_participant(redeemer)?;
+
+    if let Some(participant) = ctx.accounts.participant.as_mut() {
+        participant.set_inner(ChatParticipant::new(
+            redeemer,
+            chat_room.room_id,
+            ParticipantRole::Member,
+            ctx.bumps.participant.unwrap(),
+        ));
+    }
+
+    emit!(InviteRedeemed {
+        chat_room: chat_room.key(),
+        invite_id,
+        invitee: invite.invitee,
+        open_invites: chat_room.open_invites,
+        timestamp: now,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct InviteRedeemed {
+    pub chat_room: Pubkey,
+    pub invite_id: u64,
+    pub invitee: Pubkey,
+    pub open_invites: u32,
+    pub timestamp: i64,
+}
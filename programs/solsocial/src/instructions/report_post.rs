@@ -0,0 +1,73 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+pub struct ReportPost<'info> {
+    #[account(
+        mut,
+        seeds = [b"post", post.author.as_ref(), &post.id.to_le_bytes()],
+        bump = post.bump,
+    )]
+    pub post: Account<'info, Post>,
+
+    #[account(
+        init,
+        payer = reporter,
+        space = PostReport::LEN,
+        seeds = [b"post_report", post.key().as_ref(), reporter.key().as_ref()],
+        bump
+    )]
+    pub post_report: Account<'info, PostReport>,
+
+    #[account(
+        seeds = [b"platform_config"],
+        bump = platform_config.bump,
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+
+    #[account(mut)]
+    pub reporter: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Files a report against a post; enough reports hide it until a moderator restores it
+pub fn report_post(ctx: Context<ReportPost>, reason_code: u8) -> Result<()> {
+    let post = &mut ctx.accounts.post;
+    let reporter = ctx.accounts.reporter.key();
+    let current_time = Clock::get()?.unix_timestamp;
+
+    require!(post.author != reporter, SolSocialError::SelfInteractionNotAllowed);
+
+    let hidden = post.record_report(ctx.accounts.platform_config.report_hide_threshold)?;
+
+    ctx.accounts.post_report.set_inner(PostReport {
+        reporter,
+        post: post.key(),
+        reason_code,
+        timestamp: current_time,
+        bump: ctx.bumps.post_report,
+    });
+
+    emit!(PostReported {
+        post: post.key(),
+        reporter,
+        reason_code,
+        report_count: post.report_count,
+        hidden,
+        timestamp: current_time,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct PostReported {
+    pub post: Pubkey,
+    pub reporter: Pubkey,
+    pub reason_code: u8,
+    pub report_count: u32,
+    pub hidden: bool,
+    pub timestamp: i64,
+}
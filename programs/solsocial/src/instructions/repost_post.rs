@@ -0,0 +1,58 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+pub struct RepostPost<'info> {
+    #[account(
+        mut,
+        seeds = [b"post", post.author.as_ref(), &post.id.to_le_bytes()],
+        bump = post.bump,
+    )]
+    pub post: Account<'info, Post>,
+
+    #[account(
+        seeds = [b"platform_config"],
+        bump = platform_config.bump,
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+
+    pub reposter: Signer<'info>,
+}
+
+pub fn repost_post(ctx: Context<RepostPost>) -> Result<()> {
+    let post = &mut ctx.accounts.post;
+
+    // Authors can't boost their own post right after publishing it
+    if post.author == ctx.accounts.reposter.key() {
+        let post_age = Clock::get()?.unix_timestamp.saturating_sub(post.timestamp);
+        require!(
+            post_age >= ctx.accounts.platform_config.self_repost_cooldown_seconds,
+            SolSocialError::InteractionCooldown
+        );
+    }
+
+    post.repost(
+        ctx.accounts.platform_config.max_counted_reposts,
+        ctx.accounts.platform_config.max_engagement_score,
+    )?;
+
+    emit!(PostReposted {
+        post: post.key(),
+        reposter: ctx.accounts.reposter.key(),
+        reposts: post.reposts,
+        engagement_score: post.engagement_score,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct PostReposted {
+    pub post: Pubkey,
+    pub reposter: Pubkey,
+    pub reposts: u64,
+    pub engagement_score: u64,
+    pub timestamp: i64,
+}
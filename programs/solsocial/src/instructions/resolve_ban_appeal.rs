@@ -0,0 +1,79 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+use super::ban_user::can_moderate_users;
+
+#[derive(Accounts)]
+pub struct ResolveBanAppeal<'info> {
+    #[account(
+        mut,
+        seeds = [b"user", banned_user.authority.as_ref()],
+        bump = banned_user.bump,
+    )]
+    pub banned_user: Account<'info, User>,
+
+    #[account(
+        mut,
+        seeds = [b"ban_appeal", banned_user.authority.as_ref(), &ban_appeal.banned_at.to_le_bytes()],
+        bump = ban_appeal.bump,
+    )]
+    pub ban_appeal: Account<'info, BanAppeal>,
+
+    #[account(
+        seeds = [b"platform_config"],
+        bump = platform_config.bump,
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+
+    #[account(
+        seeds = [b"user_profile", moderator.key().as_ref()],
+        bump = moderator_profile.bump,
+    )]
+    pub moderator_profile: Option<Account<'info, UserProfile>>,
+
+    pub moderator: Signer<'info>,
+}
+
+/// Approving lifts the ban the appeal was filed against; rejecting leaves it in place
+pub fn resolve_ban_appeal(ctx: Context<ResolveBanAppeal>, approve: bool) -> Result<()> {
+    let moderator = ctx.accounts.moderator.key();
+    require!(
+        can_moderate_users(&moderator, &ctx.accounts.platform_config, ctx.accounts.moderator_profile.as_ref()),
+        SolSocialError::ModeratorPrivilegesRequired
+    );
+
+    let banned_user = &mut ctx.accounts.banned_user;
+    let ban_appeal = &mut ctx.accounts.ban_appeal;
+    require!(!ban_appeal.is_resolved, SolSocialError::InvalidResourceState);
+
+    if approve {
+        require!(
+            banned_user.banned && banned_user.banned_at == ban_appeal.banned_at,
+            SolSocialError::InvalidResourceState
+        );
+        banned_user.unban()?;
+    }
+
+    ban_appeal.is_resolved = true;
+    ban_appeal.is_approved = approve;
+    ban_appeal.resolved_by = moderator;
+
+    emit!(BanAppealResolved {
+        user: banned_user.authority,
+        appeal: ban_appeal.key(),
+        approved: approve,
+        resolved_by: moderator,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct BanAppealResolved {
+    pub user: Pubkey,
+    pub appeal: Pubkey,
+    pub approved: bool,
+    pub resolved_by: Pubkey,
+    pub timestamp: i64,
+}
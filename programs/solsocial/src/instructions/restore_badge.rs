@@ -0,0 +1,62 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+pub struct RestoreBadge<'info> {
+    #[account(
+        mut,
+        constraint = badge.user == holder.authority @ SolSocialError::InvalidPublicKey
+    )]
+    pub badge: Account<'info, UserBadge>,
+
+    #[account(
+        seeds = [b"user", holder.authority.as_ref()],
+        bump = holder.bump,
+    )]
+    pub holder: Account<'info, User>,
+
+    #[account(
+        seeds = [b"platform_config"],
+        bump = platform_config.bump,
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+
+    #[account(
+        constraint = caller.key() == badge.user
+            || caller.key() == platform_config.authority @ SolSocialError::UnauthorizedUser
+    )]
+    pub caller: Signer<'info>,
+}
+
+/// Reactivates a revoked badge once the holder is back in good standing, if the platform allows it.
+/// Only the badge's owner or the platform authority may restore it.
+pub fn restore_badge(ctx: Context<RestoreBadge>) -> Result<()> {
+    let badge = &mut ctx.accounts.badge;
+    let holder = &ctx.accounts.holder;
+    let platform_config = &ctx.accounts.platform_config;
+
+    require!(platform_config.restore_badges_on_unban, SolSocialError::FeatureDisabled);
+    require!(!badge.is_active, SolSocialError::InvalidResourceState);
+    require!(
+        holder.is_in_good_standing(platform_config.badge_reputation_floor),
+        SolSocialError::PermissionDenied
+    );
+
+    badge.is_active = true;
+
+    emit!(BadgeRestored {
+        user: badge.user,
+        badge: badge.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct BadgeRestored {
+    pub user: Pubkey,
+    pub badge: Pubkey,
+    pub timestamp: i64,
+}
@@ -0,0 +1,72 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+#[instruction(room_id: String, member: Pubkey)]
+pub struct RevalidateParticipant<'info> {
+    #[account(
+        seeds = [b"chat_room", room_id.as_bytes()],
+        bump = chat_room.bump,
+    )]
+    pub chat_room: Account<'info, ChatRoom>,
+
+    #[account(
+        mut,
+        seeds = [b"participant", chat_room.key().as_ref(), member.as_ref()],
+        bump = participant.bump,
+    )]
+    pub participant: Account<'info, ChatParticipant>,
+
+    #[account(
+        seeds = [b"user_key", chat_room.creator.as_ref()],
+        bump = creator_key.bump,
+    )]
+    pub creator_key: Account<'info, UserKey>,
+
+    #[account(
+        seeds = [b"key_holder", creator_key.key().as_ref(), member.as_ref()],
+        bump = key_holder.bump,
+    )]
+    pub key_holder: Account<'info, KeyHolder>,
+
+    pub caller: Signer<'info>,
+}
+
+/// Permissionless re-check of a member's holdings against the room's `required_key_amount`,
+/// so members who sold out after joining show as lapsed without having to message first
+pub fn revalidate_participant(
+    ctx: Context<RevalidateParticipant>,
+    _room_id: String,
+    member: Pubkey,
+) -> Result<()> {
+    let chat_room = &ctx.accounts.chat_room;
+    let participant = &mut ctx.accounts.participant;
+    let key_balance = ctx.accounts.key_holder.amount;
+
+    require_keys_eq!(participant.user, member, SolSocialError::UnauthorizedUser);
+
+    let is_lapsed = key_balance == 0 || key_balance < chat_room.required_key_amount;
+    if participant.is_lapsed != is_lapsed {
+        participant.is_lapsed = is_lapsed;
+
+        emit!(ParticipantAccessChanged {
+            chat_room: chat_room.key(),
+            user: member,
+            key_balance,
+            is_lapsed,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+    }
+
+    Ok(())
+}
+
+#[event]
+pub struct ParticipantAccessChanged {
+    pub chat_room: Pubkey,
+    pub user: Pubkey,
+    pub key_balance: u64,
+    pub is_lapsed: bool,
+    pub timestamp: i64,
+}
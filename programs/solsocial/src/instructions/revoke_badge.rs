@@ -0,0 +1,59 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+pub struct RevokeBadge<'info> {
+    #[account(
+        mut,
+        constraint = badge.user == holder.authority @ SolSocialError::InvalidPublicKey
+    )]
+    pub badge: Account<'info, UserBadge>,
+
+    #[account(
+        seeds = [b"user", holder.authority.as_ref()],
+        bump = holder.bump,
+    )]
+    pub holder: Account<'info, User>,
+
+    #[account(
+        seeds = [b"platform_config"],
+        bump = platform_config.bump,
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+}
+
+/// Permissionless: anyone may deactivate a badge once its holder is banned or
+/// has fallen below the platform's reputation floor
+pub fn revoke_badge(ctx: Context<RevokeBadge>) -> Result<()> {
+    let badge = &mut ctx.accounts.badge;
+    let holder = &ctx.accounts.holder;
+    let platform_config = &ctx.accounts.platform_config;
+
+    require!(badge.is_active, SolSocialError::InvalidResourceState);
+    require!(
+        !holder.is_in_good_standing(platform_config.badge_reputation_floor),
+        SolSocialError::PermissionDenied
+    );
+
+    badge.is_active = false;
+
+    emit!(BadgeRevoked {
+        user: badge.user,
+        badge: badge.key(),
+        banned: holder.banned,
+        reputation_score: holder.reputation_score,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct BadgeRevoked {
+    pub user: Pubkey,
+    pub badge: Pubkey,
+    pub banned: bool,
+    pub reputation_score: u64,
+    pub timestamp: i64,
+}
@@ -0,0 +1,52 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+#[instruction(target: Pubkey)]
+pub struct RevokeVerification<'info> {
+    #[account(
+        seeds = [b"platform_config"],
+        bump = platform_config.bump,
+        constraint = platform_config.authority == authority.key() @ SolSocialError::UnauthorizedUser
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"user_profile", target.as_ref()],
+        bump = target_profile.bump,
+    )]
+    pub target_profile: Account<'info, UserProfile>,
+
+    #[account(
+        mut,
+        seeds = [b"user_badge", target.as_ref(), &[BadgeType::Verified as u8]],
+        bump = verified_badge.bump,
+    )]
+    pub verified_badge: Account<'info, UserBadge>,
+
+    pub authority: Signer<'info>,
+}
+
+/// The badge account is kept, inactive, so a later `verify_user` can reactivate it
+pub fn revoke_verification(ctx: Context<RevokeVerification>, target: Pubkey) -> Result<()> {
+    let target_profile = &mut ctx.accounts.target_profile;
+    require!(target_profile.is_verified, SolSocialError::InvalidResourceState);
+
+    target_profile.is_verified = false;
+    ctx.accounts.verified_badge.is_active = false;
+
+    emit!(VerificationRevoked {
+        user: target,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct VerificationRevoked {
+    pub user: Pubkey,
+    pub timestamp: i64,
+}
@@ -0,0 +1,109 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hash;
+use crate::state::*;
+use crate::errors::*;
+use super::create_post::validate_new_post;
+
+#[derive(Accounts)]
+pub struct SchedulePost<'info> {
+    #[account(
+        init,
+        payer = author,
+        space = Post::LEN,
+        seeds = [b"post", author.key().as_ref(), &user_profile.post_count.to_le_bytes()],
+        bump
+    )]
+    pub post: Account<'info, Post>,
+
+    #[account(
+        mut,
+        seeds = [b"user_profile", author.key().as_ref()],
+        bump = user_profile.bump,
+    )]
+    pub user_profile: Account<'info, UserProfile>,
+
+    #[account(
+        seeds = [b"platform_config"],
+        bump = platform_config.bump,
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+
+    #[account(mut)]
+    pub author: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Creates the post now but keeps it hidden until `publish_scheduled_post` runs at `publish_at`
+pub fn schedule_post(
+    ctx: Context<SchedulePost>,
+    content: String,
+    publish_at: i64,
+    media_urls: Vec<String>,
+    tags: Vec<String>,
+    is_premium: bool,
+) -> Result<()> {
+    let platform_config = &ctx.accounts.platform_config;
+    let user_profile = &mut ctx.accounts.user_profile;
+    let current_time = Clock::get()?.unix_timestamp;
+
+    require!(platform_config.is_posting_enabled, SolSocialError::PostingPaused);
+    require!(publish_at > current_time, SolSocialError::InvalidTimestamp);
+    require!(
+        user_profile.scheduled_posts_count < platform_config.max_scheduled_posts,
+        SolSocialError::ResourceNotAvailable
+    );
+    // Held to the same rules as create_post, checked now rather than at publish time
+    validate_new_post(
+        platform_config,
+        user_profile,
+        &content,
+        &media_urls,
+        &tags,
+        is_premium,
+        current_time,
+    )?;
+
+    let post = &mut ctx.accounts.post;
+    let content_hash = hash(content.as_bytes()).to_bytes();
+    post.initialize(
+        user_profile.post_count,
+        ctx.accounts.author.key(),
+        content,
+        content_hash,
+        publish_at,
+        is_premium,
+        0,
+        None,
+        media_urls,
+        tags,
+        Vec::new(),
+        PostVisibility::Public,
+        ctx.bumps.post,
+    )?;
+    post.status = PostStatus::Scheduled;
+
+    user_profile.post_count = user_profile.post_count
+        .checked_add(1)
+        .ok_or(SolSocialError::ArithmeticOverflow)?;
+    user_profile.scheduled_posts_count = user_profile.scheduled_posts_count
+        .checked_add(1)
+        .ok_or(SolSocialError::ArithmeticOverflow)?;
+
+    emit!(PostScheduled {
+        post: post.key(),
+        author: post.author,
+        publish_at,
+        timestamp: current_time,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct PostScheduled {
+    pub post: Pubkey,
+    pub author: Pubkey,
+    pub publish_at: i64,
+    pub timestamp: i64,
+}
@@ -3,7 +3,9 @@ use anchor_spl::token::{self, Token, TokenAccount, Transfer};
 use crate::state::*;
 use crate::errors::*;
 use crate::events::*;
-use crate::constants::*;
+use crate::utils::bonding_curve::{BondingCurve, TradeQuote};
+use crate::utils::revenue_share::{check_sell_solvency, distribute_sell_revenue};
+use super::buy_keys::{check_referrer_payee, check_trade_deadline, close_position, protocol_fee_bps_for, release_creator_supply, with_creator_profile};
 
 #[derive(Accounts)]
 pub struct SellKeys<'info> {
@@ -12,27 +14,75 @@ pub struct SellKeys<'info> {
     
     #[account(
         mut,
-        seeds = [b"user", seller.key().as_ref()],
+        seeds = [b"user_profile", seller.key().as_ref()],
         bump = seller_profile.bump,
     )]
     pub seller_profile: Account<'info, UserProfile>,
     
+    /// CHECK: Subject account for key trading
+    pub subject: AccountInfo<'info>,
+    
+    // Carries the market's recorded sell pause
     #[account(
-        mut,
-        seeds = [b"user", subject.key().as_ref()],
+        seeds = [b"user_profile", subject.key().as_ref()],
         bump = subject_profile.bump,
     )]
     pub subject_profile: Account<'info, UserProfile>,
     
-    /// CHECK: Subject account for key trading
-    pub subject: AccountInfo<'info>,
+    // The market itself: supply, volume and trading rules
+    #[account(
+        mut,
+        seeds = [b"user_keys", subject.key().as_ref()],
+        bump = user_keys.bump,
+    )]
+    pub user_keys: Account<'info, UserKeys>,
+    
+    /// CHECK: The market creator's `UserProfile`, tracking their issuance across all of their
+    /// markets. Loaded in the handler since it's the seller's own profile on a self-sell.
+    #[account(
+        mut,
+        seeds = [b"user_profile", user_keys.creator.as_ref()],
+        bump,
+    )]
+    pub creator_profile: UncheckedAccount<'info>,
     
+    // Carries the subject's own creator fee
     #[account(
         mut,
-        seeds = [b"keys", subject.key().as_ref(), seller.key().as_ref()],
-        bump = key_holding.bump,
+        seeds = [b"user_key", subject.key().as_ref()],
+        bump = user_key.bump,
     )]
-    pub key_holding: Account<'info, KeyHolding>,
+    pub user_key: Account<'info, UserKey>,
+    
+    #[account(
+        mut,
+        seeds = [b"key_holder", user_key.key().as_ref(), seller.key().as_ref()],
+        bump = key_holder.bump,
+    )]
+    pub key_holder: Account<'info, KeyHolder>,
+    
+    /// Only needed by traders who opted in to receipts for this market
+    #[account(
+        mut,
+        seeds = [b"trade_ledger", seller.key().as_ref(), subject.key().as_ref()],
+        bump = trade_ledger.bump,
+    )]
+    pub trade_ledger: Option<Account<'info, TradeLedger>>,
+    
+    #[account(
+        init,
+        payer = seller,
+        space = TradeReceipt::LEN,
+        seeds = [b"trade_receipt", trade_ledger.key().as_ref(), &trade_ledger.next_sequence.to_le_bytes()],
+        bump
+    )]
+    pub trade_receipt: Option<Account<'info, TradeReceipt>>,
+    
+    #[account(
+        seeds = [b"platform_config"],
+        bump = platform_config.bump,
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
     
     #[account(
         mut,
@@ -62,88 +112,147 @@ pub struct SellKeys<'info> {
     )]
     pub treasury_token_account: Account<'info, TokenAccount>,
     
+    /// The seller's referrer, if any, paid a share of the trade's fees
+    #[account(mut)]
+    pub referrer_profile: Option<Account<'info, UserProfile>>,
+    
+    #[account(
+        mut,
+        token::mint = treasury.sol_mint,
+    )]
+    pub referrer_token_account: Option<Account<'info, TokenAccount>>,
+    
     pub token_program: Program<'info, Token>,
     pub system_program: Program<'info, System>,
 }
 
-pub fn sell_keys(ctx: Context<SellKeys>, amount: u64) -> Result<()> {
+pub fn sell_keys(
+    ctx: Context<SellKeys>,
+    amount: u64,
+    min_proceeds: u64,
+    deadline: Option<i64>,
+) -> Result<()> {
     let seller = &ctx.accounts.seller;
     let seller_profile = &mut ctx.accounts.seller_profile;
-    let subject_profile = &mut ctx.accounts.subject_profile;
+    let user_keys = &mut ctx.accounts.user_keys;
     let subject = &ctx.accounts.subject;
-    let key_holding = &mut ctx.accounts.key_holding;
+    let key_holder = &mut ctx.accounts.key_holder;
     let treasury = &mut ctx.accounts.treasury;
     
-    // Validate inputs
-    require!(amount > 0, SolSocialError::InvalidAmount);
-    require!(key_holding.amount >= amount, SolSocialError::InsufficientKeys);
-    require!(subject_profile.total_supply >= amount, SolSocialError::InsufficientSupply);
+    let now = Clock::get()?.unix_timestamp;
     
-    // Prevent selling the last key if seller is the subject (must maintain at least 1)
-    if seller.key() == subject.key() {
+    // Validate inputs
+    check_trade_deadline(deadline)?;
+    let is_own_market = seller.key() == subject.key();
+    validate_sell(
+        &ctx.accounts.platform_config,
+        user_keys,
+        is_own_market,
+        key_holder.amount,
+        key_holder.last_trade_at,
+        amount,
+        now,
+    )?;
+    
+    // The treasury pays every token sell, so it's the reserve that must still back the supply
+    check_sell_solvency(
+        ctx.accounts.subject_profile.sells_paused,
+        ctx.accounts.treasury_token_account.amount,
+        user_keys.total_supply,
+        ctx.accounts.platform_config.min_solvency_ratio_bps,
+    )?;
+    
+    // Calculate sell price and fees using bonding curve
+    let quote = quote_sell_for(
+        &ctx.accounts.platform_config,
+        seller_profile,
+        user_keys,
+        &ctx.accounts.user_key,
+        amount,
+    )?;
+    let sell_price = quote.base_price;
+    let protocol_fee = quote.protocol_fee;
+    let subject_fee = quote.subject_fee;
+    let seller_proceeds = quote.total;
+    
+    // Protect the seller if earlier sells shrank the supply, and with it the payout. Without a
+    // floor of their own, the reference is the price this slot opened at
+    if min_proceeds > 0 {
+        require!(seller_proceeds >= min_proceeds, SolSocialError::SlippageExceeded);
+    } else {
+        let default_max_slippage_bps = ctx.accounts.platform_config.default_max_slippage_bps;
+        require!(default_max_slippage_bps > 0, SolSocialError::InvalidSlippageTolerance);
         require!(
-            key_holding.amount > amount || subject_profile.total_supply > amount,
-            SolSocialError::CannotSellLastKey
+            BondingCurve::standard().is_within_slippage(
+                user_keys.supply_at_slot_start(Clock::get()?.slot),
+                amount,
+                sell_price,
+                default_max_slippage_bps,
+                false,
+            )?,
+            SolSocialError::SlippageExceeded
         );
     }
     
-    // Calculate sell price using bonding curve
-    let current_supply = subject_profile.total_supply;
-    let sell_price = calculate_sell_price(current_supply, amount)?;
-    
-    // Calculate fees
-    let protocol_fee = sell_price
-        .checked_mul(PROTOCOL_FEE_PERCENT)
-        .ok_or(SolSocialError::MathOverflow)?
-        .checked_div(10000)
-        .ok_or(SolSocialError::MathOverflow)?;
-    
-    let subject_fee = sell_price
-        .checked_mul(SUBJECT_FEE_PERCENT)
-        .ok_or(SolSocialError::MathOverflow)?
-        .checked_div(10000)
-        .ok_or(SolSocialError::MathOverflow)?;
-    
-    let seller_proceeds = sell_price
-        .checked_sub(protocol_fee)
-        .ok_or(SolSocialError::MathOverflow)?
-        .checked_sub(subject_fee)
-        .ok_or(SolSocialError::MathOverflow)?;
-    
-    // Update key holding
-    key_holding.amount = key_holding.amount
-        .checked_sub(amount)
-        .ok_or(SolSocialError::MathOverflow)?;
-    
-    key_holding.last_trade_timestamp = Clock::get()?.unix_timestamp;
-    
-    // Update subject profile
-    subject_profile.total_supply = subject_profile.total_supply
-        .checked_sub(amount)
-        .ok_or(SolSocialError::MathOverflow)?;
-    
-    subject_profile.total_volume = subject_profile.total_volume
-        .checked_add(sell_price)
-        .ok_or(SolSocialError::MathOverflow)?;
-    
-    subject_profile.last_trade_timestamp = Clock::get()?.unix_timestamp;
+    // The referrer's cut comes out of the fees above, so the seller's proceeds don't change
+    check_referrer_payee(
+        ctx.accounts.referrer_profile.as_deref(),
+        ctx.accounts.referrer_token_account.as_ref().map(|account| account.owner),
+    )?;
+    let distribution = distribute_sell_revenue(
+        user_keys,
+        &seller.key(),
+        ctx.accounts.referrer_profile.as_deref_mut(),
+        sell_price,
+        protocol_fee,
+        subject_fee,
+    )?;
+    
+    record_sell(
+        seller_profile,
+        user_keys,
+        &mut ctx.accounts.user_key,
+        key_holder,
+        &quote,
+        amount,
+        now,
+    )?;
+    with_creator_profile(
+        &seller.key(),
+        seller_profile,
+        &user_keys.creator,
+        &ctx.accounts.creator_profile.to_account_info(),
+        ctx.program_id,
+        |creator_profile| {
+            release_creator_supply(creator_profile, amount);
+            Ok(())
+        },
+    )?;
+    
+    issue_trade_receipt(
+        ctx.accounts.trade_ledger.as_mut(),
+        ctx.accounts.trade_receipt.as_mut(),
+        ctx.bumps.trade_receipt,
+        TradeType::Sell,
+        amount,
+        sell_price,
+        protocol_fee.checked_add(subject_fee).ok_or(SolSocialError::MathOverflow)?,
+        seller_proceeds,
+        now,
+    )?;
     
     // Update seller profile
     seller_profile.total_trades = seller_profile.total_trades
         .checked_add(1)
         .ok_or(SolSocialError::MathOverflow)?;
     
-    seller_profile.total_volume = seller_profile.total_volume
-        .checked_add(sell_price)
-        .ok_or(SolSocialError::MathOverflow)?;
-    
     // Update treasury
     treasury.total_volume = treasury.total_volume
         .checked_add(sell_price)
         .ok_or(SolSocialError::MathOverflow)?;
     
     treasury.protocol_fees_collected = treasury.protocol_fees_collected
-        .checked_add(protocol_fee)
+        .checked_add(distribution.protocol_amount)
         .ok_or(SolSocialError::MathOverflow)?;
     
     // Transfer seller proceeds
@@ -170,7 +279,7 @@ pub fn sell_keys(ctx: Context<SellKeys>, amount: u64) -> Result<()> {
     }
     
     // Transfer subject fee
-    if subject_fee > 0 {
+    if distribution.creator_amount > 0 {
         let transfer_instruction = Transfer {
             from: ctx.accounts.treasury_token_account.to_account_info(),
             to: ctx.accounts.subject_token_account.to_account_info(),
@@ -189,12 +298,37 @@ pub fn sell_keys(ctx: Context<SellKeys>, amount: u64) -> Result<()> {
             signer_seeds,
         );
         
-        token::transfer(cpi_ctx, subject_fee)?;
+        token::transfer(cpi_ctx, distribution.creator_amount)?;
+    }
+    
+    // Transfer referrer fee
+    if let Some(referrer_token_account) = &ctx.accounts.referrer_token_account {
+        if distribution.referrer_amount > 0 {
+            let transfer_instruction = Transfer {
+                from: ctx.accounts.treasury_token_account.to_account_info(),
+                to: referrer_token_account.to_account_info(),
+                authority: treasury.to_account_info(),
+            };
+            
+            let treasury_seeds = &[
+                b"treasury",
+                &[treasury.bump],
+            ];
+            let signer_seeds = &[&treasury_seeds[..]];
+            
+            let cpi_ctx = CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                transfer_instruction,
+                signer_seeds,
+            );
+            
+            token::transfer(cpi_ctx, distribution.referrer_amount)?;
+        }
     }
     
-    // Close key holding account if amount reaches zero
-    if key_holding.amount == 0 {
-        key_holding.close(seller.to_account_info())?;
+    // Close the holding if amount reaches zero
+    if key_holder.amount == 0 {
+        key_holder.close(seller.to_account_info())?;
     }
     
     // Emit sell event
@@ -206,8 +340,8 @@ pub fn sell_keys(ctx: Context<SellKeys>, amount: u64) -> Result<()> {
         protocol_fee,
         subject_fee,
         seller_proceeds,
-        supply_after: subject_profile.total_supply,
-        timestamp: Clock::get()?.unix_timestamp,
+        supply_after: user_keys.total_supply,
+        timestamp: now,
     });
     
     msg!(
@@ -216,40 +350,106 @@ pub fn sell_keys(ctx: Context<SellKeys>, amount: u64) -> Result<()> {
         subject.key(),
         amount,
         sell_price,
-        subject_profile.total_supply
+        user_keys.total_supply
+    );
+    
+    Ok(())
+}
+
+
+/// Checks every path that burns keys runs before pricing: `sell_keys` and a filled sell order
+pub(crate) fn validate_sell(
+    platform_config: &PlatformConfig,
+    user_keys: &UserKeys,
+    is_own_market: bool,
+    held: u64,
+    last_trade_at: i64,
+    amount: u64,
+    now: i64,
+) -> Result<()> {
+    require!(platform_config.is_trading_enabled, SolSocialError::TradingPaused);
+    require!(amount > 0, SolSocialError::InvalidAmount);
+    require!(held >= amount, SolSocialError::InsufficientKeys);
+    require!(user_keys.total_supply >= amount, SolSocialError::InsufficientSupply);
+    require!(user_keys.is_within_trading_window(now), SolSocialError::MarketClosed);
+    
+    // Space out a holder's consecutive sells in this market to slow coordinated dumps
+    require!(
+        user_keys.is_sell_allowed(last_trade_at, now),
+        SolSocialError::InteractionCooldown
     );
     
+    // Prevent selling the last key if seller is the subject (must maintain at least 1)
+    if is_own_market {
+        require!(
+            held > amount || user_keys.total_supply > amount,
+            SolSocialError::CannotSellLastKey
+        );
+    }
+    
     Ok(())
 }
 
-fn calculate_sell_price(supply: u64, amount: u64) -> Result<u64> {
-    if supply == 0 || amount == 0 {
-        return Ok(0);
+/// What selling `amount` keys pays this seller right now
+pub(crate) fn quote_sell_for(
+    platform_config: &PlatformConfig,
+    seller_profile: &UserProfile,
+    user_keys: &UserKeys,
+    user_key: &UserKey,
+    amount: u64,
+) -> Result<TradeQuote> {
+    BondingCurve::standard().quote_sell(
+        user_keys.total_supply,
+        amount,
+        protocol_fee_bps_for(seller_profile, platform_config)?,
+        user_key.subject_fee_percent as u64,
+    )
+}
+
+/// Settles a priced sell: uses up a free trade, closes the position if it's emptied, and
+/// moves the holding and the market. Paying the seller is the caller's.
+pub(crate) fn record_sell(
+    seller_profile: &mut UserProfile,
+    user_keys: &mut UserKeys,
+    user_key: &mut UserKey,
+    key_holder: &mut KeyHolder,
+    quote: &TradeQuote,
+    amount: u64,
+    now: i64,
+) -> Result<()> {
+    if seller_profile.free_trades_remaining > 0 {
+        seller_profile.free_trades_remaining -= 1;
     }
+    seller_profile.total_volume = seller_profile.total_volume
+        .checked_add(quote.base_price)
+        .ok_or(SolSocialError::MathOverflow)?;
     
-    // Bonding curve: price = supply^2 / 16000
-    // For selling, we calculate the area under the curve from (supply - amount) to supply
-    let supply_before = supply.checked_sub(amount).ok_or(SolSocialError::MathOverflow)?;
+    user_keys.mark_slot_start(Clock::get()?.slot);
     
-    // Calculate sum of squares from supply_before to supply-1
-    let mut total_price = 0u64;
+    // Update the holding, keeping the remaining keys at the same average cost
+    let sold_cost = (key_holder.average_price as u128)
+        .checked_mul(amount as u128)
+        .and_then(|cost| u64::try_from(cost).ok())
+        .ok_or(SolSocialError::MathOverflow)?;
+    key_holder.amount = key_holder.amount
+        .checked_sub(amount)
+        .ok_or(SolSocialError::MathOverflow)?;
+    key_holder.total_invested = key_holder.total_invested.saturating_sub(sold_cost);
+    key_holder.last_trade_at = now;
     
-    for i in supply_before..supply {
-        let price = i
-            .checked_mul(i)
-            .ok_or(SolSocialError::MathOverflow)?
-            .checked_div(16000)
-            .ok_or(SolSocialError::MathOverflow)?;
-        
-        total_price = total_price
-            .checked_add(price)
-            .ok_or(SolSocialError::MathOverflow)?;
+    if key_holder.amount == 0 {
+        close_position(Some(seller_profile), user_key);
     }
     
-    // Minimum price floor
-    if total_price < MIN_KEY_PRICE {
-        total_price = MIN_KEY_PRICE;
-    }
+    // Update the market
+    user_keys.total_supply = user_keys.total_supply
+        .checked_sub(amount)
+        .ok_or(SolSocialError::MathOverflow)?;
+    user_keys.price_per_key = BondingCurve::standard().get_price(user_keys.total_supply)?;
+    user_keys.total_volume = user_keys.total_volume
+        .checked_add(quote.base_price)
+        .ok_or(SolSocialError::MathOverflow)?;
+    user_keys.last_trade_at = now;
     
-    Ok(total_price)
+    Ok(())
 }
\ No newline at end of file
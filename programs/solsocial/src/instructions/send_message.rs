@@ -51,6 +51,19 @@ pub struct SendMessage<'info> {
     )]
     pub key_holder: Account<'info, KeyHolder>,
     
+    /// CHECK: The sender's participant record, which may not exist; when it does it is
+    /// deserialized in the handler so a muted sender can't dodge the check by omitting it
+    #[account(
+        mut,
+        seeds = [b"participant", chat_room.key().as_ref(), sender.key().as_ref()],
+        bump
+    )]
+    pub participant: UncheckedAccount<'info>,
+    
+    /// CHECK: Only used in DMs, where it must be the other party's block list; the
+    /// address is verified in the handler since the recipient comes from the room
+    pub recipient_block_list: Option<UncheckedAccount<'info>>,
+    
     pub system_program: Program<'info, System>,
     pub clock: Sysvar<'info, Clock>,
 }
@@ -59,6 +72,7 @@ pub fn send_message(
     ctx: Context<SendMessage>,
     room_id: String,
     content: String,
+    attachments: Vec<MessageAttachment>,
 ) -> Result<()> {
     let sender = &ctx.accounts.sender;
     let user_account = &mut ctx.accounts.user_account;
@@ -68,6 +82,36 @@ pub fn send_message(
     let key_holder = &ctx.accounts.key_holder;
     let clock = &ctx.accounts.clock;
 
+    // Holdings are re-checked on every message, not just at join
+    let holds_required_keys = key_holder.amount > 0 && key_holder.amount >= chat_room.required_key_amount;
+
+    // Muted senders are rejected; a timed mute that has run out is cleared instead
+    let participant_info = ctx.accounts.participant.to_account_info();
+    if !participant_info.data_is_empty() {
+        let mut participant = Account::<ChatParticipant>::try_from(&participant_info)?;
+        let mut is_changed = false;
+
+        if participant.is_muted && !participant.is_currently_muted() {
+            participant.unmute();
+            is_changed = true;
+        }
+
+        // A lapsed member who has bought back in is reinstated
+        if participant.is_lapsed && holds_required_keys {
+            participant.is_lapsed = false;
+            is_changed = true;
+        }
+
+        if is_changed {
+            participant.exit(ctx.program_id)?;
+        }
+
+        require!(
+            !participant.is_currently_muted(),
+            SolSocialError::PermissionDenied
+        );
+    }
+
     // Validate room ID length
     require!(
         room_id.len() <= 32,
@@ -76,22 +120,90 @@ pub fn send_message(
 
     // Validate content length
     require!(
-        content.len() > 0 && content.len() <= 500,
+        content.len() > 0 && content.len() <= Message::MAX_CONTENT_LENGTH,
         SolSocialError::InvalidMessageLength
     );
 
+    // Validate attachments against the message budget and the room's payload cap
+    require!(
+        attachments.len() <= Message::MAX_ATTACHMENTS,
+        SolSocialError::AccountDataTooLarge
+    );
+    require!(
+        attachments.iter().all(|attachment| {
+            attachment.url.len() <= Message::MAX_ATTACHMENT_URL_LENGTH
+                && attachment.filename.as_ref().map_or(true, |name| name.len() <= Message::MAX_ATTACHMENT_FILENAME_LENGTH)
+                && attachment.mime_type.as_ref().map_or(true, |mime| mime.len() <= Message::MAX_ATTACHMENT_MIME_LENGTH)
+        }),
+        SolSocialError::AccountDataTooLarge
+    );
+
+    // A capped room can't budget an attachment of unknown size, so the size must be declared
+    if chat_room.max_attachment_bytes > 0 {
+        require!(
+            attachments.iter().all(|attachment| attachment.size.is_some()),
+            SolSocialError::InvalidInstructionData
+        );
+    }
+
+    let total_attachment_bytes = attachments
+        .iter()
+        .try_fold(0u64, |total, attachment| total.checked_add(attachment.size.unwrap_or(0)))
+        .ok_or(SolSocialError::ArithmeticOverflow)?;
+
+    require!(
+        chat_room.max_attachment_bytes == 0 || total_attachment_bytes <= chat_room.max_attachment_bytes,
+        SolSocialError::AccountDataTooLarge
+    );
+
     // Check if chat room is active
     require!(
         chat_room.is_active,
         SolSocialError::ChatRoomInactive
     );
 
-    // Verify sender has access to this chat room
+    // Rooms with a quorum stay closed until enough participants have joined
+    require!(
+        chat_room.is_open,
+        SolSocialError::ResourceNotAvailable
+    );
+
+    // Verify sender still has access to this chat room
     require!(
-        key_holder.amount > 0,
+        holds_required_keys,
         SolSocialError::InsufficientKeyBalance
     );
 
+    // Rooms that vet members only accept messages from approved participants
+    if chat_room.require_creator_approval {
+        require!(
+            chat_room.is_participant(&sender.key()),
+            crate::state::ErrorCode::RoomAccessDenied
+        );
+    }
+
+    // In DMs the other party's block list decides whether the sender may reach them
+    if chat_room.room_type == ChatRoomType::DirectMessage {
+        if let Some(recipient) = chat_room.participants.iter().find(|participant| **participant != sender.key()) {
+            let recipient_block_list = ctx.accounts.recipient_block_list
+                .as_ref()
+                .ok_or(SolSocialError::AccessDenied)?;
+            let (expected_block_list, _) = Pubkey::find_program_address(
+                &[b"block_list", recipient.as_ref()],
+                ctx.program_id,
+            );
+            require_keys_eq!(
+                recipient_block_list.key(),
+                expected_block_list,
+                SolSocialError::AccessDenied
+            );
+            require!(
+                !BlockList::account_blocks(recipient_block_list, &sender.key())?,
+                SolSocialError::AccessDenied
+            );
+        }
+    }
+
     // Check if user is not banned from the room
     require!(
         !chat_room.banned_users.contains(&sender.key()),
@@ -126,6 +238,7 @@ pub fn send_message(
     message.is_deleted = false;
     message.reply_to = None;
     message.reactions = Vec::new();
+    message.attachments = attachments;
     message.bump = *ctx.bumps.get("message").unwrap();
 
     // Update chat room
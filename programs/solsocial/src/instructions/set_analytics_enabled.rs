@@ -0,0 +1,37 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+pub struct SetAnalyticsEnabled<'info> {
+    #[account(
+        mut,
+        seeds = [b"user_profile", authority.key().as_ref()],
+        bump = user_profile.bump,
+        constraint = user_profile.authority == authority.key() @ SolSocialError::UnauthorizedUser
+    )]
+    pub user_profile: Account<'info, UserProfile>,
+
+    pub authority: Signer<'info>,
+}
+
+/// Opting out stops stats accruing on this creator's content; existing stats are kept as they are
+pub fn set_analytics_enabled(ctx: Context<SetAnalyticsEnabled>, analytics_enabled: bool) -> Result<()> {
+    let user_profile = &mut ctx.accounts.user_profile;
+    user_profile.analytics_enabled = analytics_enabled;
+
+    emit!(AnalyticsPreferenceUpdated {
+        user: user_profile.authority,
+        analytics_enabled,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct AnalyticsPreferenceUpdated {
+    pub user: Pubkey,
+    pub analytics_enabled: bool,
+    pub timestamp: i64,
+}
@@ -0,0 +1,42 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+#[instruction(room_id: String)]
+pub struct SetAttachmentLimit<'info> {
+    #[account(
+        mut,
+        seeds = [b"chat_room", room_id.as_bytes()],
+        bump = chat_room.bump,
+        constraint = chat_room.creator == creator.key() @ SolSocialError::UnauthorizedUser
+    )]
+    pub chat_room: Account<'info, ChatRoom>,
+
+    pub creator: Signer<'info>,
+}
+
+pub fn set_attachment_limit(
+    ctx: Context<SetAttachmentLimit>,
+    _room_id: String,
+    max_attachment_bytes: u64,
+) -> Result<()> {
+    let chat_room = &mut ctx.accounts.chat_room;
+
+    chat_room.max_attachment_bytes = max_attachment_bytes;
+
+    emit!(AttachmentLimitUpdated {
+        chat_room: chat_room.key(),
+        max_attachment_bytes,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct AttachmentLimitUpdated {
+    pub chat_room: Pubkey,
+    pub max_attachment_bytes: u64,
+    pub timestamp: i64,
+}
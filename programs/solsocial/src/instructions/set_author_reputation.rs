@@ -0,0 +1,42 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+pub struct SetAuthorReputation<'info> {
+    #[account(
+        mut,
+        seeds = [b"platform_config"],
+        bump = platform_config.bump,
+        constraint = platform_config.authority == authority.key() @ SolSocialError::UnauthorizedUser
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+
+    pub authority: Signer<'info>,
+}
+
+/// Reputation an author earns per point of new peak engagement on their post, capped per post
+pub fn set_author_reputation(
+    ctx: Context<SetAuthorReputation>,
+    author_reputation_per_engagement: u16,
+    max_author_reputation_per_post: u64,
+) -> Result<()> {
+    let platform_config = &mut ctx.accounts.platform_config;
+    platform_config.author_reputation_per_engagement = author_reputation_per_engagement;
+    platform_config.max_author_reputation_per_post = max_author_reputation_per_post;
+
+    emit!(AuthorReputationUpdated {
+        author_reputation_per_engagement,
+        max_author_reputation_per_post,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct AuthorReputationUpdated {
+    pub author_reputation_per_engagement: u16,
+    pub max_author_reputation_per_post: u64,
+    pub timestamp: i64,
+}
\ No newline at end of file
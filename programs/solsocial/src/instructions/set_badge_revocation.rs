@@ -0,0 +1,42 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+pub struct SetBadgeRevocation<'info> {
+    #[account(
+        mut,
+        seeds = [b"platform_config"],
+        bump = platform_config.bump,
+        constraint = platform_config.authority == authority.key() @ SolSocialError::UnauthorizedUser
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+
+    pub authority: Signer<'info>,
+}
+
+/// Badges become revocable below `badge_reputation_floor`; `restore_badges_on_unban` lets them come back
+pub fn set_badge_revocation(
+    ctx: Context<SetBadgeRevocation>,
+    badge_reputation_floor: u64,
+    restore_badges_on_unban: bool,
+) -> Result<()> {
+    let platform_config = &mut ctx.accounts.platform_config;
+    platform_config.badge_reputation_floor = badge_reputation_floor;
+    platform_config.restore_badges_on_unban = restore_badges_on_unban;
+
+    emit!(BadgeRevocationUpdated {
+        badge_reputation_floor,
+        restore_badges_on_unban,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct BadgeRevocationUpdated {
+    pub badge_reputation_floor: u64,
+    pub restore_badges_on_unban: bool,
+    pub timestamp: i64,
+}
\ No newline at end of file
@@ -0,0 +1,42 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+pub struct SetBadgeThresholds<'info> {
+    #[account(
+        mut,
+        seeds = [b"platform_config"],
+        bump = platform_config.bump,
+        constraint = platform_config.authority == authority.key() @ SolSocialError::UnauthorizedUser
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+
+    pub authority: Signer<'info>,
+}
+
+/// Badges already awarded are kept when thresholds rise
+pub fn set_badge_thresholds(
+    ctx: Context<SetBadgeThresholds>,
+    top_trader_volume_threshold: u64,
+    influencer_follower_threshold: u64,
+) -> Result<()> {
+    let platform_config = &mut ctx.accounts.platform_config;
+    platform_config.top_trader_volume_threshold = top_trader_volume_threshold;
+    platform_config.influencer_follower_threshold = influencer_follower_threshold;
+
+    emit!(BadgeThresholdsUpdated {
+        top_trader_volume_threshold,
+        influencer_follower_threshold,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct BadgeThresholdsUpdated {
+    pub top_trader_volume_threshold: u64,
+    pub influencer_follower_threshold: u64,
+    pub timestamp: i64,
+}
@@ -0,0 +1,36 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+pub struct SetBanAppealWindow<'info> {
+    #[account(
+        mut,
+        seeds = [b"platform_config"],
+        bump = platform_config.bump,
+        constraint = platform_config.authority == authority.key() @ SolSocialError::UnauthorizedUser
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+
+    pub authority: Signer<'info>,
+}
+
+/// Only affects when appeals are accepted; existing appeals stay open for resolution
+pub fn set_ban_appeal_window(ctx: Context<SetBanAppealWindow>, ban_appeal_window_seconds: i64) -> Result<()> {
+    require!(ban_appeal_window_seconds >= 0, SolSocialError::InvalidTimestamp);
+
+    ctx.accounts.platform_config.ban_appeal_window_seconds = ban_appeal_window_seconds;
+
+    emit!(BanAppealWindowUpdated {
+        ban_appeal_window_seconds,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct BanAppealWindowUpdated {
+    pub ban_appeal_window_seconds: i64,
+    pub timestamp: i64,
+}
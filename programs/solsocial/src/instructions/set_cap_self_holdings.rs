@@ -0,0 +1,33 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+pub struct SetCapSelfHoldings<'info> {
+    #[account(
+        mut,
+        seeds = [b"platform_config"],
+        bump = platform_config.bump,
+        constraint = platform_config.authority == authority.key() @ SolSocialError::UnauthorizedUser
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+
+    pub authority: Signer<'info>,
+}
+
+pub fn set_cap_self_holdings(ctx: Context<SetCapSelfHoldings>, cap_self_holdings: bool) -> Result<()> {
+    ctx.accounts.platform_config.cap_self_holdings = cap_self_holdings;
+
+    emit!(CapSelfHoldingsUpdated {
+        cap_self_holdings,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct CapSelfHoldingsUpdated {
+    pub cap_self_holdings: bool,
+    pub timestamp: i64,
+}
@@ -0,0 +1,50 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+pub struct SetContentLengthTiers<'info> {
+    #[account(
+        mut,
+        seeds = [b"platform_config"],
+        bump = platform_config.bump,
+        constraint = platform_config.authority == authority.key() @ SolSocialError::UnauthorizedUser
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+
+    pub authority: Signer<'info>,
+}
+
+pub fn set_content_length_tiers(
+    ctx: Context<SetContentLengthTiers>,
+    base_content_length: u32,
+    content_length_per_tier: u32,
+    reputation_per_content_tier: u64,
+) -> Result<()> {
+    require!(
+        base_content_length > 0 && base_content_length as usize <= Post::MAX_TIERED_CONTENT_LENGTH,
+        SolSocialError::InvalidContentLength
+    );
+
+    let platform_config = &mut ctx.accounts.platform_config;
+    platform_config.base_content_length = base_content_length;
+    platform_config.content_length_per_tier = content_length_per_tier;
+    platform_config.reputation_per_content_tier = reputation_per_content_tier;
+
+    emit!(ContentLengthTiersUpdated {
+        base_content_length,
+        content_length_per_tier,
+        reputation_per_content_tier,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct ContentLengthTiersUpdated {
+    pub base_content_length: u32,
+    pub content_length_per_tier: u32,
+    pub reputation_per_content_tier: u64,
+    pub timestamp: i64,
+}
@@ -0,0 +1,34 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+pub struct SetCurationThreshold<'info> {
+    #[account(
+        mut,
+        seeds = [b"platform_config"],
+        bump = platform_config.bump,
+        constraint = platform_config.authority == authority.key() @ SolSocialError::UnauthorizedUser
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+
+    pub authority: Signer<'info>,
+}
+
+/// Already-curated posts stay curated; 0 switches curation off for new likes
+pub fn set_curation_threshold(ctx: Context<SetCurationThreshold>, curation_boost_threshold: u64) -> Result<()> {
+    ctx.accounts.platform_config.curation_boost_threshold = curation_boost_threshold;
+
+    emit!(CurationThresholdUpdated {
+        curation_boost_threshold,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct CurationThresholdUpdated {
+    pub curation_boost_threshold: u64,
+    pub timestamp: i64,
+}
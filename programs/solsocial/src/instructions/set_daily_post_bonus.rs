@@ -0,0 +1,37 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+pub struct SetDailyPostBonus<'info> {
+    #[account(
+        mut,
+        seeds = [b"platform_config"],
+        bump = platform_config.bump,
+        constraint = platform_config.authority == authority.key() @ SolSocialError::UnauthorizedUser
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+
+    pub authority: Signer<'info>,
+}
+
+pub fn set_daily_post_bonus(
+    ctx: Context<SetDailyPostBonus>,
+    daily_post_reputation_bonus: u64,
+) -> Result<()> {
+    let platform_config = &mut ctx.accounts.platform_config;
+    platform_config.daily_post_reputation_bonus = daily_post_reputation_bonus;
+
+    emit!(DailyPostBonusUpdated {
+        daily_post_reputation_bonus,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct DailyPostBonusUpdated {
+    pub daily_post_reputation_bonus: u64,
+    pub timestamp: i64,
+}
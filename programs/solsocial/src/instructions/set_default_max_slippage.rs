@@ -0,0 +1,36 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+pub struct SetDefaultMaxSlippage<'info> {
+    #[account(
+        mut,
+        seeds = [b"platform_config"],
+        bump = platform_config.bump,
+        constraint = platform_config.authority == authority.key() @ SolSocialError::UnauthorizedUser
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+
+    pub authority: Signer<'info>,
+}
+
+/// Bound for trades that pass none of their own; 0 makes every trade supply its own
+pub fn set_default_max_slippage(ctx: Context<SetDefaultMaxSlippage>, default_max_slippage_bps: u16) -> Result<()> {
+    require!(default_max_slippage_bps <= 10000, SolSocialError::InvalidPercentage);
+
+    ctx.accounts.platform_config.default_max_slippage_bps = default_max_slippage_bps;
+
+    emit!(DefaultMaxSlippageUpdated {
+        default_max_slippage_bps,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct DefaultMaxSlippageUpdated {
+    pub default_max_slippage_bps: u16,
+    pub timestamp: i64,
+}
\ No newline at end of file
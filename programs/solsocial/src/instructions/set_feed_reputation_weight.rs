@@ -0,0 +1,35 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+pub struct SetFeedReputationWeight<'info> {
+    #[account(
+        mut,
+        seeds = [b"platform_config"],
+        bump = platform_config.bump,
+        constraint = platform_config.authority == authority.key() @ SolSocialError::UnauthorizedUser
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+
+    pub authority: Signer<'info>,
+}
+
+pub fn set_feed_reputation_weight(ctx: Context<SetFeedReputationWeight>, feed_reputation_weight: u16) -> Result<()> {
+    require!(feed_reputation_weight <= 10000, SolSocialError::InvalidPercentage);
+
+    ctx.accounts.platform_config.feed_reputation_weight = feed_reputation_weight;
+
+    emit!(FeedReputationWeightUpdated {
+        feed_reputation_weight,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct FeedReputationWeightUpdated {
+    pub feed_reputation_weight: u16,
+    pub timestamp: i64,
+}
\ No newline at end of file
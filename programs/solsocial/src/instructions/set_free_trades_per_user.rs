@@ -0,0 +1,34 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+pub struct SetFreeTradesPerUser<'info> {
+    #[account(
+        mut,
+        seeds = [b"platform_config"],
+        bump = platform_config.bump,
+        constraint = platform_config.authority == authority.key() @ SolSocialError::UnauthorizedUser
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+
+    pub authority: Signer<'info>,
+}
+
+/// Only applies to profiles created afterwards; existing users keep their remaining allowance
+pub fn set_free_trades_per_user(ctx: Context<SetFreeTradesPerUser>, free_trades_per_user: u32) -> Result<()> {
+    ctx.accounts.platform_config.free_trades_per_user = free_trades_per_user;
+
+    emit!(FreeTradesPerUserUpdated {
+        free_trades_per_user,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct FreeTradesPerUserUpdated {
+    pub free_trades_per_user: u32,
+    pub timestamp: i64,
+}
\ No newline at end of file
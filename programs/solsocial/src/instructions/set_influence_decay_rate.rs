@@ -0,0 +1,35 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+pub struct SetInfluenceDecayRate<'info> {
+    #[account(
+        mut,
+        seeds = [b"platform_config"],
+        bump = platform_config.bump,
+        constraint = platform_config.authority == authority.key() @ SolSocialError::UnauthorizedUser
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+
+    pub authority: Signer<'info>,
+}
+
+pub fn set_influence_decay_rate(ctx: Context<SetInfluenceDecayRate>, influence_decay_rate: u16) -> Result<()> {
+    require!(influence_decay_rate <= 10000, SolSocialError::InvalidPercentage);
+
+    ctx.accounts.platform_config.influence_decay_rate = influence_decay_rate;
+
+    emit!(InfluenceDecayRateUpdated {
+        influence_decay_rate,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct InfluenceDecayRateUpdated {
+    pub influence_decay_rate: u16,
+    pub timestamp: i64,
+}
\ No newline at end of file
@@ -0,0 +1,42 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+#[instruction(room_id: String)]
+pub struct SetJoinApproval<'info> {
+    #[account(
+        mut,
+        seeds = [b"chat_room", room_id.as_bytes()],
+        bump = chat_room.bump,
+        constraint = chat_room.creator == creator.key() @ SolSocialError::UnauthorizedUser
+    )]
+    pub chat_room: Account<'info, ChatRoom>,
+
+    pub creator: Signer<'info>,
+}
+
+pub fn set_join_approval(
+    ctx: Context<SetJoinApproval>,
+    _room_id: String,
+    require_creator_approval: bool,
+) -> Result<()> {
+    let chat_room = &mut ctx.accounts.chat_room;
+
+    chat_room.require_creator_approval = require_creator_approval;
+
+    emit!(JoinApprovalUpdated {
+        chat_room: chat_room.key(),
+        require_creator_approval,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct JoinApprovalUpdated {
+    pub chat_room: Pubkey,
+    pub require_creator_approval: bool,
+    pub timestamp: i64,
+}
@@ -0,0 +1,52 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+pub struct SetKeyFee<'info> {
+    #[account(
+        mut,
+        seeds = [b"user_key", subject.key().as_ref()],
+        bump = user_key.bump,
+        constraint = user_key.subject == subject.key() @ SolSocialError::UnauthorizedUser
+    )]
+    pub user_key: Account<'info, UserKey>,
+
+    #[account(
+        seeds = [b"platform_config"],
+        bump = platform_config.bump,
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+
+    pub subject: Signer<'info>,
+}
+
+/// Sets the creator fee charged on trades of the subject's keys, up to the governance cap.
+/// The protocol fee is global and unaffected.
+pub fn set_key_fee(ctx: Context<SetKeyFee>, subject_fee_percent: u16) -> Result<()> {
+    require!(
+        subject_fee_percent <= ctx.accounts.platform_config.max_subject_fee_percent,
+        SolSocialError::InvalidCreatorFee
+    );
+
+    let user_key = &mut ctx.accounts.user_key;
+    let previous_fee_percent = user_key.subject_fee_percent;
+    user_key.subject_fee_percent = subject_fee_percent;
+
+    emit!(KeyFeeUpdated {
+        subject: user_key.subject,
+        previous_fee_percent,
+        subject_fee_percent,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct KeyFeeUpdated {
+    pub subject: Pubkey,
+    pub previous_fee_percent: u16,
+    pub subject_fee_percent: u16,
+    pub timestamp: i64,
+}
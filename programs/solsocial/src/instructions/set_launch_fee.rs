@@ -0,0 +1,34 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+pub struct SetLaunchFee<'info> {
+    #[account(
+        mut,
+        seeds = [b"platform_config"],
+        bump = platform_config.bump,
+        constraint = platform_config.authority == authority.key() @ SolSocialError::UnauthorizedUser
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+
+    pub authority: Signer<'info>,
+}
+
+pub fn set_launch_fee(ctx: Context<SetLaunchFee>, launch_fee: u64) -> Result<()> {
+    let platform_config = &mut ctx.accounts.platform_config;
+    platform_config.launch_fee = launch_fee;
+
+    emit!(LaunchFeeUpdated {
+        launch_fee,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct LaunchFeeUpdated {
+    pub launch_fee: u64,
+    pub timestamp: i64,
+}
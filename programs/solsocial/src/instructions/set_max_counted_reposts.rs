@@ -0,0 +1,34 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+pub struct SetMaxCountedReposts<'info> {
+    #[account(
+        mut,
+        seeds = [b"platform_config"],
+        bump = platform_config.bump,
+        constraint = platform_config.authority == authority.key() @ SolSocialError::UnauthorizedUser
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+
+    pub authority: Signer<'info>,
+}
+
+/// Reposts past the cap still count but stop boosting a post's engagement; 0 lifts the cap
+pub fn set_max_counted_reposts(ctx: Context<SetMaxCountedReposts>, max_counted_reposts: u64) -> Result<()> {
+    ctx.accounts.platform_config.max_counted_reposts = max_counted_reposts;
+
+    emit!(MaxCountedRepostsUpdated {
+        max_counted_reposts,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct MaxCountedRepostsUpdated {
+    pub max_counted_reposts: u64,
+    pub timestamp: i64,
+}
\ No newline at end of file
@@ -0,0 +1,35 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+pub struct SetMaxCreatorSupply<'info> {
+    #[account(
+        mut,
+        seeds = [b"platform_config"],
+        bump = platform_config.bump,
+        constraint = platform_config.authority == authority.key() @ SolSocialError::UnauthorizedUser
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+
+    pub authority: Signer<'info>,
+}
+
+/// Setting 0 lifts the cap; issuance keeps being counted either way. Creators above a lowered
+/// cap keep their supply but can't issue more until it burns.
+pub fn set_max_creator_supply(ctx: Context<SetMaxCreatorSupply>, max_creator_supply: u64) -> Result<()> {
+    ctx.accounts.platform_config.max_creator_supply = max_creator_supply;
+
+    emit!(MaxCreatorSupplyUpdated {
+        max_creator_supply,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct MaxCreatorSupplyUpdated {
+    pub max_creator_supply: u64,
+    pub timestamp: i64,
+}
@@ -0,0 +1,34 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+pub struct SetMaxEngagementScore<'info> {
+    #[account(
+        mut,
+        seeds = [b"platform_config"],
+        bump = platform_config.bump,
+        constraint = platform_config.authority == authority.key() @ SolSocialError::UnauthorizedUser
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+
+    pub authority: Signer<'info>,
+}
+
+pub fn set_max_engagement_score(ctx: Context<SetMaxEngagementScore>, max_engagement_score: u64) -> Result<()> {
+    let platform_config = &mut ctx.accounts.platform_config;
+    platform_config.max_engagement_score = max_engagement_score;
+
+    emit!(MaxEngagementScoreUpdated {
+        max_engagement_score,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct MaxEngagementScoreUpdated {
+    pub max_engagement_score: u64,
+    pub timestamp: i64,
+}
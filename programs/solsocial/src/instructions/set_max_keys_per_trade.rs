@@ -0,0 +1,39 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+pub struct SetMaxKeysPerTrade<'info> {
+    #[account(
+        mut,
+        seeds = [b"platform_config"],
+        bump = platform_config.bump,
+        constraint = platform_config.authority == authority.key() @ SolSocialError::UnauthorizedUser
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+
+    pub authority: Signer<'info>,
+}
+
+pub fn set_max_keys_per_trade(
+    ctx: Context<SetMaxKeysPerTrade>,
+    max_keys_per_trade: u64,
+) -> Result<()> {
+    require!(max_keys_per_trade > 0, SolSocialError::InvalidAmount);
+
+    let platform_config = &mut ctx.accounts.platform_config;
+    platform_config.max_keys_per_trade = max_keys_per_trade;
+
+    emit!(MaxKeysPerTradeUpdated {
+        max_keys_per_trade,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct MaxKeysPerTradeUpdated {
+    pub max_keys_per_trade: u64,
+    pub timestamp: i64,
+}
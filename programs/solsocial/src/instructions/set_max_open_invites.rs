@@ -0,0 +1,43 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+#[instruction(room_id: String)]
+pub struct SetMaxOpenInvites<'info> {
+    #[account(
+        mut,
+        seeds = [b"chat_room", room_id.as_bytes()],
+        bump = chat_room.bump,
+        constraint = chat_room.creator == creator.key() @ SolSocialError::UnauthorizedUser
+    )]
+    pub chat_room: Account<'info, ChatRoom>,
+
+    pub creator: Signer<'info>,
+}
+
+/// Lowering the cap below the current count only blocks new invites; existing ones stay valid
+pub fn set_max_open_invites(
+    ctx: Context<SetMaxOpenInvites>,
+    _room_id: String,
+    max_open_invites: u32,
+) -> Result<()> {
+    let chat_room = &mut ctx.accounts.chat_room;
+
+    chat_room.max_open_invites = max_open_invites;
+
+    emit!(MaxOpenInvitesUpdated {
+        chat_room: chat_room.key(),
+        max_open_invites,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct MaxOpenInvitesUpdated {
+    pub chat_room: Pubkey,
+    pub max_open_invites: u32,
+    pub timestamp: i64,
+}
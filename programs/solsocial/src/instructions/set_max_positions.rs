@@ -0,0 +1,34 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+pub struct SetMaxPositions<'info> {
+    #[account(
+        mut,
+        seeds = [b"platform_config"],
+        bump = platform_config.bump,
+        constraint = platform_config.authority == authority.key() @ SolSocialError::UnauthorizedUser
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+
+    pub authority: Signer<'info>,
+}
+
+/// Wallets already above a lowered cap keep their positions but can't open new ones
+pub fn set_max_positions(ctx: Context<SetMaxPositions>, max_positions: u64) -> Result<()> {
+    ctx.accounts.platform_config.max_positions = max_positions;
+
+    emit!(MaxPositionsUpdated {
+        max_positions,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct MaxPositionsUpdated {
+    pub max_positions: u64,
+    pub timestamp: i64,
+}
\ No newline at end of file
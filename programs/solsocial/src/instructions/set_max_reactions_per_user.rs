@@ -0,0 +1,43 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+#[instruction(room_id: String)]
+pub struct SetMaxReactionsPerUser<'info> {
+    #[account(
+        mut,
+        seeds = [b"chat_room", room_id.as_bytes()],
+        bump = chat_room.bump,
+        constraint = chat_room.creator == creator.key() @ SolSocialError::UnauthorizedUser
+    )]
+    pub chat_room: Account<'info, ChatRoom>,
+
+    pub creator: Signer<'info>,
+}
+
+/// Reactions already over a lowered cap stay; only new ones are refused
+pub fn set_max_reactions_per_user(
+    ctx: Context<SetMaxReactionsPerUser>,
+    _room_id: String,
+    max_reactions_per_user: u32,
+) -> Result<()> {
+    let chat_room = &mut ctx.accounts.chat_room;
+
+    chat_room.max_reactions_per_user = max_reactions_per_user;
+
+    emit!(MaxReactionsPerUserUpdated {
+        chat_room: chat_room.key(),
+        max_reactions_per_user,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct MaxReactionsPerUserUpdated {
+    pub chat_room: Pubkey,
+    pub max_reactions_per_user: u32,
+    pub timestamp: i64,
+}
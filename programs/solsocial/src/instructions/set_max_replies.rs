@@ -0,0 +1,40 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+pub struct SetMaxReplies<'info> {
+    #[account(
+        mut,
+        seeds = [b"post", post.author.as_ref(), &post.id.to_le_bytes()],
+        bump = post.bump,
+        constraint = post.author == author.key() @ SolSocialError::UnauthorizedUser
+    )]
+    pub post: Account<'info, Post>,
+
+    pub author: Signer<'info>,
+}
+
+/// Locks the thread once `max_replies` replies exist; 0 lifts the limit. A cap at or below
+/// the current count locks it straight away.
+pub fn set_max_replies(ctx: Context<SetMaxReplies>, max_replies: u64) -> Result<()> {
+    let post = &mut ctx.accounts.post;
+    post.max_replies = max_replies;
+
+    emit!(MaxRepliesUpdated {
+        post: post.key(),
+        max_replies,
+        replies: post.replies,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct MaxRepliesUpdated {
+    pub post: Pubkey,
+    pub max_replies: u64,
+    pub replies: u64,
+    pub timestamp: i64,
+}
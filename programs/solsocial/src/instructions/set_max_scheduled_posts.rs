@@ -0,0 +1,34 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+pub struct SetMaxScheduledPosts<'info> {
+    #[account(
+        mut,
+        seeds = [b"platform_config"],
+        bump = platform_config.bump,
+        constraint = platform_config.authority == authority.key() @ SolSocialError::UnauthorizedUser
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+
+    pub authority: Signer<'info>,
+}
+
+/// Lowering the cap leaves already-queued posts alone; users just can't add more until some publish
+pub fn set_max_scheduled_posts(ctx: Context<SetMaxScheduledPosts>, max_scheduled_posts: u32) -> Result<()> {
+    ctx.accounts.platform_config.max_scheduled_posts = max_scheduled_posts;
+
+    emit!(MaxScheduledPostsUpdated {
+        max_scheduled_posts,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct MaxScheduledPostsUpdated {
+    pub max_scheduled_posts: u32,
+    pub timestamp: i64,
+}
@@ -0,0 +1,36 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+pub struct SetMaxSubjectFee<'info> {
+    #[account(
+        mut,
+        seeds = [b"platform_config"],
+        bump = platform_config.bump,
+        constraint = platform_config.authority == authority.key() @ SolSocialError::UnauthorizedUser
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+
+    pub authority: Signer<'info>,
+}
+
+/// Subjects already above a lowered cap keep their fee until they next change it
+pub fn set_max_subject_fee(ctx: Context<SetMaxSubjectFee>, max_subject_fee_percent: u16) -> Result<()> {
+    require!(max_subject_fee_percent <= 10000, SolSocialError::InvalidPercentage);
+
+    ctx.accounts.platform_config.max_subject_fee_percent = max_subject_fee_percent;
+
+    emit!(MaxSubjectFeeUpdated {
+        max_subject_fee_percent,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct MaxSubjectFeeUpdated {
+    pub max_subject_fee_percent: u16,
+    pub timestamp: i64,
+}
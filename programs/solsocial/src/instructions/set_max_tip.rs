@@ -0,0 +1,36 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+pub struct SetMaxTip<'info> {
+    #[account(
+        mut,
+        seeds = [b"platform_config"],
+        bump = platform_config.bump,
+        constraint = platform_config.authority == authority.key() @ SolSocialError::UnauthorizedUser
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+
+    pub authority: Signer<'info>,
+}
+
+/// Tips above `max_tip` need the tipper's explicit confirmation; `None` lets any tip through
+pub fn set_max_tip(ctx: Context<SetMaxTip>, max_tip: Option<u64>) -> Result<()> {
+    require!(max_tip.map_or(true, |max_tip| max_tip > 0), SolSocialError::InvalidAmount);
+
+    ctx.accounts.platform_config.max_tip = max_tip;
+
+    emit!(MaxTipUpdated {
+        max_tip,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct MaxTipUpdated {
+    pub max_tip: Option<u64>,
+    pub timestamp: i64,
+}
\ No newline at end of file
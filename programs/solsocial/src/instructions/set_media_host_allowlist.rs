@@ -0,0 +1,47 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+pub struct SetMediaHostAllowlist<'info> {
+    #[account(
+        mut,
+        seeds = [b"platform_config"],
+        bump = platform_config.bump,
+        constraint = platform_config.authority == authority.key() @ SolSocialError::UnauthorizedUser
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+
+    pub authority: Signer<'info>,
+}
+
+/// Replaces the allowlist; an empty list lets posts use media from any host
+pub fn set_media_host_allowlist(ctx: Context<SetMediaHostAllowlist>, hosts: Vec<String>) -> Result<()> {
+    require!(
+        hosts.len() <= PlatformConfig::MAX_MEDIA_HOSTS,
+        SolSocialError::InvalidConfiguration
+    );
+    for host in &hosts {
+        require!(
+            !host.is_empty()
+                && host.len() <= PlatformConfig::MAX_MEDIA_HOST_LENGTH
+                && !host.contains(|c: char| c == '/' || c == ':' || c.is_whitespace()),
+            SolSocialError::InvalidDataFormat
+        );
+    }
+
+    ctx.accounts.platform_config.media_host_allowlist = hosts.clone();
+
+    emit!(MediaHostAllowlistUpdated {
+        hosts,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct MediaHostAllowlistUpdated {
+    pub hosts: Vec<String>,
+    pub timestamp: i64,
+}
@@ -0,0 +1,41 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+pub struct SetMinBuyAmount<'info> {
+    #[account(
+        mut,
+        seeds = [b"platform_config"],
+        bump = platform_config.bump,
+        constraint = platform_config.authority == authority.key() @ SolSocialError::UnauthorizedUser
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+
+    pub authority: Signer<'info>,
+}
+
+pub fn set_min_buy_amount(ctx: Context<SetMinBuyAmount>, min_buy_amount: u64) -> Result<()> {
+    let platform_config = &mut ctx.accounts.platform_config;
+
+    require!(min_buy_amount > 0, SolSocialError::InvalidAmount);
+    require!(
+        min_buy_amount <= platform_config.max_keys_per_trade,
+        SolSocialError::InvalidConfiguration
+    );
+
+    platform_config.min_buy_amount = min_buy_amount;
+
+    emit!(MinBuyAmountUpdated {
+        min_buy_amount,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct MinBuyAmountUpdated {
+    pub min_buy_amount: u64,
+    pub timestamp: i64,
+}
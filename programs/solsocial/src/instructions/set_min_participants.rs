@@ -0,0 +1,48 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+#[instruction(room_id: String)]
+pub struct SetMinParticipants<'info> {
+    #[account(
+        mut,
+        seeds = [b"chat_room", room_id.as_bytes()],
+        bump = chat_room.bump,
+        constraint = chat_room.creator == creator.key() @ SolSocialError::UnauthorizedUser
+    )]
+    pub chat_room: Account<'info, ChatRoom>,
+
+    pub creator: Signer<'info>,
+}
+
+pub fn set_min_participants(
+    ctx: Context<SetMinParticipants>,
+    _room_id: String,
+    min_participants_to_open: u32,
+) -> Result<()> {
+    let chat_room = &mut ctx.accounts.chat_room;
+
+    // Only matters until the room first opens; lowering it may open the room right away
+    require!(!chat_room.is_open, SolSocialError::InvalidResourceState);
+
+    chat_room.min_participants_to_open = min_participants_to_open;
+    chat_room.open_if_quorum_reached();
+
+    emit!(MinParticipantsUpdated {
+        chat_room: chat_room.key(),
+        min_participants_to_open,
+        is_open: chat_room.is_open,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct MinParticipantsUpdated {
+    pub chat_room: Pubkey,
+    pub min_participants_to_open: u32,
+    pub is_open: bool,
+    pub timestamp: i64,
+}
\ No newline at end of file
@@ -0,0 +1,33 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+pub struct SetMinReputationForPremium<'info> {
+    #[account(
+        mut,
+        seeds = [b"platform_config"],
+        bump = platform_config.bump,
+        constraint = platform_config.authority == authority.key() @ SolSocialError::UnauthorizedUser
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+
+    pub authority: Signer<'info>,
+}
+
+pub fn set_min_reputation_for_premium(ctx: Context<SetMinReputationForPremium>, min_reputation_for_premium: u64) -> Result<()> {
+    ctx.accounts.platform_config.min_reputation_for_premium = min_reputation_for_premium;
+
+    emit!(MinReputationForPremiumUpdated {
+        min_reputation_for_premium,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct MinReputationForPremiumUpdated {
+    pub min_reputation_for_premium: u64,
+    pub timestamp: i64,
+}
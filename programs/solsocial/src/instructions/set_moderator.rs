@@ -0,0 +1,42 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+#[instruction(user: Pubkey)]
+pub struct SetModerator<'info> {
+    #[account(
+        seeds = [b"platform_config"],
+        bump = platform_config.bump,
+        constraint = platform_config.authority == authority.key() @ SolSocialError::UnauthorizedUser
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"user_profile", user.as_ref()],
+        bump = user_profile.bump,
+    )]
+    pub user_profile: Account<'info, UserProfile>,
+
+    pub authority: Signer<'info>,
+}
+
+pub fn set_moderator(ctx: Context<SetModerator>, user: Pubkey, is_moderator: bool) -> Result<()> {
+    ctx.accounts.user_profile.is_moderator = is_moderator;
+
+    emit!(ModeratorUpdated {
+        user,
+        is_moderator,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct ModeratorUpdated {
+    pub user: Pubkey,
+    pub is_moderator: bool,
+    pub timestamp: i64,
+}
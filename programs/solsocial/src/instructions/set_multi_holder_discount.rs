@@ -0,0 +1,43 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+pub struct SetMultiHolderDiscount<'info> {
+    #[account(
+        mut,
+        seeds = [b"platform_config"],
+        bump = platform_config.bump,
+        constraint = platform_config.authority == authority.key() @ SolSocialError::UnauthorizedUser
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+
+    pub authority: Signer<'info>,
+}
+
+pub fn set_multi_holder_discount(
+    ctx: Context<SetMultiHolderDiscount>,
+    multi_holder_threshold: u64,
+    multi_holder_discount_bps: u16,
+) -> Result<()> {
+    require!(multi_holder_discount_bps <= 10000, SolSocialError::InvalidPercentage);
+
+    let platform_config = &mut ctx.accounts.platform_config;
+    platform_config.multi_holder_threshold = multi_holder_threshold;
+    platform_config.multi_holder_discount_bps = multi_holder_discount_bps;
+
+    emit!(MultiHolderDiscountUpdated {
+        multi_holder_threshold,
+        multi_holder_discount_bps,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct MultiHolderDiscountUpdated {
+    pub multi_holder_threshold: u64,
+    pub multi_holder_discount_bps: u16,
+    pub timestamp: i64,
+}
@@ -0,0 +1,92 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+#[instruction(room_id: String, member: Pubkey)]
+pub struct SetParticipantRole<'info> {
+    #[account(
+        seeds = [b"chat_room", room_id.as_bytes()],
+        bump = chat_room.bump,
+    )]
+    pub chat_room: Account<'info, ChatRoom>,
+
+    /// The caller's own record, needed unless they're the room's creator
+    #[account(
+        seeds = [b"participant", chat_room.key().as_ref(), caller.key().as_ref()],
+        bump = caller_participant.bump,
+    )]
+    pub caller_participant: Option<Account<'info, ChatParticipant>>,
+
+    #[account(
+        init_if_needed,
+        payer = caller,
+        space = ChatParticipant::LEN,
+        seeds = [b"participant", chat_room.key().as_ref(), member.as_ref()],
+        bump
+    )]
+    pub participant: Account<'info, ChatParticipant>,
+
+    #[account(mut)]
+    pub caller: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Gives a room member a role and the permissions that come with it. Open to the owner and
+/// admins, though admins can't change another admin's role; ownership only moves through
+/// transfer_room_ownership.
+pub fn set_participant_role(
+    ctx: Context<SetParticipantRole>,
+    _room_id: String,
+    member: Pubkey,
+    role: ParticipantRole,
+) -> Result<()> {
+    let chat_room = &ctx.accounts.chat_room;
+
+    let caller_role = if ctx.accounts.caller.key() == chat_room.creator {
+        Some(ParticipantRole::Owner)
+    } else {
+        ctx.accounts.caller_participant.as_ref().map(|caller| caller.role.clone())
+    };
+    require!(
+        matches!(caller_role, Some(ParticipantRole::Owner) | Some(ParticipantRole::Admin)),
+        SolSocialError::PermissionDenied
+    );
+    require!(role != ParticipantRole::Owner, SolSocialError::PermissionDenied);
+    require!(member != chat_room.creator, SolSocialError::PermissionDenied);
+    require!(chat_room.is_participant(&member), SolSocialError::InvalidResourceState);
+
+    let participant = &mut ctx.accounts.participant;
+    // Admins are peers: only the owner can change an existing admin's role
+    let is_admin = participant.user == member && participant.role == ParticipantRole::Admin;
+    require!(
+        caller_role == Some(ParticipantRole::Owner) || !is_admin,
+        SolSocialError::PermissionDenied
+    );
+    let last_read_message = participant.last_read_message;
+    **participant = ChatParticipant::new(
+        member,
+        chat_room.room_id,
+        role.clone(),
+        ctx.bumps.participant,
+    );
+    participant.last_read_message = last_read_message;
+
+    emit!(ParticipantRoleSet {
+        chat_room: chat_room.key(),
+        member,
+        role,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct ParticipantRoleSet {
+    pub chat_room: Pubkey,
+    pub member: Pubkey,
+    pub role: ParticipantRole,
+    pub timestamp: i64,
+}
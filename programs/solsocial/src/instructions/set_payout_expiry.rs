@@ -0,0 +1,36 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+pub struct SetPayoutExpiry<'info> {
+    #[account(
+        mut,
+        seeds = [b"platform_config"],
+        bump = platform_config.bump,
+        constraint = platform_config.authority == authority.key() @ SolSocialError::UnauthorizedUser
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+
+    pub authority: Signer<'info>,
+}
+
+pub fn set_payout_expiry(ctx: Context<SetPayoutExpiry>, payout_expiry_seconds: i64) -> Result<()> {
+    require!(payout_expiry_seconds >= 0, SolSocialError::InvalidConfiguration);
+
+    let platform_config = &mut ctx.accounts.platform_config;
+    platform_config.payout_expiry_seconds = payout_expiry_seconds;
+
+    emit!(PayoutExpiryUpdated {
+        payout_expiry_seconds,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct PayoutExpiryUpdated {
+    pub payout_expiry_seconds: i64,
+    pub timestamp: i64,
+}
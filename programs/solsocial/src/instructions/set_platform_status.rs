@@ -0,0 +1,43 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+pub struct SetPlatformStatus<'info> {
+    #[account(
+        mut,
+        seeds = [b"platform_config"],
+        bump = platform_config.bump,
+        constraint = platform_config.authority == authority.key() @ SolSocialError::UnauthorizedUser
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+
+    pub authority: Signer<'info>,
+}
+
+/// Emergency switches: with trading off every buy, sell and order fill reverts with
+/// `TradingPaused`, with posting off new posts and post interactions revert with `PostingPaused`
+pub fn set_platform_status(
+    ctx: Context<SetPlatformStatus>,
+    is_trading_enabled: bool,
+    is_posting_enabled: bool,
+) -> Result<()> {
+    let platform_config = &mut ctx.accounts.platform_config;
+    platform_config.is_trading_enabled = is_trading_enabled;
+    platform_config.is_posting_enabled = is_posting_enabled;
+
+    emit!(PlatformStatusUpdated {
+        is_trading_enabled,
+        is_posting_enabled,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct PlatformStatusUpdated {
+    pub is_trading_enabled: bool,
+    pub is_posting_enabled: bool,
+    pub timestamp: i64,
+}
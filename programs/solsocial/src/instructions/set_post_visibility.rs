@@ -0,0 +1,39 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+pub struct SetPostVisibility<'info> {
+    #[account(
+        mut,
+        seeds = [b"post", author.key().as_ref(), &post.id.to_le_bytes()],
+        bump = post.bump,
+        constraint = post.author == author.key() @ SolSocialError::UnauthorizedUser
+    )]
+    pub post: Account<'info, Post>,
+
+    pub author: Signer<'info>,
+}
+
+pub fn set_post_visibility(ctx: Context<SetPostVisibility>, visibility: PostVisibility) -> Result<()> {
+    let post = &mut ctx.accounts.post;
+
+    require!(post.status == PostStatus::Active, SolSocialError::PostNotActive);
+
+    post.visibility = visibility.clone();
+
+    emit!(PostVisibilityChanged {
+        post: post.key(),
+        author: post.author,
+        visibility,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct PostVisibilityChanged {
+    pub post: Pubkey,
+    pub author: Pubkey,
+    pub visibility: PostVisibility,
+}
@@ -0,0 +1,43 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+pub struct SetPremiumAccessValue<'info> {
+    #[account(
+        mut,
+        seeds = [b"post", author.key().as_ref(), &post.id.to_le_bytes()],
+        bump = post.bump,
+        constraint = post.author == author.key() @ SolSocialError::UnauthorizedUser
+    )]
+    pub post: Account<'info, Post>,
+
+    pub author: Signer<'info>,
+}
+
+/// Prices premium access in lamports of keys; zero falls back to the fixed `required_keys`
+pub fn set_premium_access_value(
+    ctx: Context<SetPremiumAccessValue>,
+    premium_access_value: u64,
+) -> Result<()> {
+    let post = &mut ctx.accounts.post;
+
+    require!(post.is_premium, SolSocialError::InvalidResourceState);
+
+    post.premium_access_value = premium_access_value;
+
+    emit!(PremiumAccessValueUpdated {
+        post: post.key(),
+        author: post.author,
+        premium_access_value,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct PremiumAccessValueUpdated {
+    pub post: Pubkey,
+    pub author: Pubkey,
+    pub premium_access_value: u64,
+}
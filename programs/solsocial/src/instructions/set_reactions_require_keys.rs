@@ -0,0 +1,43 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+#[instruction(room_id: String)]
+pub struct SetReactionsRequireKeys<'info> {
+    #[account(
+        mut,
+        seeds = [b"chat_room", room_id.as_bytes()],
+        bump = chat_room.bump,
+        constraint = chat_room.creator == creator.key() @ SolSocialError::UnauthorizedUser
+    )]
+    pub chat_room: Account<'info, ChatRoom>,
+
+    pub creator: Signer<'info>,
+}
+
+/// Existing reactions stay when the room is restricted; only new ones need keys
+pub fn set_reactions_require_keys(
+    ctx: Context<SetReactionsRequireKeys>,
+    _room_id: String,
+    reactions_require_keys: bool,
+) -> Result<()> {
+    let chat_room = &mut ctx.accounts.chat_room;
+
+    chat_room.reactions_require_keys = reactions_require_keys;
+
+    emit!(ReactionsRequireKeysUpdated {
+        chat_room: chat_room.key(),
+        reactions_require_keys,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct ReactionsRequireKeysUpdated {
+    pub chat_room: Pubkey,
+    pub reactions_require_keys: bool,
+    pub timestamp: i64,
+}
@@ -0,0 +1,40 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+pub struct SetReferralBonus<'info> {
+    #[account(
+        mut,
+        seeds = [b"user_keys", creator.key().as_ref()],
+        bump = user_keys.bump,
+        constraint = user_keys.owner == creator.key() @ SolSocialError::UnauthorizedUser
+    )]
+    pub user_keys: Account<'info, UserKeys>,
+
+    pub creator: Signer<'info>,
+}
+
+pub fn set_referral_bonus(
+    ctx: Context<SetReferralBonus>,
+    referral_bonus_bps: Option<u16>,
+) -> Result<()> {
+    let user_keys = &mut ctx.accounts.user_keys;
+
+    user_keys.set_referral_bonus(referral_bonus_bps)?;
+
+    emit!(ReferralBonusUpdated {
+        creator: ctx.accounts.creator.key(),
+        referral_bonus_bps,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct ReferralBonusUpdated {
+    pub creator: Pubkey,
+    pub referral_bonus_bps: Option<u16>,
+    pub timestamp: i64,
+}
@@ -0,0 +1,34 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+pub struct SetReportHideThreshold<'info> {
+    #[account(
+        mut,
+        seeds = [b"platform_config"],
+        bump = platform_config.bump,
+        constraint = platform_config.authority == authority.key() @ SolSocialError::UnauthorizedUser
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+
+    pub authority: Signer<'info>,
+}
+
+/// Posts already hidden stay hidden; the new threshold applies from the next report
+pub fn set_report_hide_threshold(ctx: Context<SetReportHideThreshold>, report_hide_threshold: u32) -> Result<()> {
+    ctx.accounts.platform_config.report_hide_threshold = report_hide_threshold;
+
+    emit!(ReportHideThresholdUpdated {
+        report_hide_threshold,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct ReportHideThresholdUpdated {
+    pub report_hide_threshold: u32,
+    pub timestamp: i64,
+}
@@ -0,0 +1,44 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+pub struct SetReputationBootstrap<'info> {
+    #[account(
+        mut,
+        seeds = [b"platform_config"],
+        bump = platform_config.bump,
+        constraint = platform_config.authority == authority.key() @ SolSocialError::UnauthorizedUser
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+
+    pub authority: Signer<'info>,
+}
+
+/// A rate of 0 turns bootstrapping off; buyers past a lowered cap keep what they already earned
+pub fn set_reputation_bootstrap(
+    ctx: Context<SetReputationBootstrap>,
+    bootstrap_reputation_rate: u16,
+    max_bootstrap_reputation: u64,
+) -> Result<()> {
+    require!(bootstrap_reputation_rate <= 10000, SolSocialError::InvalidPercentage);
+
+    let platform_config = &mut ctx.accounts.platform_config;
+    platform_config.bootstrap_reputation_rate = bootstrap_reputation_rate;
+    platform_config.max_bootstrap_reputation = max_bootstrap_reputation;
+
+    emit!(ReputationBootstrapUpdated {
+        bootstrap_reputation_rate,
+        max_bootstrap_reputation,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct ReputationBootstrapUpdated {
+    pub bootstrap_reputation_rate: u16,
+    pub max_bootstrap_reputation: u64,
+    pub timestamp: i64,
+}
\ No newline at end of file
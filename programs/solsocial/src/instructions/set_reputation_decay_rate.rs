@@ -0,0 +1,35 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+pub struct SetReputationDecayRate<'info> {
+    #[account(
+        mut,
+        seeds = [b"platform_config"],
+        bump = platform_config.bump,
+        constraint = platform_config.authority == authority.key() @ SolSocialError::UnauthorizedUser
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+
+    pub authority: Signer<'info>,
+}
+
+pub fn set_reputation_decay_rate(ctx: Context<SetReputationDecayRate>, reputation_decay_rate: u16) -> Result<()> {
+    require!(reputation_decay_rate <= 10000, SolSocialError::InvalidPercentage);
+
+    ctx.accounts.platform_config.reputation_decay_rate = reputation_decay_rate;
+
+    emit!(ReputationDecayRateUpdated {
+        reputation_decay_rate,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct ReputationDecayRateUpdated {
+    pub reputation_decay_rate: u16,
+    pub timestamp: i64,
+}
\ No newline at end of file
@@ -0,0 +1,37 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+pub struct SetReputationSettlementThreshold<'info> {
+    #[account(
+        mut,
+        seeds = [b"platform_config"],
+        bump = platform_config.bump,
+        constraint = platform_config.authority == authority.key() @ SolSocialError::UnauthorizedUser
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+
+    pub authority: Signer<'info>,
+}
+
+/// A threshold of 0 credits reputation immediately on every interaction
+pub fn set_reputation_settlement_threshold(
+    ctx: Context<SetReputationSettlementThreshold>,
+    reputation_settlement_threshold: u64,
+) -> Result<()> {
+    ctx.accounts.platform_config.reputation_settlement_threshold = reputation_settlement_threshold;
+
+    emit!(ReputationSettlementThresholdUpdated {
+        reputation_settlement_threshold,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct ReputationSettlementThresholdUpdated {
+    pub reputation_settlement_threshold: u64,
+    pub timestamp: i64,
+}
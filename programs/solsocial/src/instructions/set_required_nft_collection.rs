@@ -0,0 +1,43 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+#[instruction(room_id: String)]
+pub struct SetRequiredNftCollection<'info> {
+    #[account(
+        mut,
+        seeds = [b"chat_room", room_id.as_bytes()],
+        bump = chat_room.bump,
+        constraint = chat_room.creator == creator.key() @ SolSocialError::UnauthorizedUser
+    )]
+    pub chat_room: Account<'info, ChatRoom>,
+
+    pub creator: Signer<'info>,
+}
+
+/// Gating only applies to future joins; members who are already in keep their place
+pub fn set_required_nft_collection(
+    ctx: Context<SetRequiredNftCollection>,
+    _room_id: String,
+    required_nft_collection: Option<Pubkey>,
+) -> Result<()> {
+    let chat_room = &mut ctx.accounts.chat_room;
+
+    chat_room.access_control.required_nft_collection = required_nft_collection;
+
+    emit!(RequiredNftCollectionUpdated {
+        chat_room: chat_room.key(),
+        required_nft_collection,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct RequiredNftCollectionUpdated {
+    pub chat_room: Pubkey,
+    pub required_nft_collection: Option<Pubkey>,
+    pub timestamp: i64,
+}
@@ -0,0 +1,39 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+pub struct SetSelfRepostCooldown<'info> {
+    #[account(
+        mut,
+        seeds = [b"platform_config"],
+        bump = platform_config.bump,
+        constraint = platform_config.authority == authority.key() @ SolSocialError::UnauthorizedUser
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+
+    pub authority: Signer<'info>,
+}
+
+pub fn set_self_repost_cooldown(
+    ctx: Context<SetSelfRepostCooldown>,
+    self_repost_cooldown_seconds: i64,
+) -> Result<()> {
+    require!(self_repost_cooldown_seconds >= 0, SolSocialError::InvalidConfiguration);
+
+    let platform_config = &mut ctx.accounts.platform_config;
+    platform_config.self_repost_cooldown_seconds = self_repost_cooldown_seconds;
+
+    emit!(SelfRepostCooldownUpdated {
+        self_repost_cooldown_seconds,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct SelfRepostCooldownUpdated {
+    pub self_repost_cooldown_seconds: i64,
+    pub timestamp: i64,
+}
@@ -0,0 +1,40 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+pub struct SetSellInterval<'info> {
+    #[account(
+        mut,
+        seeds = [b"user_keys", creator.key().as_ref()],
+        bump = user_keys.bump,
+        constraint = user_keys.owner == creator.key() @ SolSocialError::UnauthorizedUser
+    )]
+    pub user_keys: Account<'info, UserKeys>,
+
+    pub creator: Signer<'info>,
+}
+
+pub fn set_sell_interval(
+    ctx: Context<SetSellInterval>,
+    sell_interval_seconds: i64,
+) -> Result<()> {
+    let user_keys = &mut ctx.accounts.user_keys;
+
+    user_keys.set_sell_interval(sell_interval_seconds)?;
+
+    emit!(SellIntervalUpdated {
+        creator: ctx.accounts.creator.key(),
+        sell_interval_seconds,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct SellIntervalUpdated {
+    pub creator: Pubkey,
+    pub sell_interval_seconds: i64,
+    pub timestamp: i64,
+}
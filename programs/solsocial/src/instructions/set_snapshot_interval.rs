@@ -0,0 +1,35 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+pub struct SetSnapshotInterval<'info> {
+    #[account(
+        mut,
+        seeds = [b"platform_config"],
+        bump = platform_config.bump,
+        constraint = platform_config.authority == authority.key() @ SolSocialError::UnauthorizedUser
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+
+    pub authority: Signer<'info>,
+}
+
+pub fn set_snapshot_interval(ctx: Context<SetSnapshotInterval>, min_snapshot_interval_seconds: i64) -> Result<()> {
+    require!(min_snapshot_interval_seconds >= 0, SolSocialError::InvalidTimestamp);
+
+    ctx.accounts.platform_config.min_snapshot_interval_seconds = min_snapshot_interval_seconds;
+
+    emit!(SnapshotIntervalUpdated {
+        min_snapshot_interval_seconds,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct SnapshotIntervalUpdated {
+    pub min_snapshot_interval_seconds: i64,
+    pub timestamp: i64,
+}
@@ -0,0 +1,45 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+pub struct SetSpendCap<'info> {
+    #[account(
+        mut,
+        seeds = [b"user_profile", user.key().as_ref()],
+        bump = user_profile.bump,
+    )]
+    pub user_profile: Account<'info, UserProfile>,
+
+    pub user: Signer<'info>,
+}
+
+/// Self-imposed limit on what the caller spends on key buys and tips per window; a cap of
+/// 0 removes it. Changing the cap starts a fresh window.
+pub fn set_spend_cap(ctx: Context<SetSpendCap>, spend_cap: u64, spend_window_seconds: i64) -> Result<()> {
+    require!(spend_cap == 0 || spend_window_seconds > 0, SolSocialError::InvalidTimestamp);
+
+    let user_profile = &mut ctx.accounts.user_profile;
+    let current_time = Clock::get()?.unix_timestamp;
+    user_profile.spend_cap = spend_cap;
+    user_profile.spend_window_seconds = spend_window_seconds;
+    user_profile.spend_window_start = current_time;
+    user_profile.spent_in_window = 0;
+
+    emit!(SpendCapUpdated {
+        user: ctx.accounts.user.key(),
+        spend_cap,
+        spend_window_seconds,
+        timestamp: current_time,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct SpendCapUpdated {
+    pub user: Pubkey,
+    pub spend_cap: u64,
+    pub spend_window_seconds: i64,
+    pub timestamp: i64,
+}
@@ -0,0 +1,35 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+
+#[derive(Accounts)]
+pub struct SetSubscriptionPrice<'info> {
+    #[account(
+        mut,
+        seeds = [b"user_profile", user.key().as_ref()],
+        bump = user_profile.bump,
+    )]
+    pub user_profile: Account<'info, UserProfile>,
+
+    pub user: Signer<'info>,
+}
+
+/// Monthly lamport price for access to the caller's premium posts; 0 stops new subscriptions
+/// and renewals, while existing ones run to their expiry
+pub fn set_subscription_price(ctx: Context<SetSubscriptionPrice>, subscription_price: u64) -> Result<()> {
+    ctx.accounts.user_profile.subscription_price = subscription_price;
+
+    emit!(SubscriptionPriceUpdated {
+        user: ctx.accounts.user.key(),
+        subscription_price,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct SubscriptionPriceUpdated {
+    pub user: Pubkey,
+    pub subscription_price: u64,
+    pub timestamp: i64,
+}
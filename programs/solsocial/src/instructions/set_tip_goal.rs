@@ -0,0 +1,37 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+pub struct SetTipGoal<'info> {
+    #[account(
+        mut,
+        seeds = [b"post", post.author.as_ref(), &post.id.to_le_bytes()],
+        bump = post.bump,
+        constraint = post.author == author.key() @ SolSocialError::UnauthorizedUser
+    )]
+    pub post: Account<'info, Post>,
+
+    pub author: Signer<'info>,
+}
+
+pub fn set_tip_goal(ctx: Context<SetTipGoal>, tip_goal: u64) -> Result<()> {
+    let post = &mut ctx.accounts.post;
+
+    post.set_tip_goal(tip_goal)?;
+
+    emit!(TipGoalUpdated {
+        post: post.key(),
+        author: post.author,
+        tip_goal,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct TipGoalUpdated {
+    pub post: Pubkey,
+    pub author: Pubkey,
+    pub tip_goal: u64,
+}
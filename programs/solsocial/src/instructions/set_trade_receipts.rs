@@ -0,0 +1,56 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+pub struct SetTradeReceipts<'info> {
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = TradeLedger::LEN,
+        seeds = [b"trade_ledger", owner.key().as_ref(), subject.key().as_ref()],
+        bump
+    )]
+    pub trade_ledger: Account<'info, TradeLedger>,
+
+    /// CHECK: Only used to derive the ledger for this subject's market
+    pub subject: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn set_trade_receipts(ctx: Context<SetTradeReceipts>, enabled: bool) -> Result<()> {
+    let trade_ledger = &mut ctx.accounts.trade_ledger;
+
+    if trade_ledger.owner == Pubkey::default() {
+        trade_ledger.owner = ctx.accounts.owner.key();
+        trade_ledger.subject = ctx.accounts.subject.key();
+        trade_ledger.next_sequence = 0;
+        trade_ledger.position_amount = 0;
+        trade_ledger.cost_basis = 0;
+        trade_ledger.bump = ctx.bumps.trade_ledger;
+    }
+    require_keys_eq!(trade_ledger.owner, ctx.accounts.owner.key(), SolSocialError::UnauthorizedUser);
+
+    trade_ledger.receipts_enabled = enabled;
+
+    emit!(TradeReceiptsUpdated {
+        owner: trade_ledger.owner,
+        subject: trade_ledger.subject,
+        enabled,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct TradeReceiptsUpdated {
+    pub owner: Pubkey,
+    pub subject: Pubkey,
+    pub enabled: bool,
+    pub timestamp: i64,
+}
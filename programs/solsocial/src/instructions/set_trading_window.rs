@@ -0,0 +1,43 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+pub struct SetTradingWindow<'info> {
+    #[account(
+        mut,
+        seeds = [b"user_keys", creator.key().as_ref()],
+        bump = user_keys.bump,
+        constraint = user_keys.owner == creator.key() @ SolSocialError::UnauthorizedUser
+    )]
+    pub user_keys: Account<'info, UserKeys>,
+
+    pub creator: Signer<'info>,
+}
+
+pub fn set_trading_window(
+    ctx: Context<SetTradingWindow>,
+    trading_open: Option<i64>,
+    trading_close: Option<i64>,
+) -> Result<()> {
+    let user_keys = &mut ctx.accounts.user_keys;
+
+    user_keys.set_trading_window(trading_open, trading_close)?;
+
+    emit!(TradingWindowUpdated {
+        creator: ctx.accounts.creator.key(),
+        trading_open,
+        trading_close,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct TradingWindowUpdated {
+    pub creator: Pubkey,
+    pub trading_open: Option<i64>,
+    pub trading_close: Option<i64>,
+    pub timestamp: i64,
+}
@@ -0,0 +1,47 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+pub struct SetUnfollowPenalty<'info> {
+    #[account(
+        mut,
+        seeds = [b"platform_config"],
+        bump = platform_config.bump,
+        constraint = platform_config.authority == authority.key() @ SolSocialError::UnauthorizedUser
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+
+    pub authority: Signer<'info>,
+}
+
+pub fn set_unfollow_penalty(
+    ctx: Context<SetUnfollowPenalty>,
+    unfollow_penalty_window: i64,
+    free_unfollows_per_window: u32,
+    unfollow_reputation_penalty: u64,
+) -> Result<()> {
+    require!(unfollow_penalty_window >= 0, SolSocialError::InvalidConfiguration);
+
+    let platform_config = &mut ctx.accounts.platform_config;
+    platform_config.unfollow_penalty_window = unfollow_penalty_window;
+    platform_config.free_unfollows_per_window = free_unfollows_per_window;
+    platform_config.unfollow_reputation_penalty = unfollow_reputation_penalty;
+
+    emit!(UnfollowPenaltyUpdated {
+        unfollow_penalty_window,
+        free_unfollows_per_window,
+        unfollow_reputation_penalty,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct UnfollowPenaltyUpdated {
+    pub unfollow_penalty_window: i64,
+    pub free_unfollows_per_window: u32,
+    pub unfollow_reputation_penalty: u64,
+    pub timestamp: i64,
+}
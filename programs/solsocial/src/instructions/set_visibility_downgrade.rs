@@ -0,0 +1,47 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+pub struct SetVisibilityDowngrade<'info> {
+    #[account(
+        mut,
+        seeds = [b"post", author.key().as_ref(), &post.id.to_le_bytes()],
+        bump = post.bump,
+        constraint = post.author == author.key() @ SolSocialError::UnauthorizedUser
+    )]
+    pub post: Account<'info, Post>,
+
+    pub author: Signer<'info>,
+}
+
+/// Opens a gated post to the public once it is `after_seconds` old without reaching
+/// `engagement_threshold`; an `after_seconds` of 0 turns the downgrade off
+pub fn set_visibility_downgrade(
+    ctx: Context<SetVisibilityDowngrade>,
+    after_seconds: i64,
+    engagement_threshold: u64,
+) -> Result<()> {
+    require!(after_seconds >= 0, SolSocialError::InvalidConfiguration);
+
+    let post = &mut ctx.accounts.post;
+    post.downgrade_after_seconds = after_seconds;
+    post.downgrade_engagement_threshold = engagement_threshold;
+
+    emit!(VisibilityDowngradeConfigured {
+        post: post.key(),
+        author: post.author,
+        after_seconds,
+        engagement_threshold,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct VisibilityDowngradeConfigured {
+    pub post: Pubkey,
+    pub author: Pubkey,
+    pub after_seconds: i64,
+    pub engagement_threshold: u64,
+}
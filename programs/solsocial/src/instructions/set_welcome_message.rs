@@ -0,0 +1,47 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+#[instruction(room_id: String)]
+pub struct SetWelcomeMessage<'info> {
+    #[account(
+        mut,
+        seeds = [b"chat_room", room_id.as_bytes()],
+        bump = chat_room.bump,
+        constraint = chat_room.creator == creator.key() @ SolSocialError::UnauthorizedUser
+    )]
+    pub chat_room: Account<'info, ChatRoom>,
+
+    pub creator: Signer<'info>,
+}
+
+/// An empty message turns the welcome off
+pub fn set_welcome_message(
+    ctx: Context<SetWelcomeMessage>,
+    _room_id: String,
+    welcome_message: String,
+) -> Result<()> {
+    require!(
+        welcome_message.len() <= ChatRoom::MAX_WELCOME_MESSAGE_LENGTH,
+        SolSocialError::MessageTooLong
+    );
+
+    let chat_room = &mut ctx.accounts.chat_room;
+    chat_room.welcome_message = welcome_message;
+
+    emit!(WelcomeMessageUpdated {
+        chat_room: chat_room.key(),
+        enabled: !chat_room.welcome_message.is_empty(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct WelcomeMessageUpdated {
+    pub chat_room: Pubkey,
+    pub enabled: bool,
+    pub timestamp: i64,
+}
@@ -0,0 +1,40 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+
+#[derive(Accounts)]
+pub struct SettleReputation<'info> {
+    #[account(
+        mut,
+        seeds = [b"user_profile", user_profile.authority.as_ref()],
+        bump = user_profile.bump,
+    )]
+    pub user_profile: Account<'info, UserProfile>,
+}
+
+/// Permissionless: moves whatever reputation is pending into the score, so readers can
+/// bring a profile up to date without waiting for the settlement threshold
+pub fn settle_reputation(ctx: Context<SettleReputation>) -> Result<()> {
+    let user_profile = &mut ctx.accounts.user_profile;
+
+    let settled = user_profile.settle_reputation();
+    if settled == 0 {
+        return Ok(());
+    }
+
+    emit!(ReputationSettled {
+        user: user_profile.authority,
+        settled,
+        new_score: user_profile.reputation_score,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct ReputationSettled {
+    pub user: Pubkey,
+    pub settled: u64,
+    pub new_score: u64,
+    pub timestamp: i64,
+}
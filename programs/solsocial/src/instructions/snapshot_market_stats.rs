@@ -0,0 +1,100 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+use crate::utils::bonding_curve::BondingCurve;
+
+#[derive(Accounts)]
+pub struct SnapshotMarketStats<'info> {
+    // The market the trading instructions move, so the snapshot sees its live supply
+    #[account(
+        seeds = [b"user_keys", subject.key().as_ref()],
+        bump = user_keys.bump,
+    )]
+    pub user_keys: Account<'info, UserKeys>,
+
+    /// CHECK: The subject whose market is being snapshotted
+    pub subject: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"user_key", subject.key().as_ref()],
+        bump = user_key.bump,
+    )]
+    pub user_key: Account<'info, UserKey>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = MarketSnapshot::LEN,
+        seeds = [b"market_snapshot", subject.key().as_ref(), &user_key.snapshot_count.to_le_bytes()],
+        bump
+    )]
+    pub market_snapshot: Account<'info, MarketSnapshot>,
+
+    #[account(
+        seeds = [b"platform_config"],
+        bump = platform_config.bump,
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Permissionless: persists the market's curve stats so charts can be built from chain state
+/// alone. The payer funds the snapshot account.
+pub fn snapshot_market_stats(ctx: Context<SnapshotMarketStats>) -> Result<()> {
+    let user_key = &mut ctx.accounts.user_key;
+    let now = Clock::get()?.unix_timestamp;
+
+    if user_key.snapshot_count > 0 {
+        require!(
+            now.saturating_sub(user_key.last_snapshot_at)
+                >= ctx.accounts.platform_config.min_snapshot_interval_seconds,
+            SolSocialError::InteractionCooldown
+        );
+    }
+
+    let supply = ctx.accounts.user_keys.total_supply;
+    let stats = BondingCurve::standard().get_curve_stats(supply)?;
+
+    let market_snapshot = &mut ctx.accounts.market_snapshot;
+    market_snapshot.subject = ctx.accounts.subject.key();
+    market_snapshot.sequence = user_key.snapshot_count;
+    market_snapshot.supply = stats.supply;
+    market_snapshot.current_price = stats.current_price;
+    market_snapshot.market_cap = stats.market_cap;
+    market_snapshot.liquidity = stats.liquidity;
+    market_snapshot.timestamp = now;
+    market_snapshot.bump = ctx.bumps.market_snapshot;
+
+    user_key.snapshot_count = user_key.snapshot_count
+        .checked_add(1)
+        .ok_or(SolSocialError::MathOverflow)?;
+    user_key.last_snapshot_at = now;
+
+    emit!(MarketSnapshotTaken {
+        subject: market_snapshot.subject,
+        sequence: market_snapshot.sequence,
+        supply: stats.supply,
+        current_price: stats.current_price,
+        market_cap: stats.market_cap,
+        liquidity: stats.liquidity,
+        timestamp: now,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct MarketSnapshotTaken {
+    pub subject: Pubkey,
+    pub sequence: u64,
+    pub supply: u64,
+    pub current_price: u64,
+    pub market_cap: u64,
+    pub liquidity: u64,
+    pub timestamp: i64,
+}
@@ -0,0 +1,147 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+use crate::utils::revenue_share::*;
+
+#[derive(Accounts)]
+pub struct Subscribe<'info> {
+    #[account(mut)]
+    pub creator: SystemAccount<'info>,
+
+    #[account(
+        seeds = [b"user_profile", creator.key().as_ref()],
+        bump = creator_profile.bump,
+    )]
+    pub creator_profile: Account<'info, UserProfile>,
+
+    #[account(
+        mut,
+        seeds = [b"user", creator.key().as_ref()],
+        bump = creator_user.bump,
+    )]
+    pub creator_user: Account<'info, User>,
+
+    #[account(
+        init_if_needed,
+        payer = subscriber,
+        space = Subscription::LEN,
+        seeds = [b"subscription", creator.key().as_ref(), subscriber.key().as_ref()],
+        bump
+    )]
+    pub subscription: Account<'info, Subscription>,
+
+    #[account(
+        mut,
+        seeds = [b"user_profile", subscriber.key().as_ref()],
+        bump = subscriber_profile.bump,
+    )]
+    pub subscriber_profile: Account<'info, UserProfile>,
+
+    #[account(
+        mut,
+        seeds = [b"protocol_treasury"],
+        bump
+    )]
+    pub protocol_treasury: SystemAccount<'info>,
+
+    #[account(mut)]
+    pub subscriber: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Buys or renews `months` of access to the creator's premium posts at their monthly
+/// `subscription_price`. Renewing before expiry extends the current term.
+pub fn subscribe(ctx: Context<Subscribe>, months: u8) -> Result<()> {
+    let creator = ctx.accounts.creator.key();
+    let subscriber = ctx.accounts.subscriber.key();
+    let monthly_price = ctx.accounts.creator_profile.subscription_price;
+
+    require!(monthly_price > 0, SolSocialError::FeatureDisabled);
+    require!(
+        months > 0 && months <= Subscription::MAX_MONTHS,
+        SolSocialError::InvalidAmount
+    );
+    require!(subscriber != creator, SolSocialError::SelfInteractionNotAllowed);
+
+    let amount = monthly_price
+        .checked_mul(months as u64)
+        .ok_or(SolSocialError::MathOverflow)?;
+
+    let clock = Clock::get()?;
+    ctx.accounts.subscriber_profile.record_spend(amount, clock.unix_timestamp)?;
+
+    // Same split as tips: the protocol takes its share and the creator receives the rest
+    let distribution = calculate_revenue_distribution(amount, false)?;
+    let protocol_fee = distribution.protocol_amount;
+    let creator_proceeds = amount
+        .checked_sub(protocol_fee)
+        .ok_or(SolSocialError::MathUnderflow)?;
+
+    anchor_lang::system_program::transfer(
+        CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            anchor_lang::system_program::Transfer {
+                from: ctx.accounts.subscriber.to_account_info(),
+                to: ctx.accounts.creator.to_account_info(),
+            },
+        ),
+        creator_proceeds,
+    )?;
+
+    if protocol_fee > 0 {
+        anchor_lang::system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                anchor_lang::system_program::Transfer {
+                    from: ctx.accounts.subscriber.to_account_info(),
+                    to: ctx.accounts.protocol_treasury.to_account_info(),
+                },
+            ),
+            protocol_fee,
+        )?;
+    }
+
+    let subscription = &mut ctx.accounts.subscription;
+    if subscription.subscriber == Pubkey::default() {
+        subscription.set_inner(Subscription {
+            subscriber,
+            creator,
+            started_at: clock.unix_timestamp,
+            expires_at: clock.unix_timestamp,
+            total_paid: 0,
+            bump: ctx.bumps.subscription,
+        });
+    }
+    subscription.expires_at = extend_subscription_term(subscription.expires_at, clock.unix_timestamp, months)?;
+    subscription.total_paid = subscription.total_paid
+        .checked_add(amount)
+        .ok_or(SolSocialError::MathOverflow)?;
+
+    ctx.accounts.creator_user.update_revenue(RevenueType::Subscription, creator_proceeds, &clock)?;
+
+    emit!(Subscribed {
+        subscriber,
+        creator,
+        months,
+        amount,
+        creator_proceeds,
+        protocol_fee,
+        expires_at: subscription.expires_at,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct Subscribed {
+    pub subscriber: Pubkey,
+    pub creator: Pubkey,
+    pub months: u8,
+    pub amount: u64,
+    pub creator_proceeds: u64,
+    pub protocol_fee: u64,
+    pub expires_at: i64,
+    pub timestamp: i64,
+}
@@ -0,0 +1,67 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+pub struct SweepExpiredPayout<'info> {
+    #[account(
+        mut,
+        seeds = [b"revenue_share", revenue_share.subject.as_ref()],
+        bump = revenue_share.bump,
+    )]
+    pub revenue_share: Account<'info, RevenueShare>,
+
+    #[account(
+        seeds = [b"platform_config"],
+        bump = platform_config.bump,
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"protocol_treasury"],
+        bump
+    )]
+    pub protocol_treasury: SystemAccount<'info>,
+}
+
+/// Permissionless: moves revenue a creator has left unclaimed past `payout_expiry_seconds`
+/// into the protocol treasury
+pub fn sweep_expired_payout(ctx: Context<SweepExpiredPayout>) -> Result<()> {
+    let revenue_share = &mut ctx.accounts.revenue_share;
+    let now = Clock::get()?.unix_timestamp;
+
+    require!(revenue_share.pending_withdrawal > 0, SolSocialError::InsufficientRewards);
+    require!(
+        revenue_share.is_payout_expired(now, ctx.accounts.platform_config.payout_expiry_seconds),
+        SolSocialError::ResourceLocked
+    );
+
+    // Zero the balance before any lamports move
+    let amount = revenue_share.forfeit_pending();
+
+    let escrow = revenue_share.to_account_info();
+    let rent_exempt_minimum = Rent::get()?.minimum_balance(escrow.data_len());
+    require!(
+        escrow.lamports().saturating_sub(rent_exempt_minimum) >= amount,
+        SolSocialError::InsufficientFunds
+    );
+
+    **escrow.try_borrow_mut_lamports()? -= amount;
+    **ctx.accounts.protocol_treasury.to_account_info().try_borrow_mut_lamports()? += amount;
+
+    emit!(ExpiredPayoutSwept {
+        subject: revenue_share.subject,
+        amount,
+        timestamp: now,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct ExpiredPayoutSwept {
+    pub subject: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
@@ -0,0 +1,192 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+use crate::utils::revenue_share::*;
+
+#[derive(Accounts)]
+pub struct TipPost<'info> {
+    #[account(
+        seeds = [b"post", post.author.as_ref(), &post.id.to_le_bytes()],
+        bump = post.bump,
+    )]
+    pub post: Account<'info, Post>,
+
+    #[account(
+        mut,
+        seeds = [b"post_stats", post.key().as_ref()],
+        bump = post_stats.bump,
+    )]
+    pub post_stats: Account<'info, PostStats>,
+
+    #[account(
+        mut,
+        constraint = author.key() == post.author @ SolSocialError::InvalidPublicKey
+    )]
+    pub author: SystemAccount<'info>,
+
+    #[account(
+        seeds = [b"user_profile", post.author.as_ref()],
+        bump = author_profile.bump,
+    )]
+    pub author_profile: Account<'info, UserProfile>,
+
+    #[account(
+        mut,
+        seeds = [b"user", post.author.as_ref()],
+        bump = author_user.bump,
+    )]
+    pub author_user: Account<'info, User>,
+
+    /// One record per tipper per post; `token_amount` accumulates across their tips
+    #[account(
+        init_if_needed,
+        payer = tipper,
+        space = PostInteraction::LEN,
+        seeds = [b"tip", post.key().as_ref(), tipper.key().as_ref()],
+        bump
+    )]
+    pub tip_interaction: Account<'info, PostInteraction>,
+
+    #[account(
+        mut,
+        seeds = [b"protocol_treasury"],
+        bump
+    )]
+    pub protocol_treasury: SystemAccount<'info>,
+
+    #[account(
+        seeds = [b"platform_config"],
+        bump = platform_config.bump,
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"user_profile", tipper.key().as_ref()],
+        bump = tipper_profile.bump,
+    )]
+    pub tipper_profile: Account<'info, UserProfile>,
+
+    #[account(mut)]
+    pub tipper: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn tip_post(ctx: Context<TipPost>, amount: u64, confirm_large_tip: bool) -> Result<()> {
+    let post = &ctx.accounts.post;
+    let post_stats = &mut ctx.accounts.post_stats;
+    let platform_config = &ctx.accounts.platform_config;
+
+    require!(amount > 0, SolSocialError::InvalidAmount);
+    require!(post.status == PostStatus::Active, SolSocialError::PostNotActive);
+    require!(
+        ctx.accounts.tipper.key() != post.author,
+        SolSocialError::SelfInteractionNotAllowed
+    );
+
+    // Tips above the platform cap must be explicitly confirmed to guard against fat-finger amounts
+    if let Some(max_tip) = platform_config.max_tip {
+        require!(amount <= max_tip || confirm_large_tip, SolSocialError::InvalidAmount);
+    }
+
+    ctx.accounts.tipper_profile.record_spend(amount, Clock::get()?.unix_timestamp)?;
+
+    // The protocol takes its usual share; the author receives everything else
+    let distribution = calculate_revenue_distribution(amount, false)?;
+    let protocol_fee = distribution.protocol_amount;
+    let author_proceeds = amount
+        .checked_sub(protocol_fee)
+        .ok_or(SolSocialError::MathUnderflow)?;
+
+    let transfer_instruction = anchor_lang::system_program::Transfer {
+        from: ctx.accounts.tipper.to_account_info(),
+        to: ctx.accounts.author.to_account_info(),
+    };
+    anchor_lang::system_program::transfer(
+        CpiContext::new(ctx.accounts.system_program.to_account_info(), transfer_instruction),
+        author_proceeds,
+    )?;
+
+    if protocol_fee > 0 {
+        let fee_instruction = anchor_lang::system_program::Transfer {
+            from: ctx.accounts.tipper.to_account_info(),
+            to: ctx.accounts.protocol_treasury.to_account_info(),
+        };
+        anchor_lang::system_program::transfer(
+            CpiContext::new(ctx.accounts.system_program.to_account_info(), fee_instruction),
+            protocol_fee,
+        )?;
+    }
+
+    let clock = Clock::get()?;
+    let tip_interaction = &mut ctx.accounts.tip_interaction;
+    if tip_interaction.user == Pubkey::default() {
+        tip_interaction.initialize(
+            post.id,
+            ctx.accounts.tipper.key(),
+            InteractionType::Tip,
+            clock.unix_timestamp,
+            amount,
+            ctx.bumps.tip_interaction,
+        )?;
+    } else {
+        tip_interaction.timestamp = clock.unix_timestamp;
+        tip_interaction.token_amount = tip_interaction.token_amount
+            .checked_add(amount)
+            .ok_or(SolSocialError::MathOverflow)?;
+    }
+
+    ctx.accounts.author_user.update_revenue(RevenueType::Tip, author_proceeds, &clock)?;
+
+    // Creators who opted out of analytics get no stats, including tip goal tracking
+    let tips_before = post_stats.total_tip_amount;
+    if ctx.accounts.author_profile.analytics_enabled {
+        post_stats.update_stats(&InteractionType::Tip, amount)?;
+    }
+
+    // Fires once: only the tip that crosses the goal emits
+    if post.crosses_tip_goal(tips_before, post_stats.total_tip_amount) {
+        emit!(TipGoalReached {
+            post: post.key(),
+            author: post.author,
+            tip_goal: post.tip_goal,
+            total_tip_amount: post_stats.total_tip_amount,
+            timestamp: clock.unix_timestamp,
+        });
+    }
+
+    emit!(PostTipped {
+        post: post.key(),
+        tipper: ctx.accounts.tipper.key(),
+        author: post.author,
+        amount,
+        author_proceeds,
+        protocol_fee,
+        total_tip_amount: post_stats.total_tip_amount,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct PostTipped {
+    pub post: Pubkey,
+    pub tipper: Pubkey,
+    pub author: Pubkey,
+    pub amount: u64,
+    pub author_proceeds: u64,
+    pub protocol_fee: u64,
+    pub total_tip_amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TipGoalReached {
+    pub post: Pubkey,
+    pub author: Pubkey,
+    pub tip_goal: u64,
+    pub total_tip_amount: u64,
+    pub timestamp: i64,
+}
@@ -0,0 +1,171 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+use crate::utils::reputation::REPUTATION_PER_HELD_KEY;
+use super::buy_keys::{check_self_holding_cap, close_position, open_position};
+
+#[derive(Accounts)]
+#[instruction(amount: u64, recipient: Pubkey)]
+pub struct TransferKeys<'info> {
+    #[account(mut)]
+    pub sender: Signer<'info>,
+
+    /// CHECK: The subject whose keys are being transferred
+    pub subject: AccountInfo<'info>,
+
+    #[account(
+        seeds = [b"user_keys", subject.key().as_ref()],
+        bump = user_keys.bump,
+    )]
+    pub user_keys: Account<'info, UserKeys>,
+
+    #[account(
+        mut,
+        seeds = [b"user_key", subject.key().as_ref()],
+        bump = user_key.bump,
+    )]
+    pub user_key: Account<'info, UserKey>,
+
+    #[account(
+        mut,
+        seeds = [b"key_holder", user_key.key().as_ref(), sender.key().as_ref()],
+        bump = sender_holder.bump,
+    )]
+    pub sender_holder: Account<'info, KeyHolder>,
+
+    #[account(
+        init_if_needed,
+        payer = sender,
+        space = KeyHolder::LEN,
+        seeds = [b"key_holder", user_key.key().as_ref(), recipient.as_ref()],
+        bump
+    )]
+    pub recipient_holder: Account<'info, KeyHolder>,
+
+    // Both sides keep position counts, so keys only move between wallets with a profile
+    #[account(
+        mut,
+        seeds = [b"user_profile", sender.key().as_ref()],
+        bump = sender_profile.bump,
+    )]
+    pub sender_profile: Account<'info, UserProfile>,
+
+    #[account(
+        mut,
+        seeds = [b"user_profile", recipient.as_ref()],
+        bump = recipient_profile.bump,
+    )]
+    pub recipient_profile: Account<'info, UserProfile>,
+
+    #[account(
+        seeds = [b"platform_config"],
+        bump = platform_config.bump,
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Moves keys between wallets without touching the curve, so supply and price are unchanged
+pub fn transfer_keys(ctx: Context<TransferKeys>, amount: u64, recipient: Pubkey) -> Result<()> {
+    require!(amount > 0, SolSocialError::InvalidAmount);
+    require!(recipient != ctx.accounts.sender.key(), SolSocialError::SelfTransfer);
+
+    let user_key = &mut ctx.accounts.user_key;
+    let sender_holder = &mut ctx.accounts.sender_holder;
+    let recipient_holder = &mut ctx.accounts.recipient_holder;
+    let now = Clock::get()?.unix_timestamp;
+
+    require!(sender_holder.amount >= amount, SolSocialError::InsufficientKeys);
+
+    // Moving keys to another wallet is a way out of the market too, so it waits out the
+    // same sell interval, and a subject can't be gifted past their self-holding cap
+    require!(
+        ctx.accounts.user_keys.is_sell_allowed(sender_holder.last_trade_at, now),
+        SolSocialError::InteractionCooldown
+    );
+    check_self_holding_cap(
+        &ctx.accounts.platform_config,
+        recipient == ctx.accounts.subject.key(),
+        recipient_holder.amount,
+        amount,
+    )?;
+
+    // The gifted keys carry over the sender's cost basis
+    let transferred_cost = (sender_holder.average_price as u128)
+        .checked_mul(amount as u128)
+        .and_then(|cost| u64::try_from(cost).ok())
+        .ok_or(SolSocialError::ArithmeticOverflow)?;
+
+    sender_holder.amount -= amount;
+    sender_holder.total_invested = sender_holder.total_invested.saturating_sub(transferred_cost);
+    sender_holder.last_trade_at = now;
+
+    let is_new_holder = recipient_holder.holder == Pubkey::default();
+    if is_new_holder {
+        recipient_holder.holder = recipient;
+        recipient_holder.subject = ctx.accounts.subject.key();
+        recipient_holder.amount = 0;
+        recipient_holder.average_price = 0;
+        recipient_holder.total_invested = 0;
+        recipient_holder.first_purchase_at = now;
+        recipient_holder.bump = ctx.bumps.recipient_holder;
+    }
+
+    // Supply is untouched, but the set of wallets holding keys can change
+    if recipient_holder.amount == 0 {
+        open_position(
+            &ctx.accounts.platform_config,
+            Some(&mut *ctx.accounts.recipient_profile),
+            user_key,
+        )?;
+    }
+    if sender_holder.amount == 0 {
+        close_position(Some(&mut *ctx.accounts.sender_profile), user_key);
+    }
+
+    recipient_holder.amount = recipient_holder.amount
+        .checked_add(amount)
+        .ok_or(SolSocialError::ArithmeticOverflow)?;
+    recipient_holder.total_invested = recipient_holder.total_invested
+        .checked_add(transferred_cost)
+        .ok_or(SolSocialError::ArithmeticOverflow)?;
+    recipient_holder.average_price = recipient_holder.total_invested / recipient_holder.amount;
+    // last_trade_at stays put: the recipient didn't trade, so their sell interval isn't restarted
+
+    // Reputation tracks keys held, so it moves with the keys rather than staying with the sender
+    let reputation_moved = amount
+        .checked_mul(REPUTATION_PER_HELD_KEY)
+        .ok_or(SolSocialError::ArithmeticOverflow)?;
+    let sender_profile = &mut ctx.accounts.sender_profile;
+    sender_profile.reputation_score = sender_profile.reputation_score.saturating_sub(reputation_moved);
+    let recipient_profile = &mut ctx.accounts.recipient_profile;
+    recipient_profile.reputation_score = recipient_profile.reputation_score
+        .checked_add(reputation_moved)
+        .ok_or(SolSocialError::ArithmeticOverflow)?;
+
+    emit!(KeysTransferred {
+        subject: ctx.accounts.subject.key(),
+        from: ctx.accounts.sender.key(),
+        to: recipient,
+        amount,
+        sender_balance: sender_holder.amount,
+        recipient_balance: recipient_holder.amount,
+        new_holder: is_new_holder,
+        timestamp: now,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct KeysTransferred {
+    pub subject: Pubkey,
+    pub from: Pubkey,
+    pub to: Pubkey,
+    pub amount: u64,
+    pub sender_balance: u64,
+    pub recipient_balance: u64,
+    pub new_holder: bool,
+    pub timestamp: i64,
+}
@@ -0,0 +1,90 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+#[instruction(room_id: String, new_owner: Pubkey)]
+pub struct TransferRoomOwnership<'info> {
+    #[account(
+        mut,
+        seeds = [b"chat_room", room_id.as_bytes()],
+        bump = chat_room.bump,
+        constraint = chat_room.creator == owner.key() @ SolSocialError::UnauthorizedUser
+    )]
+    pub chat_room: Account<'info, ChatRoom>,
+
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = ChatParticipant::LEN,
+        seeds = [b"participant", chat_room.key().as_ref(), owner.key().as_ref()],
+        bump
+    )]
+    pub owner_participant: Account<'info, ChatParticipant>,
+
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = ChatParticipant::LEN,
+        seeds = [b"participant", chat_room.key().as_ref(), new_owner.as_ref()],
+        bump
+    )]
+    pub new_owner_participant: Account<'info, ChatParticipant>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Hands the room to an existing member; the outgoing owner stays on as an admin
+pub fn transfer_room_ownership(
+    ctx: Context<TransferRoomOwnership>,
+    _room_id: String,
+    new_owner: Pubkey,
+) -> Result<()> {
+    let chat_room = &mut ctx.accounts.chat_room;
+    let owner = ctx.accounts.owner.key();
+
+    require!(new_owner != owner, SolSocialError::SelfInteractionNotAllowed);
+    require!(chat_room.is_participant(&new_owner), SolSocialError::InvalidResourceState);
+
+    let owner_participant = &mut ctx.accounts.owner_participant;
+    let last_read_message = owner_participant.last_read_message;
+    **owner_participant = ChatParticipant::new(
+        owner,
+        chat_room.room_id,
+        ParticipantRole::Admin,
+        ctx.bumps.owner_participant,
+    );
+    owner_participant.last_read_message = last_read_message;
+
+    let new_owner_participant = &mut ctx.accounts.new_owner_participant;
+    let last_read_message = new_owner_participant.last_read_message;
+    **new_owner_participant = ChatParticipant::new(
+        new_owner,
+        chat_room.room_id,
+        ParticipantRole::Owner,
+        ctx.bumps.new_owner_participant,
+    );
+    new_owner_participant.last_read_message = last_read_message;
+
+    chat_room.creator = new_owner;
+
+    emit!(RoomOwnershipTransferred {
+        chat_room: chat_room.key(),
+        previous_owner: owner,
+        new_owner,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct RoomOwnershipTransferred {
+    pub chat_room: Pubkey,
+    pub previous_owner: Pubkey,
+    pub new_owner: Pubkey,
+    pub timestamp: i64,
+}
@@ -0,0 +1,40 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+pub struct UnarchivePost<'info> {
+    #[account(
+        mut,
+        seeds = [b"post", post.author.as_ref(), &post.id.to_le_bytes()],
+        bump = post.bump,
+        constraint = post.author == author.key() @ SolSocialError::UnauthorizedUser
+    )]
+    pub post: Account<'info, Post>,
+
+    pub author: Signer<'info>,
+}
+
+pub fn unarchive_post(ctx: Context<UnarchivePost>) -> Result<()> {
+    let post = &mut ctx.accounts.post;
+    post.unarchive()?;
+
+    emit!(PostUnarchived {
+        post: post.key(),
+        author: post.author,
+        is_trending: post.is_trending()?,
+        virality_score: post.calculate_virality_score()?,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct PostUnarchived {
+    pub post: Pubkey,
+    pub author: Pubkey,
+    pub is_trending: bool,
+    pub virality_score: u64,
+    pub timestamp: i64,
+}
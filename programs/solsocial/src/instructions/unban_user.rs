@@ -0,0 +1,54 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+use super::ban_user::can_moderate_users;
+
+#[derive(Accounts)]
+pub struct UnbanUser<'info> {
+    #[account(
+        mut,
+        seeds = [b"user", banned_user.authority.as_ref()],
+        bump = banned_user.bump,
+    )]
+    pub banned_user: Account<'info, User>,
+
+    #[account(
+        seeds = [b"platform_config"],
+        bump = platform_config.bump,
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+
+    #[account(
+        seeds = [b"user_profile", moderator.key().as_ref()],
+        bump = moderator_profile.bump,
+    )]
+    pub moderator_profile: Option<Account<'info, UserProfile>>,
+
+    pub moderator: Signer<'info>,
+}
+
+pub fn unban_user(ctx: Context<UnbanUser>) -> Result<()> {
+    let moderator = ctx.accounts.moderator.key();
+    require!(
+        can_moderate_users(&moderator, &ctx.accounts.platform_config, ctx.accounts.moderator_profile.as_ref()),
+        SolSocialError::ModeratorPrivilegesRequired
+    );
+
+    let banned_user = &mut ctx.accounts.banned_user;
+    banned_user.unban()?;
+
+    emit!(UserUnbanned {
+        user: banned_user.authority,
+        unbanned_by: moderator,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct UserUnbanned {
+    pub user: Pubkey,
+    pub unbanned_by: Pubkey,
+    pub timestamp: i64,
+}
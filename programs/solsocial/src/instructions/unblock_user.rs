@@ -0,0 +1,41 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+pub struct UnblockUser<'info> {
+    #[account(
+        mut,
+        seeds = [b"block_list", authority.key().as_ref()],
+        bump = block_list.bump,
+    )]
+    pub block_list: Account<'info, BlockList>,
+
+    pub authority: Signer<'info>,
+}
+
+pub fn unblock_user(ctx: Context<UnblockUser>, target: Pubkey) -> Result<()> {
+    let block_list = &mut ctx.accounts.block_list;
+
+    let position = block_list
+        .blocked
+        .iter()
+        .position(|blocked| *blocked == target)
+        .ok_or(SolSocialError::UserNotBlocked)?;
+    block_list.blocked.swap_remove(position);
+
+    emit!(UserUnblocked {
+        blocker: ctx.accounts.authority.key(),
+        unblocked: target,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct UserUnblocked {
+    pub blocker: Pubkey,
+    pub unblocked: Pubkey,
+    pub timestamp: i64,
+}
@@ -0,0 +1,95 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+use crate::utils::reputation::*;
+
+#[derive(Accounts)]
+pub struct UnfollowUser<'info> {
+    #[account(
+        mut,
+        seeds = [b"follow", follower.key().as_ref(), follow_record.following.as_ref()],
+        bump = follow_record.bump,
+    )]
+    pub follow_record: Account<'info, FollowRelation>,
+
+    #[account(
+        mut,
+        seeds = [b"user_profile", follower.key().as_ref()],
+        bump = follower_profile.bump,
+    )]
+    pub follower_profile: Account<'info, UserProfile>,
+
+    #[account(
+        mut,
+        seeds = [b"user_profile", follow_record.following.as_ref()],
+        bump = following_profile.bump,
+    )]
+    pub following_profile: Account<'info, UserProfile>,
+
+    #[account(
+        seeds = [b"platform_config"],
+        bump = platform_config.bump,
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+
+    pub follower: Signer<'info>,
+}
+
+/// Deactivates rather than closes the relation so `follow_user` can reuse the PDA
+pub fn unfollow_user(ctx: Context<UnfollowUser>) -> Result<()> {
+    let follow_record = &mut ctx.accounts.follow_record;
+    let follower_profile = &mut ctx.accounts.follower_profile;
+    let following_profile = &mut ctx.accounts.following_profile;
+    let platform_config = &ctx.accounts.platform_config;
+
+    require!(follow_record.is_active, SolSocialError::NotFollowing);
+
+    follow_record.is_active = false;
+
+    follower_profile.following_count = follower_profile
+        .following_count
+        .checked_sub(1)
+        .ok_or(SolSocialError::ArithmeticOverflow)?;
+    following_profile.followers_count = following_profile
+        .followers_count
+        .checked_sub(1)
+        .ok_or(SolSocialError::ArithmeticOverflow)?;
+
+    // Churning through follows costs reputation once the free allowance for the window is used up
+    let mut reputation_penalty = 0;
+    if platform_config.unfollow_penalty_window > 0 {
+        let (window_start, recent_unfollows) = record_in_window(
+            follower_profile.unfollow_window_start,
+            follower_profile.recent_unfollows,
+            Clock::get()?.unix_timestamp,
+            platform_config.unfollow_penalty_window,
+        );
+        follower_profile.unfollow_window_start = window_start;
+        follower_profile.recent_unfollows = recent_unfollows;
+
+        if recent_unfollows > platform_config.free_unfollows_per_window {
+            reputation_penalty = std::cmp::min(
+                platform_config.unfollow_reputation_penalty,
+                follower_profile.reputation_score,
+            );
+            follower_profile.reputation_score -= reputation_penalty;
+        }
+    }
+
+    emit!(UserUnfollowed {
+        follower: ctx.accounts.follower.key(),
+        following: follow_record.following,
+        follower_count: following_profile.followers_count,
+        reputation_penalty,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct UserUnfollowed {
+    pub follower: Pubkey,
+    pub following: Pubkey,
+    pub follower_count: u64,
+    pub reputation_penalty: u64,
+}
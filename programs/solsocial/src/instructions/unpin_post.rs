@@ -0,0 +1,48 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+pub struct UnpinPost<'info> {
+    #[account(
+        mut,
+        seeds = [b"post", author.key().as_ref(), &post.id.to_le_bytes()],
+        bump = post.bump,
+        constraint = post.author == author.key() @ SolSocialError::UnauthorizedUser
+    )]
+    pub post: Account<'info, Post>,
+
+    #[account(
+        mut,
+        seeds = [b"user_profile", author.key().as_ref()],
+        bump = user_profile.bump,
+    )]
+    pub user_profile: Account<'info, UserProfile>,
+
+    pub author: Signer<'info>,
+}
+
+pub fn unpin_post(ctx: Context<UnpinPost>) -> Result<()> {
+    let post = &mut ctx.accounts.post;
+    let user_profile = &mut ctx.accounts.user_profile;
+
+    require!(post.is_pinned, SolSocialError::InvalidResourceState);
+
+    post.is_pinned = false;
+    user_profile.pinned_posts_count = user_profile.pinned_posts_count.saturating_sub(1);
+
+    emit!(PostUnpinned {
+        post: post.key(),
+        author: post.author,
+        pinned_posts_count: user_profile.pinned_posts_count,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct PostUnpinned {
+    pub post: Pubkey,
+    pub author: Pubkey,
+    pub pinned_posts_count: u64,
+}
@@ -0,0 +1,63 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+use crate::events::*;
+
+#[derive(Accounts)]
+#[instruction(room_id: String, message_id: u64)]
+pub struct UnreactToMessage<'info> {
+    pub user: Signer<'info>,
+
+    #[account(
+        seeds = [b"chat_room", room_id.as_bytes()],
+        bump = chat_room.bump,
+    )]
+    pub chat_room: Account<'info, ChatRoom>,
+
+    #[account(
+        seeds = [b"participant", chat_room.key().as_ref(), user.key().as_ref()],
+        bump = participant.bump,
+        constraint = participant.user == user.key() @ SolSocialError::UnauthorizedUser
+    )]
+    pub participant: Account<'info, ChatParticipant>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"message",
+            chat_room.key().as_ref(),
+            &message_id.to_le_bytes()
+        ],
+        bump = message.bump,
+    )]
+    pub message: Account<'info, Message>,
+}
+
+/// Removing the last user of a reaction drops the emoji from the map entirely
+pub fn unreact_to_message(
+    ctx: Context<UnreactToMessage>,
+    _room_id: String,
+    message_id: u64,
+    emoji: String,
+) -> Result<()> {
+    let user = ctx.accounts.user.key();
+    let message = &mut ctx.accounts.message;
+
+    require!(
+        ctx.accounts.participant.permissions.can_send_messages,
+        SolSocialError::PermissionDenied
+    );
+
+    message.remove_reaction(&emoji, &user)?;
+
+    emit!(MessageReactionChanged {
+        message_id,
+        chat_room: ctx.accounts.chat_room.key(),
+        user,
+        emoji: emoji.clone(),
+        added: false,
+        count: message.reactions.get(&emoji).map_or(0, |users| users.len() as u32),
+    });
+
+    Ok(())
+}
@@ -0,0 +1,44 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+pub struct UpdateKeysMetadata<'info> {
+    #[account(
+        mut,
+        seeds = [b"user_keys", creator.key().as_ref()],
+        bump = user_keys.bump,
+        constraint = user_keys.owner == creator.key() @ SolSocialError::UnauthorizedUser
+    )]
+    pub user_keys: Account<'info, UserKeys>,
+
+    pub creator: Signer<'info>,
+}
+
+/// Replaces the market's description and links shown to prospective buyers
+pub fn update_keys_metadata(
+    ctx: Context<UpdateKeysMetadata>,
+    description: String,
+    links: Vec<String>,
+) -> Result<()> {
+    let user_keys = &mut ctx.accounts.user_keys;
+
+    user_keys.set_metadata(description, links)?;
+
+    emit!(KeysMetadataUpdated {
+        creator: ctx.accounts.creator.key(),
+        description: user_keys.description.clone(),
+        links: user_keys.links.clone(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct KeysMetadataUpdated {
+    pub creator: Pubkey,
+    pub description: String,
+    pub links: Vec<String>,
+    pub timestamp: i64,
+}
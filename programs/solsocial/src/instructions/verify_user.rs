@@ -0,0 +1,73 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+#[instruction(target: Pubkey)]
+pub struct VerifyUser<'info> {
+    #[account(
+        seeds = [b"platform_config"],
+        bump = platform_config.bump,
+        constraint = platform_config.authority == authority.key() @ SolSocialError::UnauthorizedUser
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"user_profile", target.as_ref()],
+        bump = target_profile.bump,
+    )]
+    pub target_profile: Account<'info, UserProfile>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = UserBadge::LEN,
+        seeds = [b"user_badge", target.as_ref(), &[BadgeType::Verified as u8]],
+        bump
+    )]
+    pub verified_badge: Account<'info, UserBadge>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Marks the profile verified and awards its Verified badge; a badge revoked along with an
+/// earlier verification is reactivated rather than issued twice
+pub fn verify_user(ctx: Context<VerifyUser>, target: Pubkey) -> Result<()> {
+    let target_profile = &mut ctx.accounts.target_profile;
+    let verified_badge = &mut ctx.accounts.verified_badge;
+    let current_time = Clock::get()?.unix_timestamp;
+
+    require!(
+        !(target_profile.is_verified && verified_badge.is_active),
+        SolSocialError::BadgeAlreadyEarned
+    );
+
+    target_profile.is_verified = true;
+    verified_badge.set_inner(UserBadge {
+        user: target,
+        badge_type: BadgeType::Verified,
+        metadata_uri: String::new(),
+        earned_at: current_time,
+        is_active: true,
+        bump: ctx.bumps.verified_badge,
+    });
+
+    emit!(UserVerified {
+        user: target,
+        badge: verified_badge.key(),
+        timestamp: current_time,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct UserVerified {
+    pub user: Pubkey,
+    pub badge: Pubkey,
+    pub timestamp: i64,
+}
@@ -0,0 +1,55 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+pub struct WithdrawRevenue<'info> {
+    #[account(mut)]
+    pub subject: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"revenue_share", subject.key().as_ref()],
+        bump = revenue_share.bump,
+        constraint = revenue_share.subject == subject.key() @ SolSocialError::UnauthorizedUser
+    )]
+    pub revenue_share: Account<'info, RevenueShare>,
+}
+
+/// Pays the subject everything pending in their revenue escrow
+pub fn withdraw_revenue(ctx: Context<WithdrawRevenue>) -> Result<()> {
+    let revenue_share = &mut ctx.accounts.revenue_share;
+    let now = Clock::get()?.unix_timestamp;
+
+    require!(revenue_share.pending_withdrawal > 0, SolSocialError::InsufficientRewards);
+
+    // Zero the balance before any lamports move
+    let amount = revenue_share.take_pending(now)?;
+
+    let escrow = revenue_share.to_account_info();
+    let rent_exempt_minimum = Rent::get()?.minimum_balance(escrow.data_len());
+    require!(
+        escrow.lamports().saturating_sub(rent_exempt_minimum) >= amount,
+        SolSocialError::InsufficientFunds
+    );
+
+    **escrow.try_borrow_mut_lamports()? -= amount;
+    **ctx.accounts.subject.to_account_info().try_borrow_mut_lamports()? += amount;
+
+    emit!(RevenueWithdrawn {
+        subject: revenue_share.subject,
+        amount,
+        total_withdrawn: revenue_share.total_withdrawn,
+        timestamp: now,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct RevenueWithdrawn {
+    pub subject: Pubkey,
+    pub amount: u64,
+    pub total_withdrawn: u64,
+    pub timestamp: i64,
+}
@@ -1,9 +1,23 @@
 use anchor_lang::prelude::*;
 use anchor_spl::token::{self, Token, TokenAccount, Transfer};
 use std::collections::BTreeMap;
+use crate::utils::bonding_curve::BondingCurve;
+use crate::utils::reputation::REPUTATION_PER_HELD_KEY;
+use crate::utils::revenue_share::{
+    check_sell_solvency, distribute_buy_revenue, distribute_sell_revenue, is_escrow_solvent,
+};
+use crate::instructions::buy_keys::{check_referrer_payee, close_position, open_position};
+use crate::constants::CREATOR_INITIAL_KEYS;
+
+pub mod instructions;
+
+use instructions::*;
 
 declare_id!("SoLSociaL1111111111111111111111111111111111");
 
+/// Highest platform or creator fee rate the authority can set, in basis points
+pub const MAX_FEE_RATE_BPS: u64 = 1000;
+
 #[program]
 pub mod solsocial {
     use super::*;
@@ -12,11 +26,63 @@ pub mod solsocial {
         ctx: Context<InitializePlatform>,
         fee_rate: u64,
         creator_fee_rate: u64,
+        min_solvency_ratio_bps: u16,
     ) -> Result<()> {
+        require!(fee_rate <= MAX_FEE_RATE_BPS, SolSocialError::InvalidFeePercentage);
+        require!(creator_fee_rate <= MAX_FEE_RATE_BPS, SolSocialError::InvalidFeePercentage);
+        require!(min_solvency_ratio_bps <= 10000, SolSocialError::InvalidPercentage);
+
         let platform = &mut ctx.accounts.platform;
         platform.authority = ctx.accounts.authority.key();
         platform.fee_rate = fee_rate;
         platform.creator_fee_rate = creator_fee_rate;
+        platform.min_solvency_ratio_bps = min_solvency_ratio_bps;
+        platform.is_trading_enabled = true;
+        platform.is_posting_enabled = true;
+        platform.max_keys_per_trade = PlatformConfig::DEFAULT_MAX_KEYS_PER_TRADE;
+        platform.max_pinned_posts = PlatformConfig::DEFAULT_MAX_PINNED_POSTS;
+        platform.launch_fee = 0;
+        platform.base_content_length = PlatformConfig::DEFAULT_BASE_CONTENT_LENGTH;
+        platform.content_length_per_tier = PlatformConfig::DEFAULT_CONTENT_LENGTH_PER_TIER;
+        platform.reputation_per_content_tier = PlatformConfig::DEFAULT_REPUTATION_PER_CONTENT_TIER;
+        platform.daily_post_reputation_bonus = 0;
+        platform.multi_holder_threshold = 0;
+        platform.multi_holder_discount_bps = 0;
+        platform.max_engagement_score = 0;
+        platform.unfollow_penalty_window = 0;
+        platform.free_unfollows_per_window = 0;
+        platform.unfollow_reputation_penalty = 0;
+        platform.payout_expiry_seconds = 0;
+        platform.interaction_cooldown_seconds = 0;
+        platform.min_buy_amount = PlatformConfig::DEFAULT_MIN_BUY_AMOUNT;
+        platform.reputation_settlement_threshold = 0;
+        platform.self_repost_cooldown_seconds = PlatformConfig::DEFAULT_SELF_REPOST_COOLDOWN_SECONDS;
+        platform.media_host_allowlist = Vec::new();
+        platform.max_subject_fee_percent = PlatformConfig::DEFAULT_MAX_SUBJECT_FEE_PERCENT;
+        platform.cap_self_holdings = false;
+        platform.max_scheduled_posts = PlatformConfig::DEFAULT_MAX_SCHEDULED_POSTS;
+        platform.min_reputation_for_premium = 0;
+        platform.max_creator_supply = PlatformConfig::DEFAULT_MAX_CREATOR_SUPPLY;
+        platform.min_snapshot_interval_seconds = PlatformConfig::DEFAULT_MIN_SNAPSHOT_INTERVAL_SECONDS;
+        platform.report_hide_threshold = PlatformConfig::DEFAULT_REPORT_HIDE_THRESHOLD;
+        platform.ban_appeal_window_seconds = PlatformConfig::DEFAULT_BAN_APPEAL_WINDOW_SECONDS;
+        platform.curation_boost_threshold = 0;
+        platform.top_trader_volume_threshold = PlatformConfig::DEFAULT_TOP_TRADER_VOLUME_THRESHOLD;
+        platform.influencer_follower_threshold = PlatformConfig::DEFAULT_INFLUENCER_FOLLOWER_THRESHOLD;
+        platform.influence_decay_rate = PlatformConfig::DEFAULT_INFLUENCE_DECAY_RATE;
+        platform.max_tip = Some(PlatformConfig::DEFAULT_MAX_TIP);
+        platform.author_reputation_per_engagement = PlatformConfig::DEFAULT_AUTHOR_REPUTATION_PER_ENGAGEMENT;
+        platform.max_author_reputation_per_post = PlatformConfig::DEFAULT_MAX_AUTHOR_REPUTATION_PER_POST;
+        platform.max_positions = 0;
+        platform.bootstrap_reputation_rate = PlatformConfig::DEFAULT_BOOTSTRAP_REPUTATION_RATE;
+        platform.max_bootstrap_reputation = PlatformConfig::DEFAULT_MAX_BOOTSTRAP_REPUTATION;
+        platform.badge_reputation_floor = PlatformConfig::DEFAULT_BADGE_REPUTATION_FLOOR;
+        platform.restore_badges_on_unban = true;
+        platform.free_trades_per_user = PlatformConfig::DEFAULT_FREE_TRADES_PER_USER;
+        platform.feed_reputation_weight = PlatformConfig::DEFAULT_FEED_REPUTATION_WEIGHT;
+        platform.max_counted_reposts = PlatformConfig::DEFAULT_MAX_COUNTED_REPOSTS;
+        platform.default_max_slippage_bps = PlatformConfig::DEFAULT_MAX_SLIPPAGE_BPS;
+        platform.reputation_decay_rate = PlatformConfig::DEFAULT_REPUTATION_DECAY_RATE;
         platform.total_users = 0;
         platform.total_volume = 0;
         platform.bump = ctx.bumps.platform;
@@ -54,13 +120,19 @@ pub mod solsocial {
         user_profile.following_count = 0;
         user_profile.posts_count = 0;
         user_profile.reputation_score = 1000;
+        user_profile.pending_reputation = 0;
         user_profile.total_key_supply = 0;
         user_profile.key_price = 1_000_000; // 0.001 SOL in lamports
         user_profile.total_volume = 0;
         user_profile.created_at = Clock::get()?.unix_timestamp;
         user_profile.bump = ctx.bumps.user_profile;
         user_profile.is_verified = false;
+        user_profile.is_moderator = false;
+        user_profile.is_early_adopter = platform.total_users < PlatformConfig::EARLY_ADOPTER_USER_LIMIT;
         user_profile.is_active = true;
+        user_profile.sells_paused = false;
+        user_profile.analytics_enabled = true;
+        user_profile.last_reputation_update = user_profile.created_at;
 
         platform.total_users = platform.total_users.checked_add(1).unwrap();
 
@@ -79,18 +151,45 @@ pub mod solsocial {
         amount: u64,
     ) -> Result<()> {
         require!(amount > 0, SolSocialError::InvalidAmount);
-        require!(amount <= 100, SolSocialError::AmountTooLarge);
 
         let user_profile = &mut ctx.accounts.user_profile;
         let buyer_profile = &mut ctx.accounts.buyer_profile;
         let platform = &mut ctx.accounts.platform;
+        require!(platform.is_trading_enabled, SolSocialError::TradingPaused);
+        require!(amount <= platform.max_keys_per_trade, SolSocialError::AmountTooLarge);
+        require!(
+            ctx.accounts.user_keys.is_within_trading_window(Clock::get()?.unix_timestamp),
+            SolSocialError::MarketClosed
+        );
         let key_holding = &mut ctx.accounts.key_holding;
 
+        if platform.cap_self_holdings && ctx.accounts.buyer.key() == user_profile.owner {
+            require!(
+                key_holding.amount.checked_add(amount).unwrap() <= CREATOR_INITIAL_KEYS,
+                SolSocialError::SelfInteractionNotAllowed
+            );
+        }
+
         let current_supply = user_profile.total_key_supply;
-        let price = calculate_buy_price(current_supply, amount)?;
+        let price = BondingCurve::standard().get_buy_price(current_supply, amount)?;
         let platform_fee = price.checked_mul(platform.fee_rate).unwrap().checked_div(10000).unwrap();
         let creator_fee = price.checked_mul(platform.creator_fee_rate).unwrap().checked_div(10000).unwrap();
         let total_cost = price.checked_add(platform_fee).unwrap().checked_add(creator_fee).unwrap();
+        buyer_profile.record_spend(total_cost, Clock::get()?.unix_timestamp)?;
+
+        // A referrer's cut comes out of the fees, which otherwise stay in escrow with the price
+        check_referrer_payee(
+            ctx.accounts.referrer_profile.as_deref(),
+            ctx.accounts.referrer.as_ref().map(|referrer| referrer.key()),
+        )?;
+        let distribution = distribute_buy_revenue(
+            &ctx.accounts.user_keys,
+            &ctx.accounts.buyer.key(),
+            ctx.accounts.referrer_profile.as_deref_mut(),
+            price,
+            platform_fee,
+            creator_fee,
+        )?;
 
         // Transfer SOL from buyer to escrow
         let transfer_instruction = anchor_lang::system_program::Transfer {
@@ -99,25 +198,83 @@ pub mod solsocial {
         };
         anchor_lang::system_program::transfer(
             CpiContext::new(ctx.accounts.system_program.to_account_info(), transfer_instruction),
-            total_cost,
+            total_cost.checked_sub(distribution.referrer_amount).unwrap(),
         )?;
 
+        if let Some(referrer) = &ctx.accounts.referrer {
+            if distribution.referrer_amount > 0 {
+                let referrer_instruction = anchor_lang::system_program::Transfer {
+                    from: ctx.accounts.buyer.to_account_info(),
+                    to: referrer.to_account_info(),
+                };
+                anchor_lang::system_program::transfer(
+                    CpiContext::new(ctx.accounts.system_program.to_account_info(), referrer_instruction),
+                    distribution.referrer_amount,
+                )?;
+            }
+        }
+
+        if key_holding.amount == 0 {
+            open_position(platform, Some(&mut **buyer_profile), &mut ctx.accounts.user_key)?;
+        }
+
         // Update key holding
         key_holding.holder = ctx.accounts.buyer.key();
         key_holding.subject = user_profile.owner;
+        key_holding.bump = ctx.bumps.key_holding;
         key_holding.amount = key_holding.amount.checked_add(amount).unwrap();
-        key_holding.last_updated = Clock::get()?.unix_timestamp;
+        key_holding.last_trade_at = Clock::get()?.unix_timestamp;
 
         // Update user profile
         user_profile.total_key_supply = current_supply.checked_add(amount).unwrap();
-        user_profile.key_price = calculate_current_price(user_profile.total_key_supply)?;
-        user_profile.total_volume = user_profile.total_volume.checked_add(price).unwrap();
+        user_profile.key_price = BondingCurve::standard().get_price(user_profile.total_key_supply)?;
+        buyer_profile.total_volume = buyer_profile.total_volume.checked_add(price).unwrap();
 
         // Update platform stats
         platform.total_volume = platform.total_volume.checked_add(price).unwrap();
 
-        // Update buyer reputation
-        buyer_profile.reputation_score = buyer_profile.reputation_score.checked_add(amount.checked_mul(10).unwrap()).unwrap();
+        // Buys are where the pause persists: a failed sell reverts, so each buy records whether
+        // escrow still backs the supply, which may bring a paused market back above the ratio
+        let escrow_balance = ctx.accounts.escrow_account.lamports();
+        let redemption_value = BondingCurve::standard().get_liquidity(user_profile.total_key_supply)?;
+        let is_solvent = is_escrow_solvent(escrow_balance, redemption_value, platform.min_solvency_ratio_bps)?;
+
+        if user_profile.sells_paused && is_solvent {
+            user_profile.sells_paused = false;
+
+            emit!(MarketSellsResumed {
+                subject: user_profile.owner,
+                escrow_balance,
+                redemption_value,
+                timestamp: Clock::get()?.unix_timestamp,
+            });
+        } else if !user_profile.sells_paused && !is_solvent {
+            user_profile.sells_paused = true;
+
+            emit!(MarketSellsPaused {
+                subject: user_profile.owner,
+                escrow_balance,
+                redemption_value,
+                timestamp: Clock::get()?.unix_timestamp,
+            });
+        }
+
+        // Reputation tracks keys held, so sell_user_keys takes back exactly what this grants
+        let reputation_gain = amount.checked_mul(REPUTATION_PER_HELD_KEY).unwrap();
+        buyer_profile.reputation_score = buyer_profile.reputation_score.checked_add(reputation_gain).unwrap();
+
+        // Traders who opted in for this market get a receipt account per trade
+        issue_trade_receipt(
+            ctx.accounts.trade_ledger.as_mut(),
+            ctx.accounts.trade_receipt.as_mut(),
+            ctx.bumps.trade_receipt,
+            TradeType::Buy,
+            amount,
+            price,
+            platform_fee.checked_add(creator_fee).unwrap(),
+            total_cost,
+            Clock::get()?.unix_timestamp,
+        )?;
 
         emit!(KeysPurchased {
             buyer: ctx.accounts.buyer.key(),
@@ -142,31 +299,85 @@ pub mod solsocial {
         let platform = &mut ctx.accounts.platform;
         let key_holding = &mut ctx.accounts.key_holding;
 
+        require!(platform.is_trading_enabled, SolSocialError::TradingPaused);
+        require!(
+            ctx.accounts.user_keys.is_within_trading_window(Clock::get()?.unix_timestamp),
+            SolSocialError::MarketClosed
+        );
         require!(key_holding.amount >= amount, SolSocialError::InsufficientKeys);
         require!(user_profile.total_key_supply >= amount, SolSocialError::InsufficientSupply);
 
         let current_supply = user_profile.total_key_supply;
-        let price = calculate_sell_price(current_supply, amount)?;
+
+        // Degrade gracefully if escrow can no longer back the circulating supply: reject the
+        // sell instead of underflowing, even before a buy has recorded the pause
+        check_sell_solvency(
+            user_profile.sells_paused,
+            ctx.accounts.escrow_account.lamports(),
+            current_supply,
+            platform.min_solvency_ratio_bps,
+        )?;
+        let price = BondingCurve::standard().get_sell_price(current_supply, amount)?;
         let platform_fee = price.checked_mul(platform.fee_rate).unwrap().checked_div(10000).unwrap();
         let creator_fee = price.checked_mul(platform.creator_fee_rate).unwrap().checked_div(10000).unwrap();
         let seller_proceeds = price.checked_sub(platform_fee).unwrap().checked_sub(creator_fee).unwrap();
 
+        // A referrer's cut comes out of the fees, which otherwise stay in escrow
+        check_referrer_payee(
+            ctx.accounts.referrer_profile.as_deref(),
+            ctx.accounts.referrer.as_ref().map(|referrer| referrer.key()),
+        )?;
+        let distribution = distribute_sell_revenue(
+            &ctx.accounts.user_keys,
+            &ctx.accounts.seller.key(),
+            ctx.accounts.referrer_profile.as_deref_mut(),
+            price,
+            platform_fee,
+            creator_fee,
+        )?;
+
         // Transfer SOL from escrow to seller
         **ctx.accounts.escrow_account.to_account_info().try_borrow_mut_lamports()? -= seller_proceeds;
         **ctx.accounts.seller.to_account_info().try_borrow_mut_lamports()? += seller_proceeds;
 
+        if let Some(referrer) = &ctx.accounts.referrer {
+            let referrer_amount = distribution.referrer_amount;
+            **ctx.accounts.escrow_account.to_account_info().try_borrow_mut_lamports()? -= referrer_amount;
+            **referrer.to_account_info().try_borrow_mut_lamports()? += referrer_amount;
+        }
+
         // Update key holding
         key_holding.amount = key_holding.amount.checked_sub(amount).unwrap();
-        key_holding.last_updated = Clock::get()?.unix_timestamp;
+        key_holding.last_trade_at = Clock::get()?.unix_timestamp;
+        if key_holding.amount == 0 {
+            close_position(Some(&mut **seller_profile), &mut ctx.accounts.user_key);
+        }
+
+        // Give back the reputation these keys earned when bought, so buy/sell cycles net to zero
+        let reputation_loss = amount.checked_mul(REPUTATION_PER_HELD_KEY).unwrap();
+        seller_profile.reputation_score = seller_profile.reputation_score.saturating_sub(reputation_loss);
 
         // Update user profile
         user_profile.total_key_supply = current_supply.checked_sub(amount).unwrap();
-        user_profile.key_price = calculate_current_price(user_profile.total_key_supply)?;
-        user_profile.total_volume = user_profile.total_volume.checked_add(price).unwrap();
+        user_profile.key_price = BondingCurve::standard().get_price(user_profile.total_key_supply)?;
+        seller_profile.total_volume = seller_profile.total_volume.checked_add(price).unwrap();
 
         // Update platform stats
         platform.total_volume = platform.total_volume.checked_add(price).unwrap();
 
+        // Traders who opted in for this market get a receipt account per trade
+        issue_trade_receipt(
+            ctx.accounts.trade_ledger.as_mut(),
+            ctx.accounts.trade_receipt.as_mut(),
+            ctx.bumps.trade_receipt,
+            TradeType::Sell,
+            amount,
+            price,
+            platform_fee.checked_add(creator_fee).unwrap(),
+            seller_proceeds,
+            Clock::get()?.unix_timestamp,
+        )?;
+
         emit!(KeysSold {
             seller: ctx.accounts.seller.key(),
             subject: user_profile.owner,
@@ -183,37 +394,22 @@ pub mod solsocial {
         ctx: Context<CreatePost>,
         content: String,
         media_urls: Vec<String>,
+        post_type: PostType,
+        reply_to: Option<Pubkey>,
+        tags: Vec<String>,
+        single_reply_per_user: bool,
         is_premium: bool,
     ) -> Result<()> {
-        require!(content.len() <= 2000, SolSocialError::ContentTooLong);
-        require!(media_urls.len() <= 4, SolSocialError::TooManyMediaFiles);
-
-        let post = &mut ctx.accounts.post;
-        let user_profile = &mut ctx.accounts.user_profile;
-
-        post.author = ctx.accounts.author.key();
-        post.content = content.clone();
-        post.media_urls = media_urls.clone();
-        post.is_premium = is_premium;
-        post.likes_count = 0;
-        post.comments_count = 0;
-        post.reposts_count = 0;
-        post.created_at = Clock::get()?.unix_timestamp;
-        post.is_active = true;
-        post.bump = ctx.bumps.post;
-
-        user_profile.posts_count = user_profile.posts_count.checked_add(1).unwrap();
-        user_profile.reputation_score = user_profile.reputation_score.checked_add(50).unwrap();
-
-        emit!(PostCreated {
-            author: post.author,
-            post_id: post.key(),
+        instructions::create_post::create_post(
+            ctx,
             content,
+            media_urls,
+            post_type,
+            reply_to,
+            tags,
+            single_reply_per_user,
             is_premium,
-            created_at: post.created_at,
-        });
-
-        Ok(())
+        )
     }
 
     pub fn like_post(ctx: Context<LikePost>) -> Result<()> {
@@ -224,15 +420,25 @@ pub mod solsocial {
 
         require!(!like_record.is_active, SolSocialError::AlreadyLiked);
 
+        // Same cooldown as interact_post, so likes can't be spammed to farm reputation
+        let now = Clock::get()?.unix_timestamp;
+        require!(
+            now.saturating_sub(user_profile.last_interaction_at)
+                >= ctx.accounts.platform.interaction_cooldown_seconds,
+            SolSocialError::InteractionCooldown
+        );
+        user_profile.last_interaction_at = now;
+
         like_record.user = ctx.accounts.user.key();
         like_record.post = post.key();
-        like_record.created_at = Clock::get()?.unix_timestamp;
+        like_record.created_at = now;
         like_record.is_active = true;
         like_record.bump = ctx.bumps.like_record;
 
         post.likes_count = post.likes_count.checked_add(1).unwrap();
-        user_profile.reputation_score = user_profile.reputation_score.checked_add(5).unwrap();
-        author_profile.reputation_score = author_profile.reputation_score.checked_add(10).unwrap();
+        let settlement_threshold = ctx.accounts.platform.reputation_settlement_threshold;
+        user_profile.accrue_reputation(5, settlement_threshold);
+        author_profile.accrue_reputation(10, settlement_threshold);
 
         emit!(PostLiked {
             user: ctx.accounts.user.key(),
@@ -254,6 +460,10 @@ pub mod solsocial {
             SolSocialError::CannotFollowSelf
         );
         require!(!follow_record.is_active, SolSocialError::AlreadyFollowing);
+        require!(
+            !BlockList::account_blocks(&ctx.accounts.following_block_list, &ctx.accounts.follower.key())?,
+            SolSocialError::AccessDenied
+        );
 
         follow_record.follower = ctx.accounts.follower.key();
         follow_record.following = following_profile.owner;
@@ -314,44 +524,1184 @@ pub mod solsocial {
 
     pub fn send_message(
         ctx: Context<SendMessage>,
+        room_id: String,
         content: String,
-        message_type: u8,
+        attachments: Vec<MessageAttachment>,
     ) -> Result<()> {
-        require!(content.len() <= 1000, SolSocialError::MessageTooLong);
-        require!(message_type <= 2, SolSocialError::InvalidMessageType);
+        instructions::send_message::send_message(ctx, room_id, content, attachments)
+    }
 
-        let message = &mut ctx.accounts.message;
-        let chat_room = &mut ctx.accounts.chat_room;
-        let user_profile = &mut ctx.accounts.user_profile;
-        let key_holding = &ctx.accounts.key_holding;
+    pub fn set_referral_bonus(
+        ctx: Context<SetReferralBonus>,
+        referral_bonus_bps: Option<u16>,
+    ) -> Result<()> {
+        instructions::set_referral_bonus::set_referral_bonus(ctx, referral_bonus_bps)
+    }
 
-        require!(
-            key_holding.amount >= chat_room.min_keys_required,
-            SolSocialError::InsufficientKeysForChat
-        );
+    pub fn decay_influence(ctx: Context<DecayInfluence>) -> Result<()> {
+        instructions::decay_influence::decay_influence(ctx)
+    }
+
+    pub fn buy_keys(
+        ctx: Context<BuyKeys>,
+        amount: u64,
+        max_cost: u64,
+        deadline: Option<i64>,
+    ) -> Result<()> {
+        instructions::buy_keys::buy_keys(ctx, amount, max_cost, deadline)
+    }
+
+    pub fn create_proposal<'info>(
+        ctx: Context<'_, '_, '_, 'info, CreateProposal<'info>>,
+        proposal_id: u64,
+        title: String,
+        voting_period: i64,
+    ) -> Result<()> {
+        instructions::create_proposal::create_proposal(ctx, proposal_id, title, voting_period)
+    }
+
+    pub fn cast_vote(ctx: Context<CastVote>, in_favor: bool) -> Result<()> {
+        instructions::cast_vote::cast_vote(ctx, in_favor)
+    }
+
+    pub fn tip_post(ctx: Context<TipPost>, amount: u64, confirm_large_tip: bool) -> Result<()> {
+        instructions::tip_post::tip_post(ctx, amount, confirm_large_tip)
+    }
+
+    pub fn interact_post(
+        ctx: Context<InteractPost>,
+        interaction_type: u8,
+        content: Option<String>,
+    ) -> Result<()> {
+        instructions::interact_post::interact_post(ctx, interaction_type, content)
+    }
+
+    pub fn sell_keys(
+        ctx: Context<SellKeys>,
+        amount: u64,
+        min_proceeds: u64,
+        deadline: Option<i64>,
+    ) -> Result<()> {
+        instructions::sell_keys::sell_keys(ctx, amount, min_proceeds, deadline)
+    }
+
+    pub fn set_tip_goal(ctx: Context<SetTipGoal>, tip_goal: u64) -> Result<()> {
+        instructions::set_tip_goal::set_tip_goal(ctx, tip_goal)
+    }
+
+    pub fn pin_post(ctx: Context<PinPost>) -> Result<()> {
+        instructions::pin_post::pin_post(ctx)
+    }
+
+    pub fn unpin_post(ctx: Context<UnpinPost>) -> Result<()> {
+        instructions::unpin_post::unpin_post(ctx)
+    }
+
+    pub fn emit_activity_proof(ctx: Context<EmitActivityProof>) -> Result<()> {
+        instructions::emit_activity_proof::emit_activity_proof(ctx)
+    }
 
-        message.sender = ctx.accounts.sender.key();
-        message.chat_room = chat_room.key();
-        message.content = content.clone();
-        message.message_type = message_type;
-        message.created_at = Clock::get()?.unix_timestamp;
-        message.is_active = true;
-        message.bump = ctx.bumps.message;
+    pub fn edit_message(
+        ctx: Context<EditMessage>,
+        room_id: String,
+        message_id: u64,
+        new_content: String,
+        edit_reason: Option<String>,
+    ) -> Result<()> {
+        instructions::edit_message::edit_message(ctx, room_id, message_id, new_content, edit_reason)
+    }
+
+    pub fn revoke_badge(ctx: Context<RevokeBadge>) -> Result<()> {
+        instructions::revoke_badge::revoke_badge(ctx)
+    }
+
+    pub fn restore_badge(ctx: Context<RestoreBadge>) -> Result<()> {
+        instructions::restore_badge::restore_badge(ctx)
+    }
+
+    pub fn initialize_user(
+        ctx: Context<InitializeUser>,
+        username: String,
+        display_name: String,
+        bio: String,
+        avatar_url: String,
+    ) -> Result<()> {
+        instructions::initialize_user::handler(ctx, username, display_name, bio, avatar_url)
+    }
+
+    pub fn set_sell_interval(
+        ctx: Context<SetSellInterval>,
+        sell_interval_seconds: i64,
+    ) -> Result<()> {
+        instructions::set_sell_interval::set_sell_interval(ctx, sell_interval_seconds)
+    }
+
+    pub fn set_attachment_limit(
+        ctx: Context<SetAttachmentLimit>,
+        room_id: String,
+        max_attachment_bytes: u64,
+    ) -> Result<()> {
+        instructions::set_attachment_limit::set_attachment_limit(ctx, room_id, max_attachment_bytes)
+    }
+
+    pub fn propose_authority_transfer(
+        ctx: Context<ProposeAuthorityTransfer>,
+        new_authority: Pubkey,
+    ) -> Result<()> {
+        instructions::propose_authority_transfer::propose_authority_transfer(ctx, new_authority)
+    }
 
-        chat_room.messages_count = chat_room.messages_count.checked_add(1).unwrap();
-        user_profile.reputation_score = user_profile.reputation_score.checked_add(2).unwrap();
+    pub fn accept_authority(ctx: Context<AcceptAuthority>) -> Result<()> {
+        instructions::accept_authority::accept_authority(ctx)
+    }
 
-        emit!(MessageSent {
-            sender: message.sender,
-            chat_room: chat_room.key(),
+    pub fn set_trading_window(
+        ctx: Context<SetTradingWindow>,
+        trading_open: Option<i64>,
+        trading_close: Option<i64>,
+    ) -> Result<()> {
+        instructions::set_trading_window::set_trading_window(ctx, trading_open, trading_close)
+    }
+
+    pub fn create_keys(
+        ctx: Context<CreateKeys>,
+        user_pubkey: Pubkey,
+        name: String,
+        symbol: String,
+        uri: String,
+        description: String,
+        links: Vec<String>,
+    ) -> Result<()> {
+        instructions::create_keys::create_keys(
+            ctx,
+            user_pubkey,
+            name,
+            symbol,
+            uri,
+            description,
+            links,
+        )
+    }
+
+    pub fn mark_read_bulk(
+        ctx: Context<MarkReadBulk>,
+        room_id: String,
+        up_to_message_id: u64,
+    ) -> Result<()> {
+        instructions::mark_read_bulk::mark_read_bulk(ctx, room_id, up_to_message_id)
+    }
+
+    pub fn join_chat_room(ctx: Context<JoinChatRoom>, room_id: String) -> Result<()> {
+        instructions::join_chat_room::join_chat_room(ctx, room_id)
+    }
+
+    pub fn approve_join(
+        ctx: Context<ApproveJoin>,
+        room_id: String,
+        applicant: Pubkey,
+    ) -> Result<()> {
+        instructions::approve_join::approve_join(ctx, room_id, applicant)
+    }
+
+    pub fn set_join_approval(
+        ctx: Context<SetJoinApproval>,
+        room_id: String,
+        require_creator_approval: bool,
+    ) -> Result<()> {
+        instructions::set_join_approval::set_join_approval(ctx, room_id, require_creator_approval)
+    }
+
+    pub fn quote_buy(ctx: Context<QuoteBuy>, amount: u64) -> Result<()> {
+        instructions::quote_buy::quote_buy(ctx, amount)
+    }
+
+    pub fn quote_sell(ctx: Context<QuoteSell>, amount: u64) -> Result<()> {
+        instructions::quote_sell::quote_sell(ctx, amount)
+    }
+
+    pub fn repost_post(ctx: Context<RepostPost>) -> Result<()> {
+        instructions::repost_post::repost_post(ctx)
+    }
+
+    pub fn set_max_keys_per_trade(
+        ctx: Context<SetMaxKeysPerTrade>,
+        max_keys_per_trade: u64,
+    ) -> Result<()> {
+        instructions::set_max_keys_per_trade::set_max_keys_per_trade(ctx, max_keys_per_trade)
+    }
+
+    pub fn close_user_profile(ctx: Context<CloseUserProfile>) -> Result<()> {
+        instructions::close_user_profile::close_user_profile(ctx)
+    }
+
+    pub fn set_launch_fee(ctx: Context<SetLaunchFee>, launch_fee: u64) -> Result<()> {
+        instructions::set_launch_fee::set_launch_fee(ctx, launch_fee)
+    }
+
+    pub fn set_premium_access_value(
+        ctx: Context<SetPremiumAccessValue>,
+        premium_access_value: u64,
+    ) -> Result<()> {
+        instructions::set_premium_access_value::set_premium_access_value(ctx, premium_access_value)
+    }
+
+    pub fn check_post_access(ctx: Context<CheckPostAccess>) -> Result<()> {
+        instructions::check_post_access::check_post_access(ctx)
+    }
+
+    pub fn set_participant_role(
+        ctx: Context<SetParticipantRole>,
+        room_id: String,
+        member: Pubkey,
+        role: ParticipantRole,
+    ) -> Result<()> {
+        instructions::set_participant_role::set_participant_role(ctx, room_id, member, role)
+    }
+
+    pub fn delete_message(
+        ctx: Context<DeleteMessage>,
+        room_id: String,
+        message_id: u64,
+    ) -> Result<()> {
+        instructions::delete_message::delete_message(ctx, room_id, message_id)
+    }
+
+    pub fn set_content_length_tiers(
+        ctx: Context<SetContentLengthTiers>,
+        base_content_length: u32,
+        content_length_per_tier: u32,
+        reputation_per_content_tier: u64,
+    ) -> Result<()> {
+        instructions::set_content_length_tiers::set_content_length_tiers(
+            ctx,
+            base_content_length,
+            content_length_per_tier,
+            reputation_per_content_tier,
+        )
+    }
+
+    pub fn react_to_message(
+        ctx: Context<ReactToMessage>,
+        room_id: String,
+        message_id: u64,
+        emoji: String,
+    ) -> Result<()> {
+        instructions::react_to_message::react_to_message(ctx, room_id, message_id, emoji)
+    }
+
+    pub fn unreact_to_message(
+        ctx: Context<UnreactToMessage>,
+        room_id: String,
+        message_id: u64,
+        emoji: String,
+    ) -> Result<()> {
+        instructions::unreact_to_message::unreact_to_message(ctx, room_id, message_id, emoji)
+    }
+
+    pub fn set_daily_post_bonus(
+        ctx: Context<SetDailyPostBonus>,
+        daily_post_reputation_bonus: u64,
+    ) -> Result<()> {
+        instructions::set_daily_post_bonus::set_daily_post_bonus(ctx, daily_post_reputation_bonus)
+    }
+
+    pub fn mute_participant(
+        ctx: Context<MuteParticipant>,
+        room_id: String,
+        member: Pubkey,
+        duration_seconds: Option<i64>,
+    ) -> Result<()> {
+        instructions::mute_participant::mute_participant(ctx, room_id, member, duration_seconds)
+    }
+
+    pub fn transfer_keys(ctx: Context<TransferKeys>, amount: u64, recipient: Pubkey) -> Result<()> {
+        instructions::transfer_keys::transfer_keys(ctx, amount, recipient)
+    }
+
+    pub fn set_max_open_invites(
+        ctx: Context<SetMaxOpenInvites>,
+        room_id: String,
+        max_open_invites: u32,
+    ) -> Result<()> {
+        instructions::set_max_open_invites::set_max_open_invites(ctx, room_id, max_open_invites)
+    }
+
+    pub fn create_invite(
+        ctx: Context<CreateInvite>,
+        room_id: String,
+        invitee: Pubkey,
+        expires_at: Option<i64>,
+        invite_type: InviteType,
+    ) -> Result<()> {
+        instructions::create_invite::create_invite(ctx, room_id, invitee, expires_at, invite_type)
+    }
+
+    pub fn redeem_invite(
+        ctx: Context<RedeemInvite>,
+        room_id: String,
+        invite_id: u64,
+    ) -> Result<()> {
+        instructions::redeem_invite::redeem_invite(ctx, room_id, invite_id)
+    }
+
+    pub fn cancel_invite(
+        ctx: Context<CancelInvite>,
+        room_id: String,
+        invite_id: u64,
+    ) -> Result<()> {
+        instructions::cancel_invite::cancel_invite(ctx, room_id, invite_id)
+    }
+
+    pub fn set_analytics_enabled(
+        ctx: Context<SetAnalyticsEnabled>,
+        analytics_enabled: bool,
+    ) -> Result<()> {
+        instructions::set_analytics_enabled::set_analytics_enabled(ctx, analytics_enabled)
+    }
+
+    pub fn deposit_revenue(ctx: Context<DepositRevenue>, amount: u64) -> Result<()> {
+        instructions::deposit_revenue::deposit_revenue(ctx, amount)
+    }
+
+    pub fn withdraw_revenue(ctx: Context<WithdrawRevenue>) -> Result<()> {
+        instructions::withdraw_revenue::withdraw_revenue(ctx)
+    }
+
+    pub fn set_multi_holder_discount(
+        ctx: Context<SetMultiHolderDiscount>,
+        multi_holder_threshold: u64,
+        multi_holder_discount_bps: u16,
+    ) -> Result<()> {
+        instructions::set_multi_holder_discount::set_multi_holder_discount(
+            ctx,
+            multi_holder_threshold,
+            multi_holder_discount_bps,
+        )
+    }
+
+    pub fn set_post_visibility(
+        ctx: Context<SetPostVisibility>,
+        visibility: PostVisibility,
+    ) -> Result<()> {
+        instructions::set_post_visibility::set_post_visibility(ctx, visibility)
+    }
+
+    pub fn unfollow_user(ctx: Context<UnfollowUser>) -> Result<()> {
+        instructions::unfollow_user::unfollow_user(ctx)
+    }
+
+    pub fn set_max_engagement_score(
+        ctx: Context<SetMaxEngagementScore>,
+        max_engagement_score: u64,
+    ) -> Result<()> {
+        instructions::set_max_engagement_score::set_max_engagement_score(ctx, max_engagement_score)
+    }
+
+    pub fn block_user(ctx: Context<BlockUser>, target: Pubkey) -> Result<()> {
+        instructions::block_user::block_user(ctx, target)
+    }
+
+    pub fn unblock_user(ctx: Context<UnblockUser>, target: Pubkey) -> Result<()> {
+        instructions::unblock_user::unblock_user(ctx, target)
+    }
+
+    pub fn set_welcome_message(
+        ctx: Context<SetWelcomeMessage>,
+        room_id: String,
+        welcome_message: String,
+    ) -> Result<()> {
+        instructions::set_welcome_message::set_welcome_message(ctx, room_id, welcome_message)
+    }
+
+    pub fn decay_reputation(ctx: Context<DecayReputation>) -> Result<()> {
+        instructions::decay_reputation::decay_reputation(ctx)
+    }
+
+    pub fn set_max_reactions_per_user(
+        ctx: Context<SetMaxReactionsPerUser>,
+        room_id: String,
+        max_reactions_per_user: u32,
+    ) -> Result<()> {
+        instructions::set_max_reactions_per_user::set_max_reactions_per_user(
+            ctx,
+            room_id,
+            max_reactions_per_user,
+        )
+    }
+
+    pub fn set_unfollow_penalty(
+        ctx: Context<SetUnfollowPenalty>,
+        unfollow_penalty_window: i64,
+        free_unfollows_per_window: u32,
+        unfollow_reputation_penalty: u64,
+    ) -> Result<()> {
+        instructions::set_unfollow_penalty::set_unfollow_penalty(
+            ctx,
+            unfollow_penalty_window,
+            free_unfollows_per_window,
+            unfollow_reputation_penalty,
+        )
+    }
+
+    pub fn set_payout_expiry(
+        ctx: Context<SetPayoutExpiry>,
+        payout_expiry_seconds: i64,
+    ) -> Result<()> {
+        instructions::set_payout_expiry::set_payout_expiry(ctx, payout_expiry_seconds)
+    }
+
+    pub fn sweep_expired_payout(ctx: Context<SweepExpiredPayout>) -> Result<()> {
+        instructions::sweep_expired_payout::sweep_expired_payout(ctx)
+    }
+
+    pub fn set_interaction_cooldown(
+        ctx: Context<SetInteractionCooldown>,
+        interaction_cooldown_seconds: i64,
+    ) -> Result<()> {
+        instructions::set_interaction_cooldown::set_interaction_cooldown(
+            ctx,
+            interaction_cooldown_seconds,
+        )
+    }
+
+    pub fn update_keys_metadata(
+        ctx: Context<UpdateKeysMetadata>,
+        description: String,
+        links: Vec<String>,
+    ) -> Result<()> {
+        instructions::update_keys_metadata::update_keys_metadata(ctx, description, links)
+    }
+
+    pub fn buy_keys_multi<'info>(
+        ctx: Context<'_, '_, '_, 'info, BuyKeysMulti<'info>>,
+        legs: Vec<BatchBuyLeg>,
+        max_total_cost: u64,
+    ) -> Result<()> {
+        instructions::buy_keys_multi::buy_keys_multi(ctx, legs, max_total_cost)
+    }
+
+    pub fn set_min_buy_amount(ctx: Context<SetMinBuyAmount>, min_buy_amount: u64) -> Result<()> {
+        instructions::set_min_buy_amount::set_min_buy_amount(ctx, min_buy_amount)
+    }
+
+    pub fn place_order(
+        ctx: Context<PlaceOrder>,
+        order_id: u64,
+        side: TradeType,
+        target_price: u64,
+        amount: u64,
+        max_cost: u64,
+        expires_at: i64,
+    ) -> Result<()> {
+        instructions::place_order::place_order(
+            ctx,
+            order_id,
+            side,
+            target_price,
+            amount,
+            max_cost,
+            expires_at,
+        )
+    }
+
+    pub fn cancel_order(ctx: Context<CancelOrder>) -> Result<()> {
+        instructions::cancel_order::cancel_order(ctx)
+    }
+
+    pub fn fill_order(ctx: Context<FillOrder>) -> Result<()> {
+        instructions::fill_order::fill_order(ctx)
+    }
+
+    pub fn settle_reputation(ctx: Context<SettleReputation>) -> Result<()> {
+        instructions::settle_reputation::settle_reputation(ctx)
+    }
+
+    pub fn set_reputation_settlement_threshold(
+        ctx: Context<SetReputationSettlementThreshold>,
+        reputation_settlement_threshold: u64,
+    ) -> Result<()> {
+        instructions::set_reputation_settlement_threshold::set_reputation_settlement_threshold(
+            ctx,
+            reputation_settlement_threshold,
+        )
+    }
+
+    pub fn set_self_repost_cooldown(
+        ctx: Context<SetSelfRepostCooldown>,
+        self_repost_cooldown_seconds: i64,
+    ) -> Result<()> {
+        instructions::set_self_repost_cooldown::set_self_repost_cooldown(
+            ctx,
+            self_repost_cooldown_seconds,
+        )
+    }
+
+    pub fn set_platform_status(
+        ctx: Context<SetPlatformStatus>,
+        is_trading_enabled: bool,
+        is_posting_enabled: bool,
+    ) -> Result<()> {
+        instructions::set_platform_status::set_platform_status(
+            ctx,
+            is_trading_enabled,
+            is_posting_enabled,
+        )
+    }
+
+    pub fn set_media_host_allowlist(
+        ctx: Context<SetMediaHostAllowlist>,
+        hosts: Vec<String>,
+    ) -> Result<()> {
+        instructions::set_media_host_allowlist::set_media_host_allowlist(ctx, hosts)
+    }
+
+    pub fn set_reactions_require_keys(
+        ctx: Context<SetReactionsRequireKeys>,
+        room_id: String,
+        reactions_require_keys: bool,
+    ) -> Result<()> {
+        instructions::set_reactions_require_keys::set_reactions_require_keys(
+            ctx,
+            room_id,
+            reactions_require_keys,
+        )
+    }
+
+    pub fn set_key_fee(ctx: Context<SetKeyFee>, subject_fee_percent: u16) -> Result<()> {
+        instructions::set_key_fee::set_key_fee(ctx, subject_fee_percent)
+    }
+
+    pub fn set_max_subject_fee(
+        ctx: Context<SetMaxSubjectFee>,
+        max_subject_fee_percent: u16,
+    ) -> Result<()> {
+        instructions::set_max_subject_fee::set_max_subject_fee(ctx, max_subject_fee_percent)
+    }
+
+    pub fn set_visibility_downgrade(
+        ctx: Context<SetVisibilityDowngrade>,
+        after_seconds: i64,
+        engagement_threshold: u64,
+    ) -> Result<()> {
+        instructions::set_visibility_downgrade::set_visibility_downgrade(
+            ctx,
+            after_seconds,
+            engagement_threshold,
+        )
+    }
+
+    pub fn maybe_downgrade_visibility(ctx: Context<MaybeDowngradeVisibility>) -> Result<()> {
+        instructions::maybe_downgrade_visibility::maybe_downgrade_visibility(ctx)
+    }
+
+    pub fn set_cap_self_holdings(
+        ctx: Context<SetCapSelfHoldings>,
+        cap_self_holdings: bool,
+    ) -> Result<()> {
+        instructions::set_cap_self_holdings::set_cap_self_holdings(ctx, cap_self_holdings)
+    }
+
+    pub fn set_trade_receipts(ctx: Context<SetTradeReceipts>, enabled: bool) -> Result<()> {
+        instructions::set_trade_receipts::set_trade_receipts(ctx, enabled)
+    }
+
+    pub fn leave_chat_room(ctx: Context<LeaveChatRoom>, room_id: String) -> Result<()> {
+        instructions::leave_chat_room::leave_chat_room(ctx, room_id)
+    }
+
+    pub fn schedule_post(
+        ctx: Context<SchedulePost>,
+        content: String,
+        publish_at: i64,
+        media_urls: Vec<String>,
+        tags: Vec<String>,
+        is_premium: bool,
+    ) -> Result<()> {
+        instructions::schedule_post::schedule_post(
+            ctx,
             content,
-            message_type,
-            created_at: message.created_at,
-        });
+            publish_at,
+            media_urls,
+            tags,
+            is_premium,
+        )
+    }
 
-        Ok(())
+    pub fn publish_scheduled_post(ctx: Context<PublishScheduledPost>) -> Result<()> {
+        instructions::publish_scheduled_post::publish_scheduled_post(ctx)
+    }
+
+    pub fn set_max_scheduled_posts(
+        ctx: Context<SetMaxScheduledPosts>,
+        max_scheduled_posts: u32,
+    ) -> Result<()> {
+        instructions::set_max_scheduled_posts::set_max_scheduled_posts(ctx, max_scheduled_posts)
+    }
+
+    pub fn transfer_room_ownership(
+        ctx: Context<TransferRoomOwnership>,
+        room_id: String,
+        new_owner: Pubkey,
+    ) -> Result<()> {
+        instructions::transfer_room_ownership::transfer_room_ownership(ctx, room_id, new_owner)
+    }
+
+    pub fn set_min_reputation_for_premium(
+        ctx: Context<SetMinReputationForPremium>,
+        min_reputation_for_premium: u64,
+    ) -> Result<()> {
+        instructions::set_min_reputation_for_premium::set_min_reputation_for_premium(
+            ctx,
+            min_reputation_for_premium,
+        )
+    }
+
+    pub fn set_max_creator_supply(
+        ctx: Context<SetMaxCreatorSupply>,
+        max_creator_supply: u64,
+    ) -> Result<()> {
+        instructions::set_max_creator_supply::set_max_creator_supply(ctx, max_creator_supply)
+    }
+
+    pub fn snapshot_market_stats(ctx: Context<SnapshotMarketStats>) -> Result<()> {
+        instructions::snapshot_market_stats::snapshot_market_stats(ctx)
+    }
+
+    pub fn set_snapshot_interval(
+        ctx: Context<SetSnapshotInterval>,
+        min_snapshot_interval_seconds: i64,
+    ) -> Result<()> {
+        instructions::set_snapshot_interval::set_snapshot_interval(
+            ctx,
+            min_snapshot_interval_seconds,
+        )
+    }
+
+    pub fn set_required_nft_collection(
+        ctx: Context<SetRequiredNftCollection>,
+        room_id: String,
+        required_nft_collection: Option<Pubkey>,
+    ) -> Result<()> {
+        instructions::set_required_nft_collection::set_required_nft_collection(
+            ctx,
+            room_id,
+            required_nft_collection,
+        )
+    }
+
+    pub fn report_post(ctx: Context<ReportPost>, reason_code: u8) -> Result<()> {
+        instructions::report_post::report_post(ctx, reason_code)
+    }
+
+    pub fn moderate_post(ctx: Context<ModeratePost>, status: PostStatus) -> Result<()> {
+        instructions::moderate_post::moderate_post(ctx, status)
+    }
+
+    pub fn set_moderator(
+        ctx: Context<SetModerator>,
+        user: Pubkey,
+        is_moderator: bool,
+    ) -> Result<()> {
+        instructions::set_moderator::set_moderator(ctx, user, is_moderator)
+    }
+
+    pub fn set_report_hide_threshold(
+        ctx: Context<SetReportHideThreshold>,
+        report_hide_threshold: u32,
+    ) -> Result<()> {
+        instructions::set_report_hide_threshold::set_report_hide_threshold(
+            ctx,
+            report_hide_threshold,
+        )
+    }
+
+    pub fn ban_user(ctx: Context<BanUser>, ban_reason: String) -> Result<()> {
+        instructions::ban_user::ban_user(ctx, ban_reason)
+    }
+
+    pub fn unban_user(ctx: Context<UnbanUser>) -> Result<()> {
+        instructions::unban_user::unban_user(ctx)
+    }
+
+    pub fn appeal_ban(ctx: Context<AppealBan>, message: String) -> Result<()> {
+        instructions::appeal_ban::appeal_ban(ctx, message)
+    }
+
+    pub fn resolve_ban_appeal(ctx: Context<ResolveBanAppeal>, approve: bool) -> Result<()> {
+        instructions::resolve_ban_appeal::resolve_ban_appeal(ctx, approve)
+    }
+
+    pub fn set_ban_appeal_window(
+        ctx: Context<SetBanAppealWindow>,
+        ban_appeal_window_seconds: i64,
+    ) -> Result<()> {
+        instructions::set_ban_appeal_window::set_ban_appeal_window(ctx, ban_appeal_window_seconds)
+    }
+
+    pub fn create_chat(
+        ctx: Context<CreateChat>,
+        chat_id: String,
+        name: String,
+        description: String,
+        is_private: bool,
+        required_keys: u64,
+        max_participants: u32,
+        tags: Vec<String>,
+    ) -> Result<()> {
+        instructions::create_chat::create_chat(
+            ctx,
+            chat_id,
+            name,
+            description,
+            is_private,
+            required_keys,
+            max_participants,
+            tags,
+        )
+    }
+
+    pub fn verify_user(ctx: Context<VerifyUser>, target: Pubkey) -> Result<()> {
+        instructions::verify_user::verify_user(ctx, target)
+    }
+
+    pub fn revoke_verification(ctx: Context<RevokeVerification>, target: Pubkey) -> Result<()> {
+        instructions::revoke_verification::revoke_verification(ctx, target)
+    }
+
+    pub fn set_curation_threshold(
+        ctx: Context<SetCurationThreshold>,
+        curation_boost_threshold: u64,
+    ) -> Result<()> {
+        instructions::set_curation_threshold::set_curation_threshold(ctx, curation_boost_threshold)
+    }
+
+    pub fn award_badge(ctx: Context<AwardBadge>, badge_type: BadgeType) -> Result<()> {
+        instructions::award_badge::award_badge(ctx, badge_type)
+    }
+
+    pub fn set_badge_thresholds(
+        ctx: Context<SetBadgeThresholds>,
+        top_trader_volume_threshold: u64,
+        influencer_follower_threshold: u64,
+    ) -> Result<()> {
+        instructions::set_badge_thresholds::set_badge_thresholds(
+            ctx,
+            top_trader_volume_threshold,
+            influencer_follower_threshold,
+        )
+    }
+
+    pub fn archive_post(ctx: Context<ArchivePost>) -> Result<()> {
+        instructions::archive_post::archive_post(ctx)
+    }
+
+    pub fn unarchive_post(ctx: Context<UnarchivePost>) -> Result<()> {
+        instructions::unarchive_post::unarchive_post(ctx)
+    }
+
+    pub fn set_spend_cap(
+        ctx: Context<SetSpendCap>,
+        spend_cap: u64,
+        spend_window_seconds: i64,
+    ) -> Result<()> {
+        instructions::set_spend_cap::set_spend_cap(ctx, spend_cap, spend_window_seconds)
+    }
+
+    pub fn mark_read(ctx: Context<MarkRead>, room_id: String, message_id: u64) -> Result<()> {
+        instructions::mark_read::mark_read(ctx, room_id, message_id)
+    }
+
+    pub fn delete_post(ctx: Context<DeletePost>, close_account: bool) -> Result<()> {
+        instructions::delete_post::delete_post(ctx, close_account)
+    }
+
+    pub fn revalidate_participant(
+        ctx: Context<RevalidateParticipant>,
+        room_id: String,
+        member: Pubkey,
+    ) -> Result<()> {
+        instructions::revalidate_participant::revalidate_participant(ctx, room_id, member)
+    }
+
+    pub fn recompute_influence(ctx: Context<RecomputeInfluence>) -> Result<()> {
+        instructions::recompute_influence::recompute_influence(ctx)
+    }
+
+    pub fn launch_event<'info>(
+        ctx: Context<'_, '_, '_, 'info, LaunchEvent<'info>>,
+        subjects: Vec<Pubkey>,
+        seed_amount: u64,
+    ) -> Result<()> {
+        instructions::launch_event::launch_event(ctx, subjects, seed_amount)
+    }
+
+    pub fn set_max_replies(ctx: Context<SetMaxReplies>, max_replies: u64) -> Result<()> {
+        instructions::set_max_replies::set_max_replies(ctx, max_replies)
+    }
+
+    pub fn subscribe(ctx: Context<Subscribe>, months: u8) -> Result<()> {
+        instructions::subscribe::subscribe(ctx, months)
+    }
+
+    pub fn set_subscription_price(
+        ctx: Context<SetSubscriptionPrice>,
+        subscription_price: u64,
+    ) -> Result<()> {
+        instructions::set_subscription_price::set_subscription_price(ctx, subscription_price)
+    }
+
+    pub fn set_min_participants(
+        ctx: Context<SetMinParticipants>,
+        room_id: String,
+        min_participants_to_open: u32,
+    ) -> Result<()> {
+        instructions::set_min_participants::set_min_participants(
+            ctx,
+            room_id,
+            min_participants_to_open,
+        )
+    }
+
+    pub fn set_influence_decay_rate(
+        ctx: Context<SetInfluenceDecayRate>,
+        influence_decay_rate: u16,
+    ) -> Result<()> {
+        instructions::set_influence_decay_rate::set_influence_decay_rate(ctx, influence_decay_rate)
+    }
+
+    pub fn set_max_tip(ctx: Context<SetMaxTip>, max_tip: Option<u64>) -> Result<()> {
+        instructions::set_max_tip::set_max_tip(ctx, max_tip)
+    }
+
+    pub fn set_author_reputation(
+        ctx: Context<SetAuthorReputation>,
+        author_reputation_per_engagement: u16,
+        max_author_reputation_per_post: u64,
+    ) -> Result<()> {
+        instructions::set_author_reputation::set_author_reputation(
+            ctx,
+            author_reputation_per_engagement,
+            max_author_reputation_per_post,
+        )
+    }
+
+    pub fn set_max_positions(ctx: Context<SetMaxPositions>, max_positions: u64) -> Result<()> {
+        instructions::set_max_positions::set_max_positions(ctx, max_positions)
+    }
+
+    pub fn set_reputation_bootstrap(
+        ctx: Context<SetReputationBootstrap>,
+        bootstrap_reputation_rate: u16,
+        max_bootstrap_reputation: u64,
+    ) -> Result<()> {
+        instructions::set_reputation_bootstrap::set_reputation_bootstrap(
+            ctx,
+            bootstrap_reputation_rate,
+            max_bootstrap_reputation,
+        )
+    }
+
+    pub fn set_badge_revocation(
+        ctx: Context<SetBadgeRevocation>,
+        badge_reputation_floor: u64,
+        restore_badges_on_unban: bool,
+    ) -> Result<()> {
+        instructions::set_badge_revocation::set_badge_revocation(
+            ctx,
+            badge_reputation_floor,
+            restore_badges_on_unban,
+        )
+    }
+
+    pub fn set_free_trades_per_user(
+        ctx: Context<SetFreeTradesPerUser>,
+        free_trades_per_user: u32,
+    ) -> Result<()> {
+        instructions::set_free_trades_per_user::set_free_trades_per_user(ctx, free_trades_per_user)
+    }
+
+    pub fn set_feed_reputation_weight(
+        ctx: Context<SetFeedReputationWeight>,
+        feed_reputation_weight: u16,
+    ) -> Result<()> {
+        instructions::set_feed_reputation_weight::set_feed_reputation_weight(
+            ctx,
+            feed_reputation_weight,
+        )
+    }
+
+    pub fn set_max_counted_reposts(
+        ctx: Context<SetMaxCountedReposts>,
+        max_counted_reposts: u64,
+    ) -> Result<()> {
+        instructions::set_max_counted_reposts::set_max_counted_reposts(ctx, max_counted_reposts)
+    }
+
+    pub fn set_default_max_slippage(
+        ctx: Context<SetDefaultMaxSlippage>,
+        default_max_slippage_bps: u16,
+    ) -> Result<()> {
+        instructions::set_default_max_slippage::set_default_max_slippage(
+            ctx,
+            default_max_slippage_bps,
+        )
+    }
+
+    pub fn set_reputation_decay_rate(
+        ctx: Context<SetReputationDecayRate>,
+        reputation_decay_rate: u16,
+    ) -> Result<()> {
+        instructions::set_reputation_decay_rate::set_reputation_decay_rate(
+            ctx,
+            reputation_decay_rate,
+        )
     }
 
     pub fn update_platform_settings(
         ctx: Context<UpdatePlatformSettings>,
-        fee_rate: Option<u64>,
\ No newline at end of file
+        fee_rate: Option<u64>,
+        creator_fee_rate: Option<u64>,
+        max_keys_per_trade: Option<u64>,
+        min_solvency_ratio_bps: Option<u16>,
+    ) -> Result<()> {
+        let platform = &mut ctx.accounts.platform;
+        require_keys_eq!(ctx.accounts.authority.key(), platform.authority, SolSocialError::UnauthorizedUser);
+
+        // Fields left as None keep their current value
+        if let Some(fee_rate) = fee_rate {
+            require!(fee_rate <= MAX_FEE_RATE_BPS, SolSocialError::InvalidFeePercentage);
+            platform.fee_rate = fee_rate;
+        }
+        if let Some(creator_fee_rate) = creator_fee_rate {
+            require!(creator_fee_rate <= MAX_FEE_RATE_BPS, SolSocialError::InvalidFeePercentage);
+            platform.creator_fee_rate = creator_fee_rate;
+        }
+        if let Some(max_keys_per_trade) = max_keys_per_trade {
+            require!(max_keys_per_trade > 0, SolSocialError::InvalidAmount);
+            platform.max_keys_per_trade = max_keys_per_trade;
+        }
+        if let Some(min_solvency_ratio_bps) = min_solvency_ratio_bps {
+            require!(min_solvency_ratio_bps <= 10000, SolSocialError::InvalidPercentage);
+            platform.min_solvency_ratio_bps = min_solvency_ratio_bps;
+        }
+
+        emit!(PlatformSettingsUpdated {
+            authority: platform.authority,
+            fee_rate,
+            creator_fee_rate,
+            max_keys_per_trade,
+            min_solvency_ratio_bps,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+}
+
+
+#[derive(Accounts)]
+pub struct FollowUser<'info> {
+    #[account(
+        init_if_needed,
+        payer = follower,
+        space = FollowRelation::LEN,
+        seeds = [b"follow", follower.key().as_ref(), following.key().as_ref()],
+        bump
+    )]
+    pub follow_record: Account<'info, FollowRelation>,
+
+    #[account(
+        mut,
+        seeds = [b"user_profile", follower.key().as_ref()],
+        bump = follower_profile.bump,
+    )]
+    pub follower_profile: Account<'info, UserProfile>,
+
+    #[account(
+        mut,
+        seeds = [b"user_profile", following.key().as_ref()],
+        bump = following_profile.bump,
+    )]
+    pub following_profile: Account<'info, UserProfile>,
+
+    /// CHECK: may not exist yet; `BlockList::account_blocks` treats an empty account as blocking nobody
+    #[account(
+        seeds = [b"block_list", following.key().as_ref()],
+        bump,
+    )]
+    pub following_block_list: UncheckedAccount<'info>,
+
+    /// CHECK: only used to derive the followed user's PDAs
+    pub following: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub follower: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct BuyUserKeys<'info> {
+    #[account(
+        mut,
+        seeds = [b"user_profile", user_profile.owner.as_ref()],
+        bump = user_profile.bump,
+    )]
+    pub user_profile: Account<'info, UserProfile>,
+
+    #[account(
+        mut,
+        seeds = [b"user_profile", buyer.key().as_ref()],
+        bump = buyer_profile.bump,
+    )]
+    pub buyer_profile: Account<'info, UserProfile>,
+
+    #[account(
+        seeds = [b"user_keys", user_profile.owner.as_ref()],
+        bump = user_keys.bump,
+    )]
+    pub user_keys: Account<'info, UserKeys>,
+
+    #[account(
+        mut,
+        seeds = [b"user_key", user_profile.owner.as_ref()],
+        bump = user_key.bump,
+    )]
+    pub user_key: Account<'info, UserKey>,
+
+    #[account(
+        init_if_needed,
+        payer = buyer,
+        space = KeyHolder::LEN,
+        seeds = [b"key_holder", user_key.key().as_ref(), buyer.key().as_ref()],
+        bump
+    )]
+    pub key_holding: Account<'info, KeyHolder>,
+
+    /// CHECK: Escrow backing the subject's curve
+    #[account(
+        mut,
+        seeds = [b"escrow", user_profile.owner.as_ref()],
+        bump
+    )]
+    pub escrow_account: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"platform_config"],
+        bump = platform.bump,
+    )]
+    pub platform: Account<'info, PlatformConfig>,
+
+    /// Only needed by traders who opted in to receipts for this market
+    #[account(
+        mut,
+        seeds = [b"trade_ledger", buyer.key().as_ref(), user_profile.owner.as_ref()],
+        bump = trade_ledger.bump,
+    )]
+    pub trade_ledger: Option<Account<'info, TradeLedger>>,
+
+    #[account(
+        init,
+        payer = buyer,
+        space = TradeReceipt::LEN,
+        seeds = [b"trade_receipt", trade_ledger.key().as_ref(), &trade_ledger.next_sequence.to_le_bytes()],
+        bump
+    )]
+    pub trade_receipt: Option<Account<'info, TradeReceipt>>,
+
+    /// The buyer's referrer, if any, paid a share of the trade's fees
+    #[account(mut)]
+    pub referrer_profile: Option<Account<'info, UserProfile>>,
+
+    /// CHECK: The referrer's wallet, checked against `referrer_profile` in the handler
+    #[account(mut)]
+    pub referrer: Option<AccountInfo<'info>>,
+
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SellUserKeys<'info> {
+    #[account(
+        mut,
+        seeds = [b"user_profile", user_profile.owner.as_ref()],
+        bump = user_profile.bump,
+    )]
+    pub user_profile: Account<'info, UserProfile>,
+
+    #[account(
+        mut,
+        seeds = [b"user_profile", seller.key().as_ref()],
+        bump = seller_profile.bump,
+    )]
+    pub seller_profile: Account<'info, UserProfile>,
+
+    #[account(
+        seeds = [b"user_keys", user_profile.owner.as_ref()],
+        bump = user_keys.bump,
+    )]
+    pub user_keys: Account<'info, UserKeys>,
+
+    #[account(
+        mut,
+        seeds = [b"user_key", user_profile.owner.as_ref()],
+        bump = user_key.bump,
+    )]
+    pub user_key: Account<'info, UserKey>,
+
+    #[account(
+        mut,
+        seeds = [b"key_holder", user_key.key().as_ref(), seller.key().as_ref()],
+        bump = key_holding.bump,
+    )]
+    pub key_holding: Account<'info, KeyHolder>,
+
+    /// CHECK: Escrow backing the subject's curve
+    #[account(
+        mut,
+        seeds = [b"escrow", user_profile.owner.as_ref()],
+        bump
+    )]
+    pub escrow_account: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"platform_config"],
+        bump = platform.bump,
+    )]
+    pub platform: Account<'info, PlatformConfig>,
+
+    /// Only needed by traders who opted in to receipts for this market
+    #[account(
+        mut,
+        seeds = [b"trade_ledger", seller.key().as_ref(), user_profile.owner.as_ref()],
+        bump = trade_ledger.bump,
+    )]
+    pub trade_ledger: Option<Account<'info, TradeLedger>>,
+
+    #[account(
+        init,
+        payer = seller,
+        space = TradeReceipt::LEN,
+        seeds = [b"trade_receipt", trade_ledger.key().as_ref(), &trade_ledger.next_sequence.to_le_bytes()],
+        bump
+    )]
+    pub trade_receipt: Option<Account<'info, TradeReceipt>>,
+
+    /// The seller's referrer, if any, paid a share of the trade's fees
+    #[account(mut)]
+    pub referrer_profile: Option<Account<'info, UserProfile>>,
+
+    /// CHECK: The referrer's wallet, checked against `referrer_profile` in the handler
+    #[account(mut)]
+    pub referrer: Option<AccountInfo<'info>>,
+
+    #[account(mut)]
+    pub seller: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
\ No newline at end of file
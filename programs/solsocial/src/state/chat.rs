@@ -1,5 +1,6 @@
 use anchor_lang::prelude::*;
 use std::collections::BTreeMap;
+use crate::errors::SolSocialError;
 
 #[account]
 pub struct ChatRoom {
@@ -7,6 +8,16 @@ pub struct ChatRoom {
     pub creator: Pubkey,
     pub required_key_amount: u64,
     pub participants: Vec<Pubkey>,
+    pub min_participants_to_open: u32,
+    pub is_open: bool,
+    pub max_attachment_bytes: u64, // total attachment size allowed per message, 0 = no cap
+    pub require_creator_approval: bool, // joins stay pending until the creator approves them
+    pub max_open_invites: u32, // unredeemed invites allowed at once, 0 = no cap
+    pub open_invites: u32,
+    pub invite_count: u64,
+    pub welcome_message: String, // posted as a system message when someone joins, empty = none
+    pub max_reactions_per_user: u32, // distinct reactions one user may leave on a message, 0 = no cap
+    pub reactions_require_keys: bool, // only holders of the creator's keys may react
     pub message_count: u64,
     pub created_at: i64,
     pub is_active: bool,
@@ -42,6 +53,7 @@ pub struct ChatParticipant {
     pub permissions: ParticipantPermissions,
     pub is_muted: bool,
     pub muted_until: Option<i64>,
+    pub is_lapsed: bool, // found below the room's required_key_amount since joining
     pub bump: u8,
 }
 
@@ -58,6 +70,43 @@ pub struct ChatInvite {
     pub bump: u8,
 }
 
+#[account]
+pub struct JoinRequest {
+    pub room: Pubkey,
+    pub applicant: Pubkey,
+    pub requested_at: i64,
+    pub approved_at: Option<i64>,
+    pub is_approved: bool,
+    pub bump: u8,
+}
+
+impl JoinRequest {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // room
+        32 + // applicant
+        8 + // requested_at
+        1 + 8 + // approved_at
+        1 + // is_approved
+        1; // bump
+}
+
+impl ChatInvite {
+    pub const LEN: usize = 8 + // discriminator
+        8 + // invite_id
+        8 + // room_id
+        32 + // inviter
+        32 + // invitee
+        8 + // created_at
+        1 + 8 + // expires_at
+        1 + // is_used
+        1 + // invite_type
+        1; // bump
+
+    pub fn is_expired(&self, now: i64) -> bool {
+        self.expires_at.map_or(false, |expires_at| now > expires_at)
+    }
+}
+
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
 pub enum ChatRoomType {
     Public,
@@ -78,12 +127,25 @@ pub struct ChatRoomMetadata {
     pub is_nsfw: bool,
 }
 
+impl ChatRoomMetadata {
+    // Must stay in step with the metadata.tags budget in ChatRoom::LEN
+    pub const MAX_TAGS: usize = 10;
+    pub const MAX_TAG_LENGTH: usize = 50;
+
+    pub fn validate_tags(tags: &[String]) -> Result<()> {
+        require!(tags.len() <= Self::MAX_TAGS, SolSocialError::TooManyTags);
+        for tag in tags {
+            require!(tag.len() <= Self::MAX_TAG_LENGTH, SolSocialError::TagTooLong);
+        }
+        Ok(())
+    }
+}
+
 #[derive(AnchorSerialize, AnchorDeserialize, Clone)]
 pub struct AccessControl {
     pub min_key_balance: u64,
     pub required_nft_collection: Option<Pubkey>,
     pub whitelist: Vec<Pubkey>,
-    pub blacklist: Vec<Pubkey>,
     pub require_verification: bool,
 }
 
@@ -163,6 +225,16 @@ impl ChatRoom {
         32 + // creator
         8 + // required_key_amount
         4 + (32 * 100) + // participants (max 100)
+        4 + // min_participants_to_open
+        1 + // is_open
+        8 + // max_attachment_bytes
+        1 + // require_creator_approval
+        4 + // max_open_invites
+        4 + // open_invites
+        8 + // invite_count
+        4 + Self::MAX_WELCOME_MESSAGE_LENGTH + // welcome_message
+        4 + // max_reactions_per_user
+        1 + // reactions_require_keys
         8 + // message_count
         8 + // created_at
         1 + // is_active
@@ -170,20 +242,25 @@ impl ChatRoom {
         4 + 100 + // metadata.name
         4 + 500 + // metadata.description
         1 + 4 + 200 + // metadata.image_url
-        4 + (4 + 50) * 10 + // metadata.tags (max 10)
+        4 + (4 + ChatRoomMetadata::MAX_TAG_LENGTH) * ChatRoomMetadata::MAX_TAGS + // metadata.tags
         1 + 4 + // metadata.max_participants
         1 + // metadata.is_nsfw
         8 + // access_control.min_key_balance
         1 + 32 + // access_control.required_nft_collection
         4 + (32 * 50) + // access_control.whitelist (max 50)
-        4 + (32 * 50) + // access_control.blacklist (max 50)
         1 + // access_control.require_verification
         1; // bump
 
+    pub const MAX_WELCOME_MESSAGE_LENGTH: usize = 280;
+
     pub fn new(
         room_id: u64,
         creator: Pubkey,
         required_key_amount: u64,
+        min_participants_to_open: u32,
+        max_attachment_bytes: u64,
+        require_creator_approval: bool,
+        max_open_invites: u32,
         room_type: ChatRoomType,
         metadata: ChatRoomMetadata,
         access_control: AccessControl,
@@ -194,6 +271,17 @@ impl ChatRoom {
             creator,
             required_key_amount,
             participants: vec![creator],
+            min_participants_to_open,
+            // The creator counts towards the quorum
+            is_open: min_participants_to_open <= 1,
+            max_attachment_bytes,
+            require_creator_approval,
+            max_open_invites,
+            open_invites: 0,
+            invite_count: 0,
+            welcome_message: String::new(),
+            max_reactions_per_user: 0,
+            reactions_require_keys: false,
             message_count: 0,
             created_at: Clock::get().unwrap().unix_timestamp,
             is_active: true,
@@ -216,9 +304,18 @@ impl ChatRoom {
         }
 
         self.participants.push(participant);
+        self.open_if_quorum_reached();
+
         Ok(())
     }
 
+    /// Once the quorum is reached the room stays open, even if members leave later
+    pub fn open_if_quorum_reached(&mut self) {
+        if !self.is_open && self.participants.len() >= self.min_participants_to_open as usize {
+            self.is_open = true;
+        }
+    }
+
     pub fn remove_participant(&mut self, participant: Pubkey) -> Result<()> {
         let position = self.participants.iter().position(|&x| x == participant)
             .ok_or(ChatError::ParticipantNotFound)?;
@@ -231,6 +328,27 @@ impl ChatRoom {
         self.participants.contains(user)
     }
 
+    /// Reserves an open-invite slot and returns the id for the new invite
+    pub fn open_invite(&mut self) -> Result<u64> {
+        require!(
+            self.max_open_invites == 0 || self.open_invites < self.max_open_invites,
+            SolSocialError::ResourceNotAvailable
+        );
+
+        let invite_id = self.invite_count;
+        self.invite_count = self.invite_count
+            .checked_add(1)
+            .ok_or(SolSocialError::ArithmeticOverflow)?;
+        self.open_invites += 1;
+
+        Ok(invite_id)
+    }
+
+    /// Frees the slot held by a redeemed or cancelled invite
+    pub fn close_invite(&mut self) {
+        self.open_invites = self.open_invites.saturating_sub(1);
+    }
+
     pub fn increment_message_count(&mut self) {
         self.message_count += 1;
     }
@@ -241,20 +359,32 @@ impl ChatRoom {
 }
 
 impl Message {
+    // Stays under the 10,240 bytes an account created through CPI can be given
     pub const LEN: usize = 8 + // discriminator
         8 + // message_id
         8 + // room_id
         32 + // sender
-        4 + 1000 + // content (max 1000 chars)
+        4 + Self::MAX_CONTENT_LENGTH + // content
         8 + // timestamp
         1 + // message_type
         1 + 8 + // reply_to
-        4 + (4 + 10 + 4 + 32 * 10) * 10 + // reactions (max 10 types, 10 users each)
+        4 + (4 + Self::MAX_REACTION_LENGTH + 4 + 32 * Self::MAX_REACTORS_PER_TYPE) * Self::MAX_REACTION_TYPES + // reactions
         1 + // is_deleted
-        4 + (8 + 4 + 1000 + 1 + 4 + 100) * 5 + // edit_history (max 5 edits)
-        4 + (1 + 4 + 200 + 1 + 4 + 100 + 1 + 8 + 1 + 4 + 50) * 5 + // attachments (max 5)
+        4 + (8 + 4 + Self::MAX_CONTENT_LENGTH + 1 + 4 + Self::MAX_EDIT_REASON_LENGTH) * Self::MAX_EDIT_HISTORY + // edit_history
+        4 + (1 + 4 + Self::MAX_ATTACHMENT_URL_LENGTH + 1 + 4 + Self::MAX_ATTACHMENT_FILENAME_LENGTH + 1 + 8 + 1 + 4 + Self::MAX_ATTACHMENT_MIME_LENGTH) * Self::MAX_ATTACHMENTS + // attachments
         1; // bump
 
+    pub const MAX_CONTENT_LENGTH: usize = 500;
+    pub const MAX_EDIT_REASON_LENGTH: usize = 100;
+    pub const MAX_EDIT_HISTORY: usize = 5;
+    pub const MAX_ATTACHMENTS: usize = 5;
+    pub const MAX_ATTACHMENT_URL_LENGTH: usize = 200;
+    pub const MAX_ATTACHMENT_FILENAME_LENGTH: usize = 100;
+    pub const MAX_ATTACHMENT_MIME_LENGTH: usize = 50;
+    pub const MAX_REACTION_LENGTH: usize = 16;
+    pub const MAX_REACTION_TYPES: usize = 20;
+    pub const MAX_REACTORS_PER_TYPE: usize = 5;
+
     pub fn new(
         message_id: u64,
         room_id: u64,
@@ -281,6 +411,11 @@ impl Message {
         }
     }
 
+    /// Number of distinct reactions `user` currently has on this message
+    pub fn reactions_by(&self, user: &Pubkey) -> usize {
+        self.reactions.values().filter(|users| users.contains(user)).count()
+    }
+
     pub fn add_reaction(&mut self, reaction: String, user: Pubkey) -> Result<()> {
         let users = self.reactions.entry(reaction).or_insert_with(Vec::new);
         
@@ -319,6 +454,10 @@ impl Message {
             edit_reason,
         };
 
+        // Only the most recent edits are kept; the oldest falls off to stay within LEN
+        if self.edit_history.len() >= Self::MAX_EDIT_HISTORY {
+            self.edit_history.remove(0);
+        }
         self.edit_history.push(edit);
         self.content = new_content;
         Ok(())
@@ -344,6 +483,7 @@ impl ChatParticipant {
         8 + // permissions (8 bools)
         1 + // is_muted
         1 + 8 + // muted_until
+        1 + // is_lapsed
         1; // bump
 
     pub fn new(
@@ -424,6 +564,7 @@ impl ChatParticipant {
             permissions,
             is_muted: false,
             muted_until: None,
+            is_lapsed: false,
             bump,
         }
     }
@@ -436,9 +577,9 @@ impl ChatParticipant {
 
     pub fn mute(&mut self, duration_seconds: Option<i64>) {
         self.is_muted = true;
-        if let Some(duration) = duration_seconds {
-            self.muted_until = Some(Clock::get().unwrap().unix_timestamp + duration);
-        }
+        // Replace any earlier deadline so a permanent mute can't inherit a stale one
+        self.muted_until = duration_seconds
+            .map(|duration| Clock::get().unwrap().unix_timestamp + duration);
     }
 
     pub fn unmute(&mut self) {
@@ -451,4 +592,12 @@ impl ChatParticipant {
             return false;
         }
 
-        if let Some(muted_until) = self
\ No newline at end of file
+        if let Some(muted_until) = self.muted_until {
+            // Timed mutes lapse on their own once the deadline has passed
+            return Clock::get().unwrap().unix_timestamp <= muted_until;
+        }
+
+        // No deadline means a permanent mute
+        true
+    }
+}
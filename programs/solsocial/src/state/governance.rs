@@ -0,0 +1,109 @@
+use anchor_lang::prelude::*;
+use std::collections::BTreeMap;
+use crate::errors::*;
+
+pub const MAX_PROPOSAL_TITLE_LENGTH: usize = 64;
+pub const MAX_SNAPSHOT_HOLDERS: usize = 100; // mirrors the UserKeys holder cap
+
+#[account]
+pub struct Proposal {
+    pub proposal_id: u64,
+    pub subject: Pubkey,
+    pub proposer: Pubkey,
+    pub title: String,
+    pub snapshot: Pubkey,
+    pub created_at: i64,
+    pub voting_ends_at: i64,
+    pub yes_weight: u64,
+    pub no_weight: u64,
+    pub bump: u8,
+}
+
+impl Proposal {
+    pub const LEN: usize = 8 + // discriminator
+        8 + // proposal_id
+        32 + // subject
+        32 + // proposer
+        4 + MAX_PROPOSAL_TITLE_LENGTH + // title
+        32 + // snapshot
+        8 + // created_at
+        8 + // voting_ends_at
+        8 + // yes_weight
+        8 + // no_weight
+        1; // bump
+
+    pub fn is_voting_open(&self, now: i64) -> bool {
+        now < self.voting_ends_at
+    }
+
+    pub fn record_vote(&mut self, weight: u64, in_favor: bool) -> Result<()> {
+        if in_favor {
+            self.yes_weight = self.yes_weight.checked_add(weight)
+                .ok_or(SolSocialError::ArithmeticOverflow)?;
+        } else {
+            self.no_weight = self.no_weight.checked_add(weight)
+                .ok_or(SolSocialError::ArithmeticOverflow)?;
+        }
+        Ok(())
+    }
+}
+
+/// Key balances frozen when a proposal is created, so keys bought afterwards carry no voting power
+#[account]
+pub struct HolderSnapshot {
+    pub proposal: Pubkey,
+    pub subject: Pubkey,
+    pub balances: BTreeMap<Pubkey, u64>,
+    pub total_weight: u64,
+    pub taken_at: i64,
+    pub bump: u8,
+}
+
+impl HolderSnapshot {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // proposal
+        32 + // subject
+        4 + (32 + 8) * MAX_SNAPSHOT_HOLDERS + // balances
+        8 + // total_weight
+        8 + // taken_at
+        1; // bump
+
+    pub fn capture(&mut self, holders: &BTreeMap<Pubkey, u64>, taken_at: i64) -> Result<()> {
+        require!(holders.len() <= MAX_SNAPSHOT_HOLDERS, SolSocialError::InvalidVotingPower);
+
+        let mut total_weight = 0u64;
+        for amount in holders.values() {
+            total_weight = total_weight.checked_add(*amount)
+                .ok_or(SolSocialError::ArithmeticOverflow)?;
+        }
+
+        self.balances = holders.clone();
+        self.total_weight = total_weight;
+        self.taken_at = taken_at;
+        Ok(())
+    }
+
+    pub fn voting_power(&self, voter: &Pubkey) -> u64 {
+        *self.balances.get(voter).unwrap_or(&0)
+    }
+}
+
+#[account]
+pub struct VoteRecord {
+    pub proposal: Pubkey,
+    pub voter: Pubkey,
+    pub weight: u64,
+    pub in_favor: bool,
+    pub voted_at: i64,
+    pub bump: u8,
+}
+
+impl VoteRecord {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // proposal
+        32 + // voter
+        8 + // weight
+        1 + // in_favor
+        8 + // voted_at
+        1; // bump
+}
@@ -1,44 +1,152 @@
 use anchor_lang::prelude::*;
+use crate::errors::*;
 use std::collections::BTreeMap;
+use crate::utils::bonding_curve::BondingCurve;
 
 #[account]
 pub struct UserKeys {
     pub owner: Pubkey,
+    pub creator: Pubkey, // wallet that opened the market; its issuance counts against max_creator_supply
     pub total_supply: u64,
     pub holders: BTreeMap<Pubkey, u64>,
     pub price_per_key: u64,
     pub total_volume: u64,
     pub created_at: i64,
     pub last_trade_at: i64,
+    pub referral_bonus_bps: Option<u16>,
+    pub sell_interval_seconds: i64, // minimum gap between a holder's sells, 0 = no limit
+    pub trading_open: Option<i64>,
+    pub trading_close: Option<i64>,
+    pub slot_start_supply: u64, // supply before the first trade of slot_start_slot
+    pub slot_start_slot: u64,
+    pub description: String,
+    pub links: Vec<String>,
     pub bump: u8,
 }
 
 impl UserKeys {
     pub const LEN: usize = 8 + // discriminator
         32 + // owner
+        32 + // creator
         8 + // total_supply
         4 + (32 + 8) * 100 + // holders (max 100 holders)
         8 + // price_per_key
         8 + // total_volume
         8 + // created_at
         8 + // last_trade_at
+        1 + 2 + // referral_bonus_bps
+        8 + // sell_interval_seconds
+        1 + 8 + // trading_open
+        1 + 8 + // trading_close
+        8 + // slot_start_supply
+        8 + // slot_start_slot
+        4 + Self::MAX_DESCRIPTION_LENGTH + // description
+        4 + (4 + Self::MAX_LINK_LENGTH) * Self::MAX_LINKS + // links
         1; // bump
 
+    pub const MAX_DESCRIPTION_LENGTH: usize = 256;
+    pub const MAX_LINKS: usize = 3;
+    pub const MAX_LINK_LENGTH: usize = 100;
+
+    /// Upper bound for a creator referral bonus: the global referrer share plus the whole creator share
+    pub const MAX_REFERRAL_BONUS_BPS: u16 = 600;
+
     pub fn initialize(&mut self, owner: Pubkey, bump: u8) -> Result<()> {
         self.owner = owner;
+        self.creator = owner;
         self.total_supply = 0;
         self.holders = BTreeMap::new();
         self.price_per_key = Self::calculate_initial_price();
         self.total_volume = 0;
         self.created_at = Clock::get()?.unix_timestamp;
         self.last_trade_at = Clock::get()?.unix_timestamp;
+        self.referral_bonus_bps = None;
+        self.sell_interval_seconds = 0;
+        self.trading_open = None;
+        self.trading_close = None;
+        self.slot_start_supply = 0;
+        self.slot_start_slot = 0;
+        self.description = String::new();
+        self.links = Vec::new();
         self.bump = bump;
         Ok(())
     }
 
-    pub fn buy_keys(&mut self, buyer: Pubkey, amount: u64) -> Result<u64> {
+    pub fn set_referral_bonus(&mut self, referral_bonus_bps: Option<u16>) -> Result<()> {
+        if let Some(bps) = referral_bonus_bps {
+            require!(bps <= Self::MAX_REFERRAL_BONUS_BPS, SolSocialError::InvalidReferralBonus);
+        }
+
+        self.referral_bonus_bps = referral_bonus_bps;
+        Ok(())
+    }
+
+    pub fn set_metadata(&mut self, description: String, links: Vec<String>) -> Result<()> {
+        require!(
+            description.len() <= Self::MAX_DESCRIPTION_LENGTH,
+            SolSocialError::DescriptionTooLong
+        );
+        require!(links.len() <= Self::MAX_LINKS, SolSocialError::TooManyLinks);
+        require!(
+            links.iter().all(|link| link.len() <= Self::MAX_LINK_LENGTH),
+            SolSocialError::LinkTooLong
+        );
+
+        self.description = description;
+        self.links = links;
+        Ok(())
+    }
+
+    pub fn set_sell_interval(&mut self, sell_interval_seconds: i64) -> Result<()> {
+        require!(sell_interval_seconds >= 0, SolSocialError::InvalidAmount);
+
+        self.sell_interval_seconds = sell_interval_seconds;
+        Ok(())
+    }
+
+    pub fn set_trading_window(&mut self, trading_open: Option<i64>, trading_close: Option<i64>) -> Result<()> {
+        if let (Some(open), Some(close)) = (trading_open, trading_close) {
+            require!(open < close, SolSocialError::InvalidAmount);
+        }
+
+        self.trading_open = trading_open;
+        self.trading_close = trading_close;
+        Ok(())
+    }
+
+    /// Unset bounds leave that side of the window open
+    pub fn is_within_trading_window(&self, now: i64) -> bool {
+        self.trading_open.map_or(true, |open| now >= open)
+            && self.trading_close.map_or(true, |close| now <= close)
+    }
+
+    /// Supply as it stood before this slot's first trade: what anyone quoting during the
+    /// slot saw, however many trades have landed since
+    pub fn supply_at_slot_start(&self, slot: u64) -> u64 {
+        if self.slot_start_slot == slot {
+            self.slot_start_supply
+        } else {
+            self.total_supply
+        }
+    }
+
+    /// Called before a trade changes the supply, so the slot's first trade pins its start
+    pub fn mark_slot_start(&mut self, slot: u64) {
+        if self.slot_start_slot != slot {
+            self.slot_start_slot = slot;
+            self.slot_start_supply = self.total_supply;
+        }
+    }
+
+    pub fn is_sell_allowed(&self, last_sell_at: i64, now: i64) -> bool {
+        self.sell_interval_seconds == 0
+            || last_sell_at == 0
+            || now.saturating_sub(last_sell_at) >= self.sell_interval_seconds
+    }
+
+    pub fn buy_keys(&mut self, buyer: Pubkey, amount: u64, max_keys_per_trade: u64) -> Result<u64> {
         require!(amount > 0, SolSocialError::InvalidAmount);
-        require!(amount <= 10, SolSocialError::ExceedsMaxPurchase);
+        require!(amount <= max_keys_per_trade, SolSocialError::ExceedsMaxPurchase);
 
         let total_cost = self.calculate_buy_price(amount)?;
         
@@ -92,65 +200,19 @@ impl UserKeys {
     }
 
     pub fn calculate_buy_price(&self, amount: u64) -> Result<u64> {
-        let mut total_cost = 0u64;
-        let mut current_supply = self.total_supply;
-        
-        for _ in 0..amount {
-            let price = Self::get_price_for_supply(current_supply)?;
-            total_cost = total_cost.checked_add(price)
-                .ok_or(SolSocialError::MathOverflow)?;
-            current_supply = current_supply.checked_add(1)
-                .ok_or(SolSocialError::MathOverflow)?;
-        }
-        
-        Ok(total_cost)
+        BondingCurve::standard().get_buy_price(self.total_supply, amount)
     }
 
     pub fn calculate_sell_price(&self, amount: u64) -> Result<u64> {
-        let mut total_payout = 0u64;
-        let mut current_supply = self.total_supply;
-        
-        for _ in 0..amount {
-            current_supply = current_supply.checked_sub(1)
-                .ok_or(SolSocialError::MathUnderflow)?;
-            let price = Self::get_price_for_supply(current_supply)?;
-            total_payout = total_payout.checked_add(price)
-                .ok_or(SolSocialError::MathOverflow)?;
-        }
-        
-        Ok(total_payout)
+        BondingCurve::standard().get_sell_price(self.total_supply, amount)
     }
 
     fn calculate_current_price(&self) -> Result<u64> {
-        Self::get_price_for_supply(self.total_supply)
+        BondingCurve::standard().get_price(self.total_supply)
     }
 
     fn calculate_initial_price() -> u64 {
-        1_000_000 // 0.001 SOL in lamports
-    }
-
-    fn get_price_for_supply(supply: u64) -> Result<u64> {
-        // Bonding curve: price = base_price * (1 + supply/1000)^2
-        let base_price = 1_000_000u64; // 0.001 SOL
-        
-        if supply == 0 {
-            return Ok(base_price);
-        }
-        
-        // Calculate multiplier: (1000 + supply)^2 / 1000^2
-        let numerator = (1000u64.checked_add(supply)
-            .ok_or(SolSocialError::MathOverflow)?)
-            .checked_pow(2)
-            .ok_or(SolSocialError::MathOverflow)?;
-        
-        let denominator = 1_000_000u64; // 1000^2
-        
-        let price = base_price.checked_mul(numerator)
-            .ok_or(SolSocialError::MathOverflow)?
-            .checked_div(denominator)
-            .ok_or(SolSocialError::MathUnderflow)?;
-        
-        Ok(price)
+        BondingCurve::DEFAULT_BASE_PRICE
     }
 
     pub fn get_holder_balance(&self, holder: &Pubkey) -> u64 {
@@ -243,26 +305,4 @@ impl KeysGlobalState {
             .ok_or(SolSocialError::MathOverflow)?;
         Ok(())
     }
-}
-
-#[error_code]
-pub enum SolSocialError {
-    #[msg("Invalid amount specified")]
-    InvalidAmount,
-    #[msg("Exceeds maximum purchase limit")]
-    ExceedsMaxPurchase,
-    #[msg("Insufficient keys to sell")]
-    InsufficientKeys,
-    #[msg("Math overflow error")]
-    MathOverflow,
-    #[msg("Math underflow error")]
-    MathUnderflow,
-    #[msg("Unauthorized access")]
-    Unauthorized,
-    #[msg("Invalid account provided")]
-    InvalidAccount,
-    #[msg("Account already initialized")]
-    AlreadyInitialized,
-    #[msg("Account not initialized")]
-    NotInitialized,
 }
\ No newline at end of file
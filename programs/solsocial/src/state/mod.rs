@@ -1,4 +1,6 @@
 use anchor_lang::prelude::*;
+use crate::errors::SolSocialError;
+use crate::utils::reputation::accrue_reputation;
 
 #[account]
 pub struct UserProfile {
@@ -12,12 +14,34 @@ pub struct UserProfile {
     pub updated_at: i64,
     pub follower_count: u64,
     pub following_count: u64,
-    pub post_count: u64,
+    pub post_count: u64, // posts ever created; also seeds the next post, so never decremented
+    pub deleted_post_count: u64,
     pub reputation_score: u64,
+    pub pending_reputation: u64, // accrued but not yet settled into reputation_score
+    pub last_reputation_update: i64, // last time decay_reputation applied, or profile creation
+    pub recent_unfollows: u32, // unfollows since unfollow_window_start
+    pub unfollow_window_start: i64,
+    pub last_interaction_at: i64, // last like, spaced out by interaction_cooldown_seconds
     pub total_keys_owned: u64,
     pub total_keys_sold: u64,
     pub total_revenue_earned: u64,
+    pub total_volume: u64, // lamports this user has traded buying and selling keys, any market
+    pub spend_cap: u64, // self-imposed lamport limit on buys and tips per window, 0 = none
+    pub spend_window_seconds: i64,
+    pub spend_window_start: i64,
+    pub spent_in_window: u64,
+    pub subscription_price: u64, // lamports per month of premium access, 0 = not offered
+    pub pinned_posts_count: u64,
+    pub scheduled_posts_count: u32, // scheduled posts still waiting to publish
+    pub issued_key_supply: u64, // supply across markets this user created, counted while a cap is set
+    pub free_trades_remaining: u32,
+    pub active_positions: u64, // distinct key markets this user currently holds, capped by max_positions
+    pub bootstrap_reputation_earned: u64, // reputation granted so far for buying reputable creators' keys
+    pub analytics_enabled: bool, // when off, PostStats/SocialStats skip this creator's content
+    pub sells_paused: bool, // recorded by buys while escrow for this user's keys is below the solvency ratio
     pub is_verified: bool,
+    pub is_moderator: bool, // may hide, remove and restore posts via moderate_post
+    pub is_early_adopter: bool, // joined while the platform had fewer than EARLY_ADOPTER_USER_LIMIT users
     pub is_active: bool,
     pub bump: u8,
 }
@@ -35,15 +59,80 @@ impl UserProfile {
         8 + // follower_count
         8 + // following_count
         8 + // post_count
+        8 + // deleted_post_count
         8 + // reputation_score
+        8 + // pending_reputation
+        8 + // last_reputation_update
+        4 + // recent_unfollows
+        8 + // unfollow_window_start
+        8 + // last_interaction_at
         8 + // total_keys_owned
         8 + // total_keys_sold
         8 + // total_revenue_earned
+        8 + // total_volume
+        8 + // spend_cap
+        8 + // spend_window_seconds
+        8 + // spend_window_start
+        8 + // spent_in_window
+        8 + // subscription_price
+        8 + // pinned_posts_count
+        4 + // scheduled_posts_count
+        8 + // issued_key_supply
+        4 + // free_trades_remaining
+        8 + // active_positions
+        8 + // bootstrap_reputation_earned
+        1 + // analytics_enabled
+        1 + // sells_paused
         1 + // is_verified
+        1 + // is_moderator
+        1 + // is_early_adopter
         1 + // is_active
         1; // bump
+
+    /// Credits reputation per the platform's `reputation_settlement_threshold`
+    pub fn accrue_reputation(&mut self, amount: u64, threshold: u64) {
+        let (score, pending) = accrue_reputation(self.reputation_score, self.pending_reputation, amount, threshold);
+        self.reputation_score = score;
+        self.pending_reputation = pending;
+    }
+
+    /// Counts `amount` against the user's own spend cap, starting a fresh window once the
+    /// current one has elapsed. A no-op while no cap is set.
+    pub fn record_spend(&mut self, amount: u64, now: i64) -> Result<()> {
+        if self.spend_cap == 0 {
+            return Ok(());
+        }
+
+        if now.saturating_sub(self.spend_window_start) >= self.spend_window_seconds {
+            self.spend_window_start = now;
+            self.spent_in_window = 0;
+        }
+
+        let spent = self.spent_in_window
+            .checked_add(amount)
+            .ok_or(SolSocialError::ArithmeticOverflow)?;
+        require!(spent <= self.spend_cap, SolSocialError::RateLimitExceeded);
+
+        self.spent_in_window = spent;
+        Ok(())
+    }
+
+    /// Posts the user still has up, net of deletions
+    pub fn live_post_count(&self) -> u64 {
+        self.post_count.saturating_sub(self.deleted_post_count)
+    }
+
+    /// Folds any pending reputation into the score, returning how much was settled
+    pub fn settle_reputation(&mut self) -> u64 {
+        let settled = self.pending_reputation;
+        self.reputation_score = self.reputation_score.saturating_add(settled);
+        self.pending_reputation = 0;
+        settled
+    }
 }
 
+/// Created alongside `UserKeys` by `create_keys`. Live supply, price and volume are tracked
+/// on `UserKeys`; `supply` and `price` here only record the market's launch state.
 #[account]
 pub struct UserKey {
     pub subject: Pubkey,
@@ -56,6 +145,8 @@ pub struct UserKey {
     pub created_at: i64,
     pub last_trade_at: i64,
     pub is_tradeable: bool,
+    pub snapshot_count: u64, // MarketSnapshots taken of this market, also the next one's sequence
+    pub last_snapshot_at: i64,
     pub bump: u8,
 }
 
@@ -71,6 +162,8 @@ impl UserKey {
         8 + // created_at
         8 + // last_trade_at
         1 + // is_tradeable
+        8 + // snapshot_count
+        8 + // last_snapshot_at
         1; // bump
 }
 
@@ -207,6 +300,7 @@ pub struct FollowRelation {
     pub follower: Pubkey,
     pub following: Pubkey,
     pub created_at: i64,
+    pub is_active: bool,
     pub bump: u8,
 }
 
@@ -215,7 +309,41 @@ impl FollowRelation {
         32 + // follower
         32 + // following
         8 + // created_at
+        1 + // is_active
+        1; // bump
+
+    pub fn is_following(&self, follower: &Pubkey, following: &Pubkey) -> bool {
+        self.is_active && self.follower == *follower && self.following == *following
+    }
+}
+
+#[account]
+pub struct BlockList {
+    pub owner: Pubkey,
+    pub blocked: Vec<Pubkey>,
+    pub bump: u8,
+}
+
+impl BlockList {
+    pub const MAX_BLOCKED: usize = 200;
+
+    pub const LEN: usize = 8 + // discriminator
+        32 + // owner
+        4 + (32 * Self::MAX_BLOCKED) + // blocked
         1; // bump
+
+    pub fn is_blocked(&self, user: &Pubkey) -> bool {
+        self.blocked.contains(user)
+    }
+
+    /// Block lists are created on first block, so a missing account blocks nobody
+    pub fn account_blocks(block_list: &AccountInfo, user: &Pubkey) -> Result<bool> {
+        if block_list.data_is_empty() {
+            return Ok(false);
+        }
+        let block_list = Account::<BlockList>::try_from(block_list)?;
+        Ok(block_list.is_blocked(user))
+    }
 }
 
 #[account]
@@ -227,10 +355,59 @@ pub struct PlatformConfig {
     pub min_key_price: u64,
     pub max_key_price: u64,
     pub bonding_curve_coefficient: u64,
+    pub fee_rate: u64, // platform fee on SOL-settled trades, in basis points
+    pub creator_fee_rate: u64, // creator fee on SOL-settled trades, in basis points
+    pub min_solvency_ratio_bps: u16, // escrow backing below which a market's sells pause, 0 = never
+    pub total_users: u64,
+    pub total_volume: u64, // lamports of curve price traded across all SOL-settled markets
     pub reputation_decay_rate: u16,
+    pub influence_decay_rate: u16, // basis points per day of inactivity
     pub engagement_multiplier: u16,
+    pub author_reputation_per_engagement: u16,
+    pub max_author_reputation_per_post: u64,
     pub is_trading_enabled: bool,
     pub is_posting_enabled: bool,
+    pub max_tip: Option<u64>, // tips above this need explicit confirmation
+    pub max_positions: u64, // distinct key markets one wallet may hold, 0 = unlimited
+    pub max_pinned_posts: u64,
+    pub bootstrap_reputation_rate: u16, // basis points of the creator's reputation granted per purchase
+    pub max_bootstrap_reputation: u64, // lifetime bootstrap a single buyer can accrue
+    pub badge_reputation_floor: u64, // badges can be revoked below this reputation
+    pub restore_badges_on_unban: bool,
+    pub free_trades_per_user: u32, // protocol-fee-free trades granted to each new user
+    pub pending_authority: Option<Pubkey>, // proposed authority awaiting acceptance
+    pub feed_reputation_weight: u16, // basis points of feed boost per point of author reputation
+    pub max_counted_reposts: u64, // reposts per post that boost engagement, 0 = no cap
+    pub default_max_slippage_bps: u16, // applied to trades that pass no bound, 0 = none
+    pub max_keys_per_trade: u64,
+    pub launch_fee: u64, // flat lamports charged to open a key market, 0 = free
+    pub base_content_length: u32, // post length limit before any reputation tiers
+    pub content_length_per_tier: u32, // extra characters granted per reputation tier
+    pub reputation_per_content_tier: u64, // reputation needed per tier, 0 = no tiers
+    pub daily_post_reputation_bonus: u64, // granted on a user's first post of each UTC day
+    pub multi_holder_threshold: u64, // open positions needed for the fee discount, 0 = off
+    pub multi_holder_discount_bps: u16, // share of the protocol fee waived for multi-holders
+    pub max_engagement_score: u64, // engagement saturates here instead of overflowing, 0 = u64::MAX
+    pub unfollow_penalty_window: i64, // seconds over which unfollows are counted, 0 = no penalty
+    pub free_unfollows_per_window: u32, // unfollows allowed in a window before the penalty applies
+    pub unfollow_reputation_penalty: u64, // reputation lost per unfollow past the free allowance
+    pub payout_expiry_seconds: i64, // idle pending revenue older than this can be swept, 0 = never
+    pub interaction_cooldown_seconds: i64, // minimum gap between a user's post interactions, 0 = none
+    pub min_buy_amount: u64, // smallest buy allowed, to keep dust positions out
+    pub reputation_settlement_threshold: u64, // pending reputation settles at this size, 0 = write immediately
+    pub self_repost_cooldown_seconds: i64, // authors can't repost their own post until it's this old
+    pub media_host_allowlist: Vec<String>, // hosts post media may be served from, empty = any
+    pub max_subject_fee_percent: u16, // highest creator fee a subject may set, in basis points
+    pub cap_self_holdings: bool, // subjects can't hold more of their own keys than their initial allocation
+    pub max_scheduled_posts: u32, // unpublished scheduled posts one user may have queued
+    pub min_reputation_for_premium: u64, // reputation needed to create premium posts, 0 = anyone
+    pub max_creator_supply: u64, // total supply one creator may have across their markets, 0 = no cap
+    pub min_snapshot_interval_seconds: i64, // minimum gap between a market's snapshots, 0 = none
+    pub report_hide_threshold: u32, // reports that auto-hide a post, 0 = never auto-hide
+    pub ban_appeal_window_seconds: i64, // how long after a ban the user may appeal it
+    pub curation_boost_threshold: u64, // holder like weight that curates a post, 0 = curation off
+    pub top_trader_volume_threshold: u64, // lamports of volume a TopTrader badge requires exceeding
+    pub influencer_follower_threshold: u64, // followers an Influencer badge requires exceeding
     pub bump: u8,
 }
 
@@ -243,10 +420,145 @@ impl PlatformConfig {
         8 + // min_key_price
         8 + // max_key_price
         8 + // bonding_curve_coefficient
+        8 + // fee_rate
+        8 + // creator_fee_rate
+        2 + // min_solvency_ratio_bps
+        8 + // total_users
+        8 + // total_volume
         2 + // reputation_decay_rate
+        2 + // influence_decay_rate
         2 + // engagement_multiplier
+        2 + // author_reputation_per_engagement
+        8 + // max_author_reputation_per_post
         1 + // is_trading_enabled
         1 + // is_posting_enabled
+        1 + 8 + // max_tip
+        8 + // max_positions
+        8 + // max_pinned_posts
+        2 + // bootstrap_reputation_rate
+        8 + // max_bootstrap_reputation
+        8 + // badge_reputation_floor
+        1 + // restore_badges_on_unban
+        4 + // free_trades_per_user
+        1 + 32 + // pending_authority
+        2 + // feed_reputation_weight
+        8 + // max_counted_reposts
+        2 + // default_max_slippage_bps
+        8 + // max_keys_per_trade
+        8 + // launch_fee
+        4 + // base_content_length
+        4 + // content_length_per_tier
+        8 + // reputation_per_content_tier
+        8 + // daily_post_reputation_bonus
+        8 + // multi_holder_threshold
+        2 + // multi_holder_discount_bps
+        8 + // max_engagement_score
+        8 + // unfollow_penalty_window
+        4 + // free_unfollows_per_window
+        8 + // unfollow_reputation_penalty
+        8 + // payout_expiry_seconds
+        8 + // interaction_cooldown_seconds
+        8 + // min_buy_amount
+        8 + // reputation_settlement_threshold
+        8 + // self_repost_cooldown_seconds
+        4 + (4 + Self::MAX_MEDIA_HOST_LENGTH) * Self::MAX_MEDIA_HOSTS + // media_host_allowlist
+        2 + // max_subject_fee_percent
+        1 + // cap_self_holdings
+        4 + // max_scheduled_posts
+        8 + // min_reputation_for_premium
+        8 + // max_creator_supply
+        8 + // min_snapshot_interval_seconds
+        4 + // report_hide_threshold
+        8 + // ban_appeal_window_seconds
+        8 + // curation_boost_threshold
+        8 + // top_trader_volume_threshold
+        8 + // influencer_follower_threshold
+        1; // bump
+
+    pub const DEFAULT_MAX_KEYS_PER_TRADE: u64 = 1000;
+    pub const DEFAULT_MIN_BUY_AMOUNT: u64 = 1;
+    pub const DEFAULT_SELF_REPOST_COOLDOWN_SECONDS: i64 = 3600;
+    pub const DEFAULT_MAX_SUBJECT_FEE_PERCENT: u16 = 1000;
+    pub const DEFAULT_MAX_PINNED_POSTS: u64 = 3;
+    pub const DEFAULT_MAX_SCHEDULED_POSTS: u32 = 10;
+    pub const DEFAULT_MIN_SNAPSHOT_INTERVAL_SECONDS: i64 = 60;
+    pub const DEFAULT_MAX_CREATOR_SUPPLY: u64 = 10_000_000;
+    pub const DEFAULT_REPORT_HIDE_THRESHOLD: u32 = 5;
+    pub const DEFAULT_BAN_APPEAL_WINDOW_SECONDS: i64 = 7 * 24 * 60 * 60;
+    pub const DEFAULT_TOP_TRADER_VOLUME_THRESHOLD: u64 = 100_000_000_000; // 100 SOL
+    pub const DEFAULT_INFLUENCER_FOLLOWER_THRESHOLD: u64 = 1000;
+    pub const EARLY_ADOPTER_USER_LIMIT: u64 = 1000;
+    pub const MAX_MEDIA_HOSTS: usize = 10;
+    pub const MAX_MEDIA_HOST_LENGTH: usize = 64;
+    pub const DEFAULT_BASE_CONTENT_LENGTH: u32 = 2000;
+    pub const DEFAULT_CONTENT_LENGTH_PER_TIER: u32 = 500;
+    pub const DEFAULT_REPUTATION_PER_CONTENT_TIER: u64 = 1000;
+    pub const DEFAULT_INFLUENCE_DECAY_RATE: u16 = 100; // 1% per inactive day
+    pub const DEFAULT_MAX_TIP: u64 = 10_000_000_000; // 10 SOL
+    pub const DEFAULT_AUTHOR_REPUTATION_PER_ENGAGEMENT: u16 = 1;
+    pub const DEFAULT_MAX_AUTHOR_REPUTATION_PER_POST: u64 = 500;
+    pub const DEFAULT_BOOTSTRAP_REPUTATION_RATE: u16 = 100; // 1% of the creator's reputation per purchase
+    pub const DEFAULT_MAX_BOOTSTRAP_REPUTATION: u64 = 500;
+    pub const DEFAULT_BADGE_REPUTATION_FLOOR: u64 = 100;
+    pub const DEFAULT_FREE_TRADES_PER_USER: u32 = 3;
+    pub const DEFAULT_FEED_REPUTATION_WEIGHT: u16 = 10;
+    pub const DEFAULT_MAX_COUNTED_REPOSTS: u64 = 100;
+    pub const DEFAULT_MAX_SLIPPAGE_BPS: u16 = 500; // 5%
+    pub const DEFAULT_REPUTATION_DECAY_RATE: u16 = 50; // 0.5% per day
+}
+
+/// A subscriber's paid access to a creator's premium posts, extended by each renewal
+#[account]
+pub struct Subscription {
+    pub subscriber: Pubkey,
+    pub creator: Pubkey,
+    pub started_at: i64,
+    pub expires_at: i64,
+    pub total_paid: u64,
+    pub bump: u8,
+}
+
+impl Subscription {
+    pub const MAX_MONTHS: u8 = 12;
+
+    pub const LEN: usize = 8 + // discriminator
+        32 + // subscriber
+        32 + // creator
+        8 + // started_at
+        8 + // expires_at
+        8 + // total_paid
+        1; // bump
+
+    /// Premium access lapses at `expires_at` until the subscription is renewed
+    pub fn is_active(&self, now: i64) -> bool {
+        now < self.expires_at
+    }
+}
+
+/// One per ban, keyed by the ban's timestamp so a later ban can be appealed afresh
+#[account]
+pub struct BanAppeal {
+    pub user: Pubkey,
+    pub banned_at: i64,
+    pub message: String,
+    pub submitted_at: i64,
+    pub is_resolved: bool,
+    pub is_approved: bool,
+    pub resolved_by: Pubkey,
+    pub bump: u8,
+}
+
+impl BanAppeal {
+    pub const MAX_MESSAGE_LENGTH: usize = 500;
+
+    pub const LEN: usize = 8 + // discriminator
+        32 + // user
+        8 + // banned_at
+        4 + Self::MAX_MESSAGE_LENGTH + // message
+        8 + // submitted_at
+        1 + // is_resolved
+        1 + // is_approved
+        32 + // resolved_by
         1; // bump
 }
 
@@ -257,6 +569,7 @@ pub struct RevenueShare {
     pub total_withdrawn: u64,
     pub pending_withdrawal: u64,
     pub last_withdrawal_at: i64,
+    pub pending_since: i64, // when the current pending balance started accruing
     pub bump: u8,
 }
 
@@ -267,7 +580,251 @@ impl RevenueShare {
         8 + // total_withdrawn
         8 + // pending_withdrawal
         8 + // last_withdrawal_at
+        8 + // pending_since
         1; // bump
+
+    pub fn credit(&mut self, amount: u64, now: i64) -> Result<()> {
+        if self.pending_withdrawal == 0 {
+            self.pending_since = now;
+        }
+        self.total_earned = self.total_earned
+            .checked_add(amount)
+            .ok_or(ErrorCode::MathOverflow)?;
+        self.pending_withdrawal = self.pending_withdrawal
+            .checked_add(amount)
+            .ok_or(ErrorCode::MathOverflow)?;
+        Ok(())
+    }
+
+    /// Clears and returns the pending balance, recording it as withdrawn. Callers pay out
+    /// only after this, so a re-entered withdrawal finds nothing left to take.
+    pub fn take_pending(&mut self, now: i64) -> Result<u64> {
+        let amount = self.pending_withdrawal;
+        self.pending_withdrawal = 0;
+        self.total_withdrawn = self.total_withdrawn
+            .checked_add(amount)
+            .ok_or(ErrorCode::MathOverflow)?;
+        self.last_withdrawal_at = now;
+        Ok(amount)
+    }
+
+    /// Pending revenue is abandoned once neither a withdrawal nor the start of the current
+    /// accrual is within `expiry_seconds` of `now`; an expiry of 0 never expires
+    pub fn is_payout_expired(&self, now: i64, expiry_seconds: i64) -> bool {
+        let last_activity = std::cmp::max(self.last_withdrawal_at, self.pending_since);
+        expiry_seconds > 0 && now.saturating_sub(last_activity) > expiry_seconds
+    }
+
+    /// Clears the pending balance without recording it as withdrawn by the subject
+    pub fn forfeit_pending(&mut self) -> u64 {
+        let amount = self.pending_withdrawal;
+        self.pending_withdrawal = 0;
+        amount
+    }
+}
+
+#[account]
+pub struct Order {
+    pub owner: Pubkey,
+    pub subject: Pubkey,
+    pub order_id: u64,
+    pub side: TradeType,
+    pub target_price: u64, // spot price per key that triggers the fill
+    pub amount: u64,
+    pub max_cost: u64, // lamports escrowed for a buy, 0 for a sell
+    pub keeper_bounty: u64,
+    pub referrer: Option<Pubkey>, // fixed at placement, so whoever fills can't name their own
+    pub expires_at: i64,
+    pub created_at: i64,
+    pub bump: u8,
+}
+
+impl Order {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // owner
+        32 + // subject
+        8 + // order_id
+        1 + // side
+        8 + // target_price
+        8 + // amount
+        8 + // max_cost
+        8 + // keeper_bounty
+        1 + 32 + // referrer
+        8 + // expires_at
+        8 + // created_at
+        1; // bump
+
+    /// Lamports escrowed on placement and paid to whoever fills the order
+    pub const KEEPER_BOUNTY: u64 = 10_000;
+
+    pub fn is_expired(&self, now: i64) -> bool {
+        now >= self.expires_at
+    }
+
+    /// Buys trigger once the spot price falls to the target, sells once it rises to it
+    pub fn is_triggered(&self, spot_price: u64) -> bool {
+        match self.side {
+            TradeType::Buy => spot_price <= self.target_price,
+            TradeType::Sell => spot_price >= self.target_price,
+        }
+    }
+}
+
+/// A trader's opt-in record for one subject's market: sequences their receipts and tracks the
+/// cost of keys bought while opted in, so sells can report realized PnL
+#[account]
+pub struct TradeLedger {
+    pub owner: Pubkey,
+    pub subject: Pubkey,
+    pub receipts_enabled: bool,
+    pub next_sequence: u64,
+    pub position_amount: u64, // keys bought while opted in and not yet sold
+    pub cost_basis: u64, // lamports paid for position_amount
+    pub bump: u8,
+}
+
+impl TradeLedger {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // owner
+        32 + // subject
+        1 + // receipts_enabled
+        8 + // next_sequence
+        8 + // position_amount
+        8 + // cost_basis
+        1; // bump
+
+    pub fn take_sequence(&mut self) -> Result<u64> {
+        let sequence = self.next_sequence;
+        self.next_sequence = self.next_sequence
+            .checked_add(1)
+            .ok_or(ErrorCode::MathOverflow)?;
+        Ok(sequence)
+    }
+
+    pub fn record_buy(&mut self, amount: u64, total_cost: u64) -> Result<()> {
+        self.position_amount = self.position_amount
+            .checked_add(amount)
+            .ok_or(ErrorCode::MathOverflow)?;
+        self.cost_basis = self.cost_basis
+            .checked_add(total_cost)
+            .ok_or(ErrorCode::MathOverflow)?;
+        Ok(())
+    }
+
+    /// Releases the average cost of the keys sold and returns the realized PnL. Keys bought
+    /// before opting in have no recorded cost and count as zero basis.
+    pub fn record_sell(&mut self, amount: u64, proceeds: u64) -> Result<i64> {
+        let tracked = amount.min(self.position_amount);
+        let released_basis = if tracked == 0 {
+            0
+        } else {
+            ((self.cost_basis as u128) * (tracked as u128) / (self.position_amount as u128)) as u64
+        };
+
+        self.position_amount -= tracked;
+        self.cost_basis = self.cost_basis.saturating_sub(released_basis);
+
+        Ok((proceeds as i128 - released_basis as i128)
+            .clamp(i64::MIN as i128, i64::MAX as i128) as i64)
+    }
+}
+
+/// A market's curve stats at a point in time, at [b"market_snapshot", subject, sequence]
+#[account]
+pub struct MarketSnapshot {
+    pub subject: Pubkey,
+    pub sequence: u64,
+    pub supply: u64,
+    pub current_price: u64,
+    pub market_cap: u64,
+    pub liquidity: u64,
+    pub timestamp: i64,
+    pub bump: u8,
+}
+
+impl MarketSnapshot {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // subject
+        8 + // sequence
+        8 + // supply
+        8 + // current_price
+        8 + // market_cap
+        8 + // liquidity
+        8 + // timestamp
+        1; // bump
+}
+
+/// One opted-in trade, at [b"trade_receipt", trade_ledger, sequence]
+#[account]
+pub struct TradeReceipt {
+    pub owner: Pubkey,
+    pub subject: Pubkey,
+    pub sequence: u64,
+    pub trade_type: TradeType,
+    pub amount: u64,
+    pub price: u64,
+    pub fees: u64,
+    pub timestamp: i64,
+    pub realized_pnl: i64, // always 0 for buys
+    pub bump: u8,
+}
+
+impl TradeReceipt {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // owner
+        32 + // subject
+        8 + // sequence
+        1 + // trade_type
+        8 + // amount
+        8 + // price
+        8 + // fees
+        8 + // timestamp
+        8 + // realized_pnl
+        1; // bump
+}
+
+/// Writes the next receipt when the trader has opted in for this market. A receipt account
+/// passed without an opted-in ledger is rejected, so no empty receipt is left behind.
+/// `settled` is what the trader paid on a buy or received on a sell.
+pub fn issue_trade_receipt(
+    trade_ledger: Option<&mut Account<TradeLedger>>,
+    trade_receipt: Option<&mut Account<TradeReceipt>>,
+    receipt_bump: Option<u8>,
+    trade_type: TradeType,
+    amount: u64,
+    price: u64,
+    fees: u64,
+    settled: u64,
+    timestamp: i64,
+) -> Result<()> {
+    match trade_ledger {
+        Some(trade_ledger) if trade_ledger.receipts_enabled => {
+            let trade_receipt = trade_receipt.ok_or(SolSocialError::InvalidResourceState)?;
+            let realized_pnl = match trade_type {
+                TradeType::Buy => {
+                    trade_ledger.record_buy(amount, settled)?;
+                    0
+                }
+                TradeType::Sell => trade_ledger.record_sell(amount, settled)?,
+            };
+
+            trade_receipt.set_inner(TradeReceipt {
+                owner: trade_ledger.owner,
+                subject: trade_ledger.subject,
+                sequence: trade_ledger.take_sequence()?,
+                trade_type,
+                amount,
+                price,
+                fees,
+                timestamp,
+                realized_pnl,
+                bump: receipt_bump.ok_or(SolSocialError::InvalidResourceState)?,
+            });
+        }
+        _ => require!(trade_receipt.is_none(), SolSocialError::InvalidResourceState),
+    }
+
+    Ok(())
 }
 
 #[account]
@@ -316,52 +873,6 @@ pub enum TradeType {
     Sell,
 }
 
-pub mod bonding_curve {
-    use super::*;
-
-    pub fn get_price(supply: u64, amount: u64, coefficient: u64) -> Result<u64> {
-        if amount == 0 {
-            return Ok(0);
-        }
-
-        let sum_1_to_supply = supply.checked_mul(supply.checked_add(1).ok_or(ErrorCode::MathOverflow)?).ok_or(ErrorCode::MathOverflow)?.checked_div(2).ok_or(ErrorCode::MathOverflow)?;
-        let sum_1_to_supply_plus_amount = (supply.checked_add(amount).ok_or(ErrorCode::MathOverflow)?)
-            .checked_mul((supply.checked_add(amount).ok_or(ErrorCode::MathOverflow)?).checked_add(1).ok_or(ErrorCode::MathOverflow)?)
-            .ok_or(ErrorCode::MathOverflow)?
-            .checked_div(2)
-            .ok_or(ErrorCode::MathOverflow)?;
-
-        let sum_of_prices = sum_1_to_supply_plus_amount.checked_sub(sum_1_to_supply).ok_or(ErrorCode::MathOverflow)?;
-        
-        sum_of_prices.checked_mul(coefficient).ok_or(ErrorCode::MathOverflow)?.checked_div(16000).ok_or(ErrorCode::MathOverflow)
-    }
-
-    pub fn get_buy_price(supply: u64, amount: u64, coefficient: u64) -> Result<u64> {
-        get_price(supply, amount, coefficient)
-    }
-
-    pub fn get_sell_price(supply: u64, amount: u64, coefficient: u64) -> Result<u64> {
-        if supply < amount {
-            return Err(ErrorCode::InsufficientSupply.into());
-        }
-        get_price(supply.checked_sub(amount).ok_or(ErrorCode::MathOverflow)?, amount, coefficient)
-    }
-
-    pub fn get_buy_price_after_fee(supply: u64, amount: u64, coefficient: u64, protocol_fee_percent: u16, subject_fee_percent: u16) -> Result<u64> {
-        let price = get_buy_price(supply, amount, coefficient)?;
-        let protocol_fee = price.checked_mul(protocol_fee_percent as u64).ok_or(ErrorCode::MathOverflow)?.checked_div(10000).ok_or(ErrorCode::MathOverflow)?;
-        let subject_fee = price.checked_mul(subject_fee_percent as u64).ok_or(ErrorCode::MathOverflow)?.checked_div(10000).ok_or(ErrorCode::MathOverflow)?;
-        price.checked_add(protocol_fee).ok_or(ErrorCode::MathOverflow)?.checked_add(subject_fee).ok_or(ErrorCode::MathOverflow)
-    }
-
-    pub fn get_sell_price_after_fee(supply: u64, amount: u64, coefficient: u64, protocol_fee_percent: u16, subject_fee_percent: u16) -> Result<u64> {
-        let price = get_sell_price(supply, amount, coefficient)?;
-        let protocol_fee = price.checked_mul(protocol_fee_percent as u64).ok_or(ErrorCode::MathOverflow)?.checked_div(10000).ok_or(ErrorCode::MathOverflow)?;
-        let subject_fee = price.checked_mul(subject_fee_percent as u64).ok_or(ErrorCode::MathOverflow)?.checked_div(10000).ok_or(ErrorCode::MathOverflow)?;
-        price.checked_sub(protocol_fee).ok_or(ErrorCode::MathOverflow)?.checked_sub(subject_fee).ok_or(ErrorCode::MathOverflow)
-    }
-}
-
 #[error_code]
 pub enum ErrorCode {
     #[msg("Math overflow")]
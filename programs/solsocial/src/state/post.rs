@@ -1,6 +1,8 @@
 use anchor_lang::prelude::*;
 use crate::constants::*;
 use crate::errors::SolSocialError;
+use crate::utils::bonding_curve::BondingCurve;
+use crate::state::FollowRelation;
 
 #[account]
 pub struct Post {
@@ -11,11 +13,25 @@ pub struct Post {
     pub timestamp: i64,
     pub likes: u64,
     pub reposts: u64,
+    pub counted_reposts: u64, // reposts that contribute to engagement_score
     pub replies: u64,
     pub token_weight: u64,
     pub engagement_score: u64,
+    pub peak_engagement_score: u64,
+    pub feed_score: u64,
+    pub author_reputation_awarded: u64,
     pub is_premium: bool,
     pub required_keys: u64,
+    pub premium_access_value: u64, // lamports of keys needed to view, 0 = use required_keys
+    pub tip_goal: u64,
+    pub downgrade_after_seconds: i64, // gated posts open up once this old, 0 = never
+    pub downgrade_engagement_threshold: u64, // ...unless engagement has reached this
+    pub is_pinned: bool,
+    pub report_count: u32, // reports since the post was created or last restored
+    pub boost_weight: u64, // summed key-holder weight of current likes
+    pub is_curated: bool, // promoted to the curated feed; stays set once reached
+    pub single_reply_per_user: bool, // each user may reply at most once
+    pub max_replies: u64, // replies lock once this many are reached, 0 = unlimited
     pub reply_to: Option<u64>,
     pub media_urls: Vec<String>,
     pub tags: Vec<String>,
@@ -34,11 +50,25 @@ impl Post {
         8 + // timestamp
         8 + // likes
         8 + // reposts
+        8 + // counted_reposts
         8 + // replies
         8 + // token_weight
         8 + // engagement_score
+        8 + // peak_engagement_score
+        8 + // feed_score
+        8 + // author_reputation_awarded
         1 + // is_premium
         8 + // required_keys
+        8 + // premium_access_value
+        8 + // tip_goal
+        8 + // downgrade_after_seconds
+        8 + // downgrade_engagement_threshold
+        1 + // is_pinned
+        4 + // report_count
+        8 + // boost_weight
+        1 + // is_curated
+        1 + // single_reply_per_user
+        8 + // max_replies
         1 + 8 + // reply_to (Option<u64>)
         4 + (MAX_MEDIA_URLS * (4 + MAX_URL_LENGTH)) + // media_urls
         4 + (MAX_TAGS * (4 + MAX_TAG_LENGTH)) + // tags
@@ -47,6 +77,10 @@ impl Post {
         1 + // status
         1; // bump
 
+    /// Longest content any reputation tier can unlock, keeping `init` well inside
+    /// the 10KB an account can be created with alongside media and tags
+    pub const MAX_TIERED_CONTENT_LENGTH: usize = 5000;
+
     pub fn initialize(
         &mut self,
         id: u64,
@@ -83,11 +117,25 @@ impl Post {
         self.timestamp = timestamp;
         self.likes = 0;
         self.reposts = 0;
+        self.counted_reposts = 0;
         self.replies = 0;
         self.token_weight = 0;
         self.engagement_score = 0;
+        self.peak_engagement_score = 0;
+        self.feed_score = 0;
+        self.author_reputation_awarded = 0;
         self.is_premium = is_premium;
         self.required_keys = required_keys;
+        self.premium_access_value = 0;
+        self.tip_goal = 0;
+        self.downgrade_after_seconds = 0;
+        self.downgrade_engagement_threshold = 0;
+        self.is_pinned = false;
+        self.report_count = 0;
+        self.boost_weight = 0;
+        self.is_curated = false;
+        self.single_reply_per_user = false;
+        self.max_replies = 0;
         self.reply_to = reply_to;
         self.media_urls = media_urls;
         self.tags = tags;
@@ -99,69 +147,102 @@ impl Post {
         Ok(())
     }
 
-    pub fn like(&mut self) -> Result<()> {
+    pub fn like(&mut self, max_engagement_score: u64) -> Result<()> {
         require!(self.status == PostStatus::Active, SolSocialError::PostNotActive);
         
         self.likes = self.likes.checked_add(1).ok_or(SolSocialError::MathOverflow)?;
-        self.update_engagement_score()?;
+        self.update_engagement_score(max_engagement_score)?;
         
         Ok(())
     }
 
-    pub fn unlike(&mut self) -> Result<()> {
+    pub fn unlike(&mut self, max_engagement_score: u64) -> Result<()> {
         require!(self.status == PostStatus::Active, SolSocialError::PostNotActive);
         require!(self.likes > 0, SolSocialError::CannotUnlikeZeroLikes);
         
         self.likes = self.likes.checked_sub(1).ok_or(SolSocialError::MathUnderflow)?;
-        self.update_engagement_score()?;
+        self.update_engagement_score(max_engagement_score)?;
         
         Ok(())
     }
 
-    pub fn repost(&mut self) -> Result<()> {
+    /// Reposts past `max_counted_reposts` (0 = no cap) still count but no longer boost engagement
+    pub fn repost(&mut self, max_counted_reposts: u64, max_engagement_score: u64) -> Result<()> {
         require!(self.status == PostStatus::Active, SolSocialError::PostNotActive);
         
         self.reposts = self.reposts.checked_add(1).ok_or(SolSocialError::MathOverflow)?;
-        self.update_engagement_score()?;
+        if max_counted_reposts == 0 || self.counted_reposts < max_counted_reposts {
+            self.counted_reposts = self.counted_reposts.checked_add(1).ok_or(SolSocialError::MathOverflow)?;
+        }
+        self.update_engagement_score(max_engagement_score)?;
         
         Ok(())
     }
 
-    pub fn add_reply(&mut self) -> Result<()> {
+    /// Counts a new reply, refusing it once the thread has reached `max_replies`.
+    /// Likes and reposts are unaffected by the lock.
+    pub fn add_reply(&mut self, max_engagement_score: u64) -> Result<()> {
         require!(self.status == PostStatus::Active, SolSocialError::PostNotActive);
+        require!(
+            self.max_replies == 0 || self.replies < self.max_replies,
+            SolSocialError::ResourceNotAvailable
+        );
         
         self.replies = self.replies.checked_add(1).ok_or(SolSocialError::MathOverflow)?;
-        self.update_engagement_score()?;
+        self.update_engagement_score(max_engagement_score)?;
         
         Ok(())
     }
 
-    pub fn update_token_weight(&mut self, weight: u64) -> Result<()> {
+    /// Adds a holder's like weight, curating the post the first time the total reaches
+    /// `curation_threshold` (0 = curation off). Returns true only on that first crossing.
+    pub fn add_boost(&mut self, weight: u64, curation_threshold: u64) -> bool {
+        self.boost_weight = self.boost_weight.saturating_add(weight);
+
+        if curation_threshold > 0 && !self.is_curated && self.boost_weight >= curation_threshold {
+            self.is_curated = true;
+            return true;
+        }
+        false
+    }
+
+    pub fn remove_boost(&mut self, weight: u64) {
+        self.boost_weight = self.boost_weight.saturating_sub(weight);
+    }
+
+    pub fn update_token_weight(&mut self, weight: u64, max_engagement_score: u64) -> Result<()> {
         self.token_weight = weight;
-        self.update_engagement_score()?;
+        self.update_engagement_score(max_engagement_score)?;
         
         Ok(())
     }
 
-    pub fn update_engagement_score(&mut self) -> Result<()> {
-        let like_weight = self.likes.checked_mul(LIKE_WEIGHT).ok_or(SolSocialError::MathOverflow)?;
-        let repost_weight = self.reposts.checked_mul(REPOST_WEIGHT).ok_or(SolSocialError::MathOverflow)?;
-        let reply_weight = self.replies.checked_mul(REPLY_WEIGHT).ok_or(SolSocialError::MathOverflow)?;
-        let token_weight = self.token_weight.checked_mul(TOKEN_WEIGHT).ok_or(SolSocialError::MathOverflow)?;
+    /// Saturates at `max_engagement_score` (0 = u64::MAX) so viral posts stay interactable
+    pub fn update_engagement_score(&mut self, max_engagement_score: u64) -> Result<()> {
+        let like_weight = self.likes.saturating_mul(LIKE_WEIGHT);
+        let repost_weight = self.counted_reposts.saturating_mul(REPOST_WEIGHT);
+        let reply_weight = self.replies.saturating_mul(REPLY_WEIGHT);
+        let token_weight = self.token_weight.saturating_mul(TOKEN_WEIGHT);
         
         let total_engagement = like_weight
-            .checked_add(repost_weight).ok_or(SolSocialError::MathOverflow)?
-            .checked_add(reply_weight).ok_or(SolSocialError::MathOverflow)?
-            .checked_add(token_weight).ok_or(SolSocialError::MathOverflow)?;
+            .saturating_add(repost_weight)
+            .saturating_add(reply_weight)
+            .saturating_add(token_weight);
         
         let time_decay = self.calculate_time_decay()?;
-        self.engagement_score = total_engagement
-            .checked_mul(time_decay).ok_or(SolSocialError::MathOverflow)?
-            .checked_div(100).ok_or(SolSocialError::MathDivisionByZero)?;
+        // time_decay is at most 100, so the decayed score always fits back into a u64
+        self.engagement_score = (total_engagement as u128 * time_decay as u128 / 100) as u64;
+        self.cap_engagement_score(max_engagement_score);
         
         Ok(())
     }
 
+    pub fn cap_engagement_score(&mut self, max_engagement_score: u64) {
+        if max_engagement_score > 0 {
+            self.engagement_score = self.engagement_score.min(max_engagement_score);
+        }
+    }
+
     pub fn calculate_time_decay(&self) -> Result<u64> {
         let current_time = Clock::get()?.unix_timestamp;
         let age_hours = (current_time - self.timestamp) / 3600;
@@ -183,15 +264,58 @@ impl Post {
         Ok(decay_factor)
     }
 
-    pub fn can_view(&self, viewer: &Pubkey, viewer_keys: u64) -> bool {
+    /// Keys a viewer needs right now: the fixed `required_keys`, or enough keys at the
+    /// author's live price to cover `premium_access_value`
+    pub fn effective_required_keys(&self, author_key_supply: u64) -> Result<u64> {
+        if self.premium_access_value == 0 {
+            return Ok(self.required_keys);
+        }
+
+        BondingCurve::standard().keys_for_value(author_key_supply, self.premium_access_value)
+    }
+
+    /// `follow` is the viewer's follow of the author, if they have one; `is_subscribed` is
+    /// whether they hold an active subscription to the author
+    pub fn can_view(
+        &self,
+        viewer: &Pubkey,
+        viewer_keys: u64,
+        required_keys: u64,
+        follow: Option<&FollowRelation>,
+        is_subscribed: bool,
+    ) -> bool {
+        // Archived posts are kept for their author but hidden from everyone else
+        if self.status == PostStatus::Archived {
+            return self.author == *viewer;
+        }
+
+        // Subscribers see premium posts whatever their key balance
+        if self.is_premium && is_subscribed {
+            return true;
+        }
+
         match self.visibility {
             PostVisibility::Public => true,
-            PostVisibility::KeyHolders => viewer_keys >= self.required_keys,
+            PostVisibility::KeyHolders => viewer_keys >= required_keys,
             PostVisibility::Private => self.author == *viewer,
-            PostVisibility::Followers => false, // Would need follower check
+            PostVisibility::Followers => {
+                self.author == *viewer
+                    || follow.map_or(false, |follow| follow.is_following(viewer, &self.author))
+            }
         }
     }
 
+    /// A gated post with auto-downgrade enabled that is old enough and still below its
+    /// engagement threshold
+    pub fn should_downgrade(&self, now: i64) -> bool {
+        let is_gated = self.is_premium || self.visibility == PostVisibility::KeyHolders;
+
+        is_gated
+            && self.downgrade_after_seconds > 0
+            && now.saturating_sub(self.timestamp) >= self.downgrade_after_seconds
+            && self.engagement_score < self.downgrade_engagement_threshold
+    }
+
     pub fn moderate(&mut self, status: PostStatus) -> Result<()> {
         require!(
             status == PostStatus::Hidden || status == PostStatus::Removed,
@@ -208,10 +332,54 @@ impl Post {
             SolSocialError::PostNotModerated
         );
         
+        self.status = PostStatus::Active;
+        self.report_count = 0;
+        Ok(())
+    }
+
+    /// Unlike moderation, archiving is the author's own choice and can be undone by them
+    pub fn archive(&mut self) -> Result<()> {
+        require!(self.status == PostStatus::Active, SolSocialError::PostNotActive);
+
+        self.status = PostStatus::Archived;
+        Ok(())
+    }
+
+    pub fn unarchive(&mut self) -> Result<()> {
+        require!(self.status == PostStatus::Archived, SolSocialError::InvalidResourceState);
+
         self.status = PostStatus::Active;
         Ok(())
     }
 
+    /// Counts a report, hiding the post once `hide_threshold` is reached (0 = never).
+    /// Returns true when this report hid the post.
+    pub fn record_report(&mut self, hide_threshold: u32) -> Result<bool> {
+        require!(self.status == PostStatus::Active, SolSocialError::PostNotActive);
+
+        self.report_count = self.report_count
+            .checked_add(1)
+            .ok_or(SolSocialError::ArithmeticOverflow)?;
+
+        if hide_threshold > 0 && self.report_count >= hide_threshold {
+            self.moderate(PostStatus::Hidden)?;
+            return Ok(true);
+        }
+        Ok(false)
+    }
+
+    pub fn set_tip_goal(&mut self, tip_goal: u64) -> Result<()> {
+        require!(self.status == PostStatus::Active, SolSocialError::PostNotActive);
+
+        self.tip_goal = tip_goal;
+        Ok(())
+    }
+
+    /// True when a tip moves cumulative tips from below the goal to at or above it
+    pub fn crosses_tip_goal(&self, tips_before: u64, tips_after: u64) -> bool {
+        self.tip_goal > 0 && tips_before < self.tip_goal && tips_after >= self.tip_goal
+    }
+
     pub fn is_reply(&self) -> bool {
         self.reply_to.is_some()
     }
@@ -234,13 +402,17 @@ impl Post {
     }
 
     pub fn is_trending(&self) -> Result<bool> {
+        if self.status == PostStatus::Archived {
+            return Ok(false);
+        }
+
         let age_hours = self.get_age_hours()?;
         Ok(age_hours <= 24 && self.engagement_score >= TRENDING_THRESHOLD)
     }
 
     pub fn calculate_virality_score(&self) -> Result<u64> {
         let age_hours = self.get_age_hours()?;
-        if age_hours <= 0 {
+        if age_hours <= 0 || self.status == PostStatus::Archived {
             return Ok(0);
         }
         
@@ -271,6 +443,7 @@ pub enum PostStatus {
     Hidden,
     Removed,
     Archived,
+    Scheduled, // goes live once publish_scheduled_post runs at or after its timestamp
 }
 
 #[account]
@@ -312,6 +485,44 @@ impl PostInteraction {
     }
 }
 
+/// One per reporter per post, so a single account can't push a post over the hide threshold
+#[account]
+pub struct PostReport {
+    pub reporter: Pubkey,
+    pub post: Pubkey,
+    pub reason_code: u8,
+    pub timestamp: i64,
+    pub bump: u8,
+}
+
+impl PostReport {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // reporter
+        32 + // post
+        1 + // reason_code
+        8 + // timestamp
+        1; // bump
+}
+
+/// Marks that a user has replied to a single-reply post
+#[account]
+pub struct ReplyReceipt {
+    pub parent: Pubkey,
+    pub user: Pubkey,
+    pub reply: Pubkey,
+    pub timestamp: i64,
+    pub bump: u8,
+}
+
+impl ReplyReceipt {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // parent
+        32 + // user
+        32 + // reply
+        8 + // timestamp
+        1; // bump
+}
+
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
 pub enum InteractionType {
     Like,
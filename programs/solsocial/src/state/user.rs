@@ -1,5 +1,8 @@
 use anchor_lang::prelude::*;
 use std::collections::BTreeMap;
+use crate::errors::SolSocialError;
+use crate::utils::bonding_curve::BondingCurve;
+use crate::utils::reputation::calculate_influence_score;
 
 #[account]
 #[derive(Default)]
@@ -15,14 +18,25 @@ pub struct User {
     pub follower_count: u64,
     pub following_count: u64,
     pub post_count: u64,
+    pub total_likes_given: u64, // core engagement counters, kept whether or not analytics are on
+    pub total_likes_received: u64,
+    pub total_comments_made: u64,
+    pub total_comments_received: u64,
+    pub total_shares_made: u64,
+    pub total_shares_received: u64,
     pub key_supply: u64,
     pub key_price: u64,
     pub total_volume: u64,
     pub reputation_score: u64,
     pub influence_score: u64,
+    pub influence_decayed_at: i64,
+    pub last_interaction_at: i64,
     pub verified: bool,
     pub premium: bool,
     pub banned: bool,
+    pub ban_reason: String, // why the current ban was issued, empty when not banned
+    pub banned_at: i64,
+    pub banned_by: Pubkey,
     pub key_holders: Vec<KeyHolder>,
     pub social_stats: SocialStats,
     pub revenue_stats: RevenueStats,
@@ -85,6 +99,13 @@ pub struct Badge {
     pub rarity: u8,
 }
 
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum SocialStatType {
+    Like,
+    Comment,
+    Share,
+}
+
 impl User {
     pub const LEN: usize = 8 + // discriminator
         32 + // authority
@@ -98,14 +119,25 @@ impl User {
         8 + // follower_count
         8 + // following_count
         8 + // post_count
+        8 + // total_likes_given
+        8 + // total_likes_received
+        8 + // total_comments_made
+        8 + // total_comments_received
+        8 + // total_shares_made
+        8 + // total_shares_received
         8 + // key_supply
         8 + // key_price
         8 + // total_volume
         8 + // reputation_score
         8 + // influence_score
+        8 + // influence_decayed_at
+        8 + // last_interaction_at
         1 + // verified
         1 + // premium
         1 + // banned
+        4 + Self::MAX_BAN_REASON_LENGTH + // ban_reason
+        8 + // banned_at
+        32 + // banned_by
         4 + (1000 * (32 + 8 + 8 + 8)) + // key_holders (max 1000)
         (8 * 8) + // social_stats
         (8 * 8) + // revenue_stats
@@ -114,6 +146,8 @@ impl User {
         1 + // bump
         100; // padding
 
+    pub const MAX_BAN_REASON_LENGTH: usize = 200;
+
     pub fn initialize(
         &mut self,
         authority: Pubkey,
@@ -138,14 +172,25 @@ impl User {
         self.follower_count = 0;
         self.following_count = 0;
         self.post_count = 0;
+        self.total_likes_given = 0;
+        self.total_likes_received = 0;
+        self.total_comments_made = 0;
+        self.total_comments_received = 0;
+        self.total_shares_made = 0;
+        self.total_shares_received = 0;
         self.key_supply = 0;
         self.key_price = 1_000_000; // 0.001 SOL initial price
         self.total_volume = 0;
         self.reputation_score = 100;
         self.influence_score = 0;
+        self.influence_decayed_at = clock.unix_timestamp;
+        self.last_interaction_at = 0;
         self.verified = false;
         self.premium = false;
         self.banned = false;
+        self.ban_reason = String::new();
+        self.banned_at = 0;
+        self.banned_by = Pubkey::default();
         self.key_holders = Vec::new();
         self.social_stats = SocialStats::default();
         self.revenue_stats = RevenueStats::default();
@@ -217,6 +262,7 @@ impl User {
         self.key_supply = self.key_supply.checked_add(amount)
             .ok_or(SolSocialError::MathOverflow)?;
         
+        self.calculate_influence_score()?;
         Ok(())
     }
 
@@ -240,35 +286,21 @@ impl User {
             return Err(SolSocialError::KeyHolderNotFound.into());
         }
         
+        self.calculate_influence_score()?;
         Ok(())
     }
 
     pub fn calculate_key_price(&self, supply: u64, amount: u64, is_buy: bool) -> Result<u64> {
-        if supply == 0 && amount == 0 {
+        if amount == 0 {
             return Ok(0);
         }
 
-        let sum1 = if supply == 0 { 0 } else { Self::get_price_sum(supply) };
-        let sum2 = Self::get_price_sum(if is_buy { 
-            supply.checked_add(amount).ok_or(SolSocialError::MathOverflow)? 
-        } else { 
-            supply.checked_sub(amount).ok_or(SolSocialError::MathOverflow)? 
-        });
-
-        let price_diff = if sum2 > sum1 { sum2 - sum1 } else { sum1 - sum2 };
-        Ok(price_diff)
-    }
-
-    fn get_price_sum(supply: u64) -> u64 {
-        if supply == 0 {
-            return 0;
+        let curve = BondingCurve::standard();
+        if is_buy {
+            curve.get_buy_price(supply, amount)
+        } else {
+            curve.get_sell_price(supply, amount)
         }
-        
-        // Bonding curve: price = supply^2 / 16000 + supply / 1000
-        let supply_squared = supply.saturating_mul(supply);
-        let term1 = supply_squared / 16000;
-        let term2 = supply / 1000;
-        term1.saturating_add(term2)
     }
 
     pub fn update_social_stats(&mut self, stat_type: SocialStatType, amount: u64, is_given: bool) -> Result<()> {
@@ -344,6 +376,57 @@ impl User {
         Ok(())
     }
 
+    pub fn ban(&mut self, reason: String, banned_by: Pubkey, timestamp: i64) -> Result<()> {
+        require!(!self.banned, SolSocialError::InvalidResourceState);
+        require!(
+            !reason.is_empty() && reason.len() <= Self::MAX_BAN_REASON_LENGTH,
+            SolSocialError::InvalidBanReason
+        );
+
+        self.banned = true;
+        self.ban_reason = reason;
+        self.banned_at = timestamp;
+        self.banned_by = banned_by;
+        Ok(())
+    }
+
+    /// `banned_at` and `banned_by` stay behind as the record of the last ban
+    pub fn unban(&mut self) -> Result<()> {
+        require!(self.banned, SolSocialError::InvalidResourceState);
+
+        self.banned = false;
+        self.ban_reason = String::new();
+        Ok(())
+    }
+
+    pub fn is_in_good_standing(&self, reputation_floor: u64) -> bool {
+        !self.banned && self.reputation_score >= reputation_floor
+    }
+
+    /// When the user last did something: their last interaction, or joining if they never have
+    pub fn last_active_at(&self) -> i64 {
+        self.last_interaction_at.max(self.created_at)
+    }
+
+    pub fn total_engagement_received(&self) -> u64 {
+        self.social_stats.total_likes_received
+            .saturating_add(self.social_stats.total_comments_received)
+            .saturating_add(self.social_stats.total_shares_received)
+    }
+
+    /// SHA-256 over the little-endian key stats, so off-chain verifiers can recompute it from account data
+    pub fn key_stats_hash(&self) -> [u8; 32] {
+        let holder_count = self.key_holders.len() as u64;
+        anchor_lang::solana_program::hash::hashv(&[
+            self.authority.as_ref(),
+            &self.key_supply.to_le_bytes(),
+            &self.key_price.to_le_bytes(),
+            &self.total_volume.to_le_bytes(),
+            &holder_count.to_le_bytes(),
+        ])
+        .to_bytes()
+    }
+
     pub fn increment_follower_count(&mut self) -> Result<()> {
         self.follower_count = self.follower_count.checked_add(1)
             .ok_or(SolSocialError::MathOverflow)?;
@@ -354,4 +437,29 @@ impl User {
     pub fn decrement_follower_count(&mut self) -> Result<()> {
         self.follower_count = self.follower_count.checked_sub(1)
             .ok_or(SolSocialError::MathOverflow)?;
-        self.calculate_influence
\ No newline at end of file
+        self.calculate_influence_score()?;
+        Ok(())
+    }
+
+    /// Average engagement received per post
+    pub fn calculate_engagement_rate(&mut self) -> Result<()> {
+        self.social_stats.engagement_rate = self.total_engagement_received()
+            .checked_div(self.post_count)
+            .unwrap_or(0);
+        Ok(())
+    }
+
+    /// Recomputes `influence_score` from the user's current stats; every mutator of an
+    /// input calls this, and `recompute_influence` repairs accounts written before it existed
+    pub fn calculate_influence_score(&mut self) -> Result<()> {
+        self.influence_score = calculate_influence_score(
+            self.follower_count,
+            self.total_likes_received,
+            self.total_comments_received,
+            self.total_shares_received,
+            self.key_holders.len() as u64,
+            self.reputation_score,
+        );
+        Ok(())
+    }
+}
@@ -40,6 +40,8 @@ pub enum ErrorCode {
     InvalidCurveParameters,
 }
 
+/// The single pricing curve for keys. Instructions and state methods must quote
+/// through this type so buys and sells at the same supply always agree.
 pub struct BondingCurve {
     pub base_price: u64,
     pub slope: u64,
@@ -97,6 +99,17 @@ impl BondingCurve {
         })
     }
 
+    /// Curve with the platform defaults, used wherever a market has no custom parameters
+    pub fn standard() -> Self {
+        Self {
+            base_price: Self::DEFAULT_BASE_PRICE,
+            slope: Self::DEFAULT_SLOPE,
+            max_supply: Self::DEFAULT_MAX_SUPPLY,
+            creator_fee_bps: Self::DEFAULT_CREATOR_FEE_BPS,
+            protocol_fee_bps: Self::DEFAULT_PROTOCOL_FEE_BPS,
+        }
+    }
+
     pub fn get_price(&self, supply: u64) -> Result<u64> {
         require!(supply <= self.max_supply, BondingCurveError::InvalidSupply);
 
@@ -280,6 +293,17 @@ impl BondingCurve {
         let total_value = self.get_sell_price(supply, supply)?;
         Ok(total_value)
     }
+
+    /// Keys needed at the spot price for `supply` to be worth at least `value`, rounded up
+    pub fn keys_for_value(&self, supply: u64, value: u64) -> Result<u64> {
+        let price = self.get_price(supply)?;
+        require!(price > 0, BondingCurveError::PriceCalculationFailed);
+
+        Ok(value
+            .checked_add(price - 1)
+            .ok_or(BondingCurveError::Overflow)?
+            / price)
+    }
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -298,6 +322,16 @@ pub struct SellPriceBreakdown {
     pub net_price: u64,
 }
 
+/// What a trade actually charges or pays out. Trades and their quote instructions
+/// both build this through `quote_buy`/`quote_sell` so previews match execution.
+#[derive(Debug, Clone, Copy)]
+pub struct TradeQuote {
+    pub base_price: u64,
+    pub protocol_fee: u64,
+    pub subject_fee: u64,
+    pub total: u64,
+}
+
 #[derive(Debug, Clone, Copy)]
 pub struct CurveStats {
     pub current_price: u64,
@@ -308,6 +342,116 @@ pub struct CurveStats {
 }
 
 impl BondingCurve {
+    fn fee_for(base_price: u64, fee_bps: u64) -> Result<u64> {
+        Ok(base_price
+            .checked_mul(fee_bps)
+            .ok_or(BondingCurveError::Overflow)?
+            .checked_div(10000)
+            .ok_or(BondingCurveError::PriceCalculationFailed)?)
+    }
+
+    /// Buyer pays the curve price plus both fees
+    pub fn quote_buy(
+        &self,
+        supply: u64,
+        amount: u64,
+        protocol_fee_bps: u64,
+        subject_fee_bps: u64,
+    ) -> Result<TradeQuote> {
+        let base_price = self.get_buy_price(supply, amount)?;
+        let protocol_fee = Self::fee_for(base_price, protocol_fee_bps)?;
+        let subject_fee = Self::fee_for(base_price, subject_fee_bps)?;
+
+        let total = base_price
+            .checked_add(protocol_fee)
+            .ok_or(BondingCurveError::Overflow)?
+            .checked_add(subject_fee)
+            .ok_or(BondingCurveError::Overflow)?;
+
+        Ok(TradeQuote { base_price, protocol_fee, subject_fee, total })
+    }
+
+    /// Seller receives the curve price less both fees
+    pub fn quote_sell(
+        &self,
+        supply: u64,
+        amount: u64,
+        protocol_fee_bps: u64,
+        subject_fee_bps: u64,
+    ) -> Result<TradeQuote> {
+        let base_price = self.get_sell_price(supply, amount)?;
+        let protocol_fee = Self::fee_for(base_price, protocol_fee_bps)?;
+        let subject_fee = Self::fee_for(base_price, subject_fee_bps)?;
+
+        let total = base_price
+            .checked_sub(protocol_fee)
+            .ok_or(BondingCurveError::InsufficientFunds)?
+            .checked_sub(subject_fee)
+            .ok_or(BondingCurveError::InsufficientFunds)?;
+
+        Ok(TradeQuote { base_price, protocol_fee, subject_fee, total })
+    }
+
+    /// Whether `base_price` for `amount` keys stays within `max_slippage_bps` of what the
+    /// same trade cost at `reference_supply`: above it for buys, below it for sells
+    pub fn is_within_slippage(
+        &self,
+        reference_supply: u64,
+        amount: u64,
+        base_price: u64,
+        max_slippage_bps: u16,
+        is_buy: bool,
+    ) -> Result<bool> {
+        let reference = (if is_buy {
+            self.get_buy_price(reference_supply, amount)?
+        } else {
+            self.get_sell_price(reference_supply, amount)?
+        }) as u128;
+
+        let bound_bps = if is_buy {
+            10000u128 + max_slippage_bps as u128
+        } else {
+            10000u128.saturating_sub(max_slippage_bps as u128)
+        };
+
+        let bound = reference
+            .checked_mul(bound_bps)
+            .ok_or(BondingCurveError::Overflow)?
+            / 10000;
+
+        if is_buy {
+            Ok(base_price as u128 <= bound)
+        } else {
+            Ok(base_price as u128 >= bound)
+        }
+    }
+
+    /// How far a trade of `amount` keys at `supply` moves the spot price, in bps of
+    /// the pre-trade spot. Always positive; `is_buy` only picks the direction.
+    pub fn price_impact_bps(&self, supply: u64, amount: u64, is_buy: bool) -> Result<u64> {
+        let new_supply = if is_buy {
+            supply.checked_add(amount).ok_or(BondingCurveError::Overflow)?
+        } else {
+            supply.checked_sub(amount).ok_or(BondingCurveError::InvalidAmount)?
+        };
+
+        let price_before = self.get_price(supply)? as u128;
+        let price_after = self.get_price(new_supply)? as u128;
+        let moved = if price_after > price_before {
+            price_after - price_before
+        } else {
+            price_before - price_after
+        };
+
+        let impact_bps = moved
+            .checked_mul(10000)
+            .ok_or(BondingCurveError::Overflow)?
+            .checked_div(price_before)
+            .ok_or(BondingCurveError::PriceCalculationFailed)?;
+
+        Ok(u64::try_from(impact_bps).map_err(|_| BondingCurveError::Overflow)?)
+    }
+
     pub fn get_curve_stats(&self, supply: u64) -> Result<CurveStats> {
         let current_price = self.get_price(supply)?;
         let market_cap = self.get_market_cap(supply)?;
@@ -379,4 +523,51 @@ mod tests {
             breakdown.total_price,
             breakdown.base_price + breakdown.creator_fee + breakdown.protocol_fee
         );
-    }
\ No newline at end of file
+    }
+
+    #[test]
+    fn test_round_trip_never_refunds_more_than_paid() {
+        let curve = BondingCurve::standard();
+
+        for &(supply, amount) in &[(0u64, 1u64), (0, 10), (1, 1), (999, 7), (1_000, 100), (50_000, 3)] {
+            let paid = curve.get_buy_price_after_fees(supply, amount).unwrap().total_price;
+            let refunded = curve.get_sell_price_after_fees(supply + amount, amount).unwrap().net_price;
+
+            assert!(refunded <= paid, "supply {} amount {}: refund {} > paid {}", supply, amount, refunded, paid);
+        }
+    }
+
+    #[test]
+    fn test_keys_for_value_tracks_price() {
+        let curve = BondingCurve::standard();
+        let value = curve.get_price(0).unwrap() * 10;
+
+        assert_eq!(curve.keys_for_value(0, value).unwrap(), 10);
+        assert_eq!(curve.keys_for_value(0, value + 1).unwrap(), 11);
+        assert_eq!(curve.keys_for_value(0, 0).unwrap(), 0);
+
+        // At 250k supply a key costs twice as much, so half as many cover the same value
+        assert_eq!(curve.get_price(250_000).unwrap(), 2 * curve.get_price(0).unwrap());
+        assert_eq!(curve.keys_for_value(250_000, value).unwrap(), 5);
+    }
+
+    #[test]
+    fn test_price_impact_grows_with_trade_size() {
+        let curve = BondingCurve::standard();
+
+        let small = curve.price_impact_bps(1_000, 10, true).unwrap();
+        let large = curve.price_impact_bps(1_000, 10_000, true).unwrap();
+        assert!(large > small);
+
+        // Selling back what was bought returns to the same spot, so the move is the same size
+        let spot_before = curve.get_price(1_000).unwrap() as u128;
+        let spot_after = curve.get_price(11_000).unwrap() as u128;
+        assert_eq!(large as u128, (spot_after - spot_before) * 10000 / spot_before);
+        assert_eq!(
+            curve.price_impact_bps(11_000, 10_000, false).unwrap() as u128,
+            (spot_after - spot_before) * 10000 / spot_after
+        );
+
+        assert!(curve.price_impact_bps(5, 6, false).is_err());
+    }
+}
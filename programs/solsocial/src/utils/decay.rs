@@ -0,0 +1,159 @@
+use anchor_lang::prelude::*;
+use crate::errors::*;
+
+pub const SECONDS_PER_DAY: i64 = 86_400;
+pub const DECAY_BASIS_POINTS: u64 = 10_000;
+pub const MAX_DECAY_PERIODS: u64 = 365; // Anything dormant for a year is fully decayed in practice
+
+/// Whole days elapsed between `since` and `now`, zero if `now` is not after `since`
+pub fn elapsed_days(since: i64, now: i64) -> u64 {
+    if now <= since {
+        return 0;
+    }
+
+    ((now - since) / SECONDS_PER_DAY) as u64
+}
+
+/// Whether both timestamps fall on the same UTC calendar day
+pub fn is_same_utc_day(a: i64, b: i64) -> bool {
+    a.div_euclid(SECONDS_PER_DAY) == b.div_euclid(SECONDS_PER_DAY)
+}
+
+/// Compounds `rate_bps` of decay per period onto `value`
+pub fn apply_decay(value: u64, rate_bps: u16, periods: u64) -> Result<u64> {
+    require!(
+        rate_bps as u64 <= DECAY_BASIS_POINTS,
+        SolSocialError::InvalidPercentage
+    );
+
+    let retained_bps = DECAY_BASIS_POINTS - rate_bps as u64;
+    let mut decayed = value;
+
+    for _ in 0..periods.min(MAX_DECAY_PERIODS) {
+        if decayed == 0 {
+            break;
+        }
+
+        decayed = (decayed as u128)
+            .checked_mul(retained_bps as u128)
+            .ok_or(SolSocialError::ArithmeticOverflow)?
+            .checked_div(DECAY_BASIS_POINTS as u128)
+            .ok_or(SolSocialError::DivisionByZero)? as u64;
+    }
+
+    Ok(decayed)
+}
+
+/// One `rate_bps` decay step once a full day has passed since `last_update`, or `None`
+/// if it's too soon, so repeated calls within the day are no-ops
+pub fn daily_decay(value: u64, rate_bps: u16, last_update: i64, now: i64) -> Result<Option<u64>> {
+    if elapsed_days(last_update, now) == 0 {
+        return Ok(None);
+    }
+
+    apply_decay(value, rate_bps, 1).map(Some)
+}
+
+/// Decays `value` for the whole days since `last_active_at` that `decayed_through` doesn't
+/// already cover. Returns the new value and the time decay now runs through, which keeps the
+/// partial day for the next call, or `None` if less than a day is outstanding
+pub fn decay_for_inactivity(
+    value: u64,
+    rate_bps: u16,
+    last_active_at: i64,
+    decayed_through: i64,
+    now: i64,
+) -> Result<Option<(u64, i64)>> {
+    let since = std::cmp::max(last_active_at, decayed_through);
+    let days_inactive = elapsed_days(since, now);
+    if days_inactive == 0 {
+        return Ok(None);
+    }
+
+    let decayed = apply_decay(value, rate_bps, days_inactive)?;
+    let decayed_through = since
+        .checked_add(days_inactive as i64 * SECONDS_PER_DAY)
+        .ok_or(SolSocialError::ArithmeticOverflow)?;
+
+    Ok(Some((decayed, decayed_through)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_elapsed_days() {
+        assert_eq!(elapsed_days(0, SECONDS_PER_DAY - 1), 0);
+        assert_eq!(elapsed_days(0, SECONDS_PER_DAY * 3 + 5), 3);
+        assert_eq!(elapsed_days(100, 50), 0);
+    }
+
+    #[test]
+    fn test_is_same_utc_day() {
+        let midnight = SECONDS_PER_DAY * 20_000;
+        assert!(is_same_utc_day(midnight, midnight + SECONDS_PER_DAY - 1));
+        assert!(!is_same_utc_day(midnight - 1, midnight));
+        assert!(!is_same_utc_day(0, midnight));
+    }
+
+    #[test]
+    fn test_dormant_value_decays() {
+        // 10% per day over two days
+        assert_eq!(apply_decay(10_000, 1_000, 2).unwrap(), 8_100);
+    }
+
+    #[test]
+    fn test_active_value_persists() {
+        assert_eq!(apply_decay(10_000, 1_000, 0).unwrap(), 10_000);
+        assert_eq!(apply_decay(10_000, 0, 30).unwrap(), 10_000);
+    }
+
+    #[test]
+    fn test_daily_decay_once_per_day() {
+        let created = SECONDS_PER_DAY * 20_000;
+
+        // Fresh: less than a day old
+        assert_eq!(daily_decay(1_000, 500, created, created + SECONDS_PER_DAY - 1).unwrap(), None);
+
+        // Stale: a single step no matter how long it sat
+        let stale = created + SECONDS_PER_DAY * 3;
+        assert_eq!(daily_decay(1_000, 500, created, stale).unwrap(), Some(950));
+
+        // Second call the same day sees the refreshed timestamp
+        assert_eq!(daily_decay(950, 500, stale, stale + 60).unwrap(), None);
+    }
+
+    #[test]
+    fn test_daily_decay_floors_at_zero() {
+        let created = SECONDS_PER_DAY * 20_000;
+        let stale = created + SECONDS_PER_DAY;
+
+        assert_eq!(daily_decay(1_000, 10_000, created, stale).unwrap(), Some(0));
+        assert_eq!(daily_decay(0, 500, created, stale).unwrap(), Some(0));
+    }
+
+    #[test]
+    fn test_dormant_user_decays_active_user_persists() {
+        let joined = SECONDS_PER_DAY * 20_000;
+        let warped = joined + SECONDS_PER_DAY * 10;
+
+        // Dormant since joining: ten days of 10% decay
+        let (dormant, decayed_through) =
+            decay_for_inactivity(10_000, 1_000, joined, 0, warped).unwrap().unwrap();
+        assert_eq!(dormant, 3_483);
+        assert_eq!(decayed_through, warped);
+
+        // Calling again straight after doesn't decay the same days twice
+        assert_eq!(decay_for_inactivity(dormant, 1_000, joined, decayed_through, warped + 60).unwrap(), None);
+
+        // Interacted an hour before the warp: nothing outstanding
+        let last_interaction = warped - 3_600;
+        assert_eq!(decay_for_inactivity(10_000, 1_000, last_interaction, 0, warped).unwrap(), None);
+    }
+
+    #[test]
+    fn test_invalid_rate_rejected() {
+        assert!(apply_decay(10_000, 10_001, 1).is_err());
+    }
+}
@@ -0,0 +1,50 @@
+/// Host portion of a `scheme://host[:port]/path` URL, without userinfo or port.
+/// Returns None when the URL has no scheme or an empty host.
+pub fn url_host(url: &str) -> Option<&str> {
+    let (_, rest) = url.split_once("://")?;
+    let authority = rest.split(|c| c == '/' || c == '?' || c == '#').next()?;
+    let host_and_port = authority.rsplit('@').next()?;
+    let host = host_and_port.split(':').next()?;
+
+    if host.is_empty() {
+        None
+    } else {
+        Some(host)
+    }
+}
+
+/// Whether `url` is served from one of `allowlist`'s hosts. An empty allowlist permits every host.
+pub fn is_media_host_allowed(url: &str, allowlist: &[String]) -> bool {
+    if allowlist.is_empty() {
+        return true;
+    }
+
+    match url_host(url) {
+        Some(host) => allowlist.iter().any(|allowed| allowed.eq_ignore_ascii_case(host)),
+        None => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_url_host_strips_scheme_port_and_path() {
+        assert_eq!(url_host("https://ipfs.io/ipfs/Qm123"), Some("ipfs.io"));
+        assert_eq!(url_host("https://user@gateway.pinata.cloud:443/ipfs/Qm"), Some("gateway.pinata.cloud"));
+        assert_eq!(url_host("https://arweave.net?x=1"), Some("arweave.net"));
+        assert_eq!(url_host("ipfs.io/ipfs/Qm123"), None);
+        assert_eq!(url_host("https:///ipfs/Qm123"), None);
+    }
+
+    #[test]
+    fn test_media_host_allowlist() {
+        let allowlist = vec!["ipfs.io".to_string(), "arweave.net".to_string()];
+        assert!(is_media_host_allowed("https://IPFS.io/ipfs/Qm123", &allowlist));
+        assert!(!is_media_host_allowed("https://evil.example/ipfs/Qm123", &allowlist));
+        assert!(!is_media_host_allowed("https://ipfs.io.evil.example/x", &allowlist));
+        assert!(!is_media_host_allowed("not a url", &allowlist));
+        assert!(is_media_host_allowed("https://anything.example/x", &[]));
+    }
+}
@@ -0,0 +1,240 @@
+use anchor_lang::prelude::*;
+use crate::errors::*;
+use crate::utils::decay::is_same_utc_day;
+
+pub const REPUTATION_BASIS_POINTS: u64 = 10_000;
+pub const REPUTATION_PER_HELD_KEY: u64 = 10;
+
+pub const INFLUENCE_PER_FOLLOWER: u64 = 10;
+pub const INFLUENCE_PER_KEY_HOLDER: u64 = 25;
+pub const INFLUENCE_PER_LIKE: u64 = 1;
+pub const INFLUENCE_PER_COMMENT: u64 = 2;
+pub const INFLUENCE_PER_SHARE: u64 = 3;
+/// Reputation points per point of influence
+pub const REPUTATION_PER_INFLUENCE: u64 = 10;
+
+/// Reputation granted to a buyer for backing a creator, as `rate_bps` of the
+/// creator's reputation and clamped to whatever is left of the buyer's `cap`
+pub fn calculate_reputation_bootstrap(
+    creator_reputation: u64,
+    rate_bps: u16,
+    already_granted: u64,
+    cap: u64,
+) -> Result<u64> {
+    require!(
+        rate_bps as u64 <= REPUTATION_BASIS_POINTS,
+        SolSocialError::InvalidPercentage
+    );
+
+    let remaining = cap.saturating_sub(already_granted);
+    if remaining == 0 {
+        return Ok(0);
+    }
+
+    let grant = (creator_reputation as u128)
+        .checked_mul(rate_bps as u128)
+        .ok_or(SolSocialError::ArithmeticOverflow)?
+        .checked_div(REPUTATION_BASIS_POINTS as u128)
+        .ok_or(SolSocialError::DivisionByZero)? as u64;
+
+    Ok(grant.min(remaining))
+}
+
+/// Blends a post's engagement with its author's reputation: each reputation point
+/// boosts the engagement score by `weight_bps`
+pub fn calculate_feed_score(
+    engagement_score: u64,
+    author_reputation: u64,
+    weight_bps: u16,
+) -> Result<u64> {
+    let multiplier_bps = (author_reputation as u128)
+        .checked_mul(weight_bps as u128)
+        .ok_or(SolSocialError::ArithmeticOverflow)?
+        .checked_add(REPUTATION_BASIS_POINTS as u128)
+        .ok_or(SolSocialError::ArithmeticOverflow)?;
+
+    let score = (engagement_score as u128)
+        .checked_mul(multiplier_bps)
+        .ok_or(SolSocialError::ArithmeticOverflow)?
+        .checked_div(REPUTATION_BASIS_POINTS as u128)
+        .ok_or(SolSocialError::DivisionByZero)?;
+
+    Ok(u64::try_from(score).unwrap_or(u64::MAX))
+}
+
+/// The one influence formula: weighted followers, engagement received and key holders,
+/// plus a tenth of reputation. Saturates rather than failing so it can't block a mutation.
+pub fn calculate_influence_score(
+    followers: u64,
+    likes_received: u64,
+    comments_received: u64,
+    shares_received: u64,
+    key_holders: u64,
+    reputation: u64,
+) -> u64 {
+    followers.saturating_mul(INFLUENCE_PER_FOLLOWER)
+        .saturating_add(likes_received.saturating_mul(INFLUENCE_PER_LIKE))
+        .saturating_add(comments_received.saturating_mul(INFLUENCE_PER_COMMENT))
+        .saturating_add(shares_received.saturating_mul(INFLUENCE_PER_SHARE))
+        .saturating_add(key_holders.saturating_mul(INFLUENCE_PER_KEY_HOLDER))
+        .saturating_add(reputation / REPUTATION_PER_INFLUENCE)
+}
+
+/// Longest post a user may write: `base` plus `per_tier` for every full
+/// `reputation_per_tier` of reputation, never above `hard_cap`
+pub fn calculate_content_length_limit(
+    reputation: u64,
+    base: u32,
+    per_tier: u32,
+    reputation_per_tier: u64,
+    hard_cap: usize,
+) -> usize {
+    let tiers = if reputation_per_tier == 0 {
+        0
+    } else {
+        reputation / reputation_per_tier
+    };
+
+    let limit = (base as u128).saturating_add((per_tier as u128).saturating_mul(tiers as u128));
+
+    usize::try_from(limit).unwrap_or(usize::MAX).min(hard_cap)
+}
+
+/// Counts an action into a rolling window that restarts once `window` seconds have
+/// passed since `window_start`; returns the new window start and count
+pub fn record_in_window(window_start: i64, count: u32, now: i64, window: i64) -> (i64, u32) {
+    if now.saturating_sub(window_start) >= window {
+        (now, 1)
+    } else {
+        (window_start, count.saturating_add(1))
+    }
+}
+
+/// Adds `amount` to a pending balance that is folded into the score once it reaches
+/// `threshold`; a threshold of 0 writes straight to the score. Returns the new score and pending.
+pub fn accrue_reputation(score: u64, pending: u64, amount: u64, threshold: u64) -> (u64, u64) {
+    let pending = pending.saturating_add(amount);
+    if pending >= threshold {
+        (score.saturating_add(pending), 0)
+    } else {
+        (score, pending)
+    }
+}
+
+/// Bonus for a post at `now`: the full `bonus` on a user's first post of a UTC day, nothing
+/// for later posts that day. Resets at midnight UTC rather than 24 hours after the last post.
+pub fn daily_post_bonus(last_post_timestamp: i64, now: i64, bonus: u64) -> u64 {
+    if is_same_utc_day(last_post_timestamp, now) {
+        0
+    } else {
+        bonus
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bootstrap_scales_with_creator_reputation() {
+        let high = calculate_reputation_bootstrap(5_000, 100, 0, 1_000).unwrap();
+        let low = calculate_reputation_bootstrap(500, 100, 0, 1_000).unwrap();
+        assert_eq!(high, 50);
+        assert_eq!(low, 5);
+        assert!(high > low);
+    }
+
+    #[test]
+    fn test_bootstrap_respects_cap() {
+        assert_eq!(calculate_reputation_bootstrap(100_000, 100, 0, 200).unwrap(), 200);
+        assert_eq!(calculate_reputation_bootstrap(100_000, 100, 150, 200).unwrap(), 50);
+        assert_eq!(calculate_reputation_bootstrap(100_000, 100, 200, 200).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_invalid_rate_rejected() {
+        assert!(calculate_reputation_bootstrap(1_000, 10_001, 0, 1_000).is_err());
+    }
+
+    #[test]
+    fn test_feed_score_weights_author_reputation() {
+        // 1% boost per reputation point
+        let reputable = calculate_feed_score(1_000, 50, 100).unwrap();
+        let unknown = calculate_feed_score(1_000, 0, 100).unwrap();
+        assert_eq!(reputable, 1_500);
+        assert_eq!(unknown, 1_000);
+    }
+
+    #[test]
+    fn test_feed_score_without_weight_is_raw_engagement() {
+        assert_eq!(calculate_feed_score(1_000, 50, 0).unwrap(), 1_000);
+        assert_eq!(calculate_feed_score(0, 50, 100).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_content_length_grows_per_reputation_tier() {
+        assert_eq!(calculate_content_length_limit(0, 2_000, 500, 1_000, 5_000), 2_000);
+        assert_eq!(calculate_content_length_limit(999, 2_000, 500, 1_000, 5_000), 2_000);
+        assert_eq!(calculate_content_length_limit(2_500, 2_000, 500, 1_000, 5_000), 3_000);
+    }
+
+    #[test]
+    fn test_record_in_window_rolls_over() {
+        assert_eq!(record_in_window(1_000, 2, 1_500, 3_600), (1_000, 3));
+        assert_eq!(record_in_window(1_000, 2, 4_600, 3_600), (4_600, 1));
+        // A zero window never accumulates
+        assert_eq!(record_in_window(1_000, 2, 1_000, 0), (1_000, 1));
+    }
+
+    #[test]
+    fn test_daily_post_bonus_resets_each_utc_day() {
+        let day = 86_400;
+        let morning = day * 20_000 + 3_600;
+
+        // First post ever, then a second post the same day
+        assert_eq!(daily_post_bonus(0, morning, 10), 10);
+        assert_eq!(daily_post_bonus(morning, morning + 3_600, 10), 0);
+        // Just before midnight still counts as the same day
+        assert_eq!(daily_post_bonus(morning, day * 20_001 - 1, 10), 0);
+        // The next UTC day pays again, even under 24 hours later
+        assert_eq!(daily_post_bonus(day * 20_001 - 1, day * 20_001, 10), 10);
+        assert_eq!(daily_post_bonus(morning, morning + day, 10), 10);
+    }
+
+    #[test]
+    fn test_batched_reputation_matches_immediate_writes() {
+        let (mut score, mut pending) = (1_000, 0);
+        for _ in 0..3 {
+            (score, pending) = accrue_reputation(score, pending, 2, 5);
+        }
+        // Two accruals stay pending, the third crosses the threshold and settles all of it
+        assert_eq!((score, pending), (1_006, 0));
+
+        (score, pending) = accrue_reputation(score, pending, 2, 5);
+        assert_eq!((score, pending), (1_006, 2));
+        assert_eq!(score + pending, 1_000 + 4 * 2);
+
+        assert_eq!(accrue_reputation(1_000, 0, 2, 0), (1_002, 0));
+    }
+
+    #[test]
+    fn test_influence_score_for_known_inputs() {
+        assert_eq!(calculate_influence_score(0, 0, 0, 0, 0, 0), 0);
+        // A new user: only their starting reputation counts
+        assert_eq!(calculate_influence_score(0, 0, 0, 0, 0, 100), 10);
+        // 50 followers, 200 likes, 30 comments, 10 shares, 4 holders, 1_250 reputation
+        assert_eq!(calculate_influence_score(50, 200, 30, 10, 4, 1_250), 500 + 200 + 60 + 30 + 100 + 125);
+    }
+
+    #[test]
+    fn test_influence_score_saturates() {
+        assert_eq!(calculate_influence_score(u64::MAX, 1, 1, 1, 1, 1), u64::MAX);
+        assert_eq!(calculate_influence_score(0, u64::MAX, u64::MAX, 0, 0, 0), u64::MAX);
+    }
+
+    #[test]
+    fn test_content_length_respects_hard_cap() {
+        assert_eq!(calculate_content_length_limit(u64::MAX, 2_000, 500, 1_000, 5_000), 5_000);
+        assert_eq!(calculate_content_length_limit(50_000, 2_000, 500, 0, 5_000), 2_000);
+    }
+}
@@ -1,11 +1,13 @@
 use anchor_lang::prelude::*;
 use crate::state::*;
 use crate::errors::*;
+use crate::utils::bonding_curve::BondingCurve;
 
 pub const CREATOR_SHARE_BPS: u16 = 500; // 5%
 pub const PROTOCOL_SHARE_BPS: u16 = 250; // 2.5%
 pub const REFERRER_SHARE_BPS: u16 = 100; // 1%
 pub const BASIS_POINTS: u16 = 10000;
+pub const SECONDS_PER_SUBSCRIPTION_MONTH: i64 = 30 * 86_400;
 
 #[derive(Debug, Clone, Copy)]
 pub struct RevenueDistribution {
@@ -18,9 +20,25 @@ pub struct RevenueDistribution {
 pub fn calculate_revenue_distribution(
     total_amount: u64,
     has_referrer: bool,
+) -> Result<RevenueDistribution> {
+    calculate_revenue_distribution_with_referral_bonus(total_amount, has_referrer, None)
+}
+
+/// Same as `calculate_revenue_distribution`, but lets a creator override the referrer share
+/// for their market. Anything paid above the global referrer share comes out of the creator share.
+pub fn calculate_revenue_distribution_with_referral_bonus(
+    total_amount: u64,
+    has_referrer: bool,
+    referral_bonus_bps: Option<u16>,
 ) -> Result<RevenueDistribution> {
     require!(total_amount > 0, SolSocialError::InvalidAmount);
 
+    let referrer_share_bps = referral_bonus_bps.unwrap_or(REFERRER_SHARE_BPS);
+    require!(
+        referrer_share_bps <= REFERRER_SHARE_BPS + CREATOR_SHARE_BPS,
+        SolSocialError::InvalidPercentage
+    );
+
     let creator_amount = total_amount
         .checked_mul(CREATOR_SHARE_BPS as u64)
         .ok_or(SolSocialError::MathOverflow)?
@@ -35,7 +53,7 @@ pub fn calculate_revenue_distribution(
 
     let referrer_amount = if has_referrer {
         total_amount
-            .checked_mul(REFERRER_SHARE_BPS as u64)
+            .checked_mul(referrer_share_bps as u64)
             .ok_or(SolSocialError::MathOverflow)?
             .checked_div(BASIS_POINTS as u64)
             .ok_or(SolSocialError::MathOverflow)?
@@ -43,6 +61,21 @@ pub fn calculate_revenue_distribution(
         0
     };
 
+    // The creator funds any referral bonus above the global rate
+    let creator_amount = if has_referrer && referrer_share_bps > REFERRER_SHARE_BPS {
+        let global_referrer_amount = total_amount
+            .checked_mul(REFERRER_SHARE_BPS as u64)
+            .ok_or(SolSocialError::MathOverflow)?
+            .checked_div(BASIS_POINTS as u64)
+            .ok_or(SolSocialError::MathOverflow)?;
+
+        creator_amount
+            .checked_sub(referrer_amount - global_referrer_amount)
+            .ok_or(SolSocialError::MathUnderflow)?
+    } else {
+        creator_amount
+    };
+
     let distributed_amount = creator_amount
         .checked_add(protocol_amount)
         .ok_or(SolSocialError::MathOverflow)?
@@ -61,137 +94,138 @@ pub fn calculate_revenue_distribution(
     })
 }
 
-pub fn distribute_buy_revenue<'info>(
-    user_profile: &mut Account<'info, UserProfile>,
-    protocol_treasury: &mut AccountInfo<'info>,
-    referrer_profile: Option<&mut Account<'info, UserProfile>>,
-    buyer: &mut AccountInfo<'info>,
-    system_program: &Program<'info, System>,
-    total_amount: u64,
+/// Splits the fees a buy charged on top of `price` between the protocol, the creator and the
+/// buyer's referrer, crediting the referrer's earnings. The whole price still goes to the curve;
+/// moving the funds is the caller's, in whatever the market settles in.
+pub fn distribute_buy_revenue(
+    user_keys: &UserKeys,
+    buyer: &Pubkey,
+    referrer_profile: Option<&mut UserProfile>,
+    price: u64,
+    protocol_fee: u64,
+    subject_fee: u64,
 ) -> Result<RevenueDistribution> {
-    let distribution = calculate_revenue_distribution(
-        total_amount,
-        referrer_profile.is_some(),
-    )?;
-
-    // Transfer creator share
-    if distribution.creator_amount > 0 {
-        let creator_lamports = user_profile.to_account_info().lamports();
-        **user_profile.to_account_info().lamports.borrow_mut() = creator_lamports
-            .checked_add(distribution.creator_amount)
-            .ok_or(SolSocialError::MathOverflow)?;
-
-        let buyer_lamports = buyer.lamports();
-        **buyer.lamports.borrow_mut() = buyer_lamports
-            .checked_sub(distribution.creator_amount)
-            .ok_or(SolSocialError::InsufficientFunds)?;
-
-        user_profile.total_earnings = user_profile.total_earnings
-            .checked_add(distribution.creator_amount)
-            .ok_or(SolSocialError::MathOverflow)?;
-    }
+    distribute_trade_fees(
+        user_keys,
+        buyer,
+        referrer_profile,
+        price,
+        protocol_fee,
+        subject_fee,
+        price,
+    )
+}
 
-    // Transfer protocol share
-    if distribution.protocol_amount > 0 {
-        let protocol_lamports = protocol_treasury.lamports();
-        **protocol_treasury.lamports.borrow_mut() = protocol_lamports
-            .checked_add(distribution.protocol_amount)
-            .ok_or(SolSocialError::MathOverflow)?;
+/// Same split for a sell, whose fees come out of `price`: `remaining_amount` is the seller's
+/// proceeds
+pub fn distribute_sell_revenue(
+    user_keys: &UserKeys,
+    seller: &Pubkey,
+    referrer_profile: Option<&mut UserProfile>,
+    price: u64,
+    protocol_fee: u64,
+    subject_fee: u64,
+) -> Result<RevenueDistribution> {
+    let seller_proceeds = price
+        .checked_sub(protocol_fee)
+        .ok_or(SolSocialError::MathUnderflow)?
+        .checked_sub(subject_fee)
+        .ok_or(SolSocialError::MathUnderflow)?;
 
-        let buyer_lamports = buyer.lamports();
-        **buyer.lamports.borrow_mut() = buyer_lamports
-            .checked_sub(distribution.protocol_amount)
-            .ok_or(SolSocialError::InsufficientFunds)?;
-    }
+    distribute_trade_fees(
+        user_keys,
+        seller,
+        referrer_profile,
+        price,
+        protocol_fee,
+        subject_fee,
+        seller_proceeds,
+    )
+}
 
-    // Transfer referrer share if applicable
-    if let Some(ref mut referrer) = referrer_profile {
-        if distribution.referrer_amount > 0 {
-            let referrer_lamports = referrer.to_account_info().lamports();
-            **referrer.to_account_info().lamports.borrow_mut() = referrer_lamports
-                .checked_add(distribution.referrer_amount)
-                .ok_or(SolSocialError::MathOverflow)?;
-
-            let buyer_lamports = buyer.lamports();
-            **buyer.lamports.borrow_mut() = buyer_lamports
-                .checked_sub(distribution.referrer_amount)
-                .ok_or(SolSocialError::InsufficientFunds)?;
-
-            referrer.total_earnings = referrer.total_earnings
-                .checked_add(distribution.referrer_amount)
-                .ok_or(SolSocialError::MathOverflow)?;
-
-            referrer.referral_earnings = referrer.referral_earnings
-                .checked_add(distribution.referrer_amount)
-                .ok_or(SolSocialError::MathOverflow)?;
+fn distribute_trade_fees(
+    user_keys: &UserKeys,
+    trader: &Pubkey,
+    referrer_profile: Option<&mut UserProfile>,
+    price: u64,
+    protocol_fee: u64,
+    subject_fee: u64,
+    remaining_amount: u64,
+) -> Result<RevenueDistribution> {
+    let referrer = match referrer_profile {
+        Some(referrer) => referrer,
+        None => {
+            return Ok(RevenueDistribution {
+                creator_amount: subject_fee,
+                protocol_amount: protocol_fee,
+                referrer_amount: 0,
+                remaining_amount,
+            })
         }
-    }
+    };
 
-    Ok(distribution)
-}
+    // Referring your own trade, or trades on your own market, would only rebate fees to yourself
+    require!(
+        referrer.authority != *trader
+            && referrer.authority != user_keys.owner
+            && referrer.authority != user_keys.creator,
+        SolSocialError::SelfInteractionNotAllowed
+    );
 
-pub fn distribute_sell_revenue<'info>(
-    user_profile: &mut Account<'info, UserProfile>,
-    protocol_treasury: &mut AccountInfo<'info>,
-    referrer_profile: Option<&mut Account<'info, UserProfile>>,
-    seller: &mut AccountInfo<'info>,
-    total_amount: u64,
-) -> Result<RevenueDistribution> {
-    let distribution = calculate_revenue_distribution(
-        total_amount,
-        referrer_profile.is_some(),
-    )?;
-
-    // Calculate seller proceeds (total minus all fees)
-    let seller_proceeds = distribution.remaining_amount;
-
-    // Transfer seller proceeds
-    if seller_proceeds > 0 {
-        let seller_lamports = seller.lamports();
-        **seller.lamports.borrow_mut() = seller_lamports
-            .checked_add(seller_proceeds)
-            .ok_or(SolSocialError::MathOverflow)?;
-    }
+    let (creator_amount, protocol_amount, referrer_amount) =
+        split_trade_fees(price, protocol_fee, subject_fee, user_keys.referral_bonus_bps)?;
 
-    // Transfer creator share
-    if distribution.creator_amount > 0 {
-        let creator_lamports = user_profile.to_account_info().lamports();
-        **user_profile.to_account_info().lamports.borrow_mut() = creator_lamports
-            .checked_add(distribution.creator_amount)
-            .ok_or(SolSocialError::MathOverflow)?;
+    referrer.total_revenue_earned = referrer.total_revenue_earned
+        .checked_add(referrer_amount)
+        .ok_or(SolSocialError::MathOverflow)?;
 
-        user_profile.total_earnings = user_profile.total_earnings
-            .checked_add(distribution.creator_amount)
-            .ok_or(SolSocialError::MathOverflow)?;
-    }
+    Ok(RevenueDistribution {
+        creator_amount,
+        protocol_amount,
+        referrer_amount,
+        remaining_amount,
+    })
+}
 
-    // Transfer protocol share
-    if distribution.protocol_amount > 0 {
-        let protocol_lamports = protocol_treasury.lamports();
-        **protocol_treasury.lamports.borrow_mut() = protocol_lamports
-            .checked_add(distribution.protocol_amount)
-            .ok_or(SolSocialError::MathOverflow)?;
-    }
+/// Carves a referrer's cut out of a trade's fees, returning what's left of the subject fee, what's
+/// left of the protocol fee, and the referrer's cut. The referrer earns a creator's
+/// `referral_bonus_bps` of the price when set, the global share otherwise: up to the global share
+/// comes out of the protocol fee and anything above it out of the creator's subject fee, neither
+/// paying out more than it charged.
+pub fn split_trade_fees(
+    price: u64,
+    protocol_fee: u64,
+    subject_fee: u64,
+    referral_bonus_bps: Option<u16>,
+) -> Result<(u64, u64, u64)> {
+    let referrer_share_bps = referral_bonus_bps.unwrap_or(REFERRER_SHARE_BPS);
+    require!(
+        referrer_share_bps <= REFERRER_SHARE_BPS + CREATOR_SHARE_BPS,
+        SolSocialError::InvalidReferralBonus
+    );
+
+    let protocol_funded_bps = referrer_share_bps.min(REFERRER_SHARE_BPS);
+    let creator_funded_bps = referrer_share_bps - protocol_funded_bps;
+
+    let from_protocol = price
+        .checked_mul(protocol_funded_bps as u64)
+        .ok_or(SolSocialError::MathOverflow)?
+        .checked_div(BASIS_POINTS as u64)
+        .ok_or(SolSocialError::MathOverflow)?
+        .min(protocol_fee);
 
-    // Transfer referrer share if applicable
-    if let Some(ref mut referrer) = referrer_profile {
-        if distribution.referrer_amount > 0 {
-            let referrer_lamports = referrer.to_account_info().lamports();
-            **referrer.to_account_info().lamports.borrow_mut() = referrer_lamports
-                .checked_add(distribution.referrer_amount)
-                .ok_or(SolSocialError::MathOverflow)?;
-
-            referrer.total_earnings = referrer.total_earnings
-                .checked_add(distribution.referrer_amount)
-                .ok_or(SolSocialError::MathOverflow)?;
-
-            referrer.referral_earnings = referrer.referral_earnings
-                .checked_add(distribution.referrer_amount)
-                .ok_or(SolSocialError::MathOverflow)?;
-        }
-    }
+    let from_creator = price
+        .checked_mul(creator_funded_bps as u64)
+        .ok_or(SolSocialError::MathOverflow)?
+        .checked_div(BASIS_POINTS as u64)
+        .ok_or(SolSocialError::MathOverflow)?
+        .min(subject_fee);
 
-    Ok(distribution)
+    let referrer_amount = from_protocol
+        .checked_add(from_creator)
+        .ok_or(SolSocialError::MathOverflow)?;
+
+    Ok((subject_fee - from_creator, protocol_fee - from_protocol, referrer_amount))
 }
 
 pub fn calculate_creator_lifetime_value(
@@ -254,6 +288,69 @@ pub fn update_revenue_metrics(
     Ok(())
 }
 
+/// Protocol fee rate after waiving `discount_bps` of it
+pub fn apply_fee_discount(fee_bps: u64, discount_bps: u16) -> Result<u64> {
+    require!(discount_bps <= BASIS_POINTS, SolSocialError::InvalidPercentage);
+
+    Ok(fee_bps
+        .checked_mul((BASIS_POINTS - discount_bps) as u64)
+        .ok_or(SolSocialError::MathOverflow)?
+        / BASIS_POINTS as u64)
+}
+
+/// Whether `escrow_balance` covers at least `min_ratio_bps` of what it would cost
+/// to redeem the entire circulating supply
+pub fn is_escrow_solvent(
+    escrow_balance: u64,
+    redemption_value: u64,
+    min_ratio_bps: u16,
+) -> Result<bool> {
+    if redemption_value == 0 {
+        return Ok(true);
+    }
+
+    let required_balance = (redemption_value as u128)
+        .checked_mul(min_ratio_bps as u128)
+        .ok_or(SolSocialError::MathOverflow)?
+        .checked_div(BASIS_POINTS as u128)
+        .ok_or(SolSocialError::MathOverflow)?;
+
+    Ok(escrow_balance as u128 >= required_balance)
+}
+
+/// The solvency gate every sell path runs before paying out of a market's reserve. Sells stay
+/// paused while a buy has recorded the market as under-backed, and are refused even before
+/// then once `reserve_balance` covers less than `min_ratio_bps` of redeeming `supply`
+pub fn check_sell_solvency(
+    sells_paused: bool,
+    reserve_balance: u64,
+    supply: u64,
+    min_ratio_bps: u16,
+) -> Result<()> {
+    require!(!sells_paused, SolSocialError::InsufficientLiquidity);
+
+    let redemption_value = BondingCurve::standard().get_liquidity(supply)?;
+    require!(
+        is_escrow_solvent(reserve_balance, redemption_value, min_ratio_bps)?,
+        SolSocialError::InsufficientLiquidity
+    );
+
+    Ok(())
+}
+
+/// New expiry after paying for `months` more: renewals before `expires_at` stack onto the
+/// current term, while a lapsed subscription starts over from `now`
+pub fn extend_subscription_term(expires_at: i64, now: i64, months: u8) -> Result<i64> {
+    let extension = SECONDS_PER_SUBSCRIPTION_MONTH
+        .checked_mul(months as i64)
+        .ok_or(SolSocialError::MathOverflow)?;
+
+    expires_at
+        .max(now)
+        .checked_add(extension)
+        .ok_or(SolSocialError::MathOverflow.into())
+}
+
 pub fn calculate_dynamic_fee_rate(
     base_fee_bps: u16,
     volume_24h: u64,
@@ -325,6 +422,95 @@ mod tests {
         assert_eq!(distribution.remaining_amount, 925_000_000); // 92.5%
     }
 
+    #[test]
+    fn test_revenue_distribution_creator_referral_bonus() {
+        let total_amount = 1_000_000_000; // 1 SOL
+        let distribution = calculate_revenue_distribution_with_referral_bonus(total_amount, true, Some(300)).unwrap();
+
+        assert_eq!(distribution.referrer_amount, 30_000_000); // 3% custom rate
+        assert_eq!(distribution.creator_amount, 30_000_000); // 5% minus the 2% bonus
+        assert_eq!(distribution.protocol_amount, 25_000_000); // 2.5%
+        assert_eq!(distribution.remaining_amount, 915_000_000); // seller side unchanged
+
+        // Markets without an override keep paying the global rate
+        let global = calculate_revenue_distribution_with_referral_bonus(total_amount, true, None).unwrap();
+        assert_eq!(global.referrer_amount, 10_000_000); // 1%
+        assert_eq!(global.creator_amount, 50_000_000); // 5%
+    }
+
+    #[test]
+    fn test_revenue_distribution_rejects_bonus_above_creator_share() {
+        assert!(calculate_revenue_distribution_with_referral_bonus(1_000_000_000, true, Some(601)).is_err());
+    }
+
+    #[test]
+    fn test_trade_fees_pay_creator_referral_bonus() {
+        let price = 1_000_000_000; // 1 SOL
+        let protocol_fee = 25_000_000; // 2.5%
+        let subject_fee = 50_000_000; // 5%
+
+        // A creator offering 3% funds the 2% above the global share from their own fee
+        let (creator, protocol, referrer) = split_trade_fees(price, protocol_fee, subject_fee, Some(300)).unwrap();
+        assert_eq!(referrer, 30_000_000);
+        assert_eq!(protocol, 15_000_000);
+        assert_eq!(creator, 30_000_000);
+
+        // Markets without an override pay the global share, all of it from the protocol fee
+        let (creator, protocol, referrer) = split_trade_fees(price, protocol_fee, subject_fee, None).unwrap();
+        assert_eq!(referrer, 10_000_000);
+        assert_eq!(protocol, 15_000_000);
+        assert_eq!(creator, 50_000_000);
+
+        // A fee-free trade has no protocol fee to fund the global share from
+        let (creator, protocol, referrer) = split_trade_fees(price, 0, subject_fee, Some(300)).unwrap();
+        assert_eq!(referrer, 20_000_000);
+        assert_eq!(protocol, 0);
+        assert_eq!(creator, 30_000_000);
+
+        assert!(split_trade_fees(price, protocol_fee, subject_fee, Some(601)).is_err());
+    }
+
+    #[test]
+    fn test_escrow_solvency_ratio() {
+        // 95% minimum coverage of a 1 SOL redemption value
+        assert!(is_escrow_solvent(950_000_000, 1_000_000_000, 9_500).unwrap());
+        assert!(!is_escrow_solvent(949_999_999, 1_000_000_000, 9_500).unwrap());
+
+        // Nothing to redeem is always solvent
+        assert!(is_escrow_solvent(0, 0, 10_000).unwrap());
+    }
+
+    #[test]
+    fn test_fee_leakage_pauses_sells() {
+        let curve = BondingCurve::standard();
+        let supply = 20;
+        let redemption_value = curve.get_liquidity(supply).unwrap();
+
+        // Escrow that took in each buy's full price backs every outstanding sell
+        assert!(check_sell_solvency(false, redemption_value, supply, 9_500).is_ok());
+
+        // A bug paying the 7.5% of fees out of the price instead of on top of it leaks escrow
+        // with every buy, until the reserve drops under the 95% ratio
+        let mut escrow_balance = 0u64;
+        for sold in 0..supply {
+            let price = curve.get_buy_price(sold, 1).unwrap();
+            let leaked = price * (CREATOR_SHARE_BPS + PROTOCOL_SHARE_BPS) as u64 / BASIS_POINTS as u64;
+            escrow_balance += price - leaked;
+        }
+        assert!(check_sell_solvency(false, escrow_balance, supply, 9_500).is_err());
+
+        // A pause recorded by a buy holds until a later buy finds the market solvent again
+        assert!(check_sell_solvency(true, redemption_value, supply, 9_500).is_err());
+    }
+
+    #[test]
+    fn test_fee_discount() {
+        assert_eq!(apply_fee_discount(250, 0).unwrap(), 250);
+        assert_eq!(apply_fee_discount(250, 2_000).unwrap(), 200); // 20% off 2.5%
+        assert_eq!(apply_fee_discount(250, 10_000).unwrap(), 0);
+        assert!(apply_fee_discount(250, 10_001).is_err());
+    }
+
     #[test]
     fn test_dynamic_fee_calculation() {
         let base_fee = 500; // 5%
@@ -337,4 +523,81 @@ mod tests {
         let fee2 = calculate_dynamic_fee_rate(base_fee, 200_000_000_000, 2000).unwrap();
         assert!(fee2 < base_fee);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_subscription_renewal_extends_current_term() {
+        let now = 1_000_000;
+        let first = extend_subscription_term(0, now, 1).unwrap();
+        assert_eq!(first, now + SECONDS_PER_SUBSCRIPTION_MONTH);
+
+        // Renewing halfway through stacks onto the remaining time
+        let renewed = extend_subscription_term(first, now + SECONDS_PER_SUBSCRIPTION_MONTH / 2, 2).unwrap();
+        assert_eq!(renewed, first + 2 * SECONDS_PER_SUBSCRIPTION_MONTH);
+    }
+
+    #[test]
+    fn test_lapsed_subscription_restarts_from_now() {
+        let expired_at = 1_000_000;
+        let later = expired_at + 10 * SECONDS_PER_SUBSCRIPTION_MONTH;
+        assert_eq!(
+            extend_subscription_term(expired_at, later, 1).unwrap(),
+            later + SECONDS_PER_SUBSCRIPTION_MONTH
+        );
+    }
+
+    #[test]
+    fn test_expired_subscription_no_longer_opens_premium_posts() {
+        let author = Pubkey::new_unique();
+        let viewer = Pubkey::new_unique();
+        let started_at = 1_000_000;
+        let subscription = Subscription {
+            subscriber: viewer,
+            creator: author,
+            started_at,
+            expires_at: extend_subscription_term(0, started_at, 1).unwrap(),
+            total_paid: 10_000_000,
+            bump: 255,
+        };
+        let post = Post {
+            id: 0,
+            author,
+            content: String::new(),
+            content_hash: [0; 32],
+            timestamp: started_at,
+            likes: 0,
+            reposts: 0,
+            counted_reposts: 0,
+            replies: 0,
+            token_weight: 0,
+            engagement_score: 0,
+            peak_engagement_score: 0,
+            feed_score: 0,
+            author_reputation_awarded: 0,
+            is_premium: true,
+            required_keys: 1,
+            premium_access_value: 0,
+            tip_goal: 0,
+            downgrade_after_seconds: 0,
+            downgrade_engagement_threshold: 0,
+            is_pinned: false,
+            report_count: 0,
+            boost_weight: 0,
+            is_curated: false,
+            single_reply_per_user: false,
+            max_replies: 0,
+            reply_to: None,
+            media_urls: Vec::new(),
+            tags: Vec::new(),
+            mentions: Vec::new(),
+            visibility: PostVisibility::KeyHolders,
+            status: PostStatus::Active,
+            bump: 255,
+        };
+        let can_view = |now: i64| post.can_view(&viewer, 0, 1, None, subscription.is_active(now));
+
+        // A keyless subscriber sees the post for the paid term and loses it at `expires_at`
+        assert!(can_view(subscription.expires_at - 1));
+        assert!(!can_view(subscription.expires_at));
+        assert!(!can_view(subscription.expires_at + SECONDS_PER_SUBSCRIPTION_MONTH));
+    }
+}
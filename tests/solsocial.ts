@@ -1,9 +1,16 @@
 import * as anchor from "@coral-xyz/anchor";
 import { Program } from "@coral-xyz/anchor";
 import { SolSocial } from "../target/types/sol_social";
-import { PublicKey, Keypair, SystemProgram, LAMPORTS_PER_SOL } from "@solana/web3.js";
-import { TOKEN_PROGRAM_ID, ASSOCIATED_TOKEN_PROGRAM_ID, getAssociatedTokenAddress, createAssociatedTokenAccountInstruction } from "@solana/spl-token";
+import { PublicKey, Keypair, SystemProgram, LAMPORTS_PER_SOL, ComputeBudgetProgram } from "@solana/web3.js";
+import { TOKEN_PROGRAM_ID, ASSOCIATED_TOKEN_PROGRAM_ID, NATIVE_MINT, getAssociatedTokenAddress, getAccount, createAssociatedTokenAccountInstruction, createMint, getOrCreateAssociatedTokenAccount, mintTo } from "@solana/spl-token";
+import {
+  PROGRAM_ID as TOKEN_METADATA_PROGRAM_ID,
+  createCreateMetadataAccountV3Instruction,
+  createCreateMasterEditionV3Instruction,
+  createVerifySizedCollectionItemInstruction,
+} from "@metaplex-foundation/mpl-token-metadata";
 import { expect } from "chai";
+import { createHash } from "crypto";
 
 describe("SolSocial", () => {
   const provider = anchor.AnchorProvider.env();
@@ -143,33 +150,247 @@ describe("SolSocial", () => {
         throw error;
       }
     });
-  });
 
-  describe("Key Trading System", () => {
-    it("Initializes user keys", async () => {
+    it("Refuses to close a profile with live key holders", async () => {
+      const [creatorAccount] = PublicKey.findProgramAddressSync(
+        [Buffer.from("user"), creator.publicKey.toBuffer()],
+        program.programId
+      );
+
       try {
         await program.methods
-          .initializeUserKeys()
-          .accounts({
-            userKeys,
-            bondingCurve,
-            creator: creator.publicKey,
-            globalState,
-            systemProgram: SystemProgram.programId,
-          })
+          .closeUserProfile()
+          .accounts({ user: creatorAccount, authority: trader.publicKey })
+          .signers([trader])
+          .rpc();
+        expect.fail("only the profile authority may close it");
+      } catch (error) {
+        expect(error.error.errorCode.code).to.equal("AccessDenied");
+      }
+
+      try {
+        await program.methods
+          .closeUserProfile()
+          .accounts({ user: creatorAccount, authority: creator.publicKey })
           .signers([creator])
           .rpc();
+        expect.fail("a profile with outstanding keys should not close");
+      } catch (error) {
+        expect(error.error.errorCode.code).to.equal("KeysOutstanding");
+      }
 
-        const userKeysAccount = await program.account.userKeys.fetch(userKeys);
-        const bondingCurveAccount = await program.account.bondingCurve.fetch(bondingCurve);
-        
-        expect(userKeysAccount.creator.toString()).to.equal(creator.publicKey.toString());
-        expect(userKeysAccount.totalSupply.toNumber()).to.equal(0);
-        expect(bondingCurveAccount.creator.toString()).to.equal(creator.publicKey.toString());
+      const account = await program.account.user.fetch(creatorAccount);
+      expect(account.keySupply.toNumber()).to.be.greaterThan(0);
+    });
+
+    it("Lets a banned user appeal within the window and restores access on approval", async () => {
+      const [platformConfig] = PublicKey.findProgramAddressSync(
+        [Buffer.from("platform_config")],
+        program.programId
+      );
+      const [traderAccount] = PublicKey.findProgramAddressSync(
+        [Buffer.from("user"), trader.publicKey.toBuffer()],
+        program.programId
+      );
+      const appealFor = (bannedAt: anchor.BN) =>
+        PublicKey.findProgramAddressSync(
+          [Buffer.from("ban_appeal"), trader.publicKey.toBuffer(), bannedAt.toArrayLike(Buffer, "le", 8)],
+          program.programId
+        )[0];
+      const ban = (reason: string) =>
+        program.methods
+          .banUser(reason)
+          .accounts({ bannedUser: traderAccount, platformConfig, moderatorProfile: null, moderator: wallet.publicKey })
+          .rpc();
+      const appeal = async (message: string) => {
+        const { bannedAt } = await program.account.user.fetch(traderAccount);
+        await program.methods
+          .appealBan(message)
+          .accounts({ user: traderAccount, banAppeal: appealFor(bannedAt), platformConfig, authority: trader.publicKey })
+          .signers([trader])
+          .rpc();
+        return appealFor(bannedAt);
+      };
+      const setWindow = (seconds: number) =>
+        program.methods
+          .setBanAppealWindow(new anchor.BN(seconds))
+          .accounts({ platformConfig, authority: wallet.publicKey })
+          .rpc();
+
+      try {
+        await ban("");
+        expect.fail("a ban without a reason should be rejected");
       } catch (error) {
-        console.error("Initialize user keys error:", error);
-        throw error;
+        expect(error.error.errorCode.code).to.equal("InvalidBanReason");
+      }
+
+      try {
+        await program.methods
+          .banUser("not your call")
+          .accounts({ bannedUser: traderAccount, platformConfig, moderatorProfile: null, moderator: creator.publicKey })
+          .signers([creator])
+          .rpc();
+        expect.fail("only moderators can ban");
+      } catch (error) {
+        expect(error.error.errorCode.code).to.equal("ModeratorPrivilegesRequired");
+      }
+
+      await ban("spam links");
+      const banned = await program.account.user.fetch(traderAccount);
+      expect(banned.banned).to.be.true;
+      expect(banned.banReason).to.equal("spam links");
+      expect(banned.bannedBy.toString()).to.equal(wallet.publicKey.toString());
+
+      const filed = await appeal("those were my own project's links");
+      await program.methods
+        .resolveBanAppeal(true)
+        .accounts({ bannedUser: traderAccount, banAppeal: filed, platformConfig, moderatorProfile: null, moderator: wallet.publicKey })
+        .rpc();
+
+      const restored = await program.account.user.fetch(traderAccount);
+      expect(restored.banned).to.be.false;
+      expect(restored.banReason).to.equal("");
+      const resolved = await program.account.banAppeal.fetch(filed);
+      expect(resolved.isResolved).to.be.true;
+      expect(resolved.isApproved).to.be.true;
+
+      // Outside the window the ban can only be lifted by a moderator directly
+      await setWindow(0);
+      await ban("spam links again");
+      await new Promise(resolve => setTimeout(resolve, 2000));
+      try {
+        await appeal("too late");
+        expect.fail("appeals after the window should be rejected");
+      } catch (error) {
+        expect(error.error.errorCode.code).to.equal("ResourceExpired");
+      }
+
+      await program.methods
+        .unbanUser()
+        .accounts({ bannedUser: traderAccount, platformConfig, moderatorProfile: null, moderator: wallet.publicKey })
+        .rpc();
+      expect((await program.account.user.fetch(traderAccount)).banned).to.be.false;
+      await setWindow(7 * 24 * 60 * 60);
+    });
+
+    it("Lets only the platform authority verify users, awarding the badge once", async () => {
+      const [platformConfig] = PublicKey.findProgramAddressSync(
+        [Buffer.from("platform_config")],
+        program.programId
+      );
+      const [creatorProfile] = PublicKey.findProgramAddressSync(
+        [Buffer.from("user_profile"), creator.publicKey.toBuffer()],
+        program.programId
+      );
+      const [verifiedBadge] = PublicKey.findProgramAddressSync(
+        [Buffer.from("user_badge"), creator.publicKey.toBuffer(), Buffer.from([4])],
+        program.programId
+      );
+      const verify = (authority: PublicKey, signers: Keypair[] = []) =>
+        program.methods
+          .verifyUser(creator.publicKey)
+          .accounts({ platformConfig, targetProfile: creatorProfile, verifiedBadge, authority })
+          .signers(signers)
+          .rpc();
+
+      try {
+        await verify(trader.publicKey, [trader]);
+        expect.fail("only the platform authority can verify users");
+      } catch (error) {
+        expect(error.error.errorCode.code).to.equal("UnauthorizedUser");
+      }
+
+      await verify(wallet.publicKey);
+      expect((await program.account.userProfile.fetch(creatorProfile)).isVerified).to.be.true;
+      const badge = await program.account.userBadge.fetch(verifiedBadge);
+      expect(badge.badgeType).to.deep.equal({ verified: {} });
+      expect(badge.isActive).to.be.true;
+
+      try {
+        await verify(wallet.publicKey);
+        expect.fail("re-verifying should not award a second badge");
+      } catch (error) {
+        expect(error.error.errorCode.code).to.equal("BadgeAlreadyEarned");
+      }
+      expect((await program.account.userBadge.fetch(verifiedBadge)).earnedAt.toString())
+        .to.equal(badge.earnedAt.toString());
+
+      await program.methods
+        .revokeVerification(creator.publicKey)
+        .accounts({ platformConfig, targetProfile: creatorProfile, verifiedBadge, authority: wallet.publicKey })
+        .rpc();
+      expect((await program.account.userProfile.fetch(creatorProfile)).isVerified).to.be.false;
+      expect((await program.account.userBadge.fetch(verifiedBadge)).isActive).to.be.false;
+
+      // The same badge account comes back to life on re-verification
+      await verify(wallet.publicKey);
+      expect((await program.account.userBadge.fetch(verifiedBadge)).isActive).to.be.true;
+    });
+  });
+
+  describe("Key Trading System", () => {
+    it("Initializes user keys", async () => {
+      const [platformConfig] = PublicKey.findProgramAddressSync(
+        [Buffer.from("platform_config")],
+        program.programId
+      );
+      const [protocolConfig] = PublicKey.findProgramAddressSync(
+        [Buffer.from("protocol_config")],
+        program.programId
+      );
+      const [protocolTreasury] = PublicKey.findProgramAddressSync(
+        [Buffer.from("protocol_treasury")],
+        program.programId
+      );
+      const [userKey] = PublicKey.findProgramAddressSync(
+        [Buffer.from("user_key"), creator.publicKey.toBuffer()],
+        program.programId
+      );
+      const [keysMint] = PublicKey.findProgramAddressSync(
+        [Buffer.from("keys_mint"), creator.publicKey.toBuffer()],
+        program.programId
+      );
+
+      // Issuance is counted on the creator's profile, so they need one before opening a market
+      const [creatorProfile] = PublicKey.findProgramAddressSync(
+        [Buffer.from("user_profile"), creator.publicKey.toBuffer()],
+        program.programId
+      );
+      if (!(await program.account.userProfile.fetchNullable(creatorProfile))) {
+        await program.methods
+          .createUserProfile("creator", "Sells keys", "https://example.com/creator.jpg")
+          .accounts({ userProfile: creatorProfile, user: creator.publicKey, systemProgram: SystemProgram.programId })
+          .signers([creator])
+          .rpc();
       }
+
+      // create_keys opens both halves of the market every trade path reads
+      await program.methods
+        .createKeys(creator.publicKey, "Creator", "CRTR", "https://example.com/keys.json", "", [])
+        .accounts({
+          userKeys,
+          userKey,
+          keysMint,
+          keysVault: await getAssociatedTokenAddress(keysMint, userKeys, true),
+          creatorKeysAccount: await getAssociatedTokenAddress(keysMint, creator.publicKey),
+          payer: creator.publicKey,
+          protocolConfig,
+          protocolTreasury,
+          platformConfig,
+          systemProgram: SystemProgram.programId,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        })
+        .signers([creator])
+        .rpc();
+
+      const userKeysAccount = await program.account.userKeys.fetch(userKeys);
+      const userKeyAccount = await program.account.userKey.fetch(userKey);
+
+      expect(userKeysAccount.creator.toString()).to.equal(creator.publicKey.toString());
+      expect(userKeysAccount.totalSupply.toNumber()).to.be.greaterThan(0);
+      expect(userKeyAccount.subject.toString()).to.equal(creator.publicKey.toString());
+      expect(userKeyAccount.holderCount.toNumber()).to.equal(0);
     });
 
     it("Buys user keys", async () => {
@@ -178,7 +399,7 @@ describe("SolSocial", () => {
         const maxPrice = new anchor.BN(1000000);
 
         await program.methods
-          .buyKeys(amount, maxPrice)
+          .buyKeys(amount, maxPrice, null)
           .accounts({
             userKeys,
             bondingCurve,
@@ -209,7 +430,7 @@ describe("SolSocial", () => {
         const minPrice = new anchor.BN(1);
 
         await program.methods
-          .sellKeys(amount, minPrice)
+          .sellKeys(amount, minPrice, null)
           .accounts({
             userKeys,
             bondingCurve,
@@ -252,173 +473,4936 @@ describe("SolSocial", () => {
         throw error;
       }
     });
-  });
 
-  describe("Social Features", () => {
-    it("Creates social post", async () => {
-      try {
-        await program.methods
-          .createSocialPost("Hello SolSocial!", "general", [])
-          .accounts({
-            socialPost,
-            author: user.publicKey,
-            userReputation,
-            systemProgram: SystemProgram.programId,
-          })
-          .signers([user])
+    it("Caps the number of distinct markets a wallet can hold", async () => {
+      const [platformConfig] = PublicKey.findProgramAddressSync(
+        [Buffer.from("platform_config")],
+        program.programId
+      );
+      const [traderProfile] = PublicKey.findProgramAddressSync(
+        [Buffer.from("user_profile"), trader.publicKey.toBuffer()],
+        program.programId
+      );
+      const { maxPositions: previousMaxPositions } = await program.account.platformConfig.fetch(platformConfig);
+      const { activePositions } = await program.account.userProfile.fetch(traderProfile);
+
+      // Leave room for exactly two more markets on top of whatever earlier tests opened
+      const maxPositions = activePositions.add(new anchor.BN(2));
+      await program.methods
+        .setMaxPositions(maxPositions)
+        .accounts({ platformConfig, authority: wallet.publicKey })
+        .rpc();
+
+      const subjects = Array.from({ length: 3 }, () => Keypair.generate());
+      const buy = (subject: Keypair) =>
+        program.methods
+          .buyKeys(new anchor.BN(1), new anchor.BN(LAMPORTS_PER_SOL), null)
+          .accounts({ buyer: trader.publicKey, subject: subject.publicKey, platformConfig })
+          .signers([trader])
+          .rpc();
+      const sell = (subject: Keypair) =>
+        program.methods
+          .sellKeys(new anchor.BN(1), new anchor.BN(0), null)
+          .accounts({ seller: trader.publicKey, subject: subject.publicKey })
+          .signers([trader])
           .rpc();
 
-        const socialPostAccount = await program.account.socialPost.fetch(socialPost);
-        expect(socialPostAccount.author.toString()).to.equal(user.publicKey.toString());
-        expect(socialPostAccount.content).to.equal("Hello SolSocial!");
-        expect(socialPostAccount.category).to.equal("general");
-        expect(socialPostAccount.likes.toNumber()).to.equal(0);
-        expect(socialPostAccount.shares.toNumber()).to.equal(0);
+      const extra = subjects.pop();
+      for (const subject of subjects) {
+        await buy(subject);
+      }
+
+      // Topping up an existing position is always allowed
+      await buy(subjects[0]);
+
+      try {
+        await buy(extra);
+        expect.fail("opening a position beyond the cap should fail");
       } catch (error) {
-        console.error("Create social post error:", error);
-        throw error;
+        expect(error.error.errorCode.code).to.equal("ConnectionLimitExceeded");
       }
+
+      await sell(subjects[1]);
+      await buy(extra);
+
+      const { activePositions: finalPositions } = await program.account.userProfile.fetch(traderProfile);
+      expect(finalPositions.toString()).to.equal(maxPositions.toString());
+
+      await program.methods
+        .setMaxPositions(previousMaxPositions)
+        .accounts({ platformConfig, authority: wallet.publicKey })
+        .rpc();
     });
 
-    it("Likes social post", async () => {
-      try {
+    it("Waives the protocol fee on a new user's first trades", async () => {
+      const [platformConfig] = PublicKey.findProgramAddressSync(
+        [Buffer.from("platform_config")],
+        program.programId
+      );
+      const { freeTradesPerUser } = await program.account.platformConfig.fetch(platformConfig);
+      expect(freeTradesPerUser).to.be.greaterThan(0);
+      const newcomer = Keypair.generate();
+      await connection.confirmTransaction(
+        await connection.requestAirdrop(newcomer.publicKey, 5 * LAMPORTS_PER_SOL)
+      );
+
+      await program.methods
+        .initializeUser("newcomer", "Newcomer", "", "")
+        .accounts({ authority: newcomer.publicKey, platformConfig })
+        .signers([newcomer])
+        .rpc();
+
+      const purchases = [];
+      const listener = program.addEventListener("keysPurchased", (event) => purchases.push(event));
+
+      for (let i = 0; i <= freeTradesPerUser; i++) {
         await program.methods
-          .likeSocialPost()
-          .accounts({
-            socialPost,
-            user: trader.publicKey,
-            userReputation,
-          })
+          .buyKeys(new anchor.BN(1), new anchor.BN(LAMPORTS_PER_SOL), null)
+          .accounts({ buyer: newcomer.publicKey, subject: creator.publicKey, platformConfig })
+          .signers([newcomer])
+          .rpc();
+      }
+
+      await new Promise(resolve => setTimeout(resolve, 1000));
+      await program.removeEventListener(listener);
+
+      expect(purchases.length).to.equal(freeTradesPerUser + 1);
+      purchases.slice(0, freeTradesPerUser).forEach((event) => {
+        expect(event.protocolFee.toNumber()).to.equal(0);
+        expect(event.subjectFee.toNumber()).to.be.greaterThan(0);
+      });
+      expect(purchases[freeTradesPerUser].protocolFee.toNumber()).to.be.greaterThan(0);
+    });
+
+    it("Enforces the minimum interval between a holder's sells", async () => {
+      const [creatorKeys] = PublicKey.findProgramAddressSync(
+        [Buffer.from("user_keys"), creator.publicKey.toBuffer()],
+        program.programId
+      );
+      const sell = () =>
+        program.methods
+          .sellKeys(new anchor.BN(1), new anchor.BN(0), null)
+          .accounts({ seller: trader.publicKey, subject: creator.publicKey, userKeys: creatorKeys })
           .signers([trader])
           .rpc();
 
-        const socialPostAccount = await program.account.socialPost.fetch(socialPost);
-        expect(socialPostAccount.likes.toNumber()).to.equal(1);
+      await program.methods
+        .setSellInterval(new anchor.BN(2))
+        .accounts({ userKeys: creatorKeys, creator: creator.publicKey })
+        .signers([creator])
+        .rpc();
+
+      await sell();
+
+      try {
+        await sell();
+        expect.fail("an immediate second sell should be rejected");
       } catch (error) {
-        console.error("Like social post error:", error);
-        throw error;
+        expect(error.error.errorCode.code).to.equal("InteractionCooldown");
       }
+
+      await new Promise(resolve => setTimeout(resolve, 3000));
+      await sell();
+
+      await program.methods
+        .setSellInterval(new anchor.BN(0))
+        .accounts({ userKeys: creatorKeys, creator: creator.publicKey })
+        .signers([creator])
+        .rpc();
     });
 
-    it("Shares social post", async () => {
-      try {
-        await program.methods
-          .shareSocialPost()
-          .accounts({
-            socialPost,
-            user: trader.publicKey,
-            userReputation,
-          })
+    it("Only allows trading inside the market's trading window", async () => {
+      const [creatorKeys] = PublicKey.findProgramAddressSync(
+        [Buffer.from("user_keys"), creator.publicKey.toBuffer()],
+        program.programId
+      );
+      const setWindow = (open: number | null, close: number | null) =>
+        program.methods
+          .setTradingWindow(
+            open === null ? null : new anchor.BN(open),
+            close === null ? null : new anchor.BN(close)
+          )
+          .accounts({ userKeys: creatorKeys, creator: creator.publicKey })
+          .signers([creator])
+          .rpc();
+      const buy = () =>
+        program.methods
+          .buyKeys(new anchor.BN(1), new anchor.BN(LAMPORTS_PER_SOL), null)
+          .accounts({ buyer: trader.publicKey, subject: creator.publicKey, userKeys: creatorKeys })
           .signers([trader])
           .rpc();
+      const expectClosed = async () => {
+        try {
+          await buy();
+          expect.fail("trading outside the window should be rejected");
+        } catch (error) {
+          expect(error.error.errorCode.code).to.equal("MarketClosed");
+        }
+      };
+      const now = Math.floor(Date.now() / 1000);
 
-        const socialPostAccount = await program.account.socialPost.fetch(socialPost);
-        expect(socialPostAccount.shares.toNumber()).to.equal(1);
+      // Not open yet
+      await setWindow(now + 3600, now + 7200);
+      await expectClosed();
+
+      // Already closed
+      await setWindow(now - 7200, now - 3600);
+      await expectClosed();
+
+      await setWindow(now - 3600, now + 3600);
+      await buy();
+
+      // Unset bounds mean the market is always open
+      await setWindow(null, null);
+      await buy();
+    });
+
+    it("Never refunds more than was paid when buying and immediately selling", async () => {
+      const amount = new anchor.BN(3);
+      const purchases = [];
+      const sales = [];
+      const buyListener = program.addEventListener("keysPurchased", (event) => purchases.push(event));
+      const sellListener = program.addEventListener("keysSold", (event) => sales.push(event));
+
+      await program.methods
+        .buyKeys(amount, new anchor.BN(LAMPORTS_PER_SOL), null)
+        .accounts({ buyer: trader.publicKey, subject: creator.publicKey })
+        .signers([trader])
+        .rpc();
+      await program.methods
+        .sellKeys(amount, new anchor.BN(0), null)
+        .accounts({ seller: trader.publicKey, subject: creator.publicKey })
+        .signers([trader])
+        .rpc();
+
+      await new Promise(resolve => setTimeout(resolve, 1000));
+      await program.removeEventListener(buyListener);
+      await program.removeEventListener(sellListener);
+
+      const bought = purchases[0];
+      const sold = sales[0];
+      const paid = bought.price.add(bought.protocolFee).add(bought.subjectFee);
+      // Both sides quote the same curve, so the gross amounts match and fees only ever reduce the refund
+      expect(sold.price.toString()).to.equal(bought.price.toString());
+      expect(sold.sellerProceeds.lte(paid)).to.be.true;
+    });
+
+    it("Rejects a buy whose total cost exceeds max_cost", async () => {
+      const amount = new anchor.BN(2);
+      const buy = (maxCost: anchor.BN) =>
+        program.methods
+          .buyKeys(amount, maxCost, null)
+          .accounts({ buyer: trader.publicKey, subject: creator.publicKey })
+          .signers([trader]);
+
+      const { events } = await buy(new anchor.BN(LAMPORTS_PER_SOL)).simulate();
+      const quote = events.find((event) => event.name === "keysPurchased").data;
+      const totalCost = quote.price.add(quote.protocolFee).add(quote.subjectFee);
+
+      try {
+        await buy(totalCost.subn(1)).rpc();
+        expect.fail("a max_cost one lamport short of the quote should be rejected");
       } catch (error) {
-        console.error("Share social post error:", error);
-        throw error;
+        expect(error.error.errorCode.code).to.equal("SlippageExceeded");
       }
+
+      await buy(totalCost).rpc();
     });
-  });
 
-  describe("Chat Rooms", () => {
-    it("Creates chat room", async () => {
-      try {
-        await program.methods
-          .createChatRoom("VIP Chat", new anchor.BN(1))
-          .accounts({
-            chatRoom,
-            creator: creator.publicKey,
-            userKeys,
-            systemProgram: SystemProgram.programId,
-          })
-          .signers([creator])
+    it("Rejects a sell whose proceeds fall below min_proceeds", async () => {
+      const buy = (buyer: Keypair, amount: number) =>
+        program.methods
+          .buyKeys(new anchor.BN(amount), new anchor.BN(LAMPORTS_PER_SOL), null)
+          .accounts({ buyer: buyer.publicKey, subject: creator.publicKey })
+          .signers([buyer])
           .rpc();
+      const sell = (seller: Keypair, amount: number, minProceeds: anchor.BN) =>
+        program.methods
+          .sellKeys(new anchor.BN(amount), minProceeds, null)
+          .accounts({ seller: seller.publicKey, subject: creator.publicKey })
+          .signers([seller]);
 
-        const chatRoomAccount = await program.account.chatRoom.fetch(chatRoom);
-        expect(chatRoomAccount.creator.toString()).to.equal(creator.publicKey.toString());
-        expect(chatRoomAccount.name).to.equal("VIP Chat");
-        expect(chatRoomAccount.requiredKeys.toNumber()).to.equal(1);
-        expect(chatRoomAccount.isActive).to.be.true;
+      await buy(user, 2);
+      await buy(trader, 1);
+
+      const { events } = await sell(trader, 1, new anchor.BN(0)).simulate();
+      const quotedProceeds = events.find((event) => event.name === "keysSold").data.sellerProceeds;
+
+      // Another holder's sell lands first and lowers the supply
+      await sell(user, 2, new anchor.BN(0)).rpc();
+
+      try {
+        await sell(trader, 1, quotedProceeds).rpc();
+        expect.fail("a sell below the caller's floor should be rejected");
       } catch (error) {
-        console.error("Create chat room error:", error);
-        throw error;
+        expect(error.error.errorCode.code).to.equal("SlippageExceeded");
       }
+
+      await sell(trader, 1, new anchor.BN(0)).rpc();
     });
 
-    it("Joins chat room", async () => {
-      try {
-        await program.methods
-          .joinChatRoom()
-          .accounts({
-            chatRoom,
-            user: trader.publicKey,
-            userKeys,
-          })
+    it("Quotes match what buy_keys and sell_keys actually charge", async () => {
+      const amount = new anchor.BN(2);
+      const eventFrom = (events, name: string) => events.find((event) => event.name === name).data;
+
+      const buyQuote = eventFrom(
+        (await program.methods.quoteBuy(amount).accounts({ subject: creator.publicKey, trader: trader.publicKey }).simulate()).events,
+        "priceQuote"
+      );
+      const bought = eventFrom(
+        (await program.methods
+          .buyKeys(amount, new anchor.BN(LAMPORTS_PER_SOL), null)
+          .accounts({ buyer: trader.publicKey, subject: creator.publicKey })
+          .signers([trader])
+          .simulate()).events,
+        "keysPurchased"
+      );
+      expect(buyQuote.basePrice.toString()).to.equal(bought.price.toString());
+      expect(buyQuote.protocolFee.toString()).to.equal(bought.protocolFee.toString());
+      expect(buyQuote.subjectFee.toString()).to.equal(bought.subjectFee.toString());
+
+      const sellQuote = eventFrom(
+        (await program.methods.quoteSell(new anchor.BN(1)).accounts({ subject: creator.publicKey, trader: trader.publicKey }).simulate()).events,
+        "priceQuote"
+      );
+      const sold = eventFrom(
+        (await program.methods
+          .sellKeys(new anchor.BN(1), new anchor.BN(0), null)
+          .accounts({ seller: trader.publicKey, subject: creator.publicKey })
           .signers([trader])
+          .simulate()).events,
+        "keysSold"
+      );
+      expect(sellQuote.total.toString()).to.equal(sold.sellerProceeds.toString());
+    });
+
+    it("Falls back to the platform slippage default when a trade passes no bound", async () => {
+      const [platformConfig] = PublicKey.findProgramAddressSync(
+        [Buffer.from("platform_config")],
+        program.programId
+      );
+      const { defaultMaxSlippageBps, maxKeysPerTrade } = await program.account.platformConfig.fetch(platformConfig);
+      expect(defaultMaxSlippageBps).to.be.greaterThan(0);
+
+      const setDefault = (bps: number) =>
+        program.methods
+          .setDefaultMaxSlippage(bps)
+          .accounts({ platformConfig, authority: wallet.publicKey })
           .rpc();
+      const buy = (amount: number, maxCost: anchor.BN) =>
+        program.methods
+          .buyKeys(new anchor.BN(amount), maxCost, null)
+          .accounts({ buyer: trader.publicKey, subject: creator.publicKey, platformConfig })
+          .signers([trader]);
 
-        const chatRoomAccount = await program.account.chatRoom.fetch(chatRoom);
-        const isMember = chatRoomAccount.members.some(
-          member => member.toString() === trader.publicKey.toString()
-        );
-        expect(isMember).to.be.true;
+      try {
+        await setDefault(10001);
+        expect.fail("a default above 100% should be rejected");
       } catch (error) {
-        console.error("Join chat room error:", error);
-        throw error;
+        expect(error.error.errorCode.code).to.equal("InvalidPercentage");
       }
-    });
-  });
 
-  describe("Reputation System", () => {
-    it("Initializes user reputation", async () => {
+      // Nothing else has traded this slot, so an unbounded buy prices against itself
+      await buy(1, new anchor.BN(0)).rpc();
+
+      // A large buy landing first in the same transaction moves the price past a tight default
+      const largeOrder = 5000;
+      await program.methods
+        .setMaxKeysPerTrade(new anchor.BN(largeOrder))
+        .accounts({ platformConfig, authority: wallet.publicKey })
+        .rpc();
+      await setDefault(1);
+      const frontRun = await buy(largeOrder, new anchor.BN(1000 * LAMPORTS_PER_SOL)).instruction();
       try {
-        await program.methods
-          .initializeUserReputation()
-          .accounts({
-            userReputation,
-            user: user.publicKey,
-            systemProgram: SystemProgram.programId,
-          })
-          .signers([user])
+        await buy(1, new anchor.BN(0))
+          .preInstructions([ComputeBudgetProgram.setComputeUnitLimit({ units: 400_000 }), frontRun])
           .rpc();
+        expect.fail("a trade past the default slippage should be rejected");
+      } catch (error) {
+        expect(error.error.errorCode.code).to.equal("SlippageExceeded");
+      }
 
-        const userReputationAccount = await program.account.userReputation.fetch(userReputation);
-        expect(userReputationAccount.user.toString()).to.equal(user.publicKey.toString());
-        expect(userReputationAccount.score.toNumber()).to.equal(0);
-        expect(userReputationAccount.level.toNumber()).to.equal(1);
+      // An explicit bound overrides the default
+      await buy(1, new anchor.BN(LAMPORTS_PER_SOL))
+        .preInstructions([ComputeBudgetProgram.setComputeUnitLimit({ units: 400_000 }), frontRun])
+        .rpc();
+
+      // With no default either, a trade has to bring its own bound
+      await setDefault(0);
+      try {
+        await buy(1, new anchor.BN(0)).rpc();
+        expect.fail("a trade with no bound at all should be rejected");
       } catch (error) {
-        console.error("Initialize user reputation error:", error);
-        throw error;
+        expect(error.error.errorCode.code).to.equal("InvalidSlippageTolerance");
       }
+
+      await setDefault(defaultMaxSlippageBps);
+      await program.methods
+        .setMaxKeysPerTrade(maxKeysPerTrade)
+        .accounts({ platformConfig, authority: wallet.publicKey })
+        .rpc();
     });
 
-    it("Updates reputation score", async () => {
+    it("Buys 5000 keys in one call within the compute budget", async () => {
+      const [platformConfig] = PublicKey.findProgramAddressSync(
+        [Buffer.from("platform_config")],
+        program.programId
+      );
+      const amount = 5000;
+      const { maxKeysPerTrade } = await program.account.platformConfig.fetch(platformConfig);
+      expect(maxKeysPerTrade.toNumber()).to.equal(1000);
+
       try {
         await program.methods
-          .updateReputationScore(new anchor.BN(100))
-          .accounts({
-            userReputation,
-            authority: wallet.publicKey,
-          })
+          .buyKeys(maxKeysPerTrade.addn(1), new anchor.BN(1000 * LAMPORTS_PER_SOL), null)
+          .accounts({ buyer: trader.publicKey, subject: creator.publicKey, platformConfig })
+          .signers([trader])
           .rpc();
-
-        const userReputationAccount = await program.account.userReputation.fetch(userReputation);
-        expect(userReputationAccount.score.toNumber()).to.equal(100);
+        expect.fail("a buy above the per-trade cap should be rejected");
       } catch (error) {
-        console.error("Update reputation score error:", error);
-        throw error;
+        expect(error.error.errorCode.code).to.equal("ExceedsMaxAmount");
       }
-    });
-  });
 
-  describe("Revenue Distribution", () => {
-    it("Distributes trading fees", async () => {
-      try {
-        const totalFees = new anchor.BN(1000000);
+      await program.methods
+        .setMaxKeysPerTrade(new anchor.BN(amount))
+        .accounts({ platformConfig, authority: wallet.publicKey })
+        .rpc();
 
-        await program.methods
-          .distributeTradingFees(totalFees)
+      const signature = await program.methods
+        .buyKeys(new anchor.BN(amount), new anchor.BN(1000 * LAMPORTS_PER_SOL), null)
+        .accounts({ buyer: trader.publicKey, subject: creator.publicKey, platformConfig })
+        .signers([trader])
+        .rpc({ commitment: "confirmed" });
+
+      const tx = await connection.getTransaction(signature, {
+        commitment: "confirmed",
+        maxSupportedTransactionVersion: 0,
+      });
+      expect(tx.meta.err).to.be.null;
+      expect(tx.meta.computeUnitsConsumed).to.be.lessThan(200_000);
+
+      await program.methods
+        .setMaxKeysPerTrade(maxKeysPerTrade)
+        .accounts({ platformConfig, authority: wallet.publicKey })
+        .rpc();
+    });
+
+    it("Charges the platform launch fee when a key market is created", async () => {
+      const [platformConfig] = PublicKey.findProgramAddressSync(
+        [Buffer.from("platform_config")],
+        program.programId
+      );
+      const [protocolConfig] = PublicKey.findProgramAddressSync(
+        [Buffer.from("protocol_config")],
+        program.programId
+      );
+      const [protocolTreasury] = PublicKey.findProgramAddressSync(
+        [Buffer.from("protocol_treasury")],
+        program.programId
+      );
+      const launchFee = LAMPORTS_PER_SOL / 2;
+
+      const launch = async (owner: Keypair) => {
+        const [launchKeys] = PublicKey.findProgramAddressSync(
+          [Buffer.from("user_keys"), owner.publicKey.toBuffer()],
+          program.programId
+        );
+        const [keysMint] = PublicKey.findProgramAddressSync(
+          [Buffer.from("keys_mint"), owner.publicKey.toBuffer()],
+          program.programId
+        );
+        return program.methods
+          .createKeys(owner.publicKey, "Launch", "LNCH", "https://example.com/keys.json", "", [])
+          .accounts({
+            userKeys: launchKeys,
+            keysMint,
+            keysVault: await getAssociatedTokenAddress(keysMint, launchKeys, true),
+            creatorKeysAccount: await getAssociatedTokenAddress(keysMint, owner.publicKey),
+            payer: owner.publicKey,
+            protocolConfig,
+            protocolTreasury,
+            platformConfig,
+            systemProgram: SystemProgram.programId,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          })
+          .signers([owner])
+          .rpc();
+      };
+
+      const fund = async (owner: Keypair, lamports: number) => {
+        const sig = await connection.requestAirdrop(owner.publicKey, lamports);
+        await connection.confirmTransaction(sig);
+        // create_keys counts the launch against the creator's profile
+        const [ownerProfile] = PublicKey.findProgramAddressSync(
+          [Buffer.from("user_profile"), owner.publicKey.toBuffer()],
+          program.programId
+        );
+        await program.methods
+          .createUserProfile("launcher", "Opens a market", "https://example.com/launcher.jpg")
+          .accounts({ userProfile: ownerProfile, user: owner.publicKey, systemProgram: SystemProgram.programId })
+          .signers([owner])
+          .rpc();
+      };
+
+      await program.methods
+        .setLaunchFee(new anchor.BN(launchFee))
+        .accounts({ platformConfig, authority: wallet.publicKey })
+        .rpc();
+
+      const paying = Keypair.generate();
+      await fund(paying, 2 * LAMPORTS_PER_SOL);
+      const treasuryBefore = await connection.getBalance(protocolTreasury);
+      await launch(paying);
+      const treasuryAfter = await connection.getBalance(protocolTreasury);
+      // The treasury also receives the protocol fee on the creator's initial keys
+      expect(treasuryAfter - treasuryBefore).to.be.at.least(launchFee);
+
+      // The market's fee and holder bookkeeping is created with it, so it can trade right away
+      const [launchKey] = PublicKey.findProgramAddressSync(
+        [Buffer.from("user_key"), paying.publicKey.toBuffer()],
+        program.programId
+      );
+      const launched = await program.account.userKey.fetch(launchKey);
+      const { maxSubjectFeePercent } = await program.account.platformConfig.fetch(platformConfig);
+      expect(launched.subject.toString()).to.equal(paying.publicKey.toString());
+      expect(launched.subjectFeePercent).to.be.at.most(maxSubjectFeePercent);
+      expect(launched.holderCount.toNumber()).to.equal(0);
+
+      const broke = Keypair.generate();
+      await fund(broke, LAMPORTS_PER_SOL / 10);
+      try {
+        await launch(broke);
+        expect.fail("a creator who cannot cover the launch fee should be rejected");
+      } catch (error) {
+        expect(error.error.errorCode.code).to.equal("InsufficientFunds");
+      }
+
+      await program.methods
+        .setLaunchFee(new anchor.BN(0))
+        .accounts({ platformConfig, authority: wallet.publicKey })
+        .rpc();
+
+      const free = Keypair.generate();
+      await fund(free, LAMPORTS_PER_SOL / 10);
+      const freeBefore = await connection.getBalance(protocolTreasury);
+      await launch(free);
+      const freeAfter = await connection.getBalance(protocolTreasury);
+      expect(freeAfter - freeBefore).to.be.lessThan(launchFee);
+    });
+
+    it("Transfers keys to a wallet that has never held them", async () => {
+      const [userKey] = PublicKey.findProgramAddressSync(
+        [Buffer.from("user_key"), creator.publicKey.toBuffer()],
+        program.programId
+      );
+      const holderOf = (wallet: PublicKey) =>
+        PublicKey.findProgramAddressSync(
+          [Buffer.from("key_holder"), userKey.toBuffer(), wallet.toBuffer()],
+          program.programId
+        )[0];
+      const profileOf = (wallet: PublicKey) =>
+        PublicKey.findProgramAddressSync(
+          [Buffer.from("user_profile"), wallet.toBuffer()],
+          program.programId
+        )[0];
+      const transfer = (amount: number, recipient: PublicKey) =>
+        program.methods
+          .transferKeys(new anchor.BN(amount), recipient)
+          .accounts({
+            sender: trader.publicKey,
+            subject: creator.publicKey,
+            senderHolder: holderOf(trader.publicKey),
+            recipientHolder: holderOf(recipient),
+            senderProfile: profileOf(trader.publicKey),
+            recipientProfile: profileOf(recipient),
+          })
+          .signers([trader])
+          .rpc();
+
+      const [creatorKeys] = PublicKey.findProgramAddressSync(
+        [Buffer.from("user_keys"), creator.publicKey.toBuffer()],
+        program.programId
+      );
+      const [platformConfig] = PublicKey.findProgramAddressSync(
+        [Buffer.from("platform_config")],
+        program.programId
+      );
+      const friend = Keypair.generate();
+      await connection.confirmTransaction(await connection.requestAirdrop(friend.publicKey, LAMPORTS_PER_SOL));
+      await program.methods
+        .initializeUser("gift_friend", "gift_friend", "", "")
+        .accounts({ authority: friend.publicKey, platformConfig })
+        .signers([friend])
+        .rpc();
+      const before = await program.account.keyHolder.fetch(holderOf(trader.publicKey));
+      const senderReputation = (await program.account.userProfile.fetch(profileOf(trader.publicKey))).reputationScore;
+      const friendReputation = (await program.account.userProfile.fetch(profileOf(friend.publicKey))).reputationScore;
+      const { totalSupply } = await program.account.userKeys.fetch(creatorKeys);
+      const { holderCount } = await program.account.userKey.fetch(userKey);
+
+      await transfer(1, friend.publicKey);
+      expect((await program.account.userKey.fetch(userKey)).holderCount.toNumber()).to.equal(holderCount.toNumber() + 1);
+
+      const gifted = await program.account.keyHolder.fetch(holderOf(friend.publicKey));
+      expect(gifted.holder.toString()).to.equal(friend.publicKey.toString());
+      expect(gifted.subject.toString()).to.equal(creator.publicKey.toString());
+      expect(gifted.amount.toNumber()).to.equal(1);
+      // Receiving a gift isn't a trade, so it doesn't restart the recipient's sell interval
+      expect(gifted.lastTradeAt.toNumber()).to.equal(0);
+
+      // The reputation those keys earned goes with them
+      const senderReputationAfter = (await program.account.userProfile.fetch(profileOf(trader.publicKey))).reputationScore;
+      const friendReputationAfter = (await program.account.userProfile.fetch(profileOf(friend.publicKey))).reputationScore;
+      expect(senderReputation.sub(senderReputationAfter).toNumber()).to.equal(10);
+      expect(friendReputationAfter.sub(friendReputation).toNumber()).to.equal(10);
+
+      const after = await program.account.keyHolder.fetch(holderOf(trader.publicKey));
+      expect(before.amount.sub(after.amount).toNumber()).to.equal(1);
+      expect((await program.account.userKeys.fetch(creatorKeys)).totalSupply.toString()).to.equal(totalSupply.toString());
+
+      // Gifting is a way out of the market too, so it waits out the sell interval
+      await program.methods
+        .setSellInterval(new anchor.BN(60))
+        .accounts({ userKeys: creatorKeys, creator: creator.publicKey })
+        .signers([creator])
+        .rpc();
+      try {
+        await transfer(1, friend.publicKey);
+        expect.fail("a transfer inside the sell interval should be rejected");
+      } catch (error) {
+        expect(error.error.errorCode.code).to.equal("InteractionCooldown");
+      }
+      await program.methods
+        .setSellInterval(new anchor.BN(0))
+        .accounts({ userKeys: creatorKeys, creator: creator.publicKey })
+        .signers([creator])
+        .rpc();
+
+      try {
+        await transfer(after.amount.toNumber() + 1, friend.publicKey);
+        expect.fail("transferring more than the balance should fail");
+      } catch (error) {
+        expect(error.error.errorCode.code).to.equal("InsufficientKeys");
+      }
+
+      try {
+        await transfer(1, trader.publicKey);
+        expect.fail("self-transfers should be rejected");
+      } catch (error) {
+        expect(error.error.errorCode.code).to.equal("SelfTransfer");
+      }
+    });
+
+    it("Discounts the protocol fee for buyers holding many markets", async () => {
+      const [platformConfig] = PublicKey.findProgramAddressSync(
+        [Buffer.from("platform_config")],
+        program.programId
+      );
+      const { freeTradesPerUser } = await program.account.platformConfig.fetch(platformConfig);
+      const discountBps = 5000;
+
+      const newUser = async (name: string) => {
+        const wallet = Keypair.generate();
+        await connection.confirmTransaction(await connection.requestAirdrop(wallet.publicKey, 5 * LAMPORTS_PER_SOL));
+        await program.methods
+          .initializeUser(name, name, "", "")
+          .accounts({ authority: wallet.publicKey, platformConfig })
+          .signers([wallet])
+          .rpc();
+        return wallet;
+      };
+      const buy = (buyer: Keypair, subject: PublicKey) =>
+        program.methods
+          .buyKeys(new anchor.BN(1), new anchor.BN(LAMPORTS_PER_SOL), null)
+          .accounts({ buyer: buyer.publicKey, subject, platformConfig })
+          .signers([buyer]);
+
+      const secondMarket = await newUser("second_market");
+      const single = await newUser("single_holder");
+      const diversified = await newUser("diversified");
+
+      // Both open a creator position and use up their free trades so both pay the protocol fee
+      for (let i = 0; i < Math.max(freeTradesPerUser, 1); i++) {
+        await buy(single, creator.publicKey).rpc();
+        await buy(diversified, creator.publicKey).rpc();
+      }
+      await buy(diversified, secondMarket.publicKey).rpc();
+
+      await program.methods
+        .setMultiHolderDiscount(new anchor.BN(2), discountBps)
+        .accounts({ platformConfig, authority: wallet.publicKey })
+        .rpc();
+
+      // Simulated against the same supply, so only the fee rate differs
+      const protocolFee = async (buyer: Keypair) => {
+        const { events } = await buy(buyer, creator.publicKey).simulate();
+        return events.find((event) => event.name === "keysPurchased").data.protocolFee.toNumber();
+      };
+      const standardFee = await protocolFee(single);
+      const discountedFee = await protocolFee(diversified);
+
+      expect(standardFee).to.be.greaterThan(0);
+      expect(discountedFee).to.be.lessThan(standardFee);
+      expect(discountedFee).to.be.closeTo(standardFee / 2, 1);
+
+      await program.methods
+        .setMultiHolderDiscount(new anchor.BN(0), 0)
+        .accounts({ platformConfig, authority: wallet.publicKey })
+        .rpc();
+    });
+
+    it("Stores a key market's description and links and validates their limits", async () => {
+      const [creatorKeys] = PublicKey.findProgramAddressSync(
+        [Buffer.from("user_keys"), creator.publicKey.toBuffer()],
+        program.programId
+      );
+      const update = (description: string, links: string[]) =>
+        program.methods
+          .updateKeysMetadata(description, links)
+          .accounts({ userKeys: creatorKeys, creator: creator.publicKey })
+          .signers([creator])
+          .rpc();
+      const expectError = async (attempt: Promise<string>, code: string) => {
+        try {
+          await attempt;
+          expect.fail(`expected ${code}`);
+        } catch (error) {
+          expect(error.error.errorCode.code).to.equal(code);
+        }
+      };
+
+      const description = "Weekly market notes and an open trading journal.";
+      const links = ["https://example.com", "https://x.com/creator"];
+      await update(description, links);
+
+      const keys = await program.account.userKeys.fetch(creatorKeys);
+      expect(keys.description).to.equal(description);
+      expect(keys.links).to.deep.equal(links);
+
+      await expectError(update("d".repeat(257), []), "DescriptionTooLong");
+      await expectError(update("", ["a", "b", "c", "d"]), "TooManyLinks");
+      await expectError(update("", [`https://${"l".repeat(100)}`]), "LinkTooLong");
+
+      const unchanged = await program.account.userKeys.fetch(creatorKeys);
+      expect(unchanged.description).to.equal(description);
+    });
+
+    it("Buys a basket of markets atomically, reverting every leg if one fails", async () => {
+      const [platformConfig] = PublicKey.findProgramAddressSync(
+        [Buffer.from("platform_config")],
+        program.programId
+      );
+      const subjects = [creator.publicKey, user.publicKey];
+      const pda = (seeds: Buffer[]) => PublicKey.findProgramAddressSync(seeds, program.programId)[0];
+      const keyHolder = (subject: PublicKey) =>
+        pda([
+          Buffer.from("key_holder"),
+          pda([Buffer.from("user_key"), subject.toBuffer()]).toBuffer(),
+          trader.publicKey.toBuffer(),
+        ]);
+      const legAccounts = async (subject: PublicKey) => {
+        const subjectKeys = pda([Buffer.from("user_keys"), subject.toBuffer()]);
+        const { creator: marketCreator } = await program.account.userKeys.fetch(subjectKeys);
+        return [
+          pda([Buffer.from("user_profile"), subject.toBuffer()]),
+          subjectKeys,
+          await getAssociatedTokenAddress(NATIVE_MINT, subject),
+          keyHolder(subject),
+          pda([Buffer.from("user_key"), subject.toBuffer()]),
+          pda([Buffer.from("user_profile"), marketCreator.toBuffer()]),
+        ].map((pubkey) => ({ pubkey, isSigner: false, isWritable: true }));
+      };
+      const balances = async () =>
+        Promise.all(
+          subjects.map(async (subject) => {
+            const account = await program.account.keyHolder.fetchNullable(keyHolder(subject));
+            return account ? account.amount.toString() : "0";
+          })
+        );
+
+      const remainingAccounts = (await Promise.all(subjects.map(legAccounts))).flat();
+      const creatorKeys = pda([Buffer.from("user_keys"), creator.publicKey.toBuffer()]);
+      const traderTokenAccount = await getAssociatedTokenAddress(NATIVE_MINT, trader.publicKey);
+      const firstLegState = async () => {
+        const market = await program.account.userKeys.fetch(creatorKeys);
+        const { amount } = await getAccount(provider.connection, traderTokenAccount);
+        return { supply: market.supply.toString(), paid: amount.toString() };
+      };
+      const before = await balances();
+      const firstLegBefore = await firstLegState();
+
+      // Budget exactly the first leg, so it executes and the second leg trips the cap mid-batch
+      const firstLegQuote = (await program.methods
+        .quoteBuy(new anchor.BN(1))
+        .accounts({ subject: creator.publicKey, trader: trader.publicKey })
+        .simulate()).events.find((event) => event.name === "priceQuote").data;
+      try {
+        await program.methods
+          .buyKeysMulti(
+            [
+              { subject: creator.publicKey, amount: new anchor.BN(1) },
+              { subject: user.publicKey, amount: new anchor.BN(1) },
+            ],
+            firstLegQuote.total
+          )
+          .accounts({ buyer: trader.publicKey, platformConfig, paymentMint: NATIVE_MINT })
+          .remainingAccounts(remainingAccounts)
+          .signers([trader])
+          .rpc();
+        expect.fail("a batch whose second leg exceeds the budget should revert");
+      } catch (error) {
+        expect(error.error.errorCode.code).to.equal("SlippageExceeded");
+      }
+
+      // Nothing from the first leg sticks: no keys, no supply change, no payment
+      expect(await balances()).to.deep.equal(before);
+      expect(await firstLegState()).to.deep.equal(firstLegBefore);
+    });
+
+    it("Rejects buys below the configurable minimum amount", async () => {
+      const [platformConfig] = PublicKey.findProgramAddressSync(
+        [Buffer.from("platform_config")],
+        program.programId
+      );
+      const setMinimum = (minBuyAmount: number) =>
+        program.methods
+          .setMinBuyAmount(new anchor.BN(minBuyAmount))
+          .accounts({ platformConfig, authority: wallet.publicKey })
+          .rpc();
+      const buy = (amount: number) =>
+        program.methods
+          .buyKeys(new anchor.BN(amount), new anchor.BN(10 * LAMPORTS_PER_SOL), null)
+          .accounts({ buyer: trader.publicKey, subject: creator.publicKey, platformConfig })
+          .signers([trader])
+          .rpc();
+
+      expect((await program.account.platformConfig.fetch(platformConfig)).minBuyAmount.toNumber()).to.equal(1);
+
+      const minimum = 3;
+      await setMinimum(minimum);
+
+      try {
+        await buy(minimum - 1);
+        expect.fail("a buy below the minimum should be rejected");
+      } catch (error) {
+        expect(error.error.errorCode.code).to.equal("MinimumKeyAmountNotMet");
+      }
+      await buy(minimum);
+
+      await setMinimum(1);
+    });
+
+    it("Places, expires, and fills limit orders against the curve", async () => {
+      const [creatorProfile] = PublicKey.findProgramAddressSync(
+        [Buffer.from("user_profile"), creator.publicKey.toBuffer()],
+        program.programId
+      );
+      const [traderProfile] = PublicKey.findProgramAddressSync(
+        [Buffer.from("user_profile"), trader.publicKey.toBuffer()],
+        program.programId
+      );
+      const [creatorKey] = PublicKey.findProgramAddressSync(
+        [Buffer.from("user_key"), creator.publicKey.toBuffer()],
+        program.programId
+      );
+      const [traderHolding] = PublicKey.findProgramAddressSync(
+        [Buffer.from("key_holder"), creatorKey.toBuffer(), trader.publicKey.toBuffer()],
+        program.programId
+      );
+      const [escrowAccount] = PublicKey.findProgramAddressSync(
+        [Buffer.from("escrow"), creator.publicKey.toBuffer()],
+        program.programId
+      );
+      const [platformConfig] = PublicKey.findProgramAddressSync(
+        [Buffer.from("platform_config")],
+        program.programId
+      );
+      const orderAddress = (orderId: number) =>
+        PublicKey.findProgramAddressSync(
+          [
+            Buffer.from("order"),
+            trader.publicKey.toBuffer(),
+            creator.publicKey.toBuffer(),
+            new anchor.BN(orderId).toArrayLike(Buffer, "le", 8),
+          ],
+          program.programId
+        )[0];
+      const placeBuy = (orderId: number, targetPrice: anchor.BN, expiresAt: number) =>
+        program.methods
+          .placeOrder(
+            new anchor.BN(orderId),
+            { buy: {} },
+            targetPrice,
+            new anchor.BN(1),
+            new anchor.BN(LAMPORTS_PER_SOL),
+            new anchor.BN(expiresAt)
+          )
+          .accounts({
+            order: orderAddress(orderId),
+            userProfile: creatorProfile,
+            subject: creator.publicKey,
+            keyHolder: traderHolding,
+            platformConfig,
+            owner: trader.publicKey,
+          })
+          .signers([trader])
+          .rpc();
+      const fill = (orderId: number) =>
+        program.methods
+          .fillOrder()
+          .accounts({
+            order: orderAddress(orderId),
+            owner: trader.publicKey,
+            subject: creator.publicKey,
+            ownerProfile: traderProfile,
+            userProfile: creatorProfile,
+            creatorProfile,
+            keyHolder: traderHolding,
+            escrowAccount,
+            platform: platformConfig,
+            keeper: wallet.publicKey,
+          })
+          .rpc();
+      const now = () => Math.floor(Date.now() / 1000);
+
+      // Pump the curve so a buy order at today's price sits below the market
+      const targetPrice = (await program.account.userProfile.fetch(creatorProfile)).keyPrice;
+      await program.methods
+        .buyUserKeys(new anchor.BN(10))
+        .accounts({ buyer: user.publicKey, userProfile: creatorProfile, buyerProfile: userProfile, tradeLedger: null, tradeReceipt: null })
+        .signers([user])
+        .rpc();
+
+      await placeBuy(1, targetPrice, now() + 3600);
+      const order = await program.account.order.fetch(orderAddress(1));
+      expect(order.targetPrice.toString()).to.equal(targetPrice.toString());
+      expect(order.amount.toNumber()).to.equal(1);
+
+      try {
+        await fill(1);
+        expect.fail("the order should not fill above its target price");
+      } catch (error) {
+        expect(error.error.errorCode.code).to.equal("PriceOutOfRange");
+      }
+
+      // Expired orders can't be filled, only cancelled
+      await placeBuy(2, targetPrice, now() + 2);
+      await new Promise(resolve => setTimeout(resolve, 3000));
+      try {
+        await fill(2);
+        expect.fail("an expired order should not fill");
+      } catch (error) {
+        expect(error.error.errorCode.code).to.equal("OrderExpired");
+      }
+      await program.methods
+        .cancelOrder()
+        .accounts({ order: orderAddress(2), keyHolder: traderHolding, owner: trader.publicKey })
+        .signers([trader])
+        .rpc();
+      expect(await connection.getAccountInfo(orderAddress(2))).to.be.null;
+
+      // Once the price drops back to the target, any keeper can fill
+      const keysBefore = (await program.account.keyHolder.fetch(traderHolding)).amount.toNumber();
+      await program.methods
+        .sellUserKeys(new anchor.BN(10))
+        .accounts({ seller: user.publicKey, userProfile: creatorProfile, sellerProfile: userProfile, tradeLedger: null, tradeReceipt: null })
+        .signers([user])
+        .rpc();
+      const creatorBalanceBefore = await connection.getBalance(creator.publicKey);
+      await fill(1);
+
+      expect(await connection.getAccountInfo(orderAddress(1))).to.be.null;
+      expect((await program.account.keyHolder.fetch(traderHolding)).amount.toNumber()).to.equal(keysBefore + 1);
+      // The fill pays the subject fee out rather than leaving it in escrow
+      expect(await connection.getBalance(creator.publicKey)).to.be.greaterThan(creatorBalanceBefore);
+    });
+
+    it("Pays creators their own fee rate, capped by governance", async () => {
+      const [platformConfig] = PublicKey.findProgramAddressSync(
+        [Buffer.from("platform_config")],
+        program.programId
+      );
+      const [userKey] = PublicKey.findProgramAddressSync(
+        [Buffer.from("user_key"), creator.publicKey.toBuffer()],
+        program.programId
+      );
+      const setFee = (subjectFeePercent: number) =>
+        program.methods
+          .setKeyFee(subjectFeePercent)
+          .accounts({ userKey, platformConfig, subject: creator.publicKey })
+          .signers([creator])
+          .rpc();
+      const simulatedBuy = async () =>
+        (await program.methods
+          .buyKeys(new anchor.BN(1), new anchor.BN(LAMPORTS_PER_SOL), null)
+          .accounts({ buyer: trader.publicKey, subject: creator.publicKey })
+          .signers([trader])
+          .simulate()).events.find((event) => event.name === "keysPurchased").data;
+
+      const { subjectFeePercent } = await program.account.userKey.fetch(userKey);
+      const { maxSubjectFeePercent } = await program.account.platformConfig.fetch(platformConfig);
+
+      await setFee(100);
+      const lowFee = await simulatedBuy();
+      await setFee(500);
+      const highFee = await simulatedBuy();
+      expect(highFee.price.toString()).to.equal(lowFee.price.toString());
+      expect(highFee.subjectFee.gt(lowFee.subjectFee)).to.be.true;
+      expect(highFee.protocolFee.toString()).to.equal(lowFee.protocolFee.toString());
+
+      try {
+        await setFee(maxSubjectFeePercent + 1);
+        expect.fail("a fee above the governance cap should be rejected");
+      } catch (error) {
+        expect(error.error.errorCode.code).to.equal("InvalidCreatorFee");
+      }
+
+      await setFee(subjectFeePercent);
+    });
+
+    it("Stops subjects buying their own keys past the initial allocation", async () => {
+      const [platformConfig] = PublicKey.findProgramAddressSync(
+        [Buffer.from("platform_config")],
+        program.programId
+      );
+      const setCap = (enabled: boolean) =>
+        program.methods
+          .setCapSelfHoldings(enabled)
+          .accounts({ platformConfig, authority: wallet.publicKey })
+          .rpc();
+      const buy = (buyer: Keypair, amount: number) =>
+        program.methods
+          .buyKeys(new anchor.BN(amount), new anchor.BN(10 * LAMPORTS_PER_SOL), null)
+          .accounts({ buyer: buyer.publicKey, subject: creator.publicKey, platformConfig })
+          .signers([buyer])
+          .rpc();
+
+      await setCap(true);
+
+      // Creators start with a single key, so they may hold one but never two
+      await buy(creator, 1);
+      try {
+        await buy(creator, 1);
+        expect.fail("a creator should not be able to buy past their initial allocation");
+      } catch (error) {
+        expect(error.error.errorCode.code).to.equal("SelfInteractionNotAllowed");
+      }
+
+      // Everyone else trades as usual
+      await buy(trader, 2);
+
+      await setCap(false);
+    });
+
+    it("Records trade receipts only for opted-in traders", async () => {
+      const [creatorProfile] = PublicKey.findProgramAddressSync(
+        [Buffer.from("user_profile"), creator.publicKey.toBuffer()],
+        program.programId
+      );
+      const [traderProfile] = PublicKey.findProgramAddressSync(
+        [Buffer.from("user_profile"), trader.publicKey.toBuffer()],
+        program.programId
+      );
+      const [tradeLedger] = PublicKey.findProgramAddressSync(
+        [Buffer.from("trade_ledger"), trader.publicKey.toBuffer(), creator.publicKey.toBuffer()],
+        program.programId
+      );
+      const receiptAddress = (sequence: number) =>
+        PublicKey.findProgramAddressSync(
+          [Buffer.from("trade_receipt"), tradeLedger.toBuffer(), new anchor.BN(sequence).toArrayLike(Buffer, "le", 8)],
+          program.programId
+        )[0];
+      const setReceipts = (enabled: boolean) =>
+        program.methods
+          .setTradeReceipts(enabled)
+          .accounts({ tradeLedger, subject: creator.publicKey, owner: trader.publicKey })
+          .signers([trader])
+          .rpc();
+
+      await setReceipts(true);
+      await program.methods
+        .buyUserKeys(new anchor.BN(2))
+        .accounts({
+          buyer: trader.publicKey,
+          userProfile: creatorProfile,
+          buyerProfile: traderProfile,
+          tradeLedger,
+          tradeReceipt: receiptAddress(0),
+        })
+        .signers([trader])
+        .rpc();
+
+      const receipt = await program.account.tradeReceipt.fetch(receiptAddress(0));
+      expect(receipt.owner.toString()).to.equal(trader.publicKey.toString());
+      expect(receipt.subject.toString()).to.equal(creator.publicKey.toString());
+      expect(receipt.sequence.toNumber()).to.equal(0);
+      expect(receipt.tradeType).to.deep.equal({ buy: {} });
+      expect(receipt.amount.toNumber()).to.equal(2);
+      expect(receipt.price.toNumber()).to.be.greaterThan(0);
+      expect(receipt.fees.toNumber()).to.be.greaterThan(0);
+      expect(receipt.realizedPnl.toNumber()).to.equal(0);
+      expect(receipt.timestamp.toNumber()).to.be.greaterThan(0);
+
+      // Selling at a lower point on the curve, after fees, realizes a loss against the buy
+      await program.methods
+        .sellUserKeys(new anchor.BN(2))
+        .accounts({
+          seller: trader.publicKey,
+          userProfile: creatorProfile,
+          sellerProfile: traderProfile,
+          tradeLedger,
+          tradeReceipt: receiptAddress(1),
+        })
+        .signers([trader])
+        .rpc();
+      const sellReceipt = await program.account.tradeReceipt.fetch(receiptAddress(1));
+      expect(sellReceipt.tradeType).to.deep.equal({ sell: {} });
+      expect(sellReceipt.realizedPnl.toNumber()).to.be.lessThan(0);
+
+      await setReceipts(false);
+      await program.methods
+        .buyUserKeys(new anchor.BN(1))
+        .accounts({
+          buyer: trader.publicKey,
+          userProfile: creatorProfile,
+          buyerProfile: traderProfile,
+          tradeLedger,
+          tradeReceipt: null,
+        })
+        .signers([trader])
+        .rpc();
+
+      expect(await provider.connection.getAccountInfo(receiptAddress(2))).to.be.null;
+      const ledger = await program.account.tradeLedger.fetch(tradeLedger);
+      expect(ledger.nextSequence.toNumber()).to.equal(2);
+
+      // buy_keys and sell_keys write to the same ledger, continuing its sequence
+      await setReceipts(true);
+      await program.methods
+        .buyKeys(new anchor.BN(1), new anchor.BN(10 * LAMPORTS_PER_SOL), null)
+        .accounts({ buyer: trader.publicKey, subject: creator.publicKey, tradeLedger, tradeReceipt: receiptAddress(2) })
+        .signers([trader])
+        .rpc();
+      await program.methods
+        .sellKeys(new anchor.BN(1), new anchor.BN(0), null)
+        .accounts({ seller: trader.publicKey, subject: creator.publicKey, tradeLedger, tradeReceipt: receiptAddress(3) })
+        .signers([trader])
+        .rpc();
+
+      const keysBuyReceipt = await program.account.tradeReceipt.fetch(receiptAddress(2));
+      expect(keysBuyReceipt.tradeType).to.deep.equal({ buy: {} });
+      expect(keysBuyReceipt.amount.toNumber()).to.equal(1);
+      expect(keysBuyReceipt.fees.toNumber()).to.be.greaterThan(0);
+      const keysSellReceipt = await program.account.tradeReceipt.fetch(receiptAddress(3));
+      expect(keysSellReceipt.tradeType).to.deep.equal({ sell: {} });
+      expect(keysSellReceipt.sequence.toNumber()).to.equal(3);
+      expect(keysSellReceipt.realizedPnl.toNumber()).to.be.lessThan(0);
+
+      await setReceipts(false);
+    });
+
+    it("Pays referrers a creator's custom bonus on their market and the global rate otherwise", async () => {
+      const pda = (seeds: Buffer[]) => PublicKey.findProgramAddressSync(seeds, program.programId)[0];
+      const platformConfig = pda([Buffer.from("platform_config")]);
+      const creatorProfile = pda([Buffer.from("user_profile"), creator.publicKey.toBuffer()]);
+      const creatorKeys = pda([Buffer.from("user_keys"), creator.publicKey.toBuffer()]);
+      const traderProfile = pda([Buffer.from("user_profile"), trader.publicKey.toBuffer()]);
+      const referrerProfile = pda([Buffer.from("user_profile"), user.publicKey.toBuffer()]);
+      const tradeLedger = pda([Buffer.from("trade_ledger"), trader.publicKey.toBuffer(), creator.publicKey.toBuffer()]);
+      const receiptAddress = (sequence: anchor.BN) =>
+        pda([Buffer.from("trade_receipt"), tradeLedger.toBuffer(), sequence.toArrayLike(Buffer, "le", 8)]);
+      const setReceipts = (enabled: boolean) =>
+        program.methods
+          .setTradeReceipts(enabled)
+          .accounts({ tradeLedger, subject: creator.publicKey, owner: trader.publicKey })
+          .signers([trader])
+          .rpc();
+      const setReferralBonus = (bps: number | null) =>
+        program.methods
+          .setReferralBonus(bps)
+          .accounts({ userKeys: creatorKeys, creator: creator.publicKey })
+          .signers([creator])
+          .rpc();
+      // Buys one key naming `user` as referrer, returning the curve price and what `user` earned
+      const referredBuy = async () => {
+        const { nextSequence } = await program.account.tradeLedger.fetch(tradeLedger);
+        const referrerBefore = await connection.getBalance(user.publicKey);
+        await program.methods
+          .buyUserKeys(new anchor.BN(1))
+          .accounts({
+            buyer: trader.publicKey,
+            userProfile: creatorProfile,
+            buyerProfile: traderProfile,
+            tradeLedger,
+            tradeReceipt: receiptAddress(nextSequence),
+            referrerProfile,
+            referrer: user.publicKey,
+          })
+          .signers([trader])
+          .rpc();
+        const { price } = await program.account.tradeReceipt.fetch(receiptAddress(nextSequence));
+        return { price: price.toNumber(), earned: (await connection.getBalance(user.publicKey)) - referrerBefore };
+      };
+
+      // Fees wide enough to fund the whole referral from the protocol and creator shares
+      const before = await program.account.platformConfig.fetch(platformConfig);
+      await program.methods
+        .updatePlatformSettings(new anchor.BN(250), new anchor.BN(500), null, null)
+        .accounts({ platform: platformConfig, authority: wallet.publicKey })
+        .rpc();
+      await setReceipts(true);
+
+      // 3%: the global 1% from the protocol fee, the other 2% from the creator's fee
+      await setReferralBonus(300);
+      const custom = await referredBuy();
+      expect(custom.earned).to.equal(Math.floor(custom.price * 100 / 10000) + Math.floor(custom.price * 200 / 10000));
+
+      await setReferralBonus(null);
+      const global = await referredBuy();
+      expect(global.earned).to.equal(Math.floor(global.price * 100 / 10000));
+
+      // Nobody earns a referral on their own trades
+      try {
+        await program.methods
+          .buyUserKeys(new anchor.BN(1))
+          .accounts({
+            buyer: trader.publicKey,
+            userProfile: creatorProfile,
+            buyerProfile: traderProfile,
+            tradeLedger: null,
+            tradeReceipt: null,
+            referrerProfile: traderProfile,
+            referrer: trader.publicKey,
+          })
+          .signers([trader])
+          .rpc();
+        expect.fail("a buyer should not be able to refer themselves");
+      } catch (error) {
+        expect(error.error.errorCode.code).to.equal("SelfInteractionNotAllowed");
+      }
+
+      await setReceipts(false);
+      await program.methods
+        .sellUserKeys(new anchor.BN(2))
+        .accounts({ seller: trader.publicKey, userProfile: creatorProfile, sellerProfile: traderProfile, tradeLedger: null, tradeReceipt: null })
+        .signers([trader])
+        .rpc();
+      await program.methods
+        .updatePlatformSettings(before.feeRate, before.creatorFeeRate, null, null)
+        .accounts({ platform: platformConfig, authority: wallet.publicKey })
+        .rpc();
+    });
+
+    it("Caps a creator's total supply across their key markets", async () => {
+      const pda = (seeds: Buffer[]) => PublicKey.findProgramAddressSync(seeds, program.programId)[0];
+      const platformConfig = pda([Buffer.from("platform_config")]);
+      const protocolConfig = pda([Buffer.from("protocol_config")]);
+      const protocolTreasury = pda([Buffer.from("protocol_treasury")]);
+      const setCap = (cap: anchor.BN) =>
+        program.methods
+          .setMaxCreatorSupply(cap)
+          .accounts({ platformConfig, authority: wallet.publicKey })
+          .rpc();
+      const { maxCreatorSupply } = await program.account.platformConfig.fetch(platformConfig);
+      expect(maxCreatorSupply.toNumber()).to.be.greaterThan(0);
+
+      const issuer = Keypair.generate();
+      await connection.confirmTransaction(await connection.requestAirdrop(issuer.publicKey, 5 * LAMPORTS_PER_SOL));
+      const issuerProfile = pda([Buffer.from("user_profile"), issuer.publicKey.toBuffer()]);
+      await program.methods
+        .createUserProfile("issuer", "Launches markets", "https://example.com/issuer.jpg")
+        .accounts({ userProfile: issuerProfile, user: issuer.publicKey, systemProgram: SystemProgram.programId })
+        .signers([issuer])
+        .rpc();
+
+      const launch = async () => {
+        const market = Keypair.generate().publicKey;
+        const marketKeys = pda([Buffer.from("user_keys"), market.toBuffer()]);
+        const keysMint = pda([Buffer.from("keys_mint"), market.toBuffer()]);
+        await program.methods
+          .createKeys(market, "Market", "MKT", "https://example.com/keys.json", "", [])
+          .accounts({
+            userKeys: marketKeys,
+            keysMint,
+            keysVault: await getAssociatedTokenAddress(keysMint, marketKeys, true),
+            creatorKeysAccount: await getAssociatedTokenAddress(keysMint, issuer.publicKey),
+            payer: issuer.publicKey,
+            protocolConfig,
+            protocolTreasury,
+            platformConfig,
+            creatorProfile: issuerProfile,
+            systemProgram: SystemProgram.programId,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          })
+          .signers([issuer])
+          .rpc();
+        return marketKeys;
+      };
+
+      // Two markets fill the cap exactly; a third would go past it
+      await setCap(new anchor.BN(1_000_000));
+      const first = await launch();
+      const initialSupply = (await program.account.userKeys.fetch(first)).totalSupply;
+      await setCap(initialSupply.muln(2));
+      await launch();
+      expect((await program.account.userProfile.fetch(issuerProfile)).issuedKeySupply.toString())
+        .to.equal(initialSupply.muln(2).toString());
+      try {
+        await launch();
+        expect.fail("launching past the creator supply cap should fail");
+      } catch (error) {
+        expect(error.error.errorCode.code).to.equal("ExceedsMaxSupply");
+      }
+
+      // Burning supply in an existing market frees room under the cap
+      const creatorProfile = pda([Buffer.from("user_profile"), creator.publicKey.toBuffer()]);
+      const issued = (await program.account.userProfile.fetch(creatorProfile)).issuedKeySupply;
+      await setCap(issued.addn(1));
+      const buy = () =>
+        program.methods
+          .buyKeys(new anchor.BN(1), new anchor.BN(LAMPORTS_PER_SOL), null)
+          .accounts({ buyer: trader.publicKey, subject: creator.publicKey, platformConfig, creatorProfile })
+          .signers([trader])
+          .rpc();
+
+      await buy();
+      try {
+        await buy();
+        expect.fail("buying past the creator supply cap should fail");
+      } catch (error) {
+        expect(error.error.errorCode.code).to.equal("ExceedsMaxSupply");
+      }
+
+      await program.methods
+        .sellKeys(new anchor.BN(1), new anchor.BN(0), null)
+        .accounts({ seller: trader.publicKey, subject: creator.publicKey, creatorProfile })
+        .signers([trader])
+        .rpc();
+      await buy();
+
+      await setCap(maxCreatorSupply);
+    });
+
+    it("Snapshots a market's curve stats with distinct timestamps", async () => {
+      const pda = (seeds: Buffer[]) => PublicKey.findProgramAddressSync(seeds, program.programId)[0];
+      const platformConfig = pda([Buffer.from("platform_config")]);
+      const userKeys = pda([Buffer.from("user_keys"), creator.publicKey.toBuffer()]);
+      const userKey = pda([Buffer.from("user_key"), creator.publicKey.toBuffer()]);
+      const snapshotAt = (sequence: anchor.BN) =>
+        pda([Buffer.from("market_snapshot"), creator.publicKey.toBuffer(), sequence.toArrayLike(Buffer, "le", 8)]);
+      const setInterval = (seconds: number) =>
+        program.methods
+          .setSnapshotInterval(new anchor.BN(seconds))
+          .accounts({ platformConfig, authority: wallet.publicKey })
+          .rpc();
+      const snapshot = async () => {
+        const { snapshotCount } = await program.account.userKey.fetch(userKey);
+        await program.methods
+          .snapshotMarketStats()
+          .accounts({
+            userKeys,
+            subject: creator.publicKey,
+            userKey,
+            marketSnapshot: snapshotAt(snapshotCount),
+            platformConfig,
+            payer: wallet.publicKey,
+          })
+          .rpc();
+        return program.account.marketSnapshot.fetch(snapshotAt(snapshotCount));
+      };
+
+      await setInterval(1);
+      const first = await snapshot();
+      const { totalSupply } = await program.account.userKeys.fetch(userKeys);
+      expect(first.subject.toString()).to.equal(creator.publicKey.toString());
+      expect(first.supply.toString()).to.equal(totalSupply.toString());
+      expect(first.currentPrice.toNumber()).to.be.greaterThan(0);
+      expect(first.marketCap.toString()).to.equal(first.currentPrice.mul(first.supply).toString());
+      expect(first.liquidity.lte(first.marketCap)).to.be.true;
+
+      try {
+        await snapshot();
+        expect.fail("snapshots inside the minimum interval should be rejected");
+      } catch (error) {
+        expect(error.error.errorCode.code).to.equal("InteractionCooldown");
+      }
+
+      await new Promise(resolve => setTimeout(resolve, 2000));
+      const second = await snapshot();
+      expect(second.sequence.toNumber()).to.equal(first.sequence.toNumber() + 1);
+      expect(second.timestamp.toNumber()).to.be.greaterThan(first.timestamp.toNumber());
+      // Nothing traded in between, so the stats themselves are unchanged
+      expect(second.marketCap.toString()).to.equal(first.marketCap.toString());
+      expect(second.liquidity.toString()).to.equal(first.liquidity.toString());
+
+      // A trade through buy_keys shows up in the next snapshot
+      await program.methods
+        .buyKeys(new anchor.BN(1), new anchor.BN(10 * LAMPORTS_PER_SOL), null)
+        .accounts({ buyer: trader.publicKey, subject: creator.publicKey })
+        .signers([trader])
+        .rpc();
+      await new Promise(resolve => setTimeout(resolve, 2000));
+      const third = await snapshot();
+      expect(third.supply.toNumber()).to.equal(second.supply.toNumber() + 1);
+
+      await setInterval(60);
+    });
+
+    it("Quotes the price impact a trade of that size actually causes", async () => {
+      const eventFrom = (events, name: string) => events.find((event) => event.name === name).data;
+      const quoteBuy = async (size: anchor.BN) =>
+        eventFrom(
+          (await program.methods.quoteBuy(size).accounts({ subject: creator.publicKey, trader: trader.publicKey }).simulate()).events,
+          "priceQuote"
+        );
+      // Mirrors BondingCurve::get_price for the standard curve
+      const spotPrice = (supply: anchor.BN) =>
+        new anchor.BN(1_000_000).add(supply.mul(supply).muln(16_000).div(new anchor.BN(1_000_000_000)));
+      const impactOf = (supply: anchor.BN, size: anchor.BN) => {
+        const spotBefore = spotPrice(supply);
+        return spotPrice(supply.add(size)).sub(spotBefore).muln(10000).div(spotBefore);
+      };
+
+      // Sizes big enough to move the spot price by a measurable number of basis points
+      const amount = new anchor.BN(1000);
+      const quote = await quoteBuy(amount);
+      expect(quote.impactBps.toNumber()).to.be.greaterThan(0);
+      expect(quote.impactBps.toString()).to.equal(impactOf(quote.supply, amount).toString());
+
+      // A bigger trade moves the price further
+      const largerAmount = new anchor.BN(5000);
+      const largerQuote = await quoteBuy(largerAmount);
+      expect(largerQuote.impactBps.toString()).to.equal(impactOf(largerQuote.supply, largerAmount).toString());
+      expect(largerQuote.impactBps.gt(quote.impactBps)).to.be.true;
+    });
+
+    it("Rejects trades submitted after their deadline", async () => {
+      const [platformConfig] = PublicKey.findProgramAddressSync(
+        [Buffer.from("platform_config")],
+        program.programId
+      );
+      const subject = Keypair.generate();
+      const now = Math.floor(Date.now() / 1000);
+      const buy = (deadline: anchor.BN) =>
+        program.methods
+          .buyKeys(new anchor.BN(1), new anchor.BN(LAMPORTS_PER_SOL), deadline)
+          .accounts({ buyer: trader.publicKey, subject: subject.publicKey, platformConfig })
+          .signers([trader])
+          .rpc();
+
+      try {
+        await buy(new anchor.BN(now - 60));
+        expect.fail("a buy past its deadline should fail");
+      } catch (error) {
+        expect(error.error.errorCode.code).to.equal("TradeDeadlineExceeded");
+      }
+
+      await buy(new anchor.BN(now + 600));
+
+      try {
+        await program.methods
+          .sellKeys(new anchor.BN(1), new anchor.BN(0), new anchor.BN(now - 60))
+          .accounts({ seller: trader.publicKey, subject: subject.publicKey })
+          .signers([trader])
+          .rpc();
+        expect.fail("a sell past its deadline should fail");
+      } catch (error) {
+        expect(error.error.errorCode.code).to.equal("TradeDeadlineExceeded");
+      }
+
+      await program.methods
+        .sellKeys(new anchor.BN(1), new anchor.BN(0), new anchor.BN(now + 600))
+        .accounts({ seller: trader.publicKey, subject: subject.publicKey })
+        .signers([trader])
+        .rpc();
+    });
+
+    it("Waives the subject fee when a creator buys their own keys", async () => {
+      const amount = new anchor.BN(1);
+      const eventFrom = (events, name: string) => events.find((event) => event.name === name).data;
+      const simulateBuy = async (buyer: Keypair) =>
+        eventFrom(
+          (await program.methods
+            .buyKeys(amount, new anchor.BN(LAMPORTS_PER_SOL), null)
+            .accounts({ buyer: buyer.publicKey, subject: creator.publicKey })
+            .signers([buyer])
+            .simulate()).events,
+          "keysPurchased"
+        );
+
+      const selfBuy = await simulateBuy(creator);
+      expect(selfBuy.price.toNumber()).to.be.greaterThan(0);
+      expect(selfBuy.protocolFee.toNumber()).to.be.greaterThan(0);
+      expect(selfBuy.subjectFee.toNumber()).to.equal(0);
+
+      const normalBuy = await simulateBuy(trader);
+      expect(normalBuy.price.toNumber()).to.be.greaterThan(0);
+      expect(normalBuy.protocolFee.toNumber()).to.be.greaterThan(0);
+      expect(normalBuy.subjectFee.toNumber()).to.be.greaterThan(0);
+    });
+
+    it("Seeds a launch cohort's escrows from the protocol treasury", async () => {
+      const [platformConfig] = PublicKey.findProgramAddressSync(
+        [Buffer.from("platform_config")],
+        program.programId
+      );
+      const [protocolTreasury] = PublicKey.findProgramAddressSync(
+        [Buffer.from("protocol_treasury")],
+        program.programId
+      );
+      const escrowOf = (subject: PublicKey) =>
+        PublicKey.findProgramAddressSync([Buffer.from("escrow"), subject.toBuffer()], program.programId)[0];
+      const launch = (subjects: PublicKey[], seedAmount: anchor.BN) =>
+        program.methods
+          .launchEvent(subjects, seedAmount)
+          .accounts({ platformConfig, protocolTreasury, authority: wallet.publicKey })
+          .remainingAccounts(subjects.map((subject) => ({ pubkey: escrowOf(subject), isSigner: false, isWritable: true })))
+          .rpc();
+
+      await connection.confirmTransaction(
+        await connection.requestAirdrop(protocolTreasury, 2 * LAMPORTS_PER_SOL)
+      );
+
+      const cohort = [Keypair.generate().publicKey, Keypair.generate().publicKey, Keypair.generate().publicKey];
+      const seedAmount = new anchor.BN(LAMPORTS_PER_SOL / 10);
+      const treasuryBefore = await connection.getBalance(protocolTreasury);
+
+      await launch(cohort, seedAmount);
+
+      for (const subject of cohort) {
+        expect(await connection.getBalance(escrowOf(subject))).to.equal(seedAmount.toNumber());
+      }
+      const treasuryAfter = await connection.getBalance(protocolTreasury);
+      expect(treasuryBefore - treasuryAfter).to.equal(seedAmount.toNumber() * cohort.length);
+
+      const shortCohort = [Keypair.generate().publicKey, Keypair.generate().publicKey];
+      try {
+        await launch(shortCohort, new anchor.BN(treasuryAfter));
+        expect.fail("a launch the treasury can't cover should revert");
+      } catch (error) {
+        expect(error.error.errorCode.code).to.equal("InsufficientFunds");
+      }
+      expect(await connection.getBalance(escrowOf(shortCohort[0]))).to.equal(0);
+      expect(await connection.getBalance(protocolTreasury)).to.equal(treasuryAfter);
+    });
+
+    it("Waives the subject fee when a subject quotes or buys their own keys", async () => {
+      const amount = new anchor.BN(1);
+      const eventFrom = (events, name: string) => events.find((event) => event.name === name).data;
+
+      const ownQuote = eventFrom(
+        (await program.methods.quoteBuy(amount).accounts({ subject: creator.publicKey, trader: creator.publicKey }).simulate()).events,
+        "priceQuote"
+      );
+      expect(ownQuote.subjectFee.toNumber()).to.equal(0);
+
+      const ownBuy = eventFrom(
+        (await program.methods
+          .buyKeys(amount, new anchor.BN(LAMPORTS_PER_SOL), null)
+          .accounts({ buyer: creator.publicKey, subject: creator.publicKey })
+          .signers([creator])
+          .simulate()).events,
+        "keysPurchased"
+      );
+      expect(ownBuy.subjectFee.toNumber()).to.equal(0);
+      expect(ownQuote.basePrice.toString()).to.equal(ownBuy.price.toString());
+      expect(ownQuote.protocolFee.toString()).to.equal(ownBuy.protocolFee.toString());
+
+      // Anyone else still pays it
+      const otherQuote = eventFrom(
+        (await program.methods.quoteBuy(amount).accounts({ subject: creator.publicKey, trader: trader.publicKey }).simulate()).events,
+        "priceQuote"
+      );
+      expect(otherQuote.subjectFee.toNumber()).to.be.greaterThan(0);
+    });
+  });
+
+  describe("Social Features", () => {
+    it("Creates social post", async () => {
+      try {
+        await program.methods
+          .createSocialPost("Hello SolSocial!", "general", [])
+          .accounts({
+            socialPost,
+            author: user.publicKey,
+            userReputation,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([user])
+          .rpc();
+
+        const socialPostAccount = await program.account.socialPost.fetch(socialPost);
+        expect(socialPostAccount.author.toString()).to.equal(user.publicKey.toString());
+        expect(socialPostAccount.content).to.equal("Hello SolSocial!");
+        expect(socialPostAccount.category).to.equal("general");
+        expect(socialPostAccount.likes.toNumber()).to.equal(0);
+        expect(socialPostAccount.shares.toNumber()).to.equal(0);
+      } catch (error) {
+        console.error("Create social post error:", error);
+        throw error;
+      }
+    });
+
+    it("Likes social post", async () => {
+      try {
+        await program.methods
+          .likeSocialPost()
+          .accounts({
+            socialPost,
+            user: trader.publicKey,
+            userReputation,
+          })
+          .signers([trader])
+          .rpc();
+
+        const socialPostAccount = await program.account.socialPost.fetch(socialPost);
+        expect(socialPostAccount.likes.toNumber()).to.equal(1);
+      } catch (error) {
+        console.error("Like social post error:", error);
+        throw error;
+      }
+    });
+
+    it("Shares social post", async () => {
+      try {
+        await program.methods
+          .shareSocialPost()
+          .accounts({
+            socialPost,
+            user: trader.publicKey,
+            userReputation,
+          })
+          .signers([trader])
+          .rpc();
+
+        const socialPostAccount = await program.account.socialPost.fetch(socialPost);
+        expect(socialPostAccount.shares.toNumber()).to.equal(1);
+      } catch (error) {
+        console.error("Share social post error:", error);
+        throw error;
+      }
+    });
+
+    it("Requires confirmation for tips above the platform maximum", async () => {
+      const [platformConfig] = PublicKey.findProgramAddressSync(
+        [Buffer.from("platform_config")],
+        program.programId
+      );
+      const [postStats] = PublicKey.findProgramAddressSync(
+        [Buffer.from("post_stats"), socialPost.toBuffer()],
+        program.programId
+      );
+      const accounts = {
+        post: socialPost,
+        postStats,
+        author: user.publicKey,
+        platformConfig,
+        tipper: trader.publicKey,
+        systemProgram: SystemProgram.programId,
+      };
+      const { maxTip: previousMaxTip } = await program.account.platformConfig.fetch(platformConfig);
+      const maxTip = new anchor.BN(10_000_000);
+      await program.methods
+        .setMaxTip(maxTip)
+        .accounts({ platformConfig, authority: wallet.publicKey })
+        .rpc();
+      const largeTip = maxTip.add(new anchor.BN(1));
+
+      try {
+        await program.methods.tipPost(largeTip, false).accounts(accounts).signers([trader]).rpc();
+        expect.fail("unconfirmed large tip should revert");
+      } catch (error) {
+        expect(error.error.errorCode.code).to.equal("InvalidAmount");
+      }
+
+      await program.methods.tipPost(largeTip, true).accounts(accounts).signers([trader]).rpc();
+      await program.methods.tipPost(new anchor.BN(1000), false).accounts(accounts).signers([trader]).rpc();
+
+      const postStatsAccount = await program.account.postStats.fetch(postStats);
+      expect(postStatsAccount.totalTips.toNumber()).to.equal(2);
+      expect(postStatsAccount.totalTipAmount.toString()).to.equal(largeTip.add(new anchor.BN(1000)).toString());
+
+      await program.methods
+        .setMaxTip(previousMaxTip)
+        .accounts({ platformConfig, authority: wallet.publicKey })
+        .rpc();
+    });
+
+    it("Awards author reputation for engagement up to the per-post cap", async () => {
+      const [platformConfig] = PublicKey.findProgramAddressSync(
+        [Buffer.from("platform_config")],
+        program.programId
+      );
+      const { maxAuthorReputationPerPost } = await program.account.platformConfig.fetch(platformConfig);
+      expect(maxAuthorReputationPerPost.toNumber()).to.be.greaterThan(0);
+      const authorBefore = await program.account.user.fetch(userProfile);
+
+      for (const liker of [trader, creator]) {
+        await program.methods
+          .interactPost(0, null)
+          .accounts({ post: socialPost, authority: liker.publicKey, platformConfig })
+          .signers([liker])
+          .rpc();
+      }
+
+      const authorAfter = await program.account.user.fetch(userProfile);
+      const postAccount = await program.account.post.fetch(socialPost);
+      const gained = authorAfter.reputationScore.sub(authorBefore.reputationScore);
+
+      expect(gained.toNumber()).to.be.greaterThan(0);
+      expect(postAccount.authorReputationAwarded.lte(maxAuthorReputationPerPost)).to.be.true;
+
+      // Unlike and like again: engagement doesn't exceed its previous peak, so nothing more is awarded
+      for (let i = 0; i < 2; i++) {
+        await program.methods
+          .interactPost(0, null)
+          .accounts({ post: socialPost, authority: trader.publicKey, platformConfig })
+          .signers([trader])
+          .rpc();
+      }
+
+      const authorFinal = await program.account.user.fetch(userProfile);
+      expect(authorFinal.reputationScore.toString()).to.equal(authorAfter.reputationScore.toString());
+    });
+
+    it("Closes a fully reversed interaction and refunds its rent", async () => {
+      const [platformConfig] = PublicKey.findProgramAddressSync(
+        [Buffer.from("platform_config")],
+        program.programId
+      );
+      const [traderUser] = PublicKey.findProgramAddressSync(
+        [Buffer.from("user"), trader.publicKey.toBuffer()],
+        program.programId
+      );
+      const [interaction] = PublicKey.findProgramAddressSync(
+        [Buffer.from("interaction"), socialPost.toBuffer(), traderUser.toBuffer()],
+        program.programId
+      );
+      const interact = (interactionType: number) =>
+        program.methods
+          .interactPost(interactionType, null)
+          .accounts({ post: socialPost, interaction, authority: trader.publicKey, platformConfig })
+          .signers([trader])
+          .rpc();
+
+      // Like + share, then unlike: the share keeps the interaction alive
+      await interact(0);
+      await interact(2);
+      await interact(0);
+      expect(await connection.getAccountInfo(interaction)).to.not.be.null;
+
+      const rent = (await connection.getAccountInfo(interaction)).lamports;
+      const balanceBefore = await connection.getBalance(trader.publicKey);
+
+      // Unshare: nothing left, so the PDA is closed and rent returned
+      await interact(2);
+      expect(await connection.getAccountInfo(interaction)).to.be.null;
+
+      const balanceAfter = await connection.getBalance(trader.publicKey);
+      expect(balanceAfter).to.be.greaterThan(balanceBefore + rent - 10_000);
+    });
+
+    it("Emits TipGoalReached exactly once when the goal is crossed", async () => {
+      const [platformConfig] = PublicKey.findProgramAddressSync(
+        [Buffer.from("platform_config")],
+        program.programId
+      );
+      const [postStats] = PublicKey.findProgramAddressSync(
+        [Buffer.from("post_stats"), socialPost.toBuffer()],
+        program.programId
+      );
+      const { totalTipAmount } = await program.account.postStats.fetch(postStats);
+      const tipGoal = totalTipAmount.add(new anchor.BN(3000));
+
+      await program.methods
+        .setTipGoal(tipGoal)
+        .accounts({ post: socialPost, author: user.publicKey })
+        .signers([user])
+        .rpc();
+
+      const goalEvents = [];
+      const listener = program.addEventListener("tipGoalReached", (event) => goalEvents.push(event));
+
+      for (let i = 0; i < 4; i++) {
+        await program.methods
+          .tipPost(new anchor.BN(1000), false)
+          .accounts({ post: socialPost, postStats, author: user.publicKey, platformConfig, tipper: trader.publicKey })
+          .signers([trader])
+          .rpc();
+      }
+
+      await new Promise(resolve => setTimeout(resolve, 1000));
+      await program.removeEventListener(listener);
+
+      expect(goalEvents.length).to.equal(1);
+      expect(goalEvents[0].tipGoal.toString()).to.equal(tipGoal.toString());
+    });
+
+    it("Limits how many posts an author can pin at once", async () => {
+      const [platformConfig] = PublicKey.findProgramAddressSync(
+        [Buffer.from("platform_config")],
+        program.programId
+      );
+      const { maxPinnedPosts } = await program.account.platformConfig.fetch(platformConfig);
+      expect(maxPinnedPosts.toNumber()).to.equal(3);
+      const posts = Array.from({ length: maxPinnedPosts.toNumber() + 1 }, (_, i) =>
+        PublicKey.findProgramAddressSync(
+          [Buffer.from("post"), user.publicKey.toBuffer(), new anchor.BN(i).toArrayLike(Buffer, "le", 8)],
+          program.programId
+        )[0]
+      );
+      const pin = (post: PublicKey) =>
+        program.methods.pinPost().accounts({ post, userProfile, platformConfig, author: user.publicKey }).signers([user]).rpc();
+
+      const extra = posts.pop();
+      for (const post of posts) {
+        await pin(post);
+      }
+
+      try {
+        await pin(extra);
+        expect.fail("pinning beyond the cap should fail");
+      } catch (error) {
+        expect(error.error.errorCode.code).to.equal("TooManyPinnedPosts");
+      }
+
+      await program.methods
+        .unpinPost()
+        .accounts({ post: posts[0], userProfile, author: user.publicKey })
+        .signers([user])
+        .rpc();
+      await pin(extra);
+
+      const profile = await program.account.userProfile.fetch(userProfile);
+      expect(profile.pinnedPostsCount.toString()).to.equal(maxPinnedPosts.toString());
+    });
+
+    it("Stops boosting engagement once reposts pass the cap", async () => {
+      const [platformConfig] = PublicKey.findProgramAddressSync(
+        [Buffer.from("platform_config")],
+        program.programId
+      );
+      const setCap = (maxCountedReposts: anchor.BN) =>
+        program.methods
+          .setMaxCountedReposts(maxCountedReposts)
+          .accounts({ platformConfig, authority: wallet.publicKey })
+          .rpc();
+      const { maxCountedReposts } = await program.account.platformConfig.fetch(platformConfig);
+      expect(maxCountedReposts.toNumber()).to.be.greaterThan(0);
+
+      // A cap a few reposts above the post's current count keeps the run short
+      const before = await program.account.post.fetch(socialPost);
+      const cap = before.countedReposts.toNumber() + 3;
+      await setCap(new anchor.BN(cap));
+      const bots = Array.from({ length: cap - before.countedReposts.toNumber() + 2 }, () => Keypair.generate());
+
+      let scoreAtCap;
+      for (let i = 0; i < bots.length; i++) {
+        await program.methods
+          .repostPost()
+          .accounts({ post: socialPost, platformConfig, reposter: bots[i].publicKey })
+          .signers([bots[i]])
+          .rpc();
+        if (i === cap - 1 - before.countedReposts.toNumber()) {
+          scoreAtCap = (await program.account.post.fetch(socialPost)).engagementScore;
+        }
+      }
+
+      const after = await program.account.post.fetch(socialPost);
+      expect(after.reposts.toNumber()).to.equal(before.reposts.toNumber() + bots.length);
+      expect(after.countedReposts.toNumber()).to.equal(cap);
+      expect(after.engagementScore.lte(scoreAtCap)).to.be.true;
+
+      await setCap(maxCountedReposts);
+    });
+
+    it("Prices premium access by value so required keys follow the key price", async () => {
+      const [premiumPost] = PublicKey.findProgramAddressSync(
+        [Buffer.from("post"), creator.publicKey.toBuffer(), new anchor.BN(0).toArrayLike(Buffer, "le", 8)],
+        program.programId
+      );
+      const [creatorKeys] = PublicKey.findProgramAddressSync(
+        [Buffer.from("user_keys"), creator.publicKey.toBuffer()],
+        program.programId
+      );
+      const [creatorProfile] = PublicKey.findProgramAddressSync(
+        [Buffer.from("user_profile"), creator.publicKey.toBuffer()],
+        program.programId
+      );
+      const [platformConfig] = PublicKey.findProgramAddressSync(
+        [Buffer.from("platform_config")],
+        program.programId
+      );
+
+      await program.methods
+        .createPost("Premium analysis", [], { original: {} }, null, [], false, true)
+        .accounts({ post: premiumPost, userProfile: creatorProfile, platformConfig, user: creator.publicKey })
+        .signers([creator])
+        .rpc();
+
+      await program.methods
+        .setPremiumAccessValue(new anchor.BN(LAMPORTS_PER_SOL))
+        .accounts({ post: premiumPost, author: creator.publicKey })
+        .signers([creator])
+        .rpc();
+
+      const requiredKeys = async () => {
+        const { events } = await program.methods
+          .checkPostAccess()
+          .accounts({ post: premiumPost, authorKeys: creatorKeys, viewer: trader.publicKey, followRelation: null, subscription: null })
+          .signers([trader])
+          .simulate();
+        return events.find((event) => event.name === "postAccessChecked").data.requiredKeys.toNumber();
+      };
+
+      const before = await requiredKeys();
+      expect(before).to.be.greaterThan(0);
+
+      // The curve is flat near zero, so buy until supply is high enough to move the price
+      const supplyBefore = (await program.account.userKeys.fetch(creatorKeys)).totalSupply;
+      let supply = supplyBefore;
+      do {
+        await program.methods
+          .buyKeys(new anchor.BN(100), new anchor.BN(10 * LAMPORTS_PER_SOL), null)
+          .accounts({ buyer: trader.publicKey, subject: creator.publicKey })
+          .signers([trader])
+          .rpc();
+        supply = (await program.account.userKeys.fetch(creatorKeys)).totalSupply;
+      } while (supply.ltn(300));
+      expect(supply.gt(supplyBefore)).to.be.true;
+
+      const after = await requiredKeys();
+      expect(after).to.be.lessThan(before);
+    });
+
+    it("Lets higher-reputation authors write longer posts up to their tier", async () => {
+      const [platformConfig] = PublicKey.findProgramAddressSync(
+        [Buffer.from("platform_config")],
+        program.programId
+      );
+      const profileOf = (author: PublicKey) =>
+        PublicKey.findProgramAddressSync([Buffer.from("user_profile"), author.toBuffer()], program.programId)[0];
+      const post = async (author: Keypair, length: number) => {
+        const profile = profileOf(author.publicKey);
+        const { postCount } = await program.account.userProfile.fetch(profile);
+        const [postAccount] = PublicKey.findProgramAddressSync(
+          [Buffer.from("post"), author.publicKey.toBuffer(), postCount.toArrayLike(Buffer, "le", 8)],
+          program.programId
+        );
+        return program.methods
+          .createPost("x".repeat(length), [], { original: {} }, null, [], false, false)
+          .accounts({ post: postAccount, userProfile: profile, platformConfig, user: author.publicKey })
+          .signers([author])
+          .rpc();
+      };
+      const expectRejected = async (author: Keypair, length: number) => {
+        try {
+          await post(author, length);
+          expect.fail(`${length} characters should exceed the author's tier`);
+        } catch (error) {
+          expect(error.error.errorCode.code).to.equal("InvalidContentLength");
+        }
+      };
+
+      // 2000 characters, plus 500 per reputation point, capped by the Post account at 5000
+      const base = 2000;
+      const perTier = 500;
+      await program.methods
+        .setContentLengthTiers(base, perTier, new anchor.BN(1))
+        .accounts({ platformConfig, authority: wallet.publicKey })
+        .rpc();
+
+      const newcomer = Keypair.generate();
+      await connection.confirmTransaction(await connection.requestAirdrop(newcomer.publicKey, LAMPORTS_PER_SOL));
+      await program.methods
+        .createUserProfile("newcomer", "", "")
+        .accounts({ userProfile: profileOf(newcomer.publicKey), user: newcomer.publicKey })
+        .signers([newcomer])
+        .rpc();
+
+      await expectRejected(newcomer, base + 1);
+      await post(newcomer, base);
+
+      const { reputation } = await program.account.userProfile.fetch(userProfile);
+      const establishedLimit = Math.min(base + perTier * Number(reputation), 5000);
+      expect(establishedLimit).to.be.greaterThan(base);
+
+      await expectRejected(user, establishedLimit + 1);
+      await post(user, establishedLimit);
+    });
+
+    it("Skips stats for creators who opt out of analytics but keeps likes working", async () => {
+      const [platformConfig] = PublicKey.findProgramAddressSync(
+        [Buffer.from("platform_config")],
+        program.programId
+      );
+      const [postStats] = PublicKey.findProgramAddressSync(
+        [Buffer.from("post_stats"), socialPost.toBuffer()],
+        program.programId
+      );
+      const setAnalytics = (enabled: boolean) =>
+        program.methods
+          .setAnalyticsEnabled(enabled)
+          .accounts({ userProfile, authority: user.publicKey })
+          .signers([user])
+          .rpc();
+      const tip = () =>
+        program.methods
+          .tipPost(new anchor.BN(1000), false)
+          .accounts({ post: socialPost, postStats, author: user.publicKey, platformConfig, tipper: trader.publicKey })
+          .signers([trader])
+          .rpc();
+      const toggleLike = () =>
+        program.methods
+          .interactPost(0, null)
+          .accounts({ post: socialPost, authority: trader.publicKey, platformConfig })
+          .signers([trader])
+          .rpc();
+
+      const statsBefore = await program.account.postStats.fetch(postStats);
+      await tip();
+      const statsEnabled = await program.account.postStats.fetch(postStats);
+      expect(statsEnabled.totalTips.sub(statsBefore.totalTips).toNumber()).to.equal(1);
+
+      await setAnalytics(false);
+
+      await tip();
+      const statsDisabled = await program.account.postStats.fetch(postStats);
+      expect(statsDisabled.totalTips.toString()).to.equal(statsEnabled.totalTips.toString());
+      expect(statsDisabled.totalTipAmount.toString()).to.equal(statsEnabled.totalTipAmount.toString());
+
+      const [authorUser] = PublicKey.findProgramAddressSync(
+        [Buffer.from("user"), user.publicKey.toBuffer()],
+        program.programId
+      );
+      const likesBefore = (await program.account.post.fetch(socialPost)).likes;
+      const receivedBefore = (await program.account.user.fetch(authorUser)).totalLikesReceived;
+      await toggleLike();
+      const liked = await program.account.post.fetch(socialPost);
+      expect(liked.likes.toNumber()).to.not.equal(likesBefore.toNumber());
+      // Core counters keep moving with analytics off
+      const receivedAfter = (await program.account.user.fetch(authorUser)).totalLikesReceived;
+      expect(receivedAfter.toNumber()).to.not.equal(receivedBefore.toNumber());
+
+      // Put the like state and the preference back for later tests
+      await toggleLike();
+      await setAnalytics(true);
+    });
+
+    it("Shows follower-only posts to followers and hides them from everyone else", async () => {
+      const [premiumPost] = PublicKey.findProgramAddressSync(
+        [Buffer.from("post"), creator.publicKey.toBuffer(), new anchor.BN(0).toArrayLike(Buffer, "le", 8)],
+        program.programId
+      );
+      const [creatorKeys] = PublicKey.findProgramAddressSync(
+        [Buffer.from("user_keys"), creator.publicKey.toBuffer()],
+        program.programId
+      );
+      const [traderFollow] = PublicKey.findProgramAddressSync(
+        [Buffer.from("follow"), trader.publicKey.toBuffer(), creator.publicKey.toBuffer()],
+        program.programId
+      );
+
+      await program.methods
+        .setPostVisibility({ followers: {} })
+        .accounts({ post: premiumPost, author: creator.publicKey })
+        .signers([creator])
+        .rpc();
+
+      await program.methods
+        .followUser()
+        .accounts({ follower: trader.publicKey, following: creator.publicKey, followRecord: traderFollow })
+        .signers([trader])
+        .rpc();
+
+      const canView = async (viewer: Keypair, followRelation: PublicKey | null) => {
+        const { events } = await program.methods
+          .checkPostAccess()
+          .accounts({ post: premiumPost, authorKeys: creatorKeys, viewer: viewer.publicKey, followRelation, subscription: null })
+          .signers([viewer])
+          .simulate();
+        return events.find((event) => event.name === "postAccessChecked").data.canView;
+      };
+
+      expect(await canView(trader, traderFollow)).to.be.true;
+      expect(await canView(user, null)).to.be.false;
+    });
+
+    it("Unfollows and re-follows while keeping follow counts consistent", async () => {
+      const [followRecord] = PublicKey.findProgramAddressSync(
+        [Buffer.from("follow"), user.publicKey.toBuffer(), creator.publicKey.toBuffer()],
+        program.programId
+      );
+      const [creatorProfile] = PublicKey.findProgramAddressSync(
+        [Buffer.from("user_profile"), creator.publicKey.toBuffer()],
+        program.programId
+      );
+      const counts = async () => {
+        const follower = await program.account.userProfile.fetch(userProfile);
+        const following = await program.account.userProfile.fetch(creatorProfile);
+        return {
+          following: follower.followingCount.toNumber(),
+          followers: following.followersCount.toNumber(),
+        };
+      };
+
+      await program.methods
+        .followUser()
+        .accounts({ follower: user.publicKey, following: creator.publicKey, followRecord })
+        .signers([user])
+        .rpc();
+      const followed = await counts();
+
+      await program.methods
+        .unfollowUser()
+        .accounts({ followRecord, followerProfile: userProfile, followingProfile: creatorProfile, follower: user.publicKey })
+        .signers([user])
+        .rpc();
+      const unfollowed = await counts();
+      expect(unfollowed.following).to.equal(followed.following - 1);
+      expect(unfollowed.followers).to.equal(followed.followers - 1);
+      expect((await program.account.followRelation.fetch(followRecord)).isActive).to.be.false;
+
+      try {
+        await program.methods
+          .unfollowUser()
+          .accounts({ followRecord, followerProfile: userProfile, followingProfile: creatorProfile, follower: user.publicKey })
+          .signers([user])
+          .rpc();
+        expect.fail("Should not unfollow twice");
+      } catch (error) {
+        expect(error.error.errorCode.code).to.equal("NotFollowing");
+      }
+
+      await program.methods
+        .followUser()
+        .accounts({ follower: user.publicKey, following: creator.publicKey, followRecord })
+        .signers([user])
+        .rpc();
+      expect(await counts()).to.deep.equal(followed);
+    });
+
+    it("Saturates engagement at the configured ceiling so viral posts stay likeable", async () => {
+      const [platformConfig] = PublicKey.findProgramAddressSync(
+        [Buffer.from("platform_config")],
+        program.programId
+      );
+      const setCeiling = (maxEngagementScore: number) =>
+        program.methods
+          .setMaxEngagementScore(new anchor.BN(maxEngagementScore))
+          .accounts({ platformConfig, authority: wallet.publicKey })
+          .rpc();
+      const toggleLike = () =>
+        program.methods
+          .interactPost(0, null)
+          .accounts({ post: socialPost, authority: trader.publicKey, platformConfig })
+          .signers([trader])
+          .rpc();
+
+      const ceiling = 1;
+      await setCeiling(ceiling);
+
+      const reposter = Keypair.generate();
+      await program.methods
+        .repostPost()
+        .accounts({ post: socialPost, platformConfig, reposter: reposter.publicKey })
+        .signers([reposter])
+        .rpc();
+      const saturated = await program.account.post.fetch(socialPost);
+      expect(saturated.engagementScore.toNumber()).to.equal(ceiling);
+
+      await toggleLike();
+      await toggleLike();
+
+      const after = await program.account.post.fetch(socialPost);
+      // The score is recomputed from the counters, so an unlike at the ceiling can't drift below it
+      expect(after.engagementScore.toNumber()).to.equal(ceiling);
+      expect(after.likes.toNumber()).to.equal(saturated.likes.toNumber());
+
+      await setCeiling(0);
+    });
+
+    it("Rejects follows from blocked users until they are unblocked", async () => {
+      const [traderBlockList] = PublicKey.findProgramAddressSync(
+        [Buffer.from("block_list"), trader.publicKey.toBuffer()],
+        program.programId
+      );
+      const [followRecord] = PublicKey.findProgramAddressSync(
+        [Buffer.from("follow"), creator.publicKey.toBuffer(), trader.publicKey.toBuffer()],
+        program.programId
+      );
+      const follow = (followingBlockList = traderBlockList) =>
+        program.methods
+          .followUser()
+          .accounts({ follower: creator.publicKey, following: trader.publicKey, followRecord, followingBlockList })
+          .signers([creator])
+          .rpc();
+
+      await program.methods
+        .blockUser(creator.publicKey)
+        .accounts({ blockList: traderBlockList, authority: trader.publicKey })
+        .signers([trader])
+        .rpc();
+
+      try {
+        await follow();
+        expect.fail("Blocked user should not be able to follow");
+      } catch (error) {
+        expect(error.error.errorCode.code).to.equal("AccessDenied");
+      }
+
+      // Swapping in some other (empty) block list must not get around the block
+      const [creatorBlockList] = PublicKey.findProgramAddressSync(
+        [Buffer.from("block_list"), creator.publicKey.toBuffer()],
+        program.programId
+      );
+      try {
+        await follow(creatorBlockList);
+        expect.fail("Follow should only accept the target's block list");
+      } catch (error) {
+        expect(error.error.errorCode.code).to.equal("ConstraintSeeds");
+      }
+
+      await program.methods
+        .unblockUser(creator.publicKey)
+        .accounts({ blockList: traderBlockList, authority: trader.publicKey })
+        .signers([trader])
+        .rpc();
+
+      await follow();
+      const relation = await program.account.followRelation.fetch(followRecord);
+      expect(relation.isActive).to.be.true;
+      const blockList = await program.account.blockList.fetch(traderBlockList);
+      expect(blockList.blocked).to.be.empty;
+    });
+
+    it("Rejects back-to-back interactions inside the cooldown window", async () => {
+      const [platformConfig] = PublicKey.findProgramAddressSync(
+        [Buffer.from("platform_config")],
+        program.programId
+      );
+      const setCooldown = (seconds: number) =>
+        program.methods
+          .setInteractionCooldown(new anchor.BN(seconds))
+          .accounts({ platformConfig, authority: wallet.publicKey })
+          .rpc();
+      const toggleLike = () =>
+        program.methods
+          .interactPost(0, null)
+          .accounts({ post: socialPost, authority: trader.publicKey, platformConfig })
+          .signers([trader])
+          .rpc();
+      const waitOutCooldown = () => new Promise(resolve => setTimeout(resolve, 2500));
+
+      await setCooldown(2);
+      await waitOutCooldown();
+
+      await toggleLike();
+      try {
+        await toggleLike();
+        expect.fail("a second interaction inside the cooldown should be rejected");
+      } catch (error) {
+        expect(error.error.errorCode.code).to.equal("InteractionCooldown");
+      }
+
+      await waitOutCooldown();
+      await toggleLike();
+
+      // like_post shares the cooldown, even across different posts
+      const [creatorPost] = PublicKey.findProgramAddressSync(
+        [Buffer.from("post"), creator.publicKey.toBuffer(), new anchor.BN(0).toArrayLike(Buffer, "le", 8)],
+        program.programId
+      );
+      const like = (post: PublicKey) =>
+        program.methods
+          .likePost()
+          .accounts({ post, user: trader.publicKey })
+          .signers([trader])
+          .rpc();
+
+      await waitOutCooldown();
+      await like(socialPost);
+      try {
+        await like(creatorPost);
+        expect.fail("a like inside the cooldown should be rejected");
+      } catch (error) {
+        expect(error.error.errorCode.code).to.equal("InteractionCooldown");
+      }
+
+      await waitOutCooldown();
+      await like(creatorPost);
+
+      await setCooldown(0);
+    });
+
+    it("Holds back self-reposts until the post is older than the cooldown", async () => {
+      const [platformConfig] = PublicKey.findProgramAddressSync(
+        [Buffer.from("platform_config")],
+        program.programId
+      );
+      const [creatorProfile] = PublicKey.findProgramAddressSync(
+        [Buffer.from("user_profile"), creator.publicKey.toBuffer()],
+        program.programId
+      );
+      const setCooldown = (seconds: number) =>
+        program.methods
+          .setSelfRepostCooldown(new anchor.BN(seconds))
+          .accounts({ platformConfig, authority: wallet.publicKey })
+          .rpc();
+      const repost = (post: PublicKey, reposter: Keypair) =>
+        program.methods
+          .repostPost()
+          .accounts({ post, platformConfig, reposter: reposter.publicKey })
+          .signers([reposter])
+          .rpc();
+
+      await setCooldown(2);
+
+      const { postCount } = await program.account.userProfile.fetch(creatorProfile);
+      const [freshPost] = PublicKey.findProgramAddressSync(
+        [Buffer.from("post"), creator.publicKey.toBuffer(), postCount.toArrayLike(Buffer, "le", 8)],
+        program.programId
+      );
+      await program.methods
+        .createPost("fresh take", [], { original: {} }, null, [], false, false)
+        .accounts({ post: freshPost, userProfile: creatorProfile, platformConfig, user: creator.publicKey })
+        .signers([creator])
+        .rpc();
+
+      try {
+        await repost(freshPost, creator);
+        expect.fail("an immediate self-repost should be rejected");
+      } catch (error) {
+        expect(error.error.errorCode.code).to.equal("InteractionCooldown");
+      }
+
+      // Reposting someone else's post is unaffected
+      await repost(freshPost, trader);
+
+      await new Promise(resolve => setTimeout(resolve, 2500));
+      await repost(freshPost, creator);
+      expect((await program.account.post.fetch(freshPost)).reposts.toNumber()).to.equal(2);
+
+      await setCooldown(3600);
+    });
+
+    it("Only accepts media from allowlisted hosts when an allowlist is set", async () => {
+      const [platformConfig] = PublicKey.findProgramAddressSync(
+        [Buffer.from("platform_config")],
+        program.programId
+      );
+      const [creatorProfile] = PublicKey.findProgramAddressSync(
+        [Buffer.from("user_profile"), creator.publicKey.toBuffer()],
+        program.programId
+      );
+      const setAllowlist = (hosts: string[]) =>
+        program.methods
+          .setMediaHostAllowlist(hosts)
+          .accounts({ platformConfig, authority: wallet.publicKey })
+          .rpc();
+      const postWithMedia = async (mediaUrl: string) => {
+        const { postCount } = await program.account.userProfile.fetch(creatorProfile);
+        const [post] = PublicKey.findProgramAddressSync(
+          [Buffer.from("post"), creator.publicKey.toBuffer(), postCount.toArrayLike(Buffer, "le", 8)],
+          program.programId
+        );
+        await program.methods
+          .createPost("with media", [mediaUrl], { original: {} }, null, [], false, false)
+          .accounts({ post, userProfile: creatorProfile, platformConfig, user: creator.publicKey })
+          .signers([creator])
+          .rpc();
+      };
+
+      // No allowlist: any host goes
+      await postWithMedia("https://cdn.example.com/cat.png");
+
+      await setAllowlist(["ipfs.io"]);
+      await postWithMedia("https://ipfs.io/ipfs/QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG");
+      try {
+        await postWithMedia("https://cdn.example.com/cat.png");
+        expect.fail("media from a host outside the allowlist should be rejected");
+      } catch (error) {
+        expect(error.error.errorCode.code).to.equal("InvalidDataFormat");
+      }
+
+      await setAllowlist([]);
+    });
+
+    it("Opens stale low-engagement gated posts to the public", async () => {
+      const [platformConfig] = PublicKey.findProgramAddressSync(
+        [Buffer.from("platform_config")],
+        program.programId
+      );
+      const [creatorProfile] = PublicKey.findProgramAddressSync(
+        [Buffer.from("user_profile"), creator.publicKey.toBuffer()],
+        program.programId
+      );
+      const createGatedPost = async (content: string) => {
+        const { postCount } = await program.account.userProfile.fetch(creatorProfile);
+        const [post] = PublicKey.findProgramAddressSync(
+          [Buffer.from("post"), creator.publicKey.toBuffer(), postCount.toArrayLike(Buffer, "le", 8)],
+          program.programId
+        );
+        await program.methods
+          .createPost(content, [], { original: {} }, null, [], false, false)
+          .accounts({ post, userProfile: creatorProfile, platformConfig, user: creator.publicKey })
+          .signers([creator])
+          .rpc();
+        await program.methods
+          .setPostVisibility({ keyHolders: {} })
+          .accounts({ post, author: creator.publicKey })
+          .signers([creator])
+          .rpc();
+        await program.methods
+          .setVisibilityDowngrade(new anchor.BN(2), new anchor.BN(1))
+          .accounts({ post, author: creator.publicKey })
+          .signers([creator])
+          .rpc();
+        return post;
+      };
+      const maybeDowngrade = (post: PublicKey) =>
+        program.methods.maybeDowngradeVisibility().accounts({ post }).rpc();
+
+      const quietPost = await createGatedPost("nobody read this");
+      const popularPost = await createGatedPost("everybody read this");
+      await program.methods
+        .repostPost()
+        .accounts({ post: popularPost, platformConfig, reposter: trader.publicKey })
+        .signers([trader])
+        .rpc();
+
+      // Too young to downgrade yet
+      await maybeDowngrade(quietPost);
+      expect((await program.account.post.fetch(quietPost)).visibility).to.deep.equal({ keyHolders: {} });
+
+      await new Promise(resolve => setTimeout(resolve, 2500));
+      await maybeDowngrade(quietPost);
+      await maybeDowngrade(popularPost);
+
+      expect((await program.account.post.fetch(quietPost)).visibility).to.deep.equal({ public: {} });
+      expect((await program.account.post.fetch(popularPost)).visibility).to.deep.equal({ keyHolders: {} });
+    });
+
+    it("Caps how many scheduled posts a user can have queued", async () => {
+      const [platformConfig] = PublicKey.findProgramAddressSync(
+        [Buffer.from("platform_config")],
+        program.programId
+      );
+      const [creatorProfile] = PublicKey.findProgramAddressSync(
+        [Buffer.from("user_profile"), creator.publicKey.toBuffer()],
+        program.programId
+      );
+      const setCap = (cap: number) =>
+        program.methods
+          .setMaxScheduledPosts(cap)
+          .accounts({ platformConfig, authority: wallet.publicKey })
+          .rpc();
+      const schedule = async (content: string, publishAt: number) => {
+        const { postCount } = await program.account.userProfile.fetch(creatorProfile);
+        const [post] = PublicKey.findProgramAddressSync(
+          [Buffer.from("post"), creator.publicKey.toBuffer(), postCount.toArrayLike(Buffer, "le", 8)],
+          program.programId
+        );
+        await program.methods
+          .schedulePost(content, new anchor.BN(publishAt), [], [], false)
+          .accounts({ post, userProfile: creatorProfile, platformConfig, author: creator.publicKey })
+          .signers([creator])
+          .rpc();
+        return post;
+      };
+      const publishAt = Math.floor(Date.now() / 1000) + 2;
+
+      await setCap(2);
+      const first = await schedule("first scheduled post", publishAt);
+      await schedule("second scheduled post", publishAt);
+      expect((await program.account.post.fetch(first)).status).to.deep.equal({ scheduled: {} });
+
+      try {
+        await schedule("one too many", publishAt);
+        expect.fail("scheduling past the cap should fail");
+      } catch (error) {
+        expect(error.error.errorCode.code).to.equal("ResourceNotAvailable");
+      }
+
+      await new Promise(resolve => setTimeout(resolve, 3000));
+      await program.methods
+        .publishScheduledPost()
+        .accounts({ post: first, userProfile: creatorProfile })
+        .rpc();
+      expect((await program.account.post.fetch(first)).status).to.deep.equal({ active: {} });
+
+      await schedule("room for one more", Math.floor(Date.now() / 1000) + 60);
+      const profile = await program.account.userProfile.fetch(creatorProfile);
+      expect(profile.scheduledPostsCount).to.equal(2);
+
+      // Scheduled posts are held to the same media and tag rules as create_post
+      await setCap(10);
+      const { postCount } = await program.account.userProfile.fetch(creatorProfile);
+      const [nextPost] = PublicKey.findProgramAddressSync(
+        [Buffer.from("post"), creator.publicKey.toBuffer(), postCount.toArrayLike(Buffer, "le", 8)],
+        program.programId
+      );
+      const scheduleWith = (mediaUrls: string[], tags: string[]) =>
+        program.methods
+          .schedulePost("later", new anchor.BN(Math.floor(Date.now() / 1000) + 60), mediaUrls, tags, false)
+          .accounts({ post: nextPost, userProfile: creatorProfile, platformConfig, author: creator.publicKey })
+          .signers([creator])
+          .rpc();
+      const invalid: [string[], string[], string][] = [
+        [Array(11).fill("https://example.com/a.png"), [], "TooManyMediaUrls"],
+        [["https://example.com/" + "a".repeat(200)], [], "MediaUrlTooLong"],
+        [[], [""], "InvalidTagLength"],
+      ];
+      for (const [mediaUrls, tags, code] of invalid) {
+        try {
+          await scheduleWith(mediaUrls, tags);
+          expect.fail(`scheduling should fail with ${code}`);
+        } catch (error) {
+          expect(error.error.errorCode.code).to.equal(code);
+        }
+      }
+    });
+
+    it("Requires a minimum reputation to create premium posts", async () => {
+      const [platformConfig] = PublicKey.findProgramAddressSync(
+        [Buffer.from("platform_config")],
+        program.programId
+      );
+      const [traderProfile] = PublicKey.findProgramAddressSync(
+        [Buffer.from("user_profile"), trader.publicKey.toBuffer()],
+        program.programId
+      );
+      const setMinReputation = (minReputation: anchor.BN) =>
+        program.methods
+          .setMinReputationForPremium(minReputation)
+          .accounts({ platformConfig, authority: wallet.publicKey })
+          .rpc();
+      const post = async (content: string, isPremium: boolean) => {
+        const { postCount } = await program.account.userProfile.fetch(traderProfile);
+        const [postAddress] = PublicKey.findProgramAddressSync(
+          [Buffer.from("post"), trader.publicKey.toBuffer(), postCount.toArrayLike(Buffer, "le", 8)],
+          program.programId
+        );
+        await program.methods
+          .createPost(content, [], { original: {} }, null, [], false, isPremium)
+          .accounts({ post: postAddress, userProfile: traderProfile, platformConfig, user: trader.publicKey })
+          .signers([trader])
+          .rpc();
+        return postAddress;
+      };
+      const reputation = async () => (await program.account.userProfile.fetch(traderProfile)).reputationScore;
+
+      // Just out of reach: public posts still go through, premium ones don't
+      await setMinReputation((await reputation()).addn(1000));
+      const publicPost = await post("open to everyone", false);
+      expect((await program.account.post.fetch(publicPost)).isPremium).to.be.false;
+      try {
+        await post("paywalled too early", true);
+        expect.fail("a low-reputation user should not be able to create premium posts");
+      } catch (error) {
+        expect(error.error.errorCode.code).to.equal("ReputationTooLow");
+      }
+
+      // Scheduling can't be used to get around the gate
+      const { postCount } = await program.account.userProfile.fetch(traderProfile);
+      const [scheduledPost] = PublicKey.findProgramAddressSync(
+        [Buffer.from("post"), trader.publicKey.toBuffer(), postCount.toArrayLike(Buffer, "le", 8)],
+        program.programId
+      );
+      try {
+        await program.methods
+          .schedulePost("paywalled later", new anchor.BN(Math.floor(Date.now() / 1000) + 60), [], [], true)
+          .accounts({ post: scheduledPost, userProfile: traderProfile, platformConfig, author: trader.publicKey })
+          .signers([trader])
+          .rpc();
+        expect.fail("a low-reputation user should not be able to schedule premium posts");
+      } catch (error) {
+        expect(error.error.errorCode.code).to.equal("ReputationTooLow");
+      }
+
+      await setMinReputation(await reputation());
+      await post("still open to everyone", false);
+      const premiumPost = await post("for key holders", true);
+      expect((await program.account.post.fetch(premiumPost)).isPremium).to.be.true;
+
+      await setMinReputation(new anchor.BN(0));
+    });
+
+    it("Auto-hides heavily reported posts and lets only moderators restore them", async () => {
+      const [platformConfig] = PublicKey.findProgramAddressSync(
+        [Buffer.from("platform_config")],
+        program.programId
+      );
+      const [traderProfile] = PublicKey.findProgramAddressSync(
+        [Buffer.from("user_profile"), trader.publicKey.toBuffer()],
+        program.programId
+      );
+      const setThreshold = (threshold: number) =>
+        program.methods
+          .setReportHideThreshold(threshold)
+          .accounts({ platformConfig, authority: wallet.publicKey })
+          .rpc();
+      const setModerator = (member: PublicKey, isModerator: boolean) =>
+        program.methods
+          .setModerator(member, isModerator)
+          .accounts({ platformConfig, authority: wallet.publicKey })
+          .rpc();
+      const report = (reporter: Keypair, reasonCode: number) =>
+        program.methods
+          .reportPost(reasonCode)
+          .accounts({ post, platformConfig, reporter: reporter.publicKey })
+          .signers([reporter])
+          .rpc();
+      const moderate = (moderator: Keypair, status: object) =>
+        program.methods
+          .moderatePost(status)
+          .accounts({ post, moderator: moderator.publicKey })
+          .signers([moderator])
+          .rpc();
+
+      const { postCount } = await program.account.userProfile.fetch(traderProfile);
+      const [post] = PublicKey.findProgramAddressSync(
+        [Buffer.from("post"), trader.publicKey.toBuffer(), postCount.toArrayLike(Buffer, "le", 8)],
+        program.programId
+      );
+      await program.methods
+        .createPost("hot take", [], { original: {} }, null, [], false, false)
+        .accounts({ post, userProfile: traderProfile, platformConfig, user: trader.publicKey })
+        .signers([trader])
+        .rpc();
+
+      await setThreshold(2);
+      await report(user, 1);
+      let reported = await program.account.post.fetch(post);
+      expect(reported.reportCount).to.equal(1);
+      expect(reported.status).to.deep.equal({ active: {} });
+
+      try {
+        await report(user, 1);
+        expect.fail("the same account should only be able to report a post once");
+      } catch (error) {
+        expect(error).to.exist;
+      }
+
+      await report(creator, 2);
+      reported = await program.account.post.fetch(post);
+      expect(reported.reportCount).to.equal(2);
+      expect(reported.status).to.deep.equal({ hidden: {} });
+
+      try {
+        await moderate(creator, { active: {} });
+        expect.fail("only moderators can restore a post");
+      } catch (error) {
+        expect(error.error.errorCode.code).to.equal("ModeratorPrivilegesRequired");
+      }
+
+      await setModerator(user.publicKey, true);
+      await moderate(user, { active: {} });
+      const restored = await program.account.post.fetch(post);
+      expect(restored.status).to.deep.equal({ active: {} });
+      expect(restored.reportCount).to.equal(0);
+
+      await setModerator(user.publicKey, false);
+      await setThreshold(5);
+    });
+
+    it("Curates a post once holder-weighted likes reach the boost threshold", async () => {
+      const [platformConfig] = PublicKey.findProgramAddressSync(
+        [Buffer.from("platform_config")],
+        program.programId
+      );
+      const [creatorProfile] = PublicKey.findProgramAddressSync(
+        [Buffer.from("user_profile"), creator.publicKey.toBuffer()],
+        program.programId
+      );
+      const setThreshold = (threshold: anchor.BN) =>
+        program.methods
+          .setCurationThreshold(threshold)
+          .accounts({ platformConfig, authority: wallet.publicKey })
+          .rpc();
+      const toggleLike = (liker: Keypair) =>
+        program.methods
+          .interactPost(0, null)
+          .accounts({ post, authority: liker.publicKey, platformConfig })
+          .signers([liker])
+          .rpc();
+
+      const { postCount } = await program.account.userProfile.fetch(creatorProfile);
+      const [post] = PublicKey.findProgramAddressSync(
+        [Buffer.from("post"), creator.publicKey.toBuffer(), postCount.toArrayLike(Buffer, "le", 8)],
+        program.programId
+      );
+      await program.methods
+        .createPost("worth a highlight", [], { original: {} }, null, [], false, false)
+        .accounts({ post, userProfile: creatorProfile, platformConfig, user: creator.publicKey })
+        .signers([creator])
+        .rpc();
+
+      // Measure what both holders' likes weigh together, then reset them
+      await setThreshold(new anchor.BN("18446744073709551615"));
+      await toggleLike(trader);
+      await toggleLike(user);
+      const combinedWeight = (await program.account.post.fetch(post)).boostWeight;
+      expect(combinedWeight.toNumber()).to.be.greaterThan(0);
+      await toggleLike(trader);
+      await toggleLike(user);
+      expect((await program.account.post.fetch(post)).boostWeight.toNumber()).to.equal(0);
+
+      const curated = [];
+      const listener = program.addEventListener("postCurated", (event) => curated.push(event));
+
+      await setThreshold(combinedWeight);
+      await toggleLike(trader);
+      await toggleLike(user);
+      // Dropping below and climbing back over the threshold doesn't curate it again
+      await toggleLike(user);
+      await toggleLike(user);
+
+      await new Promise(resolve => setTimeout(resolve, 1000));
+      await program.removeEventListener(listener);
+
+      expect(curated.length).to.equal(1);
+      expect(curated[0].post.toString()).to.equal(post.toString());
+      expect(curated[0].boostWeight.gte(combinedWeight)).to.be.true;
+      expect((await program.account.post.fetch(post)).isCurated).to.be.true;
+
+      await setThreshold(new anchor.BN(0));
+    });
+
+    it("Archives posts out of trending and hides them from everyone but the author", async () => {
+      const [platformConfig] = PublicKey.findProgramAddressSync(
+        [Buffer.from("platform_config")],
+        program.programId
+      );
+      const [creatorProfile] = PublicKey.findProgramAddressSync(
+        [Buffer.from("user_profile"), creator.publicKey.toBuffer()],
+        program.programId
+      );
+      const [creatorKeys] = PublicKey.findProgramAddressSync(
+        [Buffer.from("user_keys"), creator.publicKey.toBuffer()],
+        program.programId
+      );
+      const eventFrom = (events, name: string) => events.find((event) => event.name === name).data;
+      const canView = async (viewer: Keypair) =>
+        eventFrom(
+          (await program.methods
+            .checkPostAccess()
+            .accounts({ post, authorKeys: creatorKeys, viewer: viewer.publicKey, followRelation: null, subscription: null })
+            .signers([viewer])
+            .simulate()).events,
+          "postAccessChecked"
+        ).canView;
+
+      const { postCount } = await program.account.userProfile.fetch(creatorProfile);
+      const [post] = PublicKey.findProgramAddressSync(
+        [Buffer.from("post"), creator.publicKey.toBuffer(), postCount.toArrayLike(Buffer, "le", 8)],
+        program.programId
+      );
+      await program.methods
+        .createPost("old news", [], { original: {} }, null, [], false, false)
+        .accounts({ post, userProfile: creatorProfile, platformConfig, user: creator.publicKey })
+        .signers([creator])
+        .rpc();
+      expect(await canView(user)).to.be.true;
+
+      try {
+        await program.methods
+          .archivePost()
+          .accounts({ post, author: user.publicKey })
+          .signers([user])
+          .rpc();
+        expect.fail("only the author can archive a post");
+      } catch (error) {
+        expect(error.error.errorCode.code).to.equal("UnauthorizedUser");
+      }
+
+      const archived = eventFrom(
+        (await program.methods
+          .archivePost()
+          .accounts({ post, author: creator.publicKey })
+          .signers([creator])
+          .simulate()).events,
+        "postArchived"
+      );
+      expect(archived.isTrending).to.be.false;
+      expect(archived.viralityScore.toNumber()).to.equal(0);
+
+      await program.methods
+        .archivePost()
+        .accounts({ post, author: creator.publicKey })
+        .signers([creator])
+        .rpc();
+      expect((await program.account.post.fetch(post)).status).to.deep.equal({ archived: {} });
+      expect(await canView(user)).to.be.false;
+      expect(await canView(creator)).to.be.true;
+
+      await program.methods
+        .unarchivePost()
+        .accounts({ post, author: creator.publicKey })
+        .signers([creator])
+        .rpc();
+      expect((await program.account.post.fetch(post)).status).to.deep.equal({ active: {} });
+      expect(await canView(user)).to.be.true;
+    });
+
+    it("Enforces a user's own spend cap across tips and buys until the window resets", async () => {
+      const [platformConfig] = PublicKey.findProgramAddressSync(
+        [Buffer.from("platform_config")],
+        program.programId
+      );
+      const [postStats] = PublicKey.findProgramAddressSync(
+        [Buffer.from("post_stats"), socialPost.toBuffer()],
+        program.programId
+      );
+      const [traderProfile] = PublicKey.findProgramAddressSync(
+        [Buffer.from("user_profile"), trader.publicKey.toBuffer()],
+        program.programId
+      );
+      const [creatorProfile] = PublicKey.findProgramAddressSync(
+        [Buffer.from("user_profile"), creator.publicKey.toBuffer()],
+        program.programId
+      );
+      const setSpendCap = (cap: anchor.BN, windowSeconds: number) =>
+        program.methods
+          .setSpendCap(cap, new anchor.BN(windowSeconds))
+          .accounts({ userProfile: traderProfile, user: trader.publicKey })
+          .signers([trader])
+          .rpc();
+      const buy = () =>
+        program.methods
+          .buyUserKeys(new anchor.BN(1))
+          .accounts({ buyer: trader.publicKey, userProfile: creatorProfile, buyerProfile: traderProfile, tradeLedger: null, tradeReceipt: null })
+          .signers([trader])
+          .rpc();
+
+      const cap = new anchor.BN(LAMPORTS_PER_SOL / 10);
+      await setSpendCap(cap, 3);
+
+      // Spend the whole cap on a tip, leaving nothing for a buy this window
+      await program.methods
+        .tipPost(cap, true)
+        .accounts({
+          post: socialPost,
+          postStats,
+          author: user.publicKey,
+          platformConfig,
+          tipperProfile: traderProfile,
+          tipper: trader.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([trader])
+        .rpc();
+      expect((await program.account.userProfile.fetch(traderProfile)).spentInWindow.toString()).to.equal(cap.toString());
+
+      try {
+        await buy();
+        expect.fail("a buy past the spend cap should be rejected");
+      } catch (error) {
+        expect(error.error.errorCode.code).to.equal("RateLimitExceeded");
+      }
+      try {
+        await program.methods
+          .buyKeys(new anchor.BN(1), new anchor.BN(LAMPORTS_PER_SOL), null)
+          .accounts({ buyer: trader.publicKey, subject: creator.publicKey })
+          .signers([trader])
+          .rpc();
+        expect.fail("buy_keys should count against the same spend cap");
+      } catch (error) {
+        expect(error.error.errorCode.code).to.equal("RateLimitExceeded");
+      }
+
+      await new Promise(resolve => setTimeout(resolve, 4000));
+      await buy();
+      const profile = await program.account.userProfile.fetch(traderProfile);
+      expect(profile.spentInWindow.gtn(0)).to.be.true;
+      expect(profile.spentInWindow.lte(cap)).to.be.true;
+
+      await setSpendCap(new anchor.BN(0), 0);
+    });
+
+    it("Allows one reply per user on single-reply posts", async () => {
+      const [platformConfig] = PublicKey.findProgramAddressSync(
+        [Buffer.from("platform_config")],
+        program.programId
+      );
+      const profileOf = (author: PublicKey) =>
+        PublicKey.findProgramAddressSync([Buffer.from("user_profile"), author.toBuffer()], program.programId)[0];
+      const nextPost = async (author: PublicKey) => {
+        const { postCount } = await program.account.userProfile.fetch(profileOf(author));
+        return PublicKey.findProgramAddressSync(
+          [Buffer.from("post"), author.toBuffer(), postCount.toArrayLike(Buffer, "le", 8)],
+          program.programId
+        )[0];
+      };
+      const reply = async (parent: PublicKey) => {
+        const [replyReceipt] = PublicKey.findProgramAddressSync(
+          [Buffer.from("reply"), parent.toBuffer(), trader.publicKey.toBuffer()],
+          program.programId
+        );
+        return program.methods
+          .createPost("my answer", [], { reply: {} }, parent, [], false, false)
+          .accounts({
+            post: await nextPost(trader.publicKey),
+            userProfile: profileOf(trader.publicKey),
+            platformConfig,
+            parentPost: parent,
+            replyReceipt,
+            user: trader.publicKey,
+          })
+          .signers([trader])
+          .rpc();
+      };
+
+      const { postCount } = await program.account.userProfile.fetch(profileOf(creator.publicKey));
+      const [openPost] = PublicKey.findProgramAddressSync(
+        [Buffer.from("post"), creator.publicKey.toBuffer(), postCount.subn(1).toArrayLike(Buffer, "le", 8)],
+        program.programId
+      );
+      const pollPost = await nextPost(creator.publicKey);
+      await program.methods
+        .createPost("one answer each", [], { original: {} }, null, [], true, false)
+        .accounts({ post: pollPost, userProfile: profileOf(creator.publicKey), platformConfig, user: creator.publicKey })
+        .signers([creator])
+        .rpc();
+
+      await reply(pollPost);
+
+      try {
+        await reply(pollPost);
+        expect.fail("a second reply to a single-reply post should fail");
+      } catch (error) {
+        expect(error.error.errorCode.code).to.equal("AlreadyCommented");
+      }
+
+      // Without the flag a repeat reply is only held back by the usual posting interval
+      await reply(openPost);
+      try {
+        await reply(openPost);
+      } catch (error) {
+        expect(error.error.errorCode.code).to.not.equal("AlreadyCommented");
+      }
+    });
+
+    it("Lets only the author delete a post and adjusts their post counts", async () => {
+      const [creatorProfile] = PublicKey.findProgramAddressSync(
+        [Buffer.from("user_profile"), creator.publicKey.toBuffer()],
+        program.programId
+      );
+      const [traderProfile] = PublicKey.findProgramAddressSync(
+        [Buffer.from("user_profile"), trader.publicKey.toBuffer()],
+        program.programId
+      );
+      const before = await program.account.userProfile.fetch(creatorProfile);
+      const [latestPost] = PublicKey.findProgramAddressSync(
+        [Buffer.from("post"), creator.publicKey.toBuffer(), before.postCount.subn(1).toArrayLike(Buffer, "le", 8)],
+        program.programId
+      );
+
+      try {
+        await program.methods
+          .deletePost(false)
+          .accounts({ post: latestPost, authorProfile: traderProfile, author: trader.publicKey })
+          .signers([trader])
+          .rpc();
+        expect.fail("only the author should be able to delete a post");
+      } catch (error) {
+        expect(error.error.errorCode.code).to.equal("UnauthorizedUser");
+      }
+
+      await program.methods
+        .deletePost(false)
+        .accounts({ post: latestPost, authorProfile: creatorProfile, author: creator.publicKey })
+        .signers([creator])
+        .rpc();
+
+      const post = await program.account.post.fetch(latestPost);
+      expect(post.status).to.deep.equal({ removed: {} });
+
+      const after = await program.account.userProfile.fetch(creatorProfile);
+      expect(after.deletedPostCount.toNumber()).to.equal(before.deletedPostCount.toNumber() + 1);
+      // New posts keep their own addresses
+      expect(after.postCount.toString()).to.equal(before.postCount.toString());
+    });
+
+    it("Locks replies at a post's max_replies but still allows likes and reposts", async () => {
+      const [platformConfig] = PublicKey.findProgramAddressSync(
+        [Buffer.from("platform_config")],
+        program.programId
+      );
+      const profileOf = (owner: PublicKey) =>
+        PublicKey.findProgramAddressSync([Buffer.from("user_profile"), owner.toBuffer()], program.programId)[0];
+      const nextPost = async (author: PublicKey) => {
+        const { postCount } = await program.account.userProfile.fetch(profileOf(author));
+        return PublicKey.findProgramAddressSync(
+          [Buffer.from("post"), author.toBuffer(), postCount.toArrayLike(Buffer, "le", 8)],
+          program.programId
+        )[0];
+      };
+      const [author, ...repliers] = Array.from({ length: 4 }, () => Keypair.generate());
+      for (const [index, member] of [author, ...repliers].entries()) {
+        await connection.confirmTransaction(await connection.requestAirdrop(member.publicKey, LAMPORTS_PER_SOL));
+        await program.methods
+          .createUserProfile(`thread${index}`, "", "")
+          .accounts({ userProfile: profileOf(member.publicKey), user: member.publicKey })
+          .signers([member])
+          .rpc();
+      }
+
+      const thread = await nextPost(author.publicKey);
+      await program.methods
+        .createPost("ask me anything", [], { original: {} }, null, [], false, false)
+        .accounts({ post: thread, userProfile: profileOf(author.publicKey), platformConfig, user: author.publicKey })
+        .signers([author])
+        .rpc();
+      await program.methods
+        .setMaxReplies(new anchor.BN(2))
+        .accounts({ post: thread, author: author.publicKey })
+        .signers([author])
+        .rpc();
+
+      const reply = async (replier: Keypair) =>
+        program.methods
+          .createPost("a reply", [], { reply: {} }, thread, [], false, false)
+          .accounts({
+            post: await nextPost(replier.publicKey),
+            userProfile: profileOf(replier.publicKey),
+            platformConfig,
+            parentPost: thread,
+            replyReceipt: null,
+            user: replier.publicKey,
+          })
+          .signers([replier])
+          .rpc();
+
+      await reply(repliers[0]);
+      await reply(repliers[1]);
+      expect((await program.account.post.fetch(thread)).replies.toNumber()).to.equal(2);
+
+      try {
+        await reply(repliers[2]);
+        expect.fail("replies past max_replies should be rejected");
+      } catch (error) {
+        expect(error.error.errorCode.code).to.equal("ResourceNotAvailable");
+      }
+
+      await program.methods
+        .interactPost(0, null)
+        .accounts({ post: thread, authority: repliers[2].publicKey, platformConfig })
+        .signers([repliers[2]])
+        .rpc();
+      await program.methods
+        .repostPost()
+        .accounts({ post: thread, platformConfig, reposter: repliers[2].publicKey })
+        .signers([repliers[2]])
+        .rpc();
+
+      const locked = await program.account.post.fetch(thread);
+      expect(locked.likes.toNumber()).to.equal(1);
+      expect(locked.reposts.toNumber()).to.equal(1);
+      expect(locked.replies.toNumber()).to.equal(2);
+    });
+  });
+
+  describe("Chat Rooms", () => {
+    it("Creates chat room", async () => {
+      try {
+        await program.methods
+          .createChatRoom("VIP Chat", new anchor.BN(1))
+          .accounts({
+            chatRoom,
+            creator: creator.publicKey,
+            userKeys,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([creator])
+          .rpc();
+
+        const chatRoomAccount = await program.account.chatRoom.fetch(chatRoom);
+        expect(chatRoomAccount.creator.toString()).to.equal(creator.publicKey.toString());
+        expect(chatRoomAccount.name).to.equal("VIP Chat");
+        expect(chatRoomAccount.requiredKeys.toNumber()).to.equal(1);
+        expect(chatRoomAccount.isActive).to.be.true;
+      } catch (error) {
+        console.error("Create chat room error:", error);
+        throw error;
+      }
+    });
+
+    it("Joins chat room", async () => {
+      try {
+        await program.methods
+          .joinChatRoom()
+          .accounts({
+            chatRoom,
+            user: trader.publicKey,
+            userKeys,
+          })
+          .signers([trader])
+          .rpc();
+
+        const chatRoomAccount = await program.account.chatRoom.fetch(chatRoom);
+        const isMember = chatRoomAccount.members.some(
+          member => member.toString() === trader.publicKey.toString()
+        );
+        expect(isMember).to.be.true;
+      } catch (error) {
+        console.error("Join chat room error:", error);
+        throw error;
+      }
+    });
+
+    it("Keeps a quorum room closed until enough participants join", async () => {
+      const roomId = "quorum-room";
+      const [quorumRoom] = PublicKey.findProgramAddressSync(
+        [Buffer.from("chat_room"), Buffer.from(roomId)],
+        program.programId
+      );
+
+      await program.methods
+        .createChatRoom(roomId, new anchor.BN(1), 3)
+        .accounts({
+          chatRoom: quorumRoom,
+          creator: creator.publicKey,
+          userKeys,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([creator])
+        .rpc();
+
+      try {
+        await program.methods
+          .sendMessage(roomId, "anyone here?", [])
+          .accounts({ chatRoom: quorumRoom, sender: creator.publicKey })
+          .signers([creator])
+          .rpc();
+        expect.fail("message should be rejected below the quorum");
+      } catch (error) {
+        expect(error.error.errorCode.code).to.equal("ResourceNotAvailable");
+      }
+
+      for (const member of [trader, user]) {
+        await program.methods
+          .joinChatRoom(roomId)
+          .accounts({
+            chatRoom: quorumRoom,
+            user: member.publicKey,
+            participant: PublicKey.findProgramAddressSync(
+              [Buffer.from("participant"), quorumRoom.toBuffer(), member.publicKey.toBuffer()],
+              program.programId
+            )[0],
+          })
+          .signers([member])
+          .rpc();
+      }
+
+      await program.methods
+        .sendMessage(roomId, "quorum reached", [])
+        .accounts({ chatRoom: quorumRoom, sender: creator.publicKey })
+        .signers([creator])
+        .rpc();
+
+      const chatRoomAccount = await program.account.chatRoom.fetch(quorumRoom);
+      expect(chatRoomAccount.isOpen).to.be.true;
+      expect(chatRoomAccount.messageCount.toNumber()).to.equal(1);
+    });
+
+    it("Enforces length caps on message edits", async () => {
+      const roomId = "quorum-room";
+      const editMessage = (content: string, reason: string | null) =>
+        program.methods
+          .editMessage(roomId, new anchor.BN(0), content, reason)
+          .accounts({ sender: creator.publicKey })
+          .signers([creator])
+          .rpc();
+
+      await editMessage("quorum reached (edited)", "r".repeat(100));
+
+      try {
+        await editMessage("quorum reached again", "r".repeat(101));
+        expect.fail("over-length edit reason should be rejected");
+      } catch (error) {
+        expect(error.error.errorCode.code).to.equal("EditReasonTooLong");
+      }
+
+      try {
+        await editMessage("c".repeat(1001), null);
+        expect.fail("over-length content should be rejected");
+      } catch (error) {
+        expect(error.error.errorCode.code).to.equal("MessageTooLong");
+      }
+    });
+
+    it("Caps the total attachment size per message", async () => {
+      const roomId = "quorum-room";
+      const attachment = (size: number) => ({
+        attachmentType: { image: {} },
+        url: "https://example.com/image.png",
+        filename: null,
+        size: new anchor.BN(size),
+        mimeType: "image/png",
+      });
+
+      await program.methods
+        .setAttachmentLimit(roomId, new anchor.BN(1_000_000))
+        .accounts({ creator: creator.publicKey })
+        .signers([creator])
+        .rpc();
+
+      await program.methods
+        .sendMessage(roomId, "two photos", [attachment(400_000), attachment(600_000)])
+        .accounts({ sender: creator.publicKey })
+        .signers([creator])
+        .rpc();
+
+      try {
+        await program.methods
+          .sendMessage(roomId, "three photos", [attachment(400_000), attachment(400_000), attachment(400_000)])
+          .accounts({ sender: creator.publicKey })
+          .signers([creator])
+          .rpc();
+        expect.fail("attachments over the room limit should be rejected");
+      } catch (error) {
+        expect(error.error.errorCode.code).to.equal("AccountDataTooLarge");
+      }
+
+      try {
+        await program.methods
+          .sendMessage(roomId, "unsized photo", [{ ...attachment(0), size: null }])
+          .accounts({ sender: creator.publicKey })
+          .signers([creator])
+          .rpc();
+        expect.fail("attachments without a size should be rejected in a capped room");
+      } catch (error) {
+        expect(error.error.errorCode.code).to.equal("InvalidInstructionData");
+      }
+    });
+
+    it("Bulk-marks messages read and never moves the pointer backwards", async () => {
+      const roomId = "quorum-room";
+      const [quorumRoom] = PublicKey.findProgramAddressSync(
+        [Buffer.from("chat_room"), Buffer.from(roomId)],
+        program.programId
+      );
+      const [participant] = PublicKey.findProgramAddressSync(
+        [Buffer.from("participant"), quorumRoom.toBuffer(), trader.publicKey.toBuffer()],
+        program.programId
+      );
+      const markRead = (upTo: number) =>
+        program.methods
+          .markReadBulk(roomId, new anchor.BN(upTo))
+          .accounts({ chatRoom: quorumRoom, participant, user: trader.publicKey })
+          .signers([trader])
+          .rpc();
+
+      await markRead(1);
+      let account = await program.account.chatParticipant.fetch(participant);
+      expect(account.lastReadMessage.toNumber()).to.equal(1);
+
+      await markRead(0);
+      account = await program.account.chatParticipant.fetch(participant);
+      expect(account.lastReadMessage.toNumber()).to.equal(1);
+    });
+
+    it("Holds joins for creator approval in vetted rooms", async () => {
+      const roomId = "vetted-room";
+      const [vettedRoom] = PublicKey.findProgramAddressSync(
+        [Buffer.from("chat_room"), Buffer.from(roomId)],
+        program.programId
+      );
+      const [joinRequest] = PublicKey.findProgramAddressSync(
+        [Buffer.from("join_request"), vettedRoom.toBuffer(), trader.publicKey.toBuffer()],
+        program.programId
+      );
+      const sendAsTrader = () =>
+        program.methods
+          .sendMessage(roomId, "hello", [])
+          .accounts({ chatRoom: vettedRoom, sender: trader.publicKey })
+          .signers([trader])
+          .rpc();
+
+      await program.methods
+        .createChatRoom(roomId, new anchor.BN(1), 1)
+        .accounts({ chatRoom: vettedRoom, creator: creator.publicKey, userKeys, systemProgram: SystemProgram.programId })
+        .signers([creator])
+        .rpc();
+      await program.methods
+        .setJoinApproval(roomId, true)
+        .accounts({ chatRoom: vettedRoom, creator: creator.publicKey })
+        .signers([creator])
+        .rpc();
+
+      await program.methods
+        .joinChatRoom(roomId)
+        .accounts({ chatRoom: vettedRoom, user: trader.publicKey, participant: null })
+        .signers([trader])
+        .rpc();
+
+      let request = await program.account.joinRequest.fetch(joinRequest);
+      expect(request.isApproved).to.be.false;
+
+      try {
+        await sendAsTrader();
+        expect.fail("an unapproved applicant should not be able to message");
+      } catch (error) {
+        expect(error.error.errorCode.code).to.equal("RoomAccessDenied");
+      }
+
+      await program.methods
+        .approveJoin(roomId, trader.publicKey)
+        .accounts({ chatRoom: vettedRoom, joinRequest, creator: creator.publicKey })
+        .signers([creator])
+        .rpc();
+
+      request = await program.account.joinRequest.fetch(joinRequest);
+      expect(request.isApproved).to.be.true;
+      await sendAsTrader();
+    });
+
+    it("Only lets the sender or a moderator delete a message", async () => {
+      const roomId = "quorum-room";
+      const [quorumRoom] = PublicKey.findProgramAddressSync(
+        [Buffer.from("chat_room"), Buffer.from(roomId)],
+        program.programId
+      );
+      const messageAt = (id: number) =>
+        PublicKey.findProgramAddressSync(
+          [Buffer.from("message"), quorumRoom.toBuffer(), new anchor.BN(id).toArrayLike(Buffer, "le", 8)],
+          program.programId
+        )[0];
+
+      // user moderates, trader is a plain member
+      for (const [member, role] of [[user, { moderator: {} }], [trader, { member: {} }]] as const) {
+        await program.methods
+          .setParticipantRole(roomId, member.publicKey, role)
+          .accounts({ chatRoom: quorumRoom, callerParticipant: null, caller: creator.publicKey })
+          .signers([creator])
+          .rpc();
+      }
+
+      const send = async (sender: Keypair, content: string) => {
+        const { messageCount } = await program.account.chatRoom.fetch(quorumRoom);
+        await program.methods
+          .sendMessage(roomId, content, [])
+          .accounts({ chatRoom: quorumRoom, sender: sender.publicKey })
+          .signers([sender])
+          .rpc();
+        return messageCount.toNumber();
+      };
+      const remove = (caller: Keypair, id: number) =>
+        program.methods
+          .deleteMessage(roomId, new anchor.BN(id))
+          .accounts({ caller: caller.publicKey, chatRoom: quorumRoom, message: messageAt(id) })
+          .signers([caller])
+          .rpc();
+
+      const ownMessage = await send(trader, "oops, wrong room");
+      await remove(trader, ownMessage);
+      let message = await program.account.message.fetch(messageAt(ownMessage));
+      expect(message.isDeleted).to.be.true;
+      expect(message.content).to.equal("[Message deleted]");
+
+      const creatorMessage = await send(creator, "keep this one");
+      try {
+        await remove(trader, creatorMessage);
+        expect.fail("a plain member should not delete someone else's message");
+      } catch (error) {
+        expect(error.error.errorCode.code).to.equal("PermissionDenied");
+      }
+      message = await program.account.message.fetch(messageAt(creatorMessage));
+      expect(message.isDeleted).to.be.false;
+
+      await remove(user, creatorMessage);
+      message = await program.account.message.fetch(messageAt(creatorMessage));
+      expect(message.isDeleted).to.be.true;
+      expect(message.content).to.equal("[Message deleted]");
+    });
+
+    it("Adds and removes message reactions, pruning emptied ones", async () => {
+      const roomId = "quorum-room";
+      const messageId = new anchor.BN(0);
+      const [quorumRoom] = PublicKey.findProgramAddressSync(
+        [Buffer.from("chat_room"), Buffer.from(roomId)],
+        program.programId
+      );
+      const [message] = PublicKey.findProgramAddressSync(
+        [Buffer.from("message"), quorumRoom.toBuffer(), messageId.toArrayLike(Buffer, "le", 8)],
+        program.programId
+      );
+      const react = (member: Keypair, emoji: string) =>
+        program.methods
+          .reactToMessage(roomId, messageId, emoji)
+          .accounts({ user: member.publicKey, chatRoom: quorumRoom, message, keyHolder: null })
+          .signers([member])
+          .rpc();
+      const unreact = (member: Keypair, emoji: string) =>
+        program.methods
+          .unreactToMessage(roomId, messageId, emoji)
+          .accounts({ user: member.publicKey, chatRoom: quorumRoom, message })
+          .signers([member])
+          .rpc();
+      const expectError = async (attempt: Promise<string>, code: string) => {
+        try {
+          await attempt;
+          expect.fail(`expected ${code}`);
+        } catch (error) {
+          expect(error.error.errorCode.code).to.equal(code);
+        }
+      };
+
+      await react(trader, "🔥");
+      await react(user, "🔥");
+      await expectError(react(trader, "🔥"), "ReactionAlreadyExists");
+      await expectError(react(trader, "x".repeat(17)), "ReactionTooLong");
+
+      let { reactions } = await program.account.message.fetch(message);
+      expect(reactions.get("🔥")).to.have.lengthOf(2);
+
+      await unreact(trader, "🔥");
+      await unreact(user, "🔥");
+      ({ reactions } = await program.account.message.fetch(message));
+      expect(reactions.has("🔥")).to.be.false;
+
+      // Distinct reaction types are capped at 20
+      for (let i = 0; i < 20; i++) {
+        await react(trader, `r${i}`);
+      }
+      await expectError(react(trader, "r20"), "TooManyReactions");
+    });
+
+    it("Rejects muted senders until a timed mute runs out", async () => {
+      const roomId = "quorum-room";
+      const [quorumRoom] = PublicKey.findProgramAddressSync(
+        [Buffer.from("chat_room"), Buffer.from(roomId)],
+        program.programId
+      );
+      const [traderParticipant] = PublicKey.findProgramAddressSync(
+        [Buffer.from("participant"), quorumRoom.toBuffer(), trader.publicKey.toBuffer()],
+        program.programId
+      );
+      // user moderates the room, so may mute trader
+      const mute = (duration: number | null) =>
+        program.methods
+          .muteParticipant(roomId, trader.publicKey, duration === null ? null : new anchor.BN(duration))
+          .accounts({ moderator: user.publicKey, chatRoom: quorumRoom, participant: traderParticipant })
+          .signers([user])
+          .rpc();
+      const send = (content: string) =>
+        program.methods
+          .sendMessage(roomId, content, [])
+          .accounts({ chatRoom: quorumRoom, sender: trader.publicKey, participant: traderParticipant })
+          .signers([trader])
+          .rpc();
+      const expectMuted = async (content: string) => {
+        try {
+          await send(content);
+          expect.fail("a muted sender should be rejected");
+        } catch (error) {
+          expect(error.error.errorCode.code).to.equal("PermissionDenied");
+        }
+      };
+
+      await mute(null);
+      await expectMuted("permanently muted");
+
+      await mute(3600);
+      await expectMuted("muted for an hour");
+
+      await mute(1);
+      await new Promise(resolve => setTimeout(resolve, 2000));
+      await send("mute has expired");
+
+      const participant = await program.account.chatParticipant.fetch(traderParticipant);
+      expect(participant.isMuted).to.be.false;
+      expect(participant.mutedUntil).to.be.null;
+    });
+
+    it("Caps how many invites a room can have outstanding", async () => {
+      const roomId = "quorum-room";
+      const [quorumRoom] = PublicKey.findProgramAddressSync(
+        [Buffer.from("chat_room"), Buffer.from(roomId)],
+        program.programId
+      );
+      const inviteAt = (id: anchor.BN) =>
+        PublicKey.findProgramAddressSync(
+          [Buffer.from("chat_invite"), quorumRoom.toBuffer(), id.toArrayLike(Buffer, "le", 8)],
+          program.programId
+        )[0];
+      const invite = async (invitee: Keypair) => {
+        const { inviteCount } = await program.account.chatRoom.fetch(quorumRoom);
+        await program.methods
+          .createInvite(roomId, invitee.publicKey, null, { direct: {} })
+          .accounts({ inviter: creator.publicKey, chatRoom: quorumRoom, inviterParticipant: null, invite: inviteAt(inviteCount) })
+          .signers([creator])
+          .rpc();
+        return inviteCount;
+      };
+
+      await program.methods
+        .setMaxOpenInvites(roomId, 2)
+        .accounts({ chatRoom: quorumRoom, creator: creator.publicKey })
+        .signers([creator])
+        .rpc();
+
+      const [first, second, third] = [Keypair.generate(), Keypair.generate(), Keypair.generate()];
+      const firstInvite = await invite(first);
+      await invite(second);
+
+      try {
+        await invite(third);
+        expect.fail("invites beyond the cap should be rejected");
+      } catch (error) {
+        expect(error.error.errorCode.code).to.equal("ResourceNotAvailable");
+      }
+
+      await program.methods
+        .redeemInvite(roomId, firstInvite)
+        .accounts({ redeemer: first.publicKey, chatRoom: quorumRoom, invite: inviteAt(firstInvite), participant: null })
+        .signers([first])
+        .rpc();
+
+      await invite(third);
+
+      const room = await program.account.chatRoom.fetch(quorumRoom);
+      expect(room.openInvites).to.equal(2);
+      expect(room.participants.map((p) => p.toString())).to.include(first.publicKey.toString());
+    });
+
+    it("Greets new members with the room's welcome message when one is set", async () => {
+      const roomId = "welcome-room";
+      const welcome = "Welcome! Be kind and stay on topic.";
+      const [welcomeRoom] = PublicKey.findProgramAddressSync(
+        [Buffer.from("chat_room"), Buffer.from(roomId)],
+        program.programId
+      );
+      const messageAt = (index: number) =>
+        PublicKey.findProgramAddressSync(
+          [Buffer.from("message"), welcomeRoom.toBuffer(), new anchor.BN(index).toArrayLike(Buffer, "le", 8)],
+          program.programId
+        )[0];
+      const participantAddress = (member: PublicKey) =>
+        PublicKey.findProgramAddressSync(
+          [Buffer.from("participant"), welcomeRoom.toBuffer(), member.toBuffer()],
+          program.programId
+        )[0];
+      const setWelcome = (message: string) =>
+        program.methods
+          .setWelcomeMessage(roomId, message)
+          .accounts({ chatRoom: welcomeRoom, creator: creator.publicKey })
+          .signers([creator])
+          .rpc();
+
+      await program.methods
+        .createChatRoom(roomId, new anchor.BN(1), 1)
+        .accounts({
+          chatRoom: welcomeRoom,
+          creator: creator.publicKey,
+          userKeys,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([creator])
+        .rpc();
+      await setWelcome(welcome);
+
+      const welcomeMessage = messageAt(0);
+      await program.methods
+        .joinChatRoom(roomId)
+        .accounts({
+          chatRoom: welcomeRoom,
+          user: trader.publicKey,
+          welcomeMessage,
+          participant: participantAddress(trader.publicKey),
+        })
+        .signers([trader])
+        .rpc();
+
+      const greeting = await program.account.message.fetch(welcomeMessage);
+      expect(greeting.content).to.equal(welcome);
+      expect(greeting.messageType).to.deep.equal({ system: {} });
+      expect(greeting.sender.toString()).to.equal(welcomeRoom.toString());
+      expect((await program.account.chatRoom.fetch(welcomeRoom)).messageCount.toNumber()).to.equal(1);
+
+      await setWelcome("");
+      await program.methods
+        .joinChatRoom(roomId)
+        .accounts({
+          chatRoom: welcomeRoom,
+          user: user.publicKey,
+          welcomeMessage: null,
+          participant: participantAddress(user.publicKey),
+        })
+        .signers([user])
+        .rpc();
+
+      expect((await program.account.chatRoom.fetch(welcomeRoom)).messageCount.toNumber()).to.equal(1);
+      expect(await provider.connection.getAccountInfo(messageAt(1))).to.be.null;
+    });
+
+    it("Caps how many distinct reactions one user can leave on a message", async () => {
+      const roomId = "quorum-room";
+      const messageId = new anchor.BN(0);
+      const [quorumRoom] = PublicKey.findProgramAddressSync(
+        [Buffer.from("chat_room"), Buffer.from(roomId)],
+        program.programId
+      );
+      const [message] = PublicKey.findProgramAddressSync(
+        [Buffer.from("message"), quorumRoom.toBuffer(), messageId.toArrayLike(Buffer, "le", 8)],
+        program.programId
+      );
+      const setCap = (maxReactionsPerUser: number) =>
+        program.methods
+          .setMaxReactionsPerUser(roomId, maxReactionsPerUser)
+          .accounts({ chatRoom: quorumRoom, creator: creator.publicKey })
+          .signers([creator])
+          .rpc();
+      const react = (emoji: string) =>
+        program.methods
+          .reactToMessage(roomId, messageId, emoji)
+          .accounts({ user: user.publicKey, chatRoom: quorumRoom, message, keyHolder: null })
+          .signers([user])
+          .rpc();
+
+      const cap = 2;
+      await setCap(cap);
+
+      // Joins reaction types trader already added, so only the per-user cap applies
+      for (let i = 0; i < cap; i++) {
+        await react(`r${i}`);
+      }
+      try {
+        await react(`r${cap}`);
+        expect.fail("reaction beyond the per-user cap should be rejected");
+      } catch (error) {
+        expect(error.error.errorCode.code).to.equal("ResourceNotAvailable");
+      }
+
+      const { reactions } = await program.account.message.fetch(message);
+      expect(reactions.get(`r${cap}`)).to.have.lengthOf(1);
+
+      await setCap(0);
+    });
+
+    it("Limits reactions to key holders when the room requires it", async () => {
+      const roomId = "quorum-room";
+      const messageId = new anchor.BN(0);
+      const [quorumRoom] = PublicKey.findProgramAddressSync(
+        [Buffer.from("chat_room"), Buffer.from(roomId)],
+        program.programId
+      );
+      const [message] = PublicKey.findProgramAddressSync(
+        [Buffer.from("message"), quorumRoom.toBuffer(), messageId.toArrayLike(Buffer, "le", 8)],
+        program.programId
+      );
+      const [userKey] = PublicKey.findProgramAddressSync(
+        [Buffer.from("user_key"), creator.publicKey.toBuffer()],
+        program.programId
+      );
+      const [traderHolder] = PublicKey.findProgramAddressSync(
+        [Buffer.from("key_holder"), userKey.toBuffer(), trader.publicKey.toBuffer()],
+        program.programId
+      );
+      const setRequireKeys = (required: boolean) =>
+        program.methods
+          .setReactionsRequireKeys(roomId, required)
+          .accounts({ chatRoom: quorumRoom, creator: creator.publicKey })
+          .signers([creator])
+          .rpc();
+      const react = (member: Keypair, emoji: string, keyHolder: PublicKey | null) =>
+        program.methods
+          .reactToMessage(roomId, messageId, emoji)
+          .accounts({ user: member.publicKey, chatRoom: quorumRoom, message, keyHolder })
+          .signers([member])
+          .rpc();
+
+      await setRequireKeys(true);
+
+      await react(trader, "💎", traderHolder);
+      try {
+        await react(user, "💎", null);
+        expect.fail("a participant without keys should not be able to react");
+      } catch (error) {
+        expect(error.error.errorCode.code).to.equal("InsufficientKeyBalance");
+      }
+
+      await setRequireKeys(false);
+      await react(user, "💎", null);
+
+      const { reactions } = await program.account.message.fetch(message);
+      expect(reactions.get("💎")).to.have.lengthOf(2);
+    });
+
+    it("Keeps the latest edits in history and refuses edits to deleted messages", async () => {
+      const roomId = "quorum-room";
+      const [quorumRoom] = PublicKey.findProgramAddressSync(
+        [Buffer.from("chat_room"), Buffer.from(roomId)],
+        program.programId
+      );
+      const messageAt = (id: number) =>
+        PublicKey.findProgramAddressSync(
+          [Buffer.from("message"), quorumRoom.toBuffer(), new anchor.BN(id).toArrayLike(Buffer, "le", 8)],
+          program.programId
+        )[0];
+      const edit = (id: number, content: string) =>
+        program.methods
+          .editMessage(roomId, new anchor.BN(id), content, null)
+          .accounts({ sender: trader.publicKey, chatRoom: quorumRoom, message: messageAt(id) })
+          .signers([trader])
+          .rpc();
+
+      const { messageCount } = await program.account.chatRoom.fetch(quorumRoom);
+      const id = messageCount.toNumber();
+      await program.methods
+        .sendMessage(roomId, "v0", [])
+        .accounts({ chatRoom: quorumRoom, sender: trader.publicKey })
+        .signers([trader])
+        .rpc();
+
+      await edit(id, "v1");
+      let message = await program.account.message.fetch(messageAt(id));
+      expect(message.content).to.equal("v1");
+      expect(message.editHistory).to.have.lengthOf(1);
+      expect(message.editHistory[0].previousContent).to.equal("v0");
+
+      // Past five edits the oldest entry is dropped
+      for (let version = 2; version <= 6; version++) {
+        await edit(id, `v${version}`);
+      }
+      message = await program.account.message.fetch(messageAt(id));
+      expect(message.editHistory).to.have.lengthOf(5);
+      expect(message.editHistory[0].previousContent).to.equal("v1");
+      expect(message.editHistory[4].previousContent).to.equal("v5");
+
+      await program.methods
+        .deleteMessage(roomId, new anchor.BN(id))
+        .accounts({ caller: trader.publicKey, chatRoom: quorumRoom, message: messageAt(id) })
+        .signers([trader])
+        .rpc();
+      try {
+        await edit(id, "v7");
+        expect.fail("a deleted message should not be editable");
+      } catch (error) {
+        expect(error.error.errorCode.code).to.equal("MessageDeleted");
+      }
+    });
+
+    it("Gates joins on key balance and the creator's block list", async () => {
+      const participantAddress = (room: PublicKey, member: PublicKey) =>
+        PublicKey.findProgramAddressSync(
+          [Buffer.from("participant"), room.toBuffer(), member.toBuffer()],
+          program.programId
+        )[0];
+      const createRoom = async (roomId: string, requiredKeys: number) => {
+        const [room] = PublicKey.findProgramAddressSync(
+          [Buffer.from("chat_room"), Buffer.from(roomId)],
+          program.programId
+        );
+        await program.methods
+          .createChatRoom(roomId, new anchor.BN(requiredKeys), 1)
+          .accounts({ chatRoom: room, creator: creator.publicKey, userKeys, systemProgram: SystemProgram.programId })
+          .signers([creator])
+          .rpc();
+        return room;
+      };
+      const join = (roomId: string, room: PublicKey, member: Keypair) =>
+        program.methods
+          .joinChatRoom(roomId)
+          .accounts({ chatRoom: room, user: member.publicKey, participant: participantAddress(room, member.publicKey) })
+          .signers([member])
+          .rpc();
+
+      const whaleRoom = await createRoom("whale-room", 1_000_000);
+      try {
+        await join("whale-room", whaleRoom, trader);
+        expect.fail("joining without enough of the creator's keys should fail");
+      } catch (error) {
+        expect(error.error.errorCode.code).to.equal("InsufficientKeyBalance");
+      }
+
+      const gatedRoom = await createRoom("gated-room", 1);
+      await program.methods
+        .blockUser(user.publicKey)
+        .accounts({ authority: creator.publicKey })
+        .signers([creator])
+        .rpc();
+      try {
+        await join("gated-room", gatedRoom, user);
+        expect.fail("a user the creator blocked should not be able to join");
+      } catch (error) {
+        expect(error.error.errorCode.code).to.equal("UserBanned");
+      }
+      await program.methods
+        .unblockUser(user.publicKey)
+        .accounts({ authority: creator.publicKey })
+        .signers([creator])
+        .rpc();
+
+      await join("gated-room", gatedRoom, trader);
+      const room = await program.account.chatRoom.fetch(gatedRoom);
+      expect(room.participants.map(p => p.toString())).to.include(trader.publicKey.toString());
+      expect(room.participants.map(p => p.toString())).to.not.include(user.publicKey.toString());
+
+      const participant = await program.account.chatParticipant.fetch(participantAddress(gatedRoom, trader.publicKey));
+      expect(participant.user.toString()).to.equal(trader.publicKey.toString());
+      expect(participant.role).to.deep.equal({ member: {} });
+    });
+
+    it("Lets members leave a room but not its owner", async () => {
+      const roomId = "gated-room";
+      const [gatedRoom] = PublicKey.findProgramAddressSync(
+        [Buffer.from("chat_room"), Buffer.from(roomId)],
+        program.programId
+      );
+      const participantAddress = (member: PublicKey) =>
+        PublicKey.findProgramAddressSync(
+          [Buffer.from("participant"), gatedRoom.toBuffer(), member.toBuffer()],
+          program.programId
+        )[0];
+      const leave = (member: Keypair) =>
+        program.methods
+          .leaveChatRoom(roomId)
+          .accounts({ user: member.publicKey, chatRoom: gatedRoom, participant: participantAddress(member.publicKey) })
+          .signers([member])
+          .rpc();
+
+      const before = (await program.account.chatRoom.fetch(gatedRoom)).participants.length;
+      await leave(trader);
+
+      const room = await program.account.chatRoom.fetch(gatedRoom);
+      expect(room.participants.length).to.equal(before - 1);
+      expect(room.participants.map(p => p.toString())).to.not.include(trader.publicKey.toString());
+      expect(await provider.connection.getAccountInfo(participantAddress(trader.publicKey))).to.be.null;
+
+      try {
+        await program.methods
+          .leaveChatRoom(roomId)
+          .accounts({ user: trader.publicKey, chatRoom: gatedRoom, participant: null })
+          .signers([trader])
+          .rpc();
+        expect.fail("leaving twice should fail");
+      } catch (error) {
+        expect(error.error.errorCode.code).to.equal("ParticipantNotFound");
+      }
+
+      try {
+        await program.methods
+          .leaveChatRoom(roomId)
+          .accounts({ user: creator.publicKey, chatRoom: gatedRoom, participant: null })
+          .signers([creator])
+          .rpc();
+        expect.fail("the owner should not be able to leave");
+      } catch (error) {
+        expect(error.error.errorCode.code).to.equal("PermissionDenied");
+      }
+    });
+
+    it("Transfers room ownership only from the owner to an existing member", async () => {
+      const roomId = "handoff-room";
+      const [handoffRoom] = PublicKey.findProgramAddressSync(
+        [Buffer.from("chat_room"), Buffer.from(roomId)],
+        program.programId
+      );
+      const participantAddress = (member: PublicKey) =>
+        PublicKey.findProgramAddressSync(
+          [Buffer.from("participant"), handoffRoom.toBuffer(), member.toBuffer()],
+          program.programId
+        )[0];
+      const transfer = (caller: Keypair, newOwner: PublicKey) =>
+        program.methods
+          .transferRoomOwnership(roomId, newOwner)
+          .accounts({
+            chatRoom: handoffRoom,
+            ownerParticipant: participantAddress(caller.publicKey),
+            newOwnerParticipant: participantAddress(newOwner),
+            owner: caller.publicKey,
+          })
+          .signers([caller])
+          .rpc();
+
+      await program.methods
+        .createChatRoom(roomId, new anchor.BN(1), 1)
+        .accounts({ chatRoom: handoffRoom, creator: creator.publicKey, userKeys, systemProgram: SystemProgram.programId })
+        .signers([creator])
+        .rpc();
+      await program.methods
+        .joinChatRoom(roomId)
+        .accounts({ chatRoom: handoffRoom, user: trader.publicKey, participant: participantAddress(trader.publicKey) })
+        .signers([trader])
+        .rpc();
+
+      try {
+        await transfer(trader, trader.publicKey);
+        expect.fail("only the owner can hand off the room");
+      } catch (error) {
+        expect(error.error.errorCode.code).to.equal("UnauthorizedUser");
+      }
+
+      try {
+        await transfer(creator, user.publicKey);
+        expect.fail("ownership can only go to a member");
+      } catch (error) {
+        expect(error.error.errorCode.code).to.equal("InvalidResourceState");
+      }
+
+      await transfer(creator, trader.publicKey);
+      const room = await program.account.chatRoom.fetch(handoffRoom);
+      expect(room.creator.toString()).to.equal(trader.publicKey.toString());
+      expect((await program.account.chatParticipant.fetch(participantAddress(trader.publicKey))).role)
+        .to.deep.equal({ owner: {} });
+      expect((await program.account.chatParticipant.fetch(participantAddress(creator.publicKey))).role)
+        .to.deep.equal({ admin: {} });
+    });
+
+    it("Lets room managers change member roles but not plain members", async () => {
+      const roomId = "roles-room";
+      const [rolesRoom] = PublicKey.findProgramAddressSync(
+        [Buffer.from("chat_room"), Buffer.from(roomId)],
+        program.programId
+      );
+      const participantAddress = (member: PublicKey) =>
+        PublicKey.findProgramAddressSync(
+          [Buffer.from("participant"), rolesRoom.toBuffer(), member.toBuffer()],
+          program.programId
+        )[0];
+      const setRole = (caller: Keypair, member: PublicKey, role: object, callerParticipant: PublicKey | null) =>
+        program.methods
+          .setParticipantRole(roomId, member, role)
+          .accounts({
+            chatRoom: rolesRoom,
+            callerParticipant,
+            participant: participantAddress(member),
+            caller: caller.publicKey,
+          })
+          .signers([caller])
+          .rpc();
+
+      await program.methods
+        .createChatRoom(roomId, new anchor.BN(1), 1)
+        .accounts({ chatRoom: rolesRoom, creator: creator.publicKey, userKeys, systemProgram: SystemProgram.programId })
+        .signers([creator])
+        .rpc();
+      for (const member of [trader, user]) {
+        await program.methods
+          .joinChatRoom(roomId)
+          .accounts({ chatRoom: rolesRoom, user: member.publicKey, participant: participantAddress(member.publicKey) })
+          .signers([member])
+          .rpc();
+      }
+
+      try {
+        await setRole(user, trader.publicKey, { moderator: {} }, participantAddress(user.publicKey));
+        expect.fail("a plain member should not be able to change roles");
+      } catch (error) {
+        expect(error.error.errorCode.code).to.equal("PermissionDenied");
+      }
+
+      await setRole(creator, trader.publicKey, { admin: {} }, null);
+      await setRole(trader, user.publicKey, { moderator: {} }, participantAddress(trader.publicKey));
+
+      const moderator = await program.account.chatParticipant.fetch(participantAddress(user.publicKey));
+      expect(moderator.role).to.deep.equal({ moderator: {} });
+      expect(moderator.permissions.canMuteUsers).to.be.true;
+      expect(moderator.permissions.canManageRoom).to.be.false;
+
+      // Admins get the stock admin permissions, which stop short of managing the room
+      const admin = await program.account.chatParticipant.fetch(participantAddress(trader.publicKey));
+      expect(admin.permissions.canKickUsers).to.be.true;
+      expect(admin.permissions.canManageRoom).to.be.false;
+
+      try {
+        await setRole(trader, user.publicKey, { owner: {} }, participantAddress(trader.publicKey));
+        expect.fail("ownership should only move through a transfer");
+      } catch (error) {
+        expect(error.error.errorCode.code).to.equal("PermissionDenied");
+      }
+
+      // Admins can't demote each other; the owner can
+      await setRole(creator, user.publicKey, { admin: {} }, null);
+      try {
+        await setRole(trader, user.publicKey, { member: {} }, participantAddress(trader.publicKey));
+        expect.fail("an admin should not be able to demote another admin");
+      } catch (error) {
+        expect(error.error.errorCode.code).to.equal("PermissionDenied");
+      }
+      await setRole(creator, user.publicKey, { moderator: {} }, null);
+    });
+
+    it("Rejects expired and already-redeemed invites", async () => {
+      const roomId = "roles-room";
+      const [rolesRoom] = PublicKey.findProgramAddressSync(
+        [Buffer.from("chat_room"), Buffer.from(roomId)],
+        program.programId
+      );
+      const inviteAt = (id: anchor.BN) =>
+        PublicKey.findProgramAddressSync(
+          [Buffer.from("chat_invite"), rolesRoom.toBuffer(), id.toArrayLike(Buffer, "le", 8)],
+          program.programId
+        )[0];
+      const invite = async (invitee: PublicKey, expiresAt: anchor.BN | null, inviteType: object) => {
+        const { inviteCount } = await program.account.chatRoom.fetch(rolesRoom);
+        await program.methods
+          .createInvite(roomId, invitee, expiresAt, inviteType)
+          .accounts({ inviter: creator.publicKey, chatRoom: rolesRoom, inviterParticipant: null, invite: inviteAt(inviteCount) })
+          .signers([creator])
+          .rpc();
+        return inviteCount;
+      };
+      const redeem = (redeemer: Keypair, inviteId: anchor.BN) =>
+        program.methods
+          .redeemInvite(roomId, inviteId)
+          .accounts({ redeemer: redeemer.publicKey, chatRoom: rolesRoom, invite: inviteAt(inviteId), participant: null })
+          .signers([redeemer])
+          .rpc();
+
+      const late = Keypair.generate();
+      const expiring = await invite(late.publicKey, new anchor.BN(Math.floor(Date.now() / 1000) + 2), { direct: {} });
+      await new Promise(resolve => setTimeout(resolve, 3000));
+      try {
+        await redeem(late, expiring);
+        expect.fail("an expired invite should not be redeemable");
+      } catch (error) {
+        expect(error.error.errorCode.code).to.equal("ResourceExpired");
+      }
+
+      // Link invites work for any wallet, but only once
+      const [holder, latecomer] = [Keypair.generate(), Keypair.generate()];
+      const link = await invite(PublicKey.default, null, { link: {} });
+      await redeem(holder, link);
+
+      const redeemed = await program.account.chatInvite.fetch(inviteAt(link));
+      expect(redeemed.isUsed).to.be.true;
+      expect(redeemed.invitee.toString()).to.equal(holder.publicKey.toString());
+      expect((await program.account.chatRoom.fetch(rolesRoom)).participants.map(p => p.toString()))
+        .to.include(holder.publicKey.toString());
+
+      try {
+        await redeem(latecomer, link);
+        expect.fail("an invite should only be redeemable once");
+      } catch (error) {
+        expect(error.error.errorCode.code).to.equal("InvalidResourceState");
+      }
+    });
+
+    it("Gates joins on a required NFT collection and skips the check for open rooms", async () => {
+      const roomAddress = (roomId: string) =>
+        PublicKey.findProgramAddressSync(
+          [Buffer.from("chat_room"), Buffer.from(roomId)],
+          program.programId
+        )[0];
+      const participantAddress = (room: PublicKey, member: PublicKey) =>
+        PublicKey.findProgramAddressSync(
+          [Buffer.from("participant"), room.toBuffer(), member.toBuffer()],
+          program.programId
+        )[0];
+      const createRoom = async (roomId: string) => {
+        const room = roomAddress(roomId);
+        await program.methods
+          .createChatRoom(roomId, new anchor.BN(1), 1)
+          .accounts({ chatRoom: room, creator: creator.publicKey, userKeys, systemProgram: SystemProgram.programId })
+          .signers([creator])
+          .rpc();
+        return room;
+      };
+      const join = (roomId: string, room: PublicKey, member: Keypair) =>
+        program.methods
+          .joinChatRoom(roomId)
+          .accounts({
+            chatRoom: room,
+            user: member.publicKey,
+            participant: participantAddress(room, member.publicKey),
+            nftTokenAccount: null,
+            nftMetadata: null,
+          })
+          .signers([member])
+          .rpc();
+
+      // No collection set: the NFT accounts are never looked at
+      const openRoom = await createRoom("open-nft-room");
+      await join("open-nft-room", openRoom, trader);
+      expect((await program.account.chatRoom.fetch(openRoom)).participants.map(p => p.toString()))
+        .to.include(trader.publicKey.toString());
+
+      const gatedRoom = await createRoom("gated-nft-room");
+      const collection = Keypair.generate().publicKey;
+      await program.methods
+        .setRequiredNftCollection("gated-nft-room", collection)
+        .accounts({ chatRoom: gatedRoom, creator: creator.publicKey })
+        .signers([creator])
+        .rpc();
+      expect((await program.account.chatRoom.fetch(gatedRoom)).accessControl.requiredNftCollection.toString())
+        .to.equal(collection.toString());
+
+      try {
+        await join("gated-nft-room", gatedRoom, trader);
+        expect.fail("joining a gated room without an NFT should fail");
+      } catch (error) {
+        expect(error.error.errorCode.code).to.equal("NFTNotOwned");
+      }
+
+      // Clearing the collection reopens the room
+      await program.methods
+        .setRequiredNftCollection("gated-nft-room", null)
+        .accounts({ chatRoom: gatedRoom, creator: creator.publicKey })
+        .signers([creator])
+        .rpc();
+      await join("gated-nft-room", gatedRoom, trader);
+    });
+
+    it("Lets holders of the required collection in and rejects NFTs from another collection", async () => {
+      const metadataAddress = (mint: PublicKey) =>
+        PublicKey.findProgramAddressSync(
+          [Buffer.from("metadata"), TOKEN_METADATA_PROGRAM_ID.toBuffer(), mint.toBuffer()],
+          TOKEN_METADATA_PROGRAM_ID
+        )[0];
+      const editionAddress = (mint: PublicKey) =>
+        PublicKey.findProgramAddressSync(
+          [Buffer.from("metadata"), TOKEN_METADATA_PROGRAM_ID.toBuffer(), mint.toBuffer(), Buffer.from("edition")],
+          TOKEN_METADATA_PROGRAM_ID
+        )[0];
+
+      // Mints a one-of-one NFT to `owner`, optionally as a verified item of `collection`
+      const mintNft = async (owner: PublicKey, collection: PublicKey | null, isCollection = false) => {
+        const mint = await createMint(connection, wallet.payer, wallet.publicKey, wallet.publicKey, 0);
+        const tokenAccount = await getOrCreateAssociatedTokenAccount(connection, wallet.payer, mint, owner);
+        await mintTo(connection, wallet.payer, mint, tokenAccount.address, wallet.payer, 1);
+
+        const tx = new anchor.web3.Transaction().add(
+          createCreateMetadataAccountV3Instruction(
+            {
+              metadata: metadataAddress(mint),
+              mint,
+              mintAuthority: wallet.publicKey,
+              payer: wallet.publicKey,
+              updateAuthority: wallet.publicKey,
+            },
+            {
+              createMetadataAccountArgsV3: {
+                data: {
+                  name: "Gate Pass",
+                  symbol: "GATE",
+                  uri: "https://example.com/nft.json",
+                  sellerFeeBasisPoints: 0,
+                  creators: null,
+                  collection: collection ? { verified: false, key: collection } : null,
+                  uses: null,
+                },
+                isMutable: true,
+                collectionDetails: isCollection ? { __kind: "V1", size: 0 } : null,
+              },
+            }
+          ),
+          createCreateMasterEditionV3Instruction(
+            {
+              edition: editionAddress(mint),
+              mint,
+              updateAuthority: wallet.publicKey,
+              mintAuthority: wallet.publicKey,
+              payer: wallet.publicKey,
+              metadata: metadataAddress(mint),
+            },
+            { createMasterEditionArgs: { maxSupply: 0 } }
+          )
+        );
+        if (collection) {
+          tx.add(
+            createVerifySizedCollectionItemInstruction({
+              metadata: metadataAddress(mint),
+              collectionAuthority: wallet.publicKey,
+              payer: wallet.publicKey,
+              collectionMint: collection,
+              collection: metadataAddress(collection),
+              collectionMasterEditionAccount: editionAddress(collection),
+            })
+          );
+        }
+        await provider.sendAndConfirm(tx);
+        return { mint, tokenAccount: tokenAccount.address, metadata: metadataAddress(mint) };
+      };
+
+      const requiredCollection = (await mintNft(wallet.publicKey, null, true)).mint;
+      const otherCollection = (await mintNft(wallet.publicKey, null, true)).mint;
+      const matchingNft = await mintNft(trader.publicKey, requiredCollection);
+      const wrongNft = await mintNft(user.publicKey, otherCollection);
+
+      const roomId = "collection-gated-room";
+      const [room] = PublicKey.findProgramAddressSync(
+        [Buffer.from("chat_room"), Buffer.from(roomId)],
+        program.programId
+      );
+      await program.methods
+        .createChatRoom(roomId, new anchor.BN(1), 1)
+        .accounts({ chatRoom: room, creator: creator.publicKey, userKeys, systemProgram: SystemProgram.programId })
+        .signers([creator])
+        .rpc();
+      await program.methods
+        .setRequiredNftCollection(roomId, requiredCollection)
+        .accounts({ chatRoom: room, creator: creator.publicKey })
+        .signers([creator])
+        .rpc();
+
+      const join = (member: Keypair, nft: { tokenAccount: PublicKey; metadata: PublicKey }) =>
+        program.methods
+          .joinChatRoom(roomId)
+          .accounts({
+            chatRoom: room,
+            user: member.publicKey,
+            participant: PublicKey.findProgramAddressSync(
+              [Buffer.from("participant"), room.toBuffer(), member.publicKey.toBuffer()],
+              program.programId
+            )[0],
+            nftTokenAccount: nft.tokenAccount,
+            nftMetadata: nft.metadata,
+          })
+          .signers([member])
+          .rpc();
+
+      // A verified item of the required collection, held by the caller, gets in
+      await join(trader, matchingNft);
+      expect((await program.account.chatRoom.fetch(room)).participants.map(p => p.toString()))
+        .to.include(trader.publicKey.toString());
+
+      // A verified NFT from a different collection does not
+      try {
+        await join(user, wrongNft);
+        expect.fail("an NFT from another collection should not open a gated room");
+      } catch (error) {
+        expect(error.error.errorCode.code).to.equal("NFTNotOwned");
+      }
+      expect((await program.account.chatRoom.fetch(room)).participants.map(p => p.toString()))
+        .to.not.include(user.publicKey.toString());
+
+      // Presenting someone else's matching NFT doesn't work either
+      try {
+        await join(user, matchingNft);
+        expect.fail("the NFT must be held by the caller");
+      } catch (error) {
+        expect(error.error.errorCode.code).to.equal("NFTNotOwned");
+      }
+    });
+
+    it("Validates chat tag count and length at creation", async () => {
+      const [creatorAccount] = PublicKey.findProgramAddressSync(
+        [Buffer.from("user"), creator.publicKey.toBuffer()],
+        program.programId
+      );
+      const [creatorKeys] = PublicKey.findProgramAddressSync(
+        [Buffer.from("user_keys"), creatorAccount.toBuffer()],
+        program.programId
+      );
+      const createChat = (chatId: string, tags: string[]) =>
+        program.methods
+          .createChat(chatId, "Tagged chat", "", false, new anchor.BN(0), 10, tags)
+          .accounts({
+            chat: PublicKey.findProgramAddressSync(
+              [Buffer.from("chat"), creator.publicKey.toBuffer(), Buffer.from(chatId)],
+              program.programId
+            )[0],
+            userProfile: creatorAccount,
+            userKeys: creatorKeys,
+            creator: creator.publicKey,
+          })
+          .signers([creator])
+          .rpc();
+
+      await createChat("tags-ok", Array.from({ length: 10 }, (_, i) => `tag${i}`.padEnd(50, "x")));
+
+      try {
+        await createChat("tags-count", Array.from({ length: 11 }, (_, i) => `tag${i}`));
+        expect.fail("more than ten tags should be rejected");
+      } catch (error) {
+        expect(error.error.errorCode.code).to.equal("TooManyTags");
+      }
+
+      try {
+        await createChat("tags-length", ["x".repeat(51)]);
+        expect.fail("a tag over 50 bytes should be rejected");
+      } catch (error) {
+        expect(error.error.errorCode.code).to.equal("TagTooLong");
+      }
+    });
+
+    it("Marks a message read and ignores older message ids", async () => {
+      const roomId = "quorum-room";
+      const [quorumRoom] = PublicKey.findProgramAddressSync(
+        [Buffer.from("chat_room"), Buffer.from(roomId)],
+        program.programId
+      );
+      const [participant] = PublicKey.findProgramAddressSync(
+        [Buffer.from("participant"), quorumRoom.toBuffer(), trader.publicKey.toBuffer()],
+        program.programId
+      );
+      const markRead = (messageId: number) =>
+        program.methods
+          .markRead(roomId, new anchor.BN(messageId))
+          .accounts({ chatRoom: quorumRoom, participant, user: trader.publicKey })
+          .signers([trader]);
+
+      const { lastReadMessage } = await program.account.chatParticipant.fetch(participant);
+      const receipt = (await markRead(lastReadMessage.toNumber()).simulate()).events
+        .find((event) => event.name === "readReceipt").data;
+      expect(receipt.user.toBase58()).to.equal(trader.publicKey.toBase58());
+      expect(receipt.lastReadMessage.toString()).to.equal(lastReadMessage.toString());
+
+      await markRead(0).rpc();
+      const account = await program.account.chatParticipant.fetch(participant);
+      expect(account.lastReadMessage.toString()).to.equal(lastReadMessage.toString());
+    });
+
+    it("Blocks messaging once a member sells below the room's key requirement", async () => {
+      const roomId = "holders-room";
+      const [holdersRoom] = PublicKey.findProgramAddressSync(
+        [Buffer.from("chat_room"), Buffer.from(roomId)],
+        program.programId
+      );
+      const [participant] = PublicKey.findProgramAddressSync(
+        [Buffer.from("participant"), holdersRoom.toBuffer(), trader.publicKey.toBuffer()],
+        program.programId
+      );
+      const [creatorKey] = PublicKey.findProgramAddressSync(
+        [Buffer.from("user_key"), creator.publicKey.toBuffer()],
+        program.programId
+      );
+      const [keyHolder] = PublicKey.findProgramAddressSync(
+        [Buffer.from("key_holder"), creatorKey.toBuffer(), trader.publicKey.toBuffer()],
+        program.programId
+      );
+      const buy = (amount: number) =>
+        program.methods
+          .buyKeys(new anchor.BN(amount), new anchor.BN(10 * LAMPORTS_PER_SOL), null)
+          .accounts({ buyer: trader.publicKey, subject: creator.publicKey })
+          .signers([trader])
+          .rpc();
+      const send = (content: string) =>
+        program.methods
+          .sendMessage(roomId, content, [])
+          .accounts({ chatRoom: holdersRoom, sender: trader.publicKey, participant })
+          .signers([trader])
+          .rpc();
+
+      await program.methods
+        .createChatRoom(roomId, new anchor.BN(1), 1)
+        .accounts({ chatRoom: holdersRoom, creator: creator.publicKey, userKeys, systemProgram: SystemProgram.programId })
+        .signers([creator])
+        .rpc();
+      await buy(1);
+      await program.methods
+        .joinChatRoom(roomId)
+        .accounts({ chatRoom: holdersRoom, user: trader.publicKey, participant })
+        .signers([trader])
+        .rpc();
+      await send("glad to be here");
+
+      const { amount } = await program.account.keyHolder.fetch(keyHolder);
+      await program.methods
+        .sellKeys(amount, new anchor.BN(0), null)
+        .accounts({ seller: trader.publicKey, subject: creator.publicKey })
+        .signers([trader])
+        .rpc();
+
+      await program.methods
+        .revalidateParticipant(roomId, trader.publicKey)
+        .accounts({ chatRoom: holdersRoom, participant, keyHolder, caller: creator.publicKey })
+        .signers([creator])
+        .rpc();
+      expect((await program.account.chatParticipant.fetch(participant)).isLapsed).to.be.true;
+
+      try {
+        await send("still here?");
+        expect.fail("a member who sold their keys should not be able to message");
+      } catch (error) {
+        expect(error.error.errorCode.code).to.equal("InsufficientKeyBalance");
+      }
+
+      await buy(1);
+      await send("back again");
+      expect((await program.account.chatParticipant.fetch(participant)).isLapsed).to.be.false;
+    });
+
+    it("Lets the creator lower a room's quorum before it opens", async () => {
+      const roomId = "lowered-quorum-room";
+      const [quorumRoom] = PublicKey.findProgramAddressSync(
+        [Buffer.from("chat_room"), Buffer.from(roomId)],
+        program.programId
+      );
+
+      await program.methods
+        .createChatRoom(roomId, new anchor.BN(1), 5)
+        .accounts({
+          chatRoom: quorumRoom,
+          creator: creator.publicKey,
+          userKeys,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([creator])
+        .rpc();
+
+      await program.methods
+        .setMinParticipants(roomId, 1)
+        .accounts({ chatRoom: quorumRoom, creator: creator.publicKey })
+        .signers([creator])
+        .rpc();
+
+      const chatRoomAccount = await program.account.chatRoom.fetch(quorumRoom);
+      expect(chatRoomAccount.minParticipantsToOpen).to.equal(1);
+      expect(chatRoomAccount.isOpen).to.be.true;
+    });
+  });
+
+  describe("Reputation System", () => {
+    it("Initializes user reputation", async () => {
+      try {
+        await program.methods
+          .initializeUserReputation()
+          .accounts({
+            userReputation,
+            user: user.publicKey,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([user])
+          .rpc();
+
+        const userReputationAccount = await program.account.userReputation.fetch(userReputation);
+        expect(userReputationAccount.user.toString()).to.equal(user.publicKey.toString());
+        expect(userReputationAccount.score.toNumber()).to.equal(0);
+        expect(userReputationAccount.level.toNumber()).to.equal(1);
+      } catch (error) {
+        console.error("Initialize user reputation error:", error);
+        throw error;
+      }
+    });
+
+    it("Updates reputation score", async () => {
+      try {
+        await program.methods
+          .updateReputationScore(new anchor.BN(100))
+          .accounts({
+            userReputation,
+            authority: wallet.publicKey,
+          })
+          .rpc();
+
+        const userReputationAccount = await program.account.userReputation.fetch(userReputation);
+        expect(userReputationAccount.score.toNumber()).to.equal(100);
+      } catch (error) {
+        console.error("Update reputation score error:", error);
+        throw error;
+      }
+    });
+
+    it("Emits an activity proof matching the user account", async () => {
+      const [userAccount] = PublicKey.findProgramAddressSync(
+        [Buffer.from("user"), user.publicKey.toBuffer()],
+        program.programId
+      );
+
+      const proofs = [];
+      const listener = program.addEventListener("activityProof", (event) => proofs.push(event));
+
+      await program.methods
+        .emitActivityProof()
+        .accounts({ user: userAccount, authority: user.publicKey })
+        .signers([user])
+        .rpc();
+
+      await new Promise(resolve => setTimeout(resolve, 1000));
+      await program.removeEventListener(listener);
+
+      const account = await program.account.user.fetch(userAccount);
+      const { socialStats } = account;
+      const expectedEngagement = socialStats.totalLikesReceived
+        .add(socialStats.totalCommentsReceived)
+        .add(socialStats.totalSharesReceived);
+      const expectedHash = createHash("sha256")
+        .update(account.authority.toBuffer())
+        .update(account.keySupply.toArrayLike(Buffer, "le", 8))
+        .update(account.keyPrice.toArrayLike(Buffer, "le", 8))
+        .update(account.totalVolume.toArrayLike(Buffer, "le", 8))
+        .update(new anchor.BN(account.keyHolders.length).toArrayLike(Buffer, "le", 8))
+        .digest();
+
+      expect(proofs.length).to.equal(1);
+      expect(proofs[0].postsCount.toString()).to.equal(account.postCount.toString());
+      expect(proofs[0].totalEngagement.toString()).to.equal(expectedEngagement.toString());
+      expect(proofs[0].tradeVolume.toString()).to.equal(account.totalVolume.toString());
+      expect(Buffer.from(proofs[0].keyStatsHash).equals(expectedHash)).to.be.true;
+    });
+
+    it("Grants the daily bonus only on the first post of a UTC day", async () => {
+      const [platformConfig] = PublicKey.findProgramAddressSync(
+        [Buffer.from("platform_config")],
+        program.programId
+      );
+      const bonus = 1000;
+      await program.methods
+        .setDailyPostBonus(new anchor.BN(bonus))
+        .accounts({ platformConfig, authority: wallet.publicKey })
+        .rpc();
+
+      const poster = Keypair.generate();
+      await connection.confirmTransaction(await connection.requestAirdrop(poster.publicKey, LAMPORTS_PER_SOL));
+      const [posterProfile] = PublicKey.findProgramAddressSync(
+        [Buffer.from("user_profile"), poster.publicKey.toBuffer()],
+        program.programId
+      );
+      await program.methods
+        .createUserProfile("daily_poster", "", "")
+        .accounts({ userProfile: posterProfile, user: poster.publicKey })
+        .signers([poster])
+        .rpc();
+
+      const post = async (content: string) => {
+        const { postCount } = await program.account.userProfile.fetch(posterProfile);
+        const [postAccount] = PublicKey.findProgramAddressSync(
+          [Buffer.from("post"), poster.publicKey.toBuffer(), postCount.toArrayLike(Buffer, "le", 8)],
+          program.programId
+        );
+        await program.methods
+          .createPost(content, [], { original: {} }, null, [], false, false)
+          .accounts({ post: postAccount, userProfile: posterProfile, platformConfig, user: poster.publicKey })
+          .signers([poster])
+          .rpc();
+        return (await program.account.userProfile.fetch(posterProfile)).reputation.toNumber();
+      };
+
+      const startingReputation = (await program.account.userProfile.fetch(posterProfile)).reputation.toNumber();
+      const afterFirst = await post("gm");
+      expect(afterFirst - startingReputation).to.equal(1 + bonus);
+
+      // The bonus lifts the poster into the shortest posting interval
+      await new Promise(resolve => setTimeout(resolve, 11_000));
+      const afterSecond = await post("gm again");
+      expect(afterSecond - afterFirst).to.equal(1);
+    });
+
+    it("Leaves a fresh profile's reputation alone when decay is called", async () => {
+      const [platformConfig] = PublicKey.findProgramAddressSync(
+        [Buffer.from("platform_config")],
+        program.programId
+      );
+      const decay = () =>
+        program.methods
+          .decayReputation()
+          .accounts({ userProfile, platformConfig })
+          .rpc();
+
+      // A non-zero default rate, so the no-op below is down to the profile being fresh
+      const { reputationDecayRate } = await program.account.platformConfig.fetch(platformConfig);
+      expect(reputationDecayRate).to.be.greaterThan(0);
+
+      const before = await program.account.userProfile.fetch(userProfile);
+      expect(before.lastReputationUpdate.toNumber()).to.be.greaterThan(0);
+      await decay();
+      await decay();
+      const after = await program.account.userProfile.fetch(userProfile);
+
+      expect(after.reputationScore.toString()).to.equal(before.reputationScore.toString());
+      expect(after.lastReputationUpdate.toString()).to.equal(before.lastReputationUpdate.toString());
+    });
+
+    it("Lets the authority tune the reputation decay rate", async () => {
+      const [platformConfig] = PublicKey.findProgramAddressSync(
+        [Buffer.from("platform_config")],
+        program.programId
+      );
+      const { reputationDecayRate } = await program.account.platformConfig.fetch(platformConfig);
+
+      try {
+        await program.methods
+          .setReputationDecayRate(10001)
+          .accounts({ platformConfig, authority: wallet.publicKey })
+          .rpc();
+        expect.fail("rates above 100% should be rejected");
+      } catch (error) {
+        expect(error.error.errorCode.code).to.equal("InvalidPercentage");
+      }
+
+      try {
+        await program.methods
+          .setReputationDecayRate(100)
+          .accounts({ platformConfig, authority: user.publicKey })
+          .signers([user])
+          .rpc();
+        expect.fail("only the authority can change the decay rate");
+      } catch (error) {
+        expect(error.error.errorCode.code).to.equal("UnauthorizedUser");
+      }
+
+      await program.methods
+        .setReputationDecayRate(100)
+        .accounts({ platformConfig, authority: wallet.publicKey })
+        .rpc();
+      expect((await program.account.platformConfig.fetch(platformConfig)).reputationDecayRate).to.equal(100);
+
+      await program.methods
+        .setReputationDecayRate(reputationDecayRate)
+        .accounts({ platformConfig, authority: wallet.publicKey })
+        .rpc();
+    });
+
+    it("Penalizes rapid serial unfollows but not occasional ones", async () => {
+      const [platformConfig] = PublicKey.findProgramAddressSync(
+        [Buffer.from("platform_config")],
+        program.programId
+      );
+      const [followRecord] = PublicKey.findProgramAddressSync(
+        [Buffer.from("follow"), creator.publicKey.toBuffer(), trader.publicKey.toBuffer()],
+        program.programId
+      );
+      const [creatorProfile] = PublicKey.findProgramAddressSync(
+        [Buffer.from("user_profile"), creator.publicKey.toBuffer()],
+        program.programId
+      );
+      const [traderProfile] = PublicKey.findProgramAddressSync(
+        [Buffer.from("user_profile"), trader.publicKey.toBuffer()],
+        program.programId
+      );
+      const setPenalty = (window: number, freeUnfollows: number, penalty: number) =>
+        program.methods
+          .setUnfollowPenalty(new anchor.BN(window), freeUnfollows, new anchor.BN(penalty))
+          .accounts({ platformConfig, authority: wallet.publicKey })
+          .rpc();
+      const follow = () =>
+        program.methods
+          .followUser()
+          .accounts({ follower: creator.publicKey, following: trader.publicKey, followRecord })
+          .signers([creator])
+          .rpc();
+      const unfollow = () =>
+        program.methods
+          .unfollowUser()
+          .accounts({
+            followRecord,
+            followerProfile: creatorProfile,
+            followingProfile: traderProfile,
+            platformConfig,
+            follower: creator.publicKey,
+          })
+          .signers([creator])
+          .rpc();
+      const reputation = async () =>
+        (await program.account.userProfile.fetch(creatorProfile)).reputationScore.toNumber();
+
+      const penalty = 10;
+      await setPenalty(3600, 1, penalty);
+
+      // creator followed trader earlier; the first unfollow in the window is free
+      let before = await reputation();
+      await unfollow();
+      expect(await reputation()).to.equal(before);
+
+      await follow();
+      before = await reputation();
+      await unfollow();
+      expect(await reputation()).to.equal(before - penalty);
+
+      await setPenalty(0, 0, 0);
+    });
+
+    it("Leaves reputation unchanged after buying and fully selling keys", async () => {
+      const [creatorProfile] = PublicKey.findProgramAddressSync(
+        [Buffer.from("user_profile"), creator.publicKey.toBuffer()],
+        program.programId
+      );
+      const [traderProfile] = PublicKey.findProgramAddressSync(
+        [Buffer.from("user_profile"), trader.publicKey.toBuffer()],
+        program.programId
+      );
+      const amount = new anchor.BN(5);
+      const reputation = async () =>
+        (await program.account.userProfile.fetch(traderProfile)).reputationScore.toString();
+
+      const before = await reputation();
+
+      await program.methods
+        .buyUserKeys(amount)
+        .accounts({ buyer: trader.publicKey, userProfile: creatorProfile, buyerProfile: traderProfile, tradeLedger: null, tradeReceipt: null })
+        .signers([trader])
+        .rpc();
+      expect(await reputation()).to.not.equal(before);
+
+      await program.methods
+        .sellUserKeys(amount)
+        .accounts({ seller: trader.publicKey, userProfile: creatorProfile, sellerProfile: traderProfile, tradeLedger: null, tradeReceipt: null })
+        .signers([trader])
+        .rpc();
+      expect(await reputation()).to.equal(before);
+    });
+
+    it("Batches interaction reputation until it settles", async () => {
+      const roomId = "quorum-room";
+      const [platformConfig] = PublicKey.findProgramAddressSync(
+        [Buffer.from("platform_config")],
+        program.programId
+      );
+      const [creatorProfile] = PublicKey.findProgramAddressSync(
+        [Buffer.from("user_profile"), creator.publicKey.toBuffer()],
+        program.programId
+      );
+      const setThreshold = (threshold: number) =>
+        program.methods
+          .setReputationSettlementThreshold(new anchor.BN(threshold))
+          .accounts({ platformConfig, authority: wallet.publicKey })
+          .rpc();
+      const send = (content: string) =>
+        program.methods
+          .sendMessage(roomId, content, [])
+          .accounts({ sender: creator.publicKey })
+          .signers([creator])
+          .rpc();
+      const profile = () => program.account.userProfile.fetch(creatorProfile);
+
+      const start = await profile();
+      const startScore = start.reputationScore.toNumber() + start.pendingReputation.toNumber();
+
+      // Each message is worth 2, so two stay pending and the third settles all three
+      await setThreshold(5);
+      await send("batched one");
+      await send("batched two");
+      let current = await profile();
+      expect(current.reputationScore.toNumber()).to.equal(start.reputationScore.toNumber());
+      expect(current.pendingReputation.toNumber()).to.equal(start.pendingReputation.toNumber() + 4);
+
+      await send("batched three");
+      current = await profile();
+      expect(current.pendingReputation.toNumber()).to.equal(0);
+      expect(current.reputationScore.toNumber()).to.equal(startScore + 6);
+
+      await send("batched four");
+      await program.methods
+        .settleReputation()
+        .accounts({ userProfile: creatorProfile })
+        .rpc();
+      current = await profile();
+      expect(current.pendingReputation.toNumber()).to.equal(0);
+      // Same total immediate writes would have produced
+      expect(current.reputationScore.toNumber()).to.equal(startScore + 4 * 2);
+
+      await setThreshold(0);
+    });
+
+    it("Awards claimable badges only past their eligibility boundaries", async () => {
+      const [platformConfig] = PublicKey.findProgramAddressSync(
+        [Buffer.from("platform_config")],
+        program.programId
+      );
+      const profileOf = (member: Keypair) =>
+        PublicKey.findProgramAddressSync(
+          [Buffer.from("user_profile"), member.publicKey.toBuffer()],
+          program.programId
+        )[0];
+      const claim = (member: Keypair, badgeType: object, index: number) =>
+        program.methods
+          .awardBadge(badgeType)
+          .accounts({
+            userProfile: profileOf(member),
+            badge: PublicKey.findProgramAddressSync(
+              [Buffer.from("user_badge"), member.publicKey.toBuffer(), Buffer.from([index])],
+              program.programId
+            )[0],
+            platformConfig,
+            user: member.publicKey,
+          })
+          .signers([member])
+          .rpc();
+      const setThresholds = (volume: anchor.BN, followers: anchor.BN) =>
+        program.methods
+          .setBadgeThresholds(volume, followers)
+          .accounts({ platformConfig, authority: wallet.publicKey })
+          .rpc();
+
+      // Profiles in this suite were all created well inside the first thousand users
+      expect((await program.account.userProfile.fetch(profileOf(creator))).isEarlyAdopter).to.be.true;
+      await claim(creator, { earlyAdopter: {} }, 0);
+      try {
+        await claim(creator, { earlyAdopter: {} }, 0);
+        expect.fail("a badge should only be awarded once");
+      } catch (error) {
+        expect(error.error.errorCode.code).to.equal("BadgeAlreadyEarned");
+      }
+
+      // Volume is the trader's own: a buy adds its price to the buyer's profile
+      const volumeBefore = (await program.account.userProfile.fetch(profileOf(trader))).totalVolume;
+      const purchases = [];
+      const listener = program.addEventListener("keysPurchased", (event) => purchases.push(event));
+      await program.methods
+        .buyKeys(new anchor.BN(1), new anchor.BN(LAMPORTS_PER_SOL), null)
+        .accounts({ buyer: trader.publicKey, subject: creator.publicKey })
+        .signers([trader])
+        .rpc();
+      await new Promise(resolve => setTimeout(resolve, 1000));
+      await program.removeEventListener(listener);
+      const { totalVolume } = await program.account.userProfile.fetch(profileOf(trader));
+      expect(totalVolume.toString()).to.equal(volumeBefore.add(purchases[0].price).toString());
+
+      const { followerCount } = await program.account.userProfile.fetch(profileOf(creator));
+      expect(followerCount.toNumber()).to.be.greaterThan(0);
+
+      // Exactly at the threshold is not enough; the stat has to exceed it
+      await setThresholds(totalVolume, followerCount);
+      try {
+        await claim(trader, { topTrader: {} }, 1);
+        expect.fail("volume equal to the threshold should not qualify");
+      } catch (error) {
+        expect(error.error.errorCode.code).to.equal("ReputationTooLow");
+      }
+      try {
+        await claim(creator, { influencer: {} }, 2);
+        expect.fail("followers equal to the threshold should not qualify");
+      } catch (error) {
+        expect(error.error.errorCode.code).to.equal("InsufficientInfluence");
+      }
+
+      await setThresholds(totalVolume.subn(1), followerCount.subn(1));
+      await claim(trader, { topTrader: {} }, 1);
+      await claim(creator, { influencer: {} }, 2);
+
+      try {
+        await claim(user, { verified: {} }, 4);
+        expect.fail("verified badges are only issued by the authority");
+      } catch (error) {
+        expect(error.error.errorCode.code).to.equal("PermissionDenied");
+      }
+
+      await setThresholds(new anchor.BN(100_000_000_000), new anchor.BN(1000));
+    });
+
+    it("Lets the authority tune the influence decay rate", async () => {
+      const [platformConfig] = PublicKey.findProgramAddressSync(
+        [Buffer.from("platform_config")],
+        program.programId
+      );
+      const { influenceDecayRate } = await program.account.platformConfig.fetch(platformConfig);
+      expect(influenceDecayRate).to.be.greaterThan(0);
+
+      try {
+        await program.methods
+          .setInfluenceDecayRate(10001)
+          .accounts({ platformConfig, authority: wallet.publicKey })
+          .rpc();
+        expect.fail("rates above 100% should be rejected");
+      } catch (error) {
+        expect(error.error.errorCode.code).to.equal("InvalidPercentage");
+      }
+
+      await program.methods
+        .setInfluenceDecayRate(250)
+        .accounts({ platformConfig, authority: wallet.publicKey })
+        .rpc();
+      expect((await program.account.platformConfig.fetch(platformConfig)).influenceDecayRate).to.equal(250);
+
+      await program.methods
+        .setInfluenceDecayRate(influenceDecayRate)
+        .accounts({ platformConfig, authority: wallet.publicKey })
+        .rpc();
+    });
+
+    it("Bootstraps buyer reputation from key purchases up to the configured cap", async () => {
+      const [platformConfig] = PublicKey.findProgramAddressSync(
+        [Buffer.from("platform_config")],
+        program.programId
+      );
+      const [traderProfile] = PublicKey.findProgramAddressSync(
+        [Buffer.from("user_profile"), trader.publicKey.toBuffer()],
+        program.programId
+      );
+      const config = await program.account.platformConfig.fetch(platformConfig);
+      expect(config.bootstrapReputationRate).to.be.greaterThan(0);
+
+      // A tight cap so a couple of purchases exhaust it
+      const before = await program.account.userProfile.fetch(traderProfile);
+      const cap = before.bootstrapReputationEarned.add(new anchor.BN(1));
+      await program.methods
+        .setReputationBootstrap(config.bootstrapReputationRate, cap)
+        .accounts({ platformConfig, authority: wallet.publicKey })
+        .rpc();
+
+      for (let i = 0; i < 2; i++) {
+        await program.methods
+          .buyKeys(new anchor.BN(1), new anchor.BN(LAMPORTS_PER_SOL), null)
+          .accounts({ buyer: trader.publicKey, subject: creator.publicKey, platformConfig })
+          .signers([trader])
+          .rpc();
+      }
+
+      const after = await program.account.userProfile.fetch(traderProfile);
+      expect(after.bootstrapReputationEarned.toString()).to.equal(cap.toString());
+      expect(after.reputationScore.sub(before.reputationScore).toNumber()).to.equal(1);
+
+      await program.methods
+        .setReputationBootstrap(config.bootstrapReputationRate, config.maxBootstrapReputation)
+        .accounts({ platformConfig, authority: wallet.publicKey })
+        .rpc();
+    });
+
+    it("Only lets a badge's owner or the authority restore it", async () => {
+      const [platformConfig] = PublicKey.findProgramAddressSync(
+        [Buffer.from("platform_config")],
+        program.programId
+      );
+      const { badgeReputationFloor, restoreBadgesOnUnban } =
+        await program.account.platformConfig.fetch(platformConfig);
+      expect(badgeReputationFloor.toNumber()).to.be.greaterThan(0);
+      expect(restoreBadgesOnUnban).to.be.true;
+
+      const [badge] = PublicKey.findProgramAddressSync(
+        [Buffer.from("user_badge"), creator.publicKey.toBuffer(), Buffer.from([0])],
+        program.programId
+      );
+      const [holder] = PublicKey.findProgramAddressSync(
+        [Buffer.from("user"), creator.publicKey.toBuffer()],
+        program.programId
+      );
+
+      try {
+        await program.methods
+          .restoreBadge()
+          .accounts({ badge, holder, platformConfig, caller: trader.publicKey })
+          .signers([trader])
+          .rpc();
+        expect.fail("a stranger should not be able to restore someone else's badge");
+      } catch (error) {
+        expect(error.error.errorCode.code).to.equal("UnauthorizedUser");
+      }
+    });
+
+    it("Lets the authority tune how much author reputation boosts the feed", async () => {
+      const [platformConfig] = PublicKey.findProgramAddressSync(
+        [Buffer.from("platform_config")],
+        program.programId
+      );
+      const { feedReputationWeight } = await program.account.platformConfig.fetch(platformConfig);
+      expect(feedReputationWeight).to.be.greaterThan(0);
+
+      try {
+        await program.methods
+          .setFeedReputationWeight(10001)
+          .accounts({ platformConfig, authority: wallet.publicKey })
+          .rpc();
+        expect.fail("weights above 100% per point should be rejected");
+      } catch (error) {
+        expect(error.error.errorCode.code).to.equal("InvalidPercentage");
+      }
+
+      await program.methods
+        .setFeedReputationWeight(25)
+        .accounts({ platformConfig, authority: wallet.publicKey })
+        .rpc();
+      expect((await program.account.platformConfig.fetch(platformConfig)).feedReputationWeight).to.equal(25);
+
+      await program.methods
+        .setFeedReputationWeight(feedReputationWeight)
+        .accounts({ platformConfig, authority: wallet.publicKey })
+        .rpc();
+    });
+  });
+
+  describe("Revenue Distribution", () => {
+    it("Distributes trading fees", async () => {
+      try {
+        const totalFees = new anchor.BN(1000000);
+
+        await program.methods
+          .distributeTradingFees(totalFees)
           .accounts({
             userKeys,
             creator: creator.publicKey,
@@ -434,6 +5418,479 @@ describe("SolSocial", () => {
         throw error;
       }
     });
+
+    it("Pays out pending revenue once and never twice", async () => {
+      const [revenueShare] = PublicKey.findProgramAddressSync(
+        [Buffer.from("revenue_share"), creator.publicKey.toBuffer()],
+        program.programId
+      );
+      const deposit = new anchor.BN(LAMPORTS_PER_SOL / 10);
+
+      await program.methods
+        .depositRevenue(deposit)
+        .accounts({ payer: wallet.publicKey, subject: creator.publicKey, revenueShare })
+        .rpc();
+
+      const withdraw = () =>
+        program.methods
+          .withdrawRevenue()
+          .accounts({ subject: creator.publicKey, revenueShare })
+          .signers([creator])
+          .rpc();
+
+      const { pendingWithdrawal, totalWithdrawn } = await program.account.revenueShare.fetch(revenueShare);
+      const escrowBefore = await connection.getBalance(revenueShare);
+
+      await withdraw();
+
+      const afterFirst = await program.account.revenueShare.fetch(revenueShare);
+      const escrowAfterFirst = await connection.getBalance(revenueShare);
+      expect(afterFirst.pendingWithdrawal.toNumber()).to.equal(0);
+      expect(afterFirst.totalWithdrawn.sub(totalWithdrawn).toString()).to.equal(pendingWithdrawal.toString());
+      expect(escrowBefore - escrowAfterFirst).to.equal(pendingWithdrawal.toNumber());
+
+      try {
+        await withdraw();
+        expect.fail("a second withdrawal with nothing pending should fail");
+      } catch (error) {
+        expect(error.error.errorCode.code).to.equal("InsufficientRewards");
+      }
+
+      expect(await connection.getBalance(revenueShare)).to.equal(escrowAfterFirst);
+      const afterSecond = await program.account.revenueShare.fetch(revenueShare);
+      expect(afterSecond.totalWithdrawn.toString()).to.equal(afterFirst.totalWithdrawn.toString());
+    });
+
+    it("Pays tip proceeds to the author net of the protocol share", async () => {
+      const [platformConfig] = PublicKey.findProgramAddressSync(
+        [Buffer.from("platform_config")],
+        program.programId
+      );
+      const [postStats] = PublicKey.findProgramAddressSync(
+        [Buffer.from("post_stats"), socialPost.toBuffer()],
+        program.programId
+      );
+      const [protocolTreasury] = PublicKey.findProgramAddressSync(
+        [Buffer.from("protocol_treasury")],
+        program.programId
+      );
+      const [tipInteraction] = PublicKey.findProgramAddressSync(
+        [Buffer.from("tip"), socialPost.toBuffer(), trader.publicKey.toBuffer()],
+        program.programId
+      );
+      const tip = (tipper: Keypair, amount: anchor.BN) =>
+        program.methods
+          .tipPost(amount, false)
+          .accounts({ post: socialPost, postStats, author: user.publicKey, platformConfig, protocolTreasury, tipper: tipper.publicKey })
+          .signers([tipper])
+          .rpc();
+
+      const amount = new anchor.BN(1_000_000);
+      const protocolFee = amount.muln(250).divn(10_000);
+      const interactionBefore = await program.account.postInteraction.fetchNullable(tipInteraction);
+      const authorBefore = await provider.connection.getBalance(user.publicKey);
+      const treasuryBefore = await provider.connection.getBalance(protocolTreasury);
+
+      await tip(trader, amount);
+
+      const authorAfter = await provider.connection.getBalance(user.publicKey);
+      const treasuryAfter = await provider.connection.getBalance(protocolTreasury);
+      expect(authorAfter - authorBefore).to.equal(amount.sub(protocolFee).toNumber());
+      expect(treasuryAfter - treasuryBefore).to.equal(protocolFee.toNumber());
+
+      const interaction = await program.account.postInteraction.fetch(tipInteraction);
+      expect(interaction.interactionType).to.deep.equal({ tip: {} });
+      const previousTips = interactionBefore ? interactionBefore.tokenAmount : new anchor.BN(0);
+      expect(interaction.tokenAmount.toString()).to.equal(previousTips.add(amount).toString());
+
+      try {
+        await tip(user, amount);
+        expect.fail("authors should not be able to tip themselves");
+      } catch (error) {
+        expect(error.error.errorCode.code).to.equal("SelfInteractionNotAllowed");
+      }
+    });
+
+    it("Sweeps pending revenue to the treasury only after it expires", async () => {
+      const [platformConfig] = PublicKey.findProgramAddressSync(
+        [Buffer.from("platform_config")],
+        program.programId
+      );
+      const [revenueShare] = PublicKey.findProgramAddressSync(
+        [Buffer.from("revenue_share"), creator.publicKey.toBuffer()],
+        program.programId
+      );
+      const [protocolTreasury] = PublicKey.findProgramAddressSync(
+        [Buffer.from("protocol_treasury")],
+        program.programId
+      );
+      const setExpiry = (seconds: number) =>
+        program.methods
+          .setPayoutExpiry(new anchor.BN(seconds))
+          .accounts({ platformConfig, authority: wallet.publicKey })
+          .rpc();
+      const sweep = () =>
+        program.methods
+          .sweepExpiredPayout()
+          .accounts({ revenueShare, platformConfig, protocolTreasury })
+          .rpc();
+
+      await program.methods
+        .depositRevenue(new anchor.BN(LAMPORTS_PER_SOL / 20))
+        .accounts({ payer: wallet.publicKey, subject: creator.publicKey, revenueShare })
+        .rpc();
+
+      await setExpiry(3600);
+      try {
+        await sweep();
+        expect.fail("fresh payouts should not be sweepable");
+      } catch (error) {
+        expect(error.error.errorCode.code).to.equal("ResourceLocked");
+      }
+
+      await setExpiry(1);
+      await new Promise(resolve => setTimeout(resolve, 2500));
+
+      const { pendingWithdrawal } = await program.account.revenueShare.fetch(revenueShare);
+      const treasuryBefore = await connection.getBalance(protocolTreasury);
+
+      await sweep();
+
+      const swept = await program.account.revenueShare.fetch(revenueShare);
+      expect(swept.pendingWithdrawal.toNumber()).to.equal(0);
+      expect((await connection.getBalance(protocolTreasury)) - treasuryBefore).to.equal(pendingWithdrawal.toNumber());
+
+      await setExpiry(0);
+    });
+
+    it("Opens premium posts to active subscribers and extends renewals", async () => {
+      const [premiumPost] = PublicKey.findProgramAddressSync(
+        [Buffer.from("post"), creator.publicKey.toBuffer(), new anchor.BN(0).toArrayLike(Buffer, "le", 8)],
+        program.programId
+      );
+      const [creatorKeys] = PublicKey.findProgramAddressSync(
+        [Buffer.from("user_keys"), creator.publicKey.toBuffer()],
+        program.programId
+      );
+      const [creatorProfile] = PublicKey.findProgramAddressSync(
+        [Buffer.from("user_profile"), creator.publicKey.toBuffer()],
+        program.programId
+      );
+      const [creatorUser] = PublicKey.findProgramAddressSync(
+        [Buffer.from("user"), creator.publicKey.toBuffer()],
+        program.programId
+      );
+      const [subscription] = PublicKey.findProgramAddressSync(
+        [Buffer.from("subscription"), creator.publicKey.toBuffer(), user.publicKey.toBuffer()],
+        program.programId
+      );
+      const monthlyPrice = new anchor.BN(LAMPORTS_PER_SOL / 100);
+      const month = 30 * 86_400;
+      const canView = async (viewerSubscription: PublicKey | null) => {
+        const { events } = await program.methods
+          .checkPostAccess()
+          .accounts({
+            post: premiumPost,
+            authorKeys: creatorKeys,
+            viewer: user.publicKey,
+            followRelation: null,
+            subscription: viewerSubscription,
+          })
+          .signers([user])
+          .simulate();
+        return events.find((event) => event.name === "postAccessChecked").data.canView;
+      };
+      const subscribe = (months: number) =>
+        program.methods
+          .subscribe(months)
+          .accounts({ creator: creator.publicKey, creatorProfile, creatorUser, subscription, subscriber: user.publicKey })
+          .signers([user])
+          .rpc();
+
+      await program.methods
+        .setPostVisibility({ keyHolders: {} })
+        .accounts({ post: premiumPost, author: creator.publicKey })
+        .signers([creator])
+        .rpc();
+      expect(await canView(null)).to.be.false;
+
+      try {
+        await subscribe(1);
+        expect.fail("subscribing should fail until the creator sets a price");
+      } catch (error) {
+        expect(error.error.errorCode.code).to.equal("FeatureDisabled");
+      }
+
+      await program.methods
+        .setSubscriptionPrice(monthlyPrice)
+        .accounts({ userProfile: creatorProfile, user: creator.publicKey })
+        .signers([creator])
+        .rpc();
+
+      const revenueBefore = (await program.account.user.fetch(creatorUser)).revenueStats.subscriptionRevenue;
+      await subscribe(1);
+      const first = await program.account.subscription.fetch(subscription);
+      expect(first.expiresAt.sub(first.startedAt).toNumber()).to.equal(month);
+      expect(first.totalPaid.toString()).to.equal(monthlyPrice.toString());
+      expect(await canView(subscription)).to.be.true;
+
+      const revenueAfter = (await program.account.user.fetch(creatorUser)).revenueStats.subscriptionRevenue;
+      expect(revenueAfter.gt(revenueBefore)).to.be.true;
+      expect(revenueAfter.sub(revenueBefore).lt(monthlyPrice)).to.be.true;
+
+      // Renewing early stacks onto the remaining term
+      await subscribe(2);
+      const renewed = await program.account.subscription.fetch(subscription);
+      expect(renewed.expiresAt.sub(first.expiresAt).toNumber()).to.equal(2 * month);
+      expect(renewed.totalPaid.toString()).to.equal(monthlyPrice.muln(3).toString());
+    });
+  });
+
+  describe("Governance", () => {
+    it("Weights votes by the holder snapshot taken at proposal creation", async () => {
+      const proposalId = new anchor.BN(1);
+      const [proposal] = PublicKey.findProgramAddressSync(
+        [Buffer.from("proposal"), creator.publicKey.toBuffer(), proposalId.toArrayLike(Buffer, "le", 8)],
+        program.programId
+      );
+      const [holderSnapshot] = PublicKey.findProgramAddressSync(
+        [Buffer.from("holder_snapshot"), proposal.toBuffer()],
+        program.programId
+      );
+      const voteRecord = (voter: PublicKey) =>
+        PublicKey.findProgramAddressSync(
+          [Buffer.from("vote"), proposal.toBuffer(), voter.toBuffer()],
+          program.programId
+        )[0];
+
+      const [userKey] = PublicKey.findProgramAddressSync(
+        [Buffer.from("user_key"), creator.publicKey.toBuffer()],
+        program.programId
+      );
+      // Every KeyHolder of the market, found by its subject (after the discriminator and holder)
+      const keyHolders = (
+        await program.account.keyHolder.all([
+          { memcmp: { offset: 8 + 32, bytes: creator.publicKey.toBase58() } },
+        ])
+      ).map(({ publicKey }) => ({ pubkey: publicKey, isSigner: false, isWritable: false }));
+      const createProposal = (holders: typeof keyHolders) =>
+        program.methods
+          .createProposal(proposalId, "Raise chat key requirement", new anchor.BN(3600))
+          .accounts({ proposal, holderSnapshot, userKeys, userKey, proposer: trader.publicKey })
+          .remainingAccounts(holders)
+          .signers([trader])
+          .rpc();
+
+      // Leaving a holder out of the snapshot would silence their vote
+      try {
+        await createProposal(keyHolders.slice(1));
+        expect.fail("a snapshot missing a holder should be rejected");
+      } catch (error) {
+        expect(error.error.errorCode.code).to.equal("InvalidVotingPower");
+      }
+
+      await createProposal(keyHolders);
+
+      const snapshotBefore = await program.account.holderSnapshot.fetch(holderSnapshot);
+
+      // Keys bought after the snapshot must not add voting power
+      await program.methods
+        .buyKeys(new anchor.BN(5), new anchor.BN(10_000_000), null)
+        .accounts({ userKeys, bondingCurve, buyer: trader.publicKey, creator: creator.publicKey, globalState })
+        .signers([trader])
+        .rpc();
+
+      await program.methods
+        .castVote(true)
+        .accounts({ proposal, holderSnapshot, voteRecord: voteRecord(trader.publicKey), voter: trader.publicKey })
+        .signers([trader])
+        .rpc();
+
+      const proposalAccount = await program.account.proposal.fetch(proposal);
+      expect(proposalAccount.yesWeight.toNumber()).to.equal(snapshotBefore.totalWeight.toNumber());
+
+      try {
+        await program.methods
+          .castVote(true)
+          .accounts({ proposal, holderSnapshot, voteRecord: voteRecord(user.publicKey), voter: user.publicKey })
+          .signers([user])
+          .rpc();
+        expect.fail("holder without snapshot balance should not vote");
+      } catch (error) {
+        expect(error.error.errorCode.code).to.equal("InvalidVotingPower");
+      }
+    });
+
+    it("Hands off platform authority only after the new authority accepts", async () => {
+      const [platformConfig] = PublicKey.findProgramAddressSync(
+        [Buffer.from("platform_config")],
+        program.programId
+      );
+      const mistaken = Keypair.generate();
+      const multisig = Keypair.generate();
+      const propose = (newAuthority: PublicKey) =>
+        program.methods
+          .proposeAuthorityTransfer(newAuthority)
+          .accounts({ platformConfig, authority: wallet.publicKey })
+          .rpc();
+
+      await propose(mistaken.publicKey);
+      // A mistaken proposal can be replaced before anyone accepts it
+      await propose(multisig.publicKey);
+
+      let config = await program.account.platformConfig.fetch(platformConfig);
+      expect(config.authority.toString()).to.equal(wallet.publicKey.toString());
+      expect(config.pendingAuthority.toString()).to.equal(multisig.publicKey.toString());
+
+      try {
+        await program.methods
+          .acceptAuthority()
+          .accounts({ platformConfig, newAuthority: mistaken.publicKey })
+          .signers([mistaken])
+          .rpc();
+        expect.fail("a replaced proposal should not be acceptable");
+      } catch (error) {
+        expect(error.error.errorCode.code).to.equal("UnauthorizedUser");
+      }
+
+      await program.methods
+        .acceptAuthority()
+        .accounts({ platformConfig, newAuthority: multisig.publicKey })
+        .signers([multisig])
+        .rpc();
+
+      config = await program.account.platformConfig.fetch(platformConfig);
+      expect(config.authority.toString()).to.equal(multisig.publicKey.toString());
+      expect(config.pendingAuthority).to.be.null;
+
+      // Hand authority back so later tests keep using the provider wallet
+      await program.methods
+        .proposeAuthorityTransfer(wallet.publicKey)
+        .accounts({ platformConfig, authority: multisig.publicKey })
+        .signers([multisig])
+        .rpc();
+      await program.methods
+        .acceptAuthority()
+        .accounts({ platformConfig, newAuthority: wallet.publicKey })
+        .rpc();
+    });
+
+    it("Rejects trades and posts while the platform is paused", async () => {
+      const [platformConfig] = PublicKey.findProgramAddressSync(
+        [Buffer.from("platform_config")],
+        program.programId
+      );
+      const [creatorProfile] = PublicKey.findProgramAddressSync(
+        [Buffer.from("user_profile"), creator.publicKey.toBuffer()],
+        program.programId
+      );
+      const [traderProfile] = PublicKey.findProgramAddressSync(
+        [Buffer.from("user_profile"), trader.publicKey.toBuffer()],
+        program.programId
+      );
+      const setStatus = (trading: boolean, posting: boolean) =>
+        program.methods
+          .setPlatformStatus(trading, posting)
+          .accounts({ platformConfig, authority: wallet.publicKey })
+          .rpc();
+
+      const gated: [string, () => Promise<string>][] = [
+        ["TradingPaused", () =>
+          program.methods
+            .buyUserKeys(new anchor.BN(1))
+            .accounts({ buyer: trader.publicKey, userProfile: creatorProfile, buyerProfile: traderProfile, tradeLedger: null, tradeReceipt: null })
+            .signers([trader])
+            .rpc()],
+        ["TradingPaused", () =>
+          program.methods
+            .sellUserKeys(new anchor.BN(1))
+            .accounts({ seller: trader.publicKey, userProfile: creatorProfile, sellerProfile: traderProfile, tradeLedger: null, tradeReceipt: null })
+            .signers([trader])
+            .rpc()],
+        ["TradingPaused", () =>
+          program.methods
+            .buyKeys(new anchor.BN(1), new anchor.BN(10 * LAMPORTS_PER_SOL), null)
+            .accounts({ buyer: trader.publicKey, subject: creator.publicKey, platformConfig })
+            .signers([trader])
+            .rpc()],
+        ["PostingPaused", async () => {
+          const { postCount } = await program.account.userProfile.fetch(creatorProfile);
+          const [post] = PublicKey.findProgramAddressSync(
+            [Buffer.from("post"), creator.publicKey.toBuffer(), postCount.toArrayLike(Buffer, "le", 8)],
+            program.programId
+          );
+          return program.methods
+            .createPost("while paused", [], { original: {} }, null, [], false, false)
+            .accounts({ post, userProfile: creatorProfile, platformConfig, user: creator.publicKey })
+            .signers([creator])
+            .rpc();
+        }],
+        ["PostingPaused", () =>
+          program.methods
+            .interactPost(0, null)
+            .accounts({ post: socialPost, authority: trader.publicKey, platformConfig })
+            .signers([trader])
+            .rpc()],
+      ];
+
+      await setStatus(false, false);
+      for (const [code, call] of gated) {
+        try {
+          await call();
+          expect.fail(`expected ${code} while paused`);
+        } catch (error) {
+          expect(error.error.errorCode.code).to.equal(code);
+        }
+      }
+
+      await setStatus(true, true);
+      for (const [, call] of gated) {
+        await call();
+      }
+    });
+
+    it("Restricts platform settings updates to the authority and bounds fee rates", async () => {
+      const [platformConfig] = PublicKey.findProgramAddressSync(
+        [Buffer.from("platform_config")],
+        program.programId
+      );
+      const before = await program.account.platformConfig.fetch(platformConfig);
+
+      try {
+        await program.methods
+          .updatePlatformSettings(new anchor.BN(100), null, null, null)
+          .accounts({ platform: platformConfig, authority: trader.publicKey })
+          .signers([trader])
+          .rpc();
+        expect.fail("only the platform authority may update settings");
+      } catch (error) {
+        expect(error.error.errorCode.code).to.equal("UnauthorizedUser");
+      }
+
+      try {
+        await program.methods
+          .updatePlatformSettings(null, new anchor.BN(1001), null, null)
+          .accounts({ platform: platformConfig, authority: wallet.publicKey })
+          .rpc();
+        expect.fail("fee rates above 1000 bps should be rejected");
+      } catch (error) {
+        expect(error.error.errorCode.code).to.equal("InvalidFeePercentage");
+      }
+
+      // Unset fields are left alone
+      await program.methods
+        .updatePlatformSettings(new anchor.BN(1000), null, null, null)
+        .accounts({ platform: platformConfig, authority: wallet.publicKey })
+        .rpc();
+      const updated = await program.account.platformConfig.fetch(platformConfig);
+      expect(updated.feeRate.toNumber()).to.equal(1000);
+      expect(updated.creatorFeeRate.toString()).to.equal(before.creatorFeeRate.toString());
+
+      await program.methods
+        .updatePlatformSettings(before.feeRate, null, null, null)
+        .accounts({ platform: platformConfig, authority: wallet.publicKey })
+        .rpc();
+    });
   });
 
   describe("Error Handling", () => {